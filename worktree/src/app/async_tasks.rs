@@ -1,4 +1,4 @@
-use crate::domain::repository::{GitCommit, GitStatus};
+use crate::domain::repository::{GitCommit, GitStatus, RefComparison};
 
 #[derive(Debug)]
 pub enum AsyncAction {
@@ -32,6 +32,20 @@ pub enum AsyncResult {
         path: String,
         result: anyhow::Result<Vec<GitCommit>>,
     },
+    /// A further page of the history view; `query.skip` is where it starts.
+    HistoryPageFetched {
+        path: String,
+        query: crate::domain::repository::HistoryQuery,
+        result: anyhow::Result<Vec<GitCommit>>,
+    },
+    CommitDetailFetched {
+        path: String,
+        result: anyhow::Result<crate::domain::repository::CommitDetail>,
+    },
+    ComparisonFetched {
+        path: String,
+        result: anyhow::Result<RefComparison>,
+    },
     SyncCompleted {
         branch: String,
         result: anyhow::Result<()>,
@@ -69,6 +83,10 @@ pub enum AsyncResult {
     CommitMessageGenerated {
         result: anyhow::Result<String>,
     },
+    /// A cherry-pick or revert finished; `Ok` carries the success message.
+    CommitApplied {
+        result: anyhow::Result<String>,
+    },
     StashesFetched {
         path: String,
         result: anyhow::Result<Vec<crate::domain::repository::StashEntry>>,
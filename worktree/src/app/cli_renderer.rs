@@ -1,5 +1,8 @@
 use crate::app::model::AppState;
-use crate::domain::repository::Worktree;
+use crate::domain::cache::CacheStatus;
+use crate::domain::list_layout::{GroupMode, group_of, is_pinned};
+use crate::domain::repository::{BranchInfo, Remote, Worktree};
+use crate::ui::widgets::worktree_list::format_size;
 use anyhow::Result;
 use comfy_table::Table;
 use owo_colors::OwoColorize;
@@ -46,24 +49,122 @@ impl CliRenderer {
         Ok(())
     }
 
-    pub fn render_listing_table(worktrees: &[Worktree]) {
+    pub fn render_listing_table(worktrees: &[Worktree], group: GroupMode) {
         let mut table = Table::new();
-        table.set_header(vec!["Branch", "Commit", "Path", "Status"]);
+        let mut header = vec!["Branch", "Commit", "Path", "Status"];
+        if group != GroupMode::None {
+            header.insert(0, "Group");
+        }
+        table.set_header(header);
 
         for wt in worktrees {
             let status = if wt.is_bare {
                 "Bare"
             } else if wt.is_detached {
                 "Detached"
+            } else if is_pinned(wt) {
+                "Pinned"
             } else {
                 "Active"
             };
-            table.add_row(vec![&wt.branch, &wt.commit, &wt.path, status]);
+            let mut row = vec![wt.branch.as_str(), &wt.commit, &wt.path, status];
+            if group != GroupMode::None {
+                row.insert(0, group_of(wt, group).unwrap_or(""));
+            }
+            table.add_row(row);
         }
 
         println!("{table}");
     }
 
+    pub fn render_branch_table(branches: &[BranchInfo]) {
+        let mut table = Table::new();
+        table.set_header(vec![
+            "Branch",
+            "Tip",
+            "Date",
+            "Upstream",
+            "Ahead/Behind",
+            "Worktree",
+        ]);
+
+        for branch in branches {
+            let tracking = if branch.upstream_gone {
+                "gone".to_string()
+            } else if branch.upstream.is_some() {
+                format!("⇡{} ⇣{}", branch.ahead, branch.behind)
+            } else {
+                String::new()
+            };
+            table.add_row(vec![
+                branch.name.as_str(),
+                branch.tip.as_str(),
+                branch.date.as_str(),
+                branch.upstream.as_deref().unwrap_or(""),
+                tracking.as_str(),
+                branch.worktree.as_deref().unwrap_or(""),
+            ]);
+        }
+
+        println!("{table}");
+    }
+
+    pub fn render_remote_table(remotes: &[Remote]) {
+        let mut table = Table::new();
+        table.set_header(vec!["Remote", "Fetch URL", "Push URL"]);
+
+        for remote in remotes {
+            table.add_row(vec![
+                remote.name.as_str(),
+                remote.fetch_url.as_str(),
+                remote.push_url.as_str(),
+            ]);
+        }
+
+        println!("{table}");
+    }
+
+    pub fn render_cache_table(caches: &[CacheStatus]) {
+        let mut table = Table::new();
+        table.set_header(vec![
+            "Cache",
+            "Location",
+            "Size",
+            "Worktrees",
+            "Configured by",
+        ]);
+
+        for cache in caches {
+            let worktrees = if cache.worktrees.is_empty() {
+                "-".to_string()
+            } else {
+                cache.worktrees.join(", ")
+            };
+            table.add_row(vec![
+                cache.kind.label().to_string(),
+                cache.location.clone(),
+                format_size(cache.size_bytes).into_owned(),
+                worktrees,
+                cache.configured_by.clone(),
+            ]);
+        }
+
+        println!("{table}");
+    }
+
+    pub fn confirm(question: &str) -> bool {
+        use std::io::{BufRead, IsTerminal, Write};
+        let stdin = std::io::stdin();
+        if !stdin.is_terminal() {
+            return false;
+        }
+        print!("{} {question} (y/N): ", "?".yellow().bold());
+        let _ = std::io::stdout().flush();
+        let mut answer = String::new();
+        stdin.lock().read_line(&mut answer).is_ok()
+            && matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+    }
+
     pub fn render_feedback_prompt() {
         println!("\n{}", "━".repeat(60).cyan().dimmed());
         println!("{}", "Thank you for using the Worktree Manager.".bold());
@@ -75,6 +176,16 @@ impl CliRenderer {
         );
     }
 
+    /// Goes to stderr so it never mixes with output piped to other tools.
+    pub fn render_expired_warning(branches: &[String]) {
+        eprintln!(
+            "{} {} worktree(s) expired: {}. Run 'worktree clean --expired' to remove them.",
+            "⚠".yellow(),
+            branches.len(),
+            branches.join(", ")
+        );
+    }
+
     #[allow(clippy::wildcard_in_or_patterns)]
     pub fn render(state: AppState) {
         match state {
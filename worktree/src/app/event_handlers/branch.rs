@@ -27,15 +27,11 @@ pub fn handle_branch_events<R: ProjectRepository + Clone + Send + Sync + 'static
         KeyCode::Esc | KeyCode::Char('q') => {
             return Some(prev_state.clone());
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if !branches.is_empty() {
-                *selected_index = (*selected_index + 1) % branches.len();
-            }
+        KeyCode::Down | KeyCode::Char('j') if !branches.is_empty() => {
+            *selected_index = (*selected_index + 1) % branches.len();
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            if !branches.is_empty() {
-                *selected_index = (*selected_index + branches.len() - 1) % branches.len();
-            }
+        KeyCode::Up | KeyCode::Char('k') if !branches.is_empty() => {
+            *selected_index = (*selected_index + branches.len() - 1) % branches.len();
         }
         KeyCode::Enter => {
             if let Some(branch) = branches.get(*selected_index) {
@@ -0,0 +1,306 @@
+use crate::app::intent::Intent;
+use crate::app::model::{AppState, PromptType};
+use crate::domain::repository::{BranchInfo, ProjectRepository};
+
+/// Branches listed with the current remote toggle; local branches always come first.
+pub fn visible_branches(branches: &[BranchInfo], show_remote: bool) -> Vec<&BranchInfo> {
+    branches
+        .iter()
+        .filter(|b| show_remote || !b.is_remote())
+        .collect()
+}
+
+pub fn open_branch_manager<R: ProjectRepository>(repo: &R, current_state: &AppState) -> AppState {
+    match repo.list_branch_info() {
+        Ok(branches) => AppState::ManagingBranches {
+            branches,
+            selected_index: 0,
+            show_remote: false,
+            prev_state: Box::new(current_state.clone()),
+        },
+        Err(e) => AppState::Error(
+            format!("Failed to list branches: {e}"),
+            Box::new(current_state.clone()),
+        ),
+    }
+}
+
+/// Re-reads the branches after a change made from the manager; other states are returned as is.
+pub fn reload_branches<R: ProjectRepository>(repo: &R, state: &AppState) -> AppState {
+    let mut next = state.clone();
+    if let AppState::ManagingBranches {
+        branches,
+        selected_index,
+        show_remote,
+        ..
+    } = &mut next
+    {
+        match repo.list_branch_info() {
+            Ok(fresh) => {
+                *branches = fresh;
+                let count = visible_branches(branches, *show_remote).len();
+                *selected_index = (*selected_index).min(count.saturating_sub(1));
+            }
+            Err(e) => {
+                return AppState::Error(
+                    format!("Failed to list branches: {e}"),
+                    Box::new(state.clone()),
+                );
+            }
+        }
+    }
+    next
+}
+
+pub fn handle_branch_manager_events<R: ProjectRepository>(
+    event: &crossterm::event::Event,
+    repo: &R,
+    branches: &[BranchInfo],
+    selected_index: &mut usize,
+    show_remote: &mut bool,
+    prev_state: &AppState,
+    current_state: &AppState,
+) -> Option<AppState> {
+    use crossterm::event::{Event, KeyCode};
+    let Event::Key(key) = event else {
+        return None;
+    };
+
+    let visible = visible_branches(branches, *show_remote);
+    let error = |message: String| Some(AppState::Error(message, Box::new(current_state.clone())));
+    let prompt = |prompt_type: PromptType, input: String| {
+        Some(AppState::Prompting {
+            prompt_type,
+            input,
+            prev_state: Box::new(current_state.clone()),
+        })
+    };
+
+    let normalized_code = match key.code {
+        KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+        code => code,
+    };
+    match normalized_code {
+        KeyCode::Esc | KeyCode::Char('q') => return Some(prev_state.clone()),
+        KeyCode::Down | KeyCode::Char('j') if !visible.is_empty() => {
+            *selected_index = (*selected_index + 1) % visible.len();
+        }
+        KeyCode::Up | KeyCode::Char('k') if !visible.is_empty() => {
+            *selected_index = (*selected_index + visible.len() - 1) % visible.len();
+        }
+        KeyCode::Char('a') => {
+            *show_remote = !*show_remote;
+            *selected_index = 0;
+        }
+        KeyCode::Char('n') => {
+            let base = visible.get(*selected_index).map_or("HEAD", |b| &b.name);
+            return prompt(
+                PromptType::CreateBranch {
+                    base: base.to_string(),
+                },
+                String::new(),
+            );
+        }
+        KeyCode::Char('r' | 'u' | 'd') => {
+            let branch = visible.get(*selected_index)?;
+            if branch.is_remote() {
+                return error(format!(
+                    "'{}' is a remote-tracking branch; open it in a worktree to get a local branch",
+                    branch.name
+                ));
+            }
+            let name = branch.name.clone();
+            match normalized_code {
+                KeyCode::Char('r') => {
+                    return prompt(PromptType::RenameBranch { old: name.clone() }, name);
+                }
+                KeyCode::Char('u') => {
+                    let upstream = branch
+                        .upstream
+                        .clone()
+                        .unwrap_or_else(|| format!("origin/{name}"));
+                    return prompt(PromptType::SetUpstream { branch: name }, upstream);
+                }
+                _ => {}
+            }
+            if let Some(path) = &branch.worktree {
+                return error(format!(
+                    "'{name}' is checked out in {path}. Remove that worktree first."
+                ));
+            }
+            let message = match repo.branch_unmerged_into(&name) {
+                Ok(Some(target)) => {
+                    format!("'{name}' has commits that are not in '{target}'. Delete it anyway?")
+                }
+                Ok(None) => format!("Delete branch '{name}'?"),
+                Err(e) => return error(format!("Failed to check '{name}': {e}")),
+            };
+            return Some(AppState::Confirming {
+                title: " DELETE BRANCH ".into(),
+                message,
+                action: Box::new(Intent::DeleteBranch { name, force: true }),
+                prev_state: Box::new(current_state.clone()),
+            });
+        }
+        KeyCode::Enter | KeyCode::Char('w') => {
+            let branch = visible.get(*selected_index)?;
+            let local_name = branch.local_name();
+            let local = branches
+                .iter()
+                .find(|b| !b.is_remote() && b.name == local_name);
+            if let Some(path) = local.and_then(|b| b.worktree.as_ref()) {
+                return error(format!("'{local_name}' is already checked out in {path}"));
+            }
+            // Keeping the suggested name checks the branch out; a remote branch without a local
+            // counterpart becomes a new tracking branch.
+            let base_ref = if local.is_some() {
+                local_name.to_string()
+            } else {
+                branch.name.clone()
+            };
+            return prompt(
+                PromptType::NameNewWorktree {
+                    base_ref,
+                    template: None,
+                },
+                local_name.to_string(),
+            );
+        }
+        _ => {}
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::test_utils::scaffolding::MockRepoBuilder;
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    fn branch(name: &str, remote: Option<&str>, worktree: Option<&str>) -> BranchInfo {
+        BranchInfo {
+            name: name.into(),
+            remote: remote.map(String::from),
+            tip: "abc1234".into(),
+            subject: "Initial commit".into(),
+            date: "2026-01-01".into(),
+            timestamp: 0,
+            upstream: None,
+            ahead: 0,
+            behind: 0,
+            upstream_gone: false,
+            worktree: worktree.map(String::from),
+        }
+    }
+
+    fn sample() -> Vec<BranchInfo> {
+        vec![
+            branch("main", None, Some("/hub/main")),
+            branch("wip-login", None, None),
+            branch("origin/feature", Some("origin"), None),
+            branch("origin/main", Some("origin"), None),
+        ]
+    }
+
+    #[test]
+    fn test_branch_manager_actions() {
+        let repo = MockRepoBuilder::default().build();
+        let branches = sample();
+        let mut selected = 1;
+        let mut show_remote = false;
+        let current = AppState::SettingUpDefaults;
+
+        let mut press = |code: KeyCode, selected: &mut usize| {
+            handle_branch_manager_events(
+                &key(code),
+                &repo,
+                &branches,
+                selected,
+                &mut show_remote,
+                &AppState::SetupComplete,
+                &current,
+            )
+        };
+
+        match press(KeyCode::Char('d'), &mut selected) {
+            Some(AppState::Confirming {
+                message, action, ..
+            }) => {
+                assert!(message.contains("not in 'main'"));
+                assert!(matches!(
+                    *action,
+                    Intent::DeleteBranch { ref name, force: true } if name == "wip-login"
+                ));
+            }
+            other => panic!("Expected delete confirmation, got {other:?}"),
+        }
+
+        match press(KeyCode::Char('u'), &mut selected) {
+            Some(AppState::Prompting {
+                prompt_type: PromptType::SetUpstream { branch },
+                input,
+                ..
+            }) => {
+                assert_eq!(branch, "wip-login");
+                assert_eq!(input, "origin/wip-login");
+            }
+            other => panic!("Expected upstream prompt, got {other:?}"),
+        }
+
+        // The checked-out branch cannot be deleted.
+        selected = 0;
+        assert!(matches!(
+            press(KeyCode::Char('d'), &mut selected),
+            Some(AppState::Error(..))
+        ));
+
+        // Remote branches only show up after toggling, and open as new tracking branches.
+        press(KeyCode::Char('a'), &mut selected);
+        selected = 2;
+        match press(KeyCode::Enter, &mut selected) {
+            Some(AppState::Prompting {
+                prompt_type: PromptType::NameNewWorktree { base_ref, .. },
+                input,
+                ..
+            }) => {
+                assert_eq!(base_ref, "origin/feature");
+                assert_eq!(input, "feature");
+            }
+            other => panic!("Expected worktree name prompt, got {other:?}"),
+        }
+        selected = 3;
+        assert!(matches!(
+            press(KeyCode::Enter, &mut selected),
+            Some(AppState::Error(..))
+        ));
+    }
+
+    #[test]
+    fn test_reload_branches_clamps_selection() {
+        let repo = MockRepoBuilder::default()
+            .with_branches(vec![branch("main", None, None)])
+            .build();
+        let state = AppState::ManagingBranches {
+            branches: sample(),
+            selected_index: 1,
+            show_remote: false,
+            prev_state: Box::new(AppState::SetupComplete),
+        };
+
+        match reload_branches(&repo, &state) {
+            AppState::ManagingBranches {
+                branches,
+                selected_index,
+                ..
+            } => {
+                assert_eq!(branches.len(), 1);
+                assert_eq!(selected_index, 0);
+            }
+            other => panic!("Expected ManagingBranches, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,445 @@
+use crate::app::async_tasks::AsyncResult;
+use crate::app::model::{AppState, PromptType};
+use crate::domain::port::port_commits;
+use crate::domain::repository::{CommitDetail, ProjectRepository, Worktree};
+use crate::infrastructure::clipboard::{copy_to_clipboard, copy_via_terminal};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::helpers::{create_timed_state, scroll_text};
+
+fn short(hash: &str) -> &str {
+    hash.get(..7).unwrap_or(hash)
+}
+
+/// Runs a cherry-pick or revert in the background and shows a progress modal.
+fn spawn_commit_action<R, F>(
+    repo: &R,
+    message: String,
+    done: String,
+    current_state: &AppState,
+    async_tx: &UnboundedSender<AsyncResult>,
+    action: F,
+) -> AppState
+where
+    R: ProjectRepository + Clone + Send + Sync + 'static,
+    F: FnOnce(R) -> anyhow::Result<()> + Send + 'static,
+{
+    let repo_clone = repo.clone();
+    let tx = async_tx.clone();
+    tokio::task::spawn_blocking(move || {
+        let result = action(repo_clone).map(|()| done);
+        let _ = tx.send(AsyncResult::CommitApplied { result });
+    });
+    AppState::ApplyingCommit {
+        message,
+        prev_state: Box::new(current_state.clone()),
+    }
+}
+
+/// Opens the picker for the worktree that `hashes` (oldest first) get ported into.
+pub fn pick_port_target<R: ProjectRepository>(
+    repo: &R,
+    source: &str,
+    hashes: Vec<String>,
+    current_state: &AppState,
+) -> AppState {
+    let targets: Vec<Worktree> = match repo.list_worktrees() {
+        Ok(worktrees) => worktrees
+            .into_iter()
+            .filter(|wt| !wt.is_bare && wt.path != source)
+            .collect(),
+        Err(e) => {
+            return AppState::Error(
+                format!("Failed to list worktrees: {e}"),
+                Box::new(current_state.clone()),
+            );
+        }
+    };
+    if targets.is_empty() {
+        return AppState::Error(
+            "No other worktree to cherry-pick into".into(),
+            Box::new(current_state.clone()),
+        );
+    }
+    AppState::PickingCherryPickTarget {
+        hashes,
+        source: source.to_string(),
+        targets,
+        selected_index: 0,
+        prev_state: Box::new(current_state.clone()),
+    }
+}
+
+/// Cherry-picks `hashes` into `target`, then drops them from `source` when moving.
+///
+/// A conflict leaves the cherry-pick in progress and reports it with an explanation.
+fn port_to_worktree<R: ProjectRepository>(
+    repo: &R,
+    source: &str,
+    target: &Worktree,
+    hashes: &[String],
+    remove: bool,
+) -> anyhow::Result<()> {
+    if remove {
+        repo.check_droppable(source, hashes)?;
+    }
+    let outcome = port_commits(repo, &target.path, hashes);
+    if let Some(conflict) = outcome.conflict {
+        let mut message = format!(
+            "Cherry-pick of {} into {} stopped on conflicts ({} of {} applied).",
+            short(&conflict.commit),
+            target.branch,
+            outcome.ported.len(),
+            hashes.len()
+        );
+        if let Ok(explanation) = repo.explain_rebase_conflict(&conflict.diff) {
+            message.push_str(&format!("\n\n{explanation}"));
+        }
+        message.push_str(&format!(
+            "\n\nResolve in {}, then run 'git cherry-pick --continue' (or '--abort').",
+            target.path
+        ));
+        anyhow::bail!(message);
+    }
+    if remove {
+        repo.drop_commits(source, hashes)?;
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle_commit_detail_events<R: ProjectRepository + Clone + Send + Sync + 'static>(
+    event: &crossterm::event::Event,
+    repo: &R,
+    path: &str,
+    branch: &str,
+    detail: &CommitDetail,
+    scroll: &mut u16,
+    max_scroll: u16,
+    prev_state: &AppState,
+    current_state: &AppState,
+    async_tx: &UnboundedSender<AsyncResult>,
+) -> Option<AppState> {
+    use crossterm::event::{Event, KeyCode};
+
+    if scroll_text(event, scroll, max_scroll) {
+        return None;
+    }
+
+    let Event::Key(key) = event else {
+        return None;
+    };
+    let normalized_code = match key.code {
+        KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+        code => code,
+    };
+    match normalized_code {
+        KeyCode::Esc | KeyCode::Char('q') => return Some(prev_state.clone()),
+        KeyCode::Char('y') => {
+            let copied =
+                copy_to_clipboard(&detail.hash).or_else(|_| copy_via_terminal(&detail.hash));
+            return Some(match copied {
+                Ok(()) => create_timed_state(
+                    AppState::CommitActionComplete {
+                        message: format!("Copied {}", detail.hash),
+                        prev_state: Box::new(current_state.clone()),
+                    },
+                    current_state.clone(),
+                    800,
+                ),
+                Err(e) => AppState::Error(
+                    format!("Failed to copy hash: {e}"),
+                    Box::new(current_state.clone()),
+                ),
+            });
+        }
+        KeyCode::Char('p') => {
+            return Some(pick_port_target(
+                repo,
+                path,
+                vec![detail.hash.clone()],
+                current_state,
+            ));
+        }
+        KeyCode::Char('r') => {
+            let hash = detail.hash.clone();
+            let path_clone = path.to_string();
+            return Some(spawn_commit_action(
+                repo,
+                format!("Reverting {} on {branch}", short(&hash)),
+                format!("Reverted {} on {branch}", short(&hash)),
+                current_state,
+                async_tx,
+                move |r| r.revert_commit(&path_clone, &hash),
+            ));
+        }
+        KeyCode::Char('w') => {
+            return Some(AppState::Prompting {
+                prompt_type: PromptType::NameNewWorktree {
+                    base_ref: detail.hash.clone(),
+                    template: None,
+                },
+                input: String::new(),
+                prev_state: Box::new(current_state.clone()),
+            });
+        }
+        KeyCode::Char('f') => {
+            return Some(match repo.get_commit_files(path, &detail.hash) {
+                Ok(files) => AppState::ViewingCommitFiles {
+                    path: path.to_string(),
+                    branch: branch.to_string(),
+                    commit: crate::domain::repository::GitCommit {
+                        hash: short(&detail.hash).to_string(),
+                        author: detail.author.clone(),
+                        date: detail.author_date.clone(),
+                        message: detail.subject.clone(),
+                        graph: String::new(),
+                    },
+                    files,
+                    selected_index: 0,
+                    prev_state: Box::new(current_state.clone()),
+                },
+                Err(e) => AppState::Error(
+                    format!("Failed to list files of {}: {e}", short(&detail.hash)),
+                    Box::new(current_state.clone()),
+                ),
+            });
+        }
+        _ => {}
+    }
+    None
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle_cherry_pick_target_events<R: ProjectRepository + Clone + Send + Sync + 'static>(
+    event: &crossterm::event::Event,
+    repo: &R,
+    hashes: &[String],
+    source: &str,
+    targets: &[Worktree],
+    selected_index: &mut usize,
+    prev_state: &AppState,
+    async_tx: &UnboundedSender<AsyncResult>,
+) -> Option<AppState> {
+    use crossterm::event::{Event, KeyCode};
+    let Event::Key(key) = event else {
+        return None;
+    };
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => return Some(prev_state.clone()),
+        KeyCode::Down | KeyCode::Char('j') if !targets.is_empty() => {
+            *selected_index = (*selected_index + 1) % targets.len();
+        }
+        KeyCode::Up | KeyCode::Char('k') if !targets.is_empty() => {
+            *selected_index = (*selected_index + targets.len() - 1) % targets.len();
+        }
+        KeyCode::Enter | KeyCode::Char('m' | 'M') => {
+            let target = targets.get(*selected_index)?.clone();
+            let remove = key.code != KeyCode::Enter;
+            let what = match hashes {
+                [hash] => short(hash).to_string(),
+                _ => format!("{} commits", hashes.len()),
+            };
+            let (verb, done) = if remove {
+                ("Moving", "Moved")
+            } else {
+                ("Cherry-picking", "Cherry-picked")
+            };
+            // Marks in the history view are spent once the commits are ported.
+            let mut back = prev_state.clone();
+            if let AppState::ViewingHistory { marked, .. } = &mut back {
+                marked.clear();
+            }
+            let (hashes, source) = (hashes.to_vec(), source.to_string());
+            return Some(spawn_commit_action(
+                repo,
+                format!("{verb} {what} into {}", target.branch),
+                format!("{done} {what} into {}", target.branch),
+                &back,
+                async_tx,
+                move |r| port_to_worktree(&r, &source, &target, &hashes, remove),
+            ));
+        }
+        _ => {}
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::renderers::commit_detail::commit_detail_max_scroll;
+    use crate::app::test_utils::scaffolding::MockRepoBuilder;
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+    use tokio::sync::mpsc;
+
+    fn key(c: char) -> Event {
+        Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()))
+    }
+
+    fn worktree(path: &str, branch: &str, is_bare: bool) -> Worktree {
+        Worktree {
+            path: path.into(),
+            commit: "abcdef1".into(),
+            branch: branch.into(),
+            is_bare,
+            is_detached: false,
+            status_summary: None,
+            size_bytes: 0,
+            metadata: None,
+            upstream: None,
+            contexts: Vec::new(),
+            sync_drift: Vec::new(),
+        }
+    }
+
+    fn detail() -> CommitDetail {
+        CommitDetail {
+            hash: "abc1234def5678".into(),
+            author: "Jane <jane@example.com>".into(),
+            author_date: "2026-01-01 10:00:00 +0000".into(),
+            committer: "Jane <jane@example.com>".into(),
+            committer_date: "2026-01-01 10:00:00 +0000".into(),
+            parents: vec!["0123456789".into()],
+            refs: vec![],
+            subject: "Fix bug".into(),
+            body: String::new(),
+            files: vec![],
+            diff: "+a\n+b\n".into(),
+        }
+    }
+
+    #[test]
+    fn test_commit_detail_actions() {
+        let repo = MockRepoBuilder::default()
+            .with_worktrees(vec![
+                worktree("/hub", "", true),
+                worktree("/hub/main", "main", false),
+                worktree("/hub/dev", "dev", false),
+            ])
+            .build();
+        let (async_tx, _async_rx) = mpsc::unbounded_channel();
+        let detail = detail();
+        let mut scroll = 0;
+        let current = AppState::SettingUpDefaults;
+
+        let run = |event: &Event, scroll: &mut u16| {
+            handle_commit_detail_events(
+                event,
+                &repo,
+                "/hub/main",
+                "main",
+                &detail,
+                scroll,
+                commit_detail_max_scroll(&detail),
+                &AppState::SetupComplete,
+                &current,
+                &async_tx,
+            )
+        };
+
+        // Scrolling is clamped to the content.
+        assert!(run(&key('j'), &mut scroll).is_none());
+        assert_eq!(scroll, 1);
+        let end = Event::Key(KeyEvent::new(KeyCode::End, KeyModifiers::empty()));
+        run(&end, &mut scroll);
+        run(&key('j'), &mut scroll);
+        let max = scroll;
+        run(&key('j'), &mut scroll);
+        assert_eq!(scroll, max);
+
+        match run(&key('w'), &mut scroll) {
+            Some(AppState::Prompting {
+                prompt_type: PromptType::NameNewWorktree { base_ref, .. },
+                ..
+            }) => assert_eq!(base_ref, "abc1234def5678"),
+            other => panic!("Expected worktree name prompt, got {other:?}"),
+        }
+
+        match run(&key('p'), &mut scroll) {
+            Some(AppState::PickingCherryPickTarget {
+                hashes,
+                source,
+                targets,
+                ..
+            }) => {
+                assert_eq!(hashes, vec!["abc1234def5678"]);
+                assert_eq!(source, "/hub/main");
+                let branches: Vec<&str> = targets.iter().map(|t| t.branch.as_str()).collect();
+                assert_eq!(branches, vec!["dev"]);
+            }
+            other => panic!("Expected cherry-pick target picker, got {other:?}"),
+        }
+
+        assert!(matches!(
+            run(&key('q'), &mut scroll),
+            Some(AppState::SetupComplete)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cherry_pick_target_enter_applies_commit() {
+        let repo = MockRepoBuilder::default().build();
+        let (async_tx, mut async_rx) = mpsc::unbounded_channel();
+        let targets = vec![worktree("/hub/dev", "dev", false)];
+        let mut selected = 0;
+
+        let res = handle_cherry_pick_target_events(
+            &Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty())),
+            &repo,
+            &["abc1234def5678".to_string()],
+            "/hub/main",
+            &targets,
+            &mut selected,
+            &AppState::SetupComplete,
+            &async_tx,
+        );
+        match res {
+            Some(AppState::ApplyingCommit { message, .. }) => {
+                assert_eq!(message, "Cherry-picking abc1234 into dev");
+            }
+            other => panic!("Expected ApplyingCommit state, got {other:?}"),
+        }
+        match async_rx.recv().await {
+            Some(AsyncResult::CommitApplied { result }) => {
+                assert_eq!(result.unwrap(), "Cherry-picked abc1234 into dev");
+            }
+            _ => panic!("Expected CommitApplied result"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cherry_pick_target_move_reports_conflict() {
+        let repo = MockRepoBuilder::default().build();
+        let (async_tx, mut async_rx) = mpsc::unbounded_channel();
+        let targets = vec![worktree("/hub/dev", "dev", false)];
+        let hashes = vec!["aaa1111".to_string(), "conflict".to_string()];
+        let mut selected = 0;
+
+        let res = handle_cherry_pick_target_events(
+            &key('m'),
+            &repo,
+            &hashes,
+            "/hub/main",
+            &targets,
+            &mut selected,
+            &AppState::SetupComplete,
+            &async_tx,
+        );
+        match res {
+            Some(AppState::ApplyingCommit { message, .. }) => {
+                assert_eq!(message, "Moving 2 commits into dev");
+            }
+            other => panic!("Expected ApplyingCommit state, got {other:?}"),
+        }
+        match async_rx.recv().await {
+            Some(AsyncResult::CommitApplied { result }) => {
+                let err = result.unwrap_err().to_string();
+                assert!(err.contains("(1 of 2 applied)"));
+                assert!(err.contains("mock explanation"));
+            }
+            _ => panic!("Expected CommitApplied result"),
+        }
+    }
+}
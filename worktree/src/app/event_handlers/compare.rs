@@ -0,0 +1,251 @@
+use crate::app::async_tasks::AsyncResult;
+use crate::app::model::AppState;
+use crate::app::renderers::compare::comparison_lines;
+use crate::domain::repository::{ProjectRepository, RefComparison, Worktree};
+use crate::ui::theme::CyberTheme;
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::helpers::scroll_text;
+
+/// Builds the compare picker for `source`: other worktrees first, then the remaining branches.
+pub fn pick_compare_target<R: ProjectRepository>(
+    repo: &R,
+    source: &Worktree,
+    worktrees: &[Worktree],
+    current_state: &AppState,
+) -> AppState {
+    let left = source.head_ref().to_string();
+    let mut options: Vec<String> = worktrees
+        .iter()
+        .filter(|wt| !wt.is_bare && wt.path != source.path)
+        .map(|wt| wt.head_ref().to_string())
+        .filter(|r| *r != left)
+        .collect();
+    match repo.list_branches() {
+        Ok(branches) => {
+            for branch in branches {
+                if branch != left && !options.contains(&branch) {
+                    options.push(branch);
+                }
+            }
+        }
+        Err(e) => {
+            return AppState::Error(
+                format!("Failed to list branches: {e}"),
+                Box::new(current_state.clone()),
+            );
+        }
+    }
+    if options.is_empty() {
+        return AppState::Error(
+            format!("Nothing to compare '{left}' with"),
+            Box::new(current_state.clone()),
+        );
+    }
+    AppState::PickingCompareTarget {
+        path: source.path.clone(),
+        left,
+        options,
+        selected_index: 0,
+        prev_state: Box::new(current_state.clone()),
+    }
+}
+
+/// Runs the comparison in the background; `prev_state` is where the result view returns to.
+fn start_comparison<R: ProjectRepository + Clone + Send + Sync + 'static>(
+    repo: &R,
+    path: &str,
+    left: &str,
+    right: &str,
+    prev_state: &AppState,
+    async_tx: &UnboundedSender<AsyncResult>,
+) -> AppState {
+    let repo_clone = repo.clone();
+    let tx = async_tx.clone();
+    let (path, left_ref, right_ref) = (path.to_string(), left.to_string(), right.to_string());
+    tokio::task::spawn_blocking(move || {
+        let result = repo_clone.compare_refs(&path, &left_ref, &right_ref);
+        let _ = tx.send(AsyncResult::ComparisonFetched { path, result });
+    });
+    AppState::LoadingComparison {
+        left: left.to_string(),
+        right: right.to_string(),
+        prev_state: Box::new(prev_state.clone()),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle_compare_target_events<R: ProjectRepository + Clone + Send + Sync + 'static>(
+    event: &crossterm::event::Event,
+    repo: &R,
+    path: &str,
+    left: &str,
+    options: &[String],
+    selected_index: &mut usize,
+    prev_state: &AppState,
+    async_tx: &UnboundedSender<AsyncResult>,
+) -> Option<AppState> {
+    use crossterm::event::{Event, KeyCode};
+    let Event::Key(key) = event else {
+        return None;
+    };
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => return Some(prev_state.clone()),
+        KeyCode::Down | KeyCode::Char('j') if !options.is_empty() => {
+            *selected_index = (*selected_index + 1) % options.len();
+        }
+        KeyCode::Up | KeyCode::Char('k') if !options.is_empty() => {
+            *selected_index = (*selected_index + options.len() - 1) % options.len();
+        }
+        KeyCode::Enter => {
+            let right = options.get(*selected_index)?;
+            return Some(start_comparison(
+                repo, path, left, right, prev_state, async_tx,
+            ));
+        }
+        _ => {}
+    }
+    None
+}
+
+pub fn handle_comparison_events<R: ProjectRepository + Clone + Send + Sync + 'static>(
+    event: &crossterm::event::Event,
+    repo: &R,
+    path: &str,
+    comparison: &RefComparison,
+    scroll: &mut u16,
+    prev_state: &AppState,
+    async_tx: &UnboundedSender<AsyncResult>,
+) -> Option<AppState> {
+    use crossterm::event::{Event, KeyCode};
+
+    let max_scroll = u16::try_from(
+        comparison_lines(comparison, &CyberTheme::default())
+            .len()
+            .saturating_sub(1),
+    )
+    .unwrap_or(u16::MAX);
+    if scroll_text(event, scroll, max_scroll) {
+        return None;
+    }
+
+    let Event::Key(key) = event else {
+        return None;
+    };
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q' | 'Q') => Some(prev_state.clone()),
+        KeyCode::Char('s' | 'S') => Some(start_comparison(
+            repo,
+            path,
+            &comparison.right,
+            &comparison.left,
+            prev_state,
+            async_tx,
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::test_utils::scaffolding::MockRepoBuilder;
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+    use tokio::sync::mpsc;
+
+    fn worktree(path: &str, branch: &str, is_detached: bool) -> Worktree {
+        Worktree {
+            path: path.into(),
+            commit: "abcdef1".into(),
+            branch: branch.into(),
+            is_bare: false,
+            is_detached,
+            status_summary: None,
+            size_bytes: 0,
+            metadata: None,
+            upstream: None,
+            contexts: Vec::new(),
+            sync_drift: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_pick_compare_target_lists_other_worktrees() {
+        let repo = MockRepoBuilder::default().build();
+        let worktrees = vec![
+            worktree("/hub/main", "main", false),
+            worktree("/hub/feat", "feat", false),
+            worktree("/hub/bisect", "", true),
+        ];
+
+        match pick_compare_target(&repo, &worktrees[1], &worktrees, &AppState::SetupComplete) {
+            AppState::PickingCompareTarget {
+                path,
+                left,
+                options,
+                ..
+            } => {
+                assert_eq!(path, "/hub/feat");
+                assert_eq!(left, "feat");
+                assert_eq!(options, vec!["main", "abcdef1"]);
+            }
+            other => panic!("Expected PickingCompareTarget, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compare_target_enter_and_swap() {
+        let repo = MockRepoBuilder::default().build();
+        let (async_tx, mut async_rx) = mpsc::unbounded_channel();
+        let options = vec!["main".to_string(), "dev".to_string()];
+        let mut selected = 0;
+        let enter = Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        let down = Event::Key(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
+
+        handle_compare_target_events(
+            &down,
+            &repo,
+            "/hub/feat",
+            "feat",
+            &options,
+            &mut selected,
+            &AppState::SetupComplete,
+            &async_tx,
+        );
+        let res = handle_compare_target_events(
+            &enter,
+            &repo,
+            "/hub/feat",
+            "feat",
+            &options,
+            &mut selected,
+            &AppState::SetupComplete,
+            &async_tx,
+        );
+        assert!(matches!(
+            res,
+            Some(AppState::LoadingComparison { ref left, ref right, .. }) if left == "feat" && right == "dev"
+        ));
+        let Some(AsyncResult::ComparisonFetched { result, .. }) = async_rx.recv().await else {
+            panic!("Expected ComparisonFetched result");
+        };
+        let comparison = result.unwrap();
+
+        let mut scroll = 0;
+        let swap = Event::Key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::empty()));
+        let res = handle_comparison_events(
+            &swap,
+            &repo,
+            "/hub/feat",
+            &comparison,
+            &mut scroll,
+            &AppState::SetupComplete,
+            &async_tx,
+        );
+        assert!(matches!(
+            res,
+            Some(AppState::LoadingComparison { ref left, ref right, .. }) if left == "dev" && right == "feat"
+        ));
+    }
+}
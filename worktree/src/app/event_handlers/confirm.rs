@@ -1,7 +1,27 @@
+use crate::app::event_handlers::branch_manager::reload_branches;
 use crate::app::intent::Intent;
 use crate::app::model::AppState;
+use crate::domain::artifacts::find_worktree_artifacts;
 use crate::domain::repository::ProjectRepository;
 
+/// Removes artifacts from every worktree except the one the TUI was started in.
+fn clean_artifacts<R: ProjectRepository>(
+    repo: &R,
+    inactive_days: Option<u64>,
+) -> anyhow::Result<()> {
+    let current_dir = std::env::current_dir()?.canonicalize()?;
+    let now = chrono::Utc::now().timestamp();
+    for wt in find_worktree_artifacts(repo, inactive_days, now)? {
+        let is_current = std::path::Path::new(&wt.path)
+            .canonicalize()
+            .is_ok_and(|p| current_dir.starts_with(p));
+        if !is_current {
+            repo.remove_artifacts(&wt.path, &wt.artifacts)?;
+        }
+    }
+    Ok(())
+}
+
 pub fn handle_confirm_events<R: ProjectRepository>(
     event: &crossterm::event::Event,
     repo: &R,
@@ -31,6 +51,43 @@ pub fn handle_confirm_events<R: ProjectRepository>(
                     Box::new(prev_state.clone()),
                 ));
             }
+            if let Intent::DeleteBranch { name, .. } = action {
+                return Some(match repo.delete_branch(name) {
+                    Ok(()) => reload_branches(repo, prev_state),
+                    Err(e) => AppState::Error(
+                        format!("Failed to delete branch: {e}"),
+                        Box::new(prev_state.clone()),
+                    ),
+                });
+            }
+            if let Intent::CleanArtifacts { inactive_days, .. } = action {
+                let mut listing = prev_state.clone();
+                listing.request_refresh();
+                return Some(match clean_artifacts(repo, *inactive_days) {
+                    Ok(()) => listing,
+                    Err(e) => AppState::Error(
+                        format!("Failed to clean artifacts: {e}"),
+                        Box::new(listing),
+                    ),
+                });
+            }
+            if let Intent::RemoveWorktrees { paths, force } = action {
+                // Back to the listing the checklist was opened from, reloaded.
+                let mut listing = match prev_state {
+                    AppState::ReviewingStale { prev_state, .. } => (**prev_state).clone(),
+                    other => other.clone(),
+                };
+                listing.request_refresh();
+                for path in paths {
+                    if let Err(e) = repo.remove_worktree(path, *force) {
+                        return Some(AppState::Error(
+                            format!("Failed to remove worktree: {e}"),
+                            Box::new(listing),
+                        ));
+                    }
+                }
+                return Some(listing);
+            }
             Some(prev_state.clone())
         }
         KeyCode::Esc | KeyCode::Char('n' | 'q') => Some(prev_state.clone()),
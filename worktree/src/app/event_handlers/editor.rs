@@ -1,8 +1,42 @@
-use crate::app::model::{AppState, EditorConfig};
+use crate::app::model::{AppState, EditorConfig, EditorTarget};
 use crate::domain::repository::ProjectRepository;
-use std::process::Command;
+use crate::infrastructure::editor::{resolve_editor, sort_by_availability};
 
-use super::helpers::create_timed_state;
+/// Opens `target` with the preferred editor (project, then global, then `$VISUAL`/`$EDITOR`),
+/// or shows the picker when none resolves or `always_pick` is set.
+pub fn open_in_editor<R: ProjectRepository>(
+    repo: &R,
+    branch: &str,
+    target: EditorTarget,
+    always_pick: bool,
+    prev_state: AppState,
+) -> AppState {
+    let mut editors = repo
+        .list_editors()
+        .unwrap_or_else(|_| EditorConfig::defaults());
+
+    if !always_pick {
+        let preferred = repo.get_preferred_editor().ok().flatten();
+        if let Some(editor) = resolve_editor(preferred.as_deref(), &editors) {
+            return AppState::LaunchingEditor {
+                branch: branch.to_string(),
+                editor,
+                target,
+                prev_state: Box::new(prev_state),
+            };
+        }
+    }
+
+    let installed = sort_by_availability(&mut editors);
+    AppState::SelectingEditor {
+        branch: branch.to_string(),
+        options: editors,
+        installed,
+        selected: 0,
+        target,
+        prev_state: Box::new(prev_state),
+    }
+}
 
 pub fn handle_editor_events<R: ProjectRepository>(
     event: &crossterm::event::Event,
@@ -10,6 +44,7 @@ pub fn handle_editor_events<R: ProjectRepository>(
     branch: &str,
     options: &[EditorConfig],
     selected: &mut usize,
+    target: &EditorTarget,
     prev_state: &AppState,
 ) -> Option<AppState> {
     use crossterm::event::{Event, KeyCode};
@@ -24,6 +59,11 @@ pub fn handle_editor_events<R: ProjectRepository>(
         _ => key_code,
     };
 
+    if options.is_empty() {
+        return matches!(normalized_code, KeyCode::Esc | KeyCode::Enter)
+            .then(|| prev_state.clone());
+    }
+
     match normalized_code {
         KeyCode::Up | KeyCode::Char('k') => {
             *selected = if *selected > 0 {
@@ -40,31 +80,21 @@ pub fn handle_editor_events<R: ProjectRepository>(
             };
         }
         KeyCode::Enter => {
-            let editor = options[*selected].command.clone();
-            let _ = repo.set_preferred_editor(&editor);
-            let path = if let AppState::ListingWorktrees {
-                worktrees,
-                table_state,
-                ..
-            } = prev_state
-            {
-                table_state
-                    .selected()
-                    .and_then(|i| worktrees.get(i))
-                    .map(|wt| wt.path.clone())
-            } else {
-                None
-            };
-            if let Some(p) = path {
-                let prev_clone = prev_state.clone();
-                let opening_state = AppState::OpeningEditor {
-                    branch: branch.to_string(),
-                    editor,
-                    prev_state: Box::new(prev_state.clone()),
-                };
-                let _ = Command::new(&options[*selected].command).arg(&p).spawn();
-                return Some(create_timed_state(opening_state, prev_clone, 800));
+            let editor = options.get(*selected)?.clone();
+            // Remember the choice for this project; fall back to the global preference.
+            let saved = repo.get_project_config().and_then(|mut config| {
+                config.editor = Some(editor.name.clone());
+                repo.save_project_config(&config)
+            });
+            if saved.is_err() {
+                let _ = repo.set_preferred_editor(&editor.command);
             }
+            return Some(AppState::LaunchingEditor {
+                branch: branch.to_string(),
+                editor,
+                target: target.clone(),
+                prev_state: Box::new(prev_state.clone()),
+            });
         }
         KeyCode::Esc => {
             return Some(prev_state.clone());
@@ -29,3 +29,29 @@ pub fn move_selection(state: &mut TableState, len: usize, delta: isize) {
     });
     state.select(Some(i));
 }
+
+/// Scrolls a text pane with j/k, arrows, PgUp/PgDn/Space, Home/End and the mouse wheel.
+///
+/// Returns whether the event was a scroll event.
+pub fn scroll_text(event: &crossterm::event::Event, scroll: &mut u16, max_scroll: u16) -> bool {
+    use crossterm::event::{Event, KeyCode, MouseEventKind};
+    let delta: i32 = match event {
+        Event::Key(key) => match key.code {
+            KeyCode::Down | KeyCode::Char('j') => 1,
+            KeyCode::Up | KeyCode::Char('k') => -1,
+            KeyCode::PageDown | KeyCode::Char(' ') => 15,
+            KeyCode::PageUp => -15,
+            KeyCode::Home => i32::MIN / 2,
+            KeyCode::End => i32::MAX / 2,
+            _ => return false,
+        },
+        Event::Mouse(mouse) => match mouse.kind {
+            MouseEventKind::ScrollDown => 3,
+            MouseEventKind::ScrollUp => -3,
+            _ => return false,
+        },
+        _ => return false,
+    };
+    *scroll = (i32::from(*scroll).saturating_add(delta)).clamp(0, i32::from(max_scroll)) as u16;
+    true
+}
@@ -1,16 +1,134 @@
-use crate::app::model::AppState;
+use crate::app::async_tasks::AsyncResult;
+use crate::app::model::{AppState, EditorTarget};
+use crate::domain::repository::{ChangedFile, GitCommit, HistoryQuery, ProjectRepository};
+use tokio::sync::mpsc::UnboundedSender;
 
+use super::commit_detail::pick_port_target;
+use super::editor::open_in_editor;
+
+/// How close to the last loaded row the selection gets before the next page is fetched.
+const PREFETCH_DISTANCE: usize = 5;
+
+/// Real commits (not graph-only rows) that are already loaded.
+fn loaded_commits(commits: &[GitCommit]) -> usize {
+    commits.iter().filter(|c| !c.hash.is_empty()).count()
+}
+
+/// Replaces the list with the first page of `query`.
 #[allow(clippy::too_many_arguments)]
-pub fn handle_history_events(
+fn reload_history<R: ProjectRepository>(
+    repo: &R,
+    path: &str,
+    commits: &mut Vec<GitCommit>,
+    selected_index: &mut usize,
+    query: &mut HistoryQuery,
+    has_more: &mut bool,
+    loading_more: &mut bool,
+    mut new_query: HistoryQuery,
+) -> anyhow::Result<()> {
+    new_query.skip = 0;
+    let page = repo.get_history(path, &new_query)?;
+    *has_more = loaded_commits(&page) >= new_query.limit;
+    // A page still in flight belongs to the old query and is dropped when it arrives.
+    *loading_more = false;
+    *selected_index = page.iter().position(|c| !c.hash.is_empty()).unwrap_or(0);
+    *commits = page;
+    *query = new_query;
+    Ok(())
+}
+
+/// Fetches the next page in the background once the selection gets close to the end of what
+/// is loaded. The view appends it when [`AsyncResult::HistoryPageFetched`] arrives.
+#[allow(clippy::too_many_arguments)]
+fn load_more_if_needed<R: ProjectRepository + Clone + Send + Sync + 'static>(
+    repo: &R,
+    path: &str,
+    commits: &[GitCommit],
+    selected_index: usize,
+    query: &mut HistoryQuery,
+    has_more: bool,
+    loading_more: &mut bool,
+    async_tx: &UnboundedSender<AsyncResult>,
+) {
+    if !has_more || *loading_more || selected_index + PREFETCH_DISTANCE < commits.len() {
+        return;
+    }
+    query.skip = loaded_commits(commits);
+    *loading_more = true;
+    let repo_clone = repo.clone();
+    let path_clone = path.to_string();
+    let page_query = query.clone();
+    let tx = async_tx.clone();
+    tokio::task::spawn_blocking(move || {
+        let result = repo_clone.get_history(&path_clone, &page_query);
+        let _ = tx.send(AsyncResult::HistoryPageFetched {
+            path: path_clone,
+            query: page_query,
+            result,
+        });
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle_history_events<R: ProjectRepository + Clone + Send + Sync + 'static>(
     event: &crossterm::event::Event,
-    commits: &[crate::domain::repository::GitCommit],
+    repo: &R,
+    path: &str,
+    branch: &str,
+    commits: &mut Vec<GitCommit>,
     selected_index: &mut usize,
+    query: &mut HistoryQuery,
+    has_more: &mut bool,
+    loading_more: &mut bool,
+    search: &mut Option<String>,
+    marked: &mut Vec<String>,
     prev_state: &AppState,
+    current_state: &AppState,
+    async_tx: &UnboundedSender<AsyncResult>,
 ) -> Option<AppState> {
-    use crate::app::renderers::helpers::centered_rect;
+    use crate::app::renderers::history::{history_list_area, history_scroll_offset};
     use crossterm::event::{Event, KeyCode, MouseButton, MouseEventKind};
     use ratatui::layout::Rect;
 
+    let load_error = |e: anyhow::Error| {
+        Some(AppState::Error(
+            format!("Failed to fetch history: {e}"),
+            Box::new(current_state.clone()),
+        ))
+    };
+
+    if let Some(input) = search {
+        let Event::Key(key) = event else {
+            return None;
+        };
+        match key.code {
+            KeyCode::Esc => *search = None,
+            KeyCode::Enter => {
+                let mut new_query = query.clone();
+                new_query.apply_search(input);
+                *search = None;
+                if let Err(e) = reload_history(
+                    repo,
+                    path,
+                    commits,
+                    selected_index,
+                    query,
+                    has_more,
+                    loading_more,
+                    new_query,
+                ) {
+                    return load_error(e);
+                }
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+        return None;
+    }
+
     match event {
         Event::Key(key) => {
             let key_code = key.code;
@@ -20,74 +138,584 @@ pub fn handle_history_events(
             };
 
             match normalized_code {
+                KeyCode::Esc if query.is_filtered() => {
+                    let mut new_query = query.clone();
+                    new_query.apply_search("");
+                    if let Err(e) = reload_history(
+                        repo,
+                        path,
+                        commits,
+                        selected_index,
+                        query,
+                        has_more,
+                        loading_more,
+                        new_query,
+                    ) {
+                        return load_error(e);
+                    }
+                }
                 KeyCode::Esc | KeyCode::Char('q') => {
                     return Some(prev_state.clone());
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if !commits.is_empty() {
-                        let mut next = (*selected_index + 1) % commits.len();
-                        // Skip graph-only lines
-                        while next != *selected_index && commits[next].hash.is_empty() {
-                            next = (next + 1) % commits.len();
-                        }
+                KeyCode::Char('/') => *search = Some(query.search_text()),
+                KeyCode::Down | KeyCode::Char('j') if !commits.is_empty() => {
+                    load_more_if_needed(
+                        repo,
+                        path,
+                        commits,
+                        *selected_index,
+                        query,
+                        *has_more,
+                        loading_more,
+                        async_tx,
+                    );
+                    let mut next = (*selected_index + 1) % commits.len();
+                    // Skip graph-only lines
+                    while next != *selected_index && commits[next].hash.is_empty() {
+                        next = (next + 1) % commits.len();
+                    }
+                    // Wait at the last row for the page being fetched instead of wrapping.
+                    if next > *selected_index || !*has_more {
                         *selected_index = next;
                     }
                 }
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if !commits.is_empty() {
-                        let mut prev = (*selected_index + commits.len() - 1) % commits.len();
-                        // Skip graph-only lines
-                        while prev != *selected_index && commits[prev].hash.is_empty() {
-                            prev = (prev + commits.len() - 1) % commits.len();
-                        }
-                        *selected_index = prev;
+                KeyCode::Up | KeyCode::Char('k') if !commits.is_empty() => {
+                    let mut prev = (*selected_index + commits.len() - 1) % commits.len();
+                    // Skip graph-only lines
+                    while prev != *selected_index && commits[prev].hash.is_empty() {
+                        prev = (prev + commits.len() - 1) % commits.len();
                     }
+                    *selected_index = prev;
+                }
+                KeyCode::Enter => {
+                    let commit = commits
+                        .get(*selected_index)
+                        .filter(|c| !c.hash.is_empty())?;
+                    let repo_clone = repo.clone();
+                    let path_clone = path.to_string();
+                    let hash = commit.hash.clone();
+                    let tx = async_tx.clone();
+                    tokio::task::spawn_blocking(move || {
+                        let result = repo_clone.get_commit_detail(&path_clone, &hash);
+                        let _ = tx.send(AsyncResult::CommitDetailFetched {
+                            path: path_clone,
+                            result,
+                        });
+                    });
+                    return Some(AppState::LoadingCommitDetail {
+                        branch: branch.to_string(),
+                        prev_state: Box::new(current_state.clone()),
+                    });
+                }
+                KeyCode::Char(' ') => {
+                    let commit = commits
+                        .get(*selected_index)
+                        .filter(|c| !c.hash.is_empty())?;
+                    if let Some(pos) = marked.iter().position(|h| *h == commit.hash) {
+                        marked.remove(pos);
+                    } else {
+                        marked.push(commit.hash.clone());
+                    }
+                }
+                KeyCode::Char('p') => {
+                    // The log lists newest first; commits are applied oldest first.
+                    let hashes: Vec<String> = if marked.is_empty() {
+                        let commit = commits
+                            .get(*selected_index)
+                            .filter(|c| !c.hash.is_empty())?;
+                        vec![commit.hash.clone()]
+                    } else {
+                        commits
+                            .iter()
+                            .rev()
+                            .filter(|c| marked.contains(&c.hash))
+                            .map(|c| c.hash.clone())
+                            .collect()
+                    };
+                    return Some(pick_port_target(repo, path, hashes, current_state));
+                }
+                KeyCode::Char('f' | 'e') => {
+                    let commit = commits
+                        .get(*selected_index)
+                        .filter(|c| !c.hash.is_empty())?;
+                    return Some(match repo.get_commit_files(path, &commit.hash) {
+                        Ok(files) => AppState::ViewingCommitFiles {
+                            path: path.to_string(),
+                            branch: branch.to_string(),
+                            commit: commit.clone(),
+                            files,
+                            selected_index: 0,
+                            prev_state: Box::new(current_state.clone()),
+                        },
+                        Err(e) => AppState::Error(
+                            format!("Failed to list files of {}: {e}", commit.hash),
+                            Box::new(current_state.clone()),
+                        ),
+                    });
                 }
                 _ => {}
             }
         }
-        Event::Mouse(mouse) => {
-            match mouse.kind {
-                MouseEventKind::Down(MouseButton::Left) => {
-                    if let Ok((w, h)) = crossterm::terminal::size() {
-                        let area = Rect::new(0, 0, w, h);
-                        let popup_area = centered_rect(85, 80, area);
-
-                        // Inner area calculation (borders)
-                        let inner_x = popup_area.x + 1;
-                        let inner_y = popup_area.y + 1;
-                        let inner_w = popup_area.width.saturating_sub(2);
-                        let inner_h = popup_area.height.saturating_sub(2);
-
-                        let col = mouse.column;
-                        let row = mouse.row;
-
-                        if col >= inner_x
-                            && col < inner_x + inner_w
-                            && row >= inner_y
-                            && row < inner_y + inner_h
-                        {
-                            let relative_y = row.saturating_sub(inner_y) as usize;
-                            if relative_y < commits.len() && !commits[relative_y].hash.is_empty() {
-                                *selected_index = relative_y;
-                            }
+        Event::Mouse(mouse) => match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Ok((w, h)) = crossterm::terminal::size() {
+                    let list_area = history_list_area(Rect::new(0, 0, w, h), query.is_filtered());
+                    let col = mouse.column;
+                    let row = mouse.row;
+
+                    if col >= list_area.x
+                        && col < list_area.x + list_area.width
+                        && row >= list_area.y
+                        && row < list_area.y + list_area.height
+                    {
+                        let index = history_scroll_offset(*selected_index, list_area.height)
+                            + usize::from(row - list_area.y);
+                        if index < commits.len() && !commits[index].hash.is_empty() {
+                            *selected_index = index;
                         }
                     }
                 }
-                MouseEventKind::ScrollDown => {
-                    if !commits.is_empty() {
-                        *selected_index = (*selected_index + 1) % commits.len();
-                    }
+            }
+            MouseEventKind::ScrollDown if !commits.is_empty() => {
+                load_more_if_needed(
+                    repo,
+                    path,
+                    commits,
+                    *selected_index,
+                    query,
+                    *has_more,
+                    loading_more,
+                    async_tx,
+                );
+                if *selected_index + 1 < commits.len() || !*has_more {
+                    *selected_index = (*selected_index + 1) % commits.len();
                 }
-                MouseEventKind::ScrollUp => {
-                    if !commits.is_empty() {
-                        *selected_index = (*selected_index + commits.len() - 1) % commits.len();
-                    }
+            }
+            MouseEventKind::ScrollUp if !commits.is_empty() => {
+                *selected_index = (*selected_index + commits.len() - 1) % commits.len();
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+    None
+}
+
+/// Navigates a commit's changed files; `Enter`/`e` opens the selected one at its first hunk.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_commit_files_events<R: ProjectRepository>(
+    event: &crossterm::event::Event,
+    repo: &R,
+    path: &str,
+    branch: &str,
+    files: &[ChangedFile],
+    selected_index: &mut usize,
+    prev_state: &AppState,
+    current_state: &AppState,
+) -> Option<AppState> {
+    use crossterm::event::{Event, KeyCode, MouseEventKind};
+
+    match event {
+        Event::Key(key) => match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return Some(prev_state.clone()),
+            KeyCode::Down | KeyCode::Char('j') if !files.is_empty() => {
+                *selected_index = (*selected_index + 1) % files.len();
+            }
+            KeyCode::Up | KeyCode::Char('k') if !files.is_empty() => {
+                *selected_index = (*selected_index + files.len() - 1) % files.len();
+            }
+            KeyCode::Enter | KeyCode::Char('e' | 'E') => {
+                let file = files.get(*selected_index)?;
+                let absolute = std::path::Path::new(path).join(&file.path);
+                if file.status == 'D' || !absolute.exists() {
+                    return Some(AppState::Error(
+                        format!("{} no longer exists in this worktree", file.path),
+                        Box::new(current_state.clone()),
+                    ));
                 }
-                _ => {}
+                let target = EditorTarget {
+                    path: path.to_string(),
+                    file: Some(absolute.to_string_lossy().to_string()),
+                    line: file.line,
+                };
+                return Some(open_in_editor(
+                    repo,
+                    branch,
+                    target,
+                    key.code == KeyCode::Char('E'),
+                    current_state.clone(),
+                ));
             }
-        }
+            _ => {}
+        },
+        Event::Mouse(mouse) => match mouse.kind {
+            MouseEventKind::ScrollDown if !files.is_empty() => {
+                *selected_index = (*selected_index + 1) % files.len();
+            }
+            MouseEventKind::ScrollUp if !files.is_empty() => {
+                *selected_index = (*selected_index + files.len() - 1) % files.len();
+            }
+            _ => {}
+        },
         _ => {}
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::test_utils::scaffolding::MockRepoBuilder;
+    use crate::domain::repository::{GitCommit, Worktree};
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+    use tokio::sync::mpsc;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    fn sample_commits() -> Vec<GitCommit> {
+        vec![GitCommit {
+            hash: "abc1234".into(),
+            author: "Jane".into(),
+            date: "2026-01-01".into(),
+            message: "Fix bug".into(),
+            graph: "*".into(),
+        }]
+    }
+
+    #[tokio::test]
+    async fn test_history_enter_loads_commit_detail() {
+        let repo = MockRepoBuilder::default().build();
+        let mut commits = sample_commits();
+        let mut selected = 0;
+        let (async_tx, mut async_rx) = mpsc::unbounded_channel();
+
+        let res = handle_history_events(
+            &key(KeyCode::Enter),
+            &repo,
+            "/hub/main",
+            "main",
+            &mut commits,
+            &mut selected,
+            &mut HistoryQuery::default(),
+            &mut false,
+            &mut false,
+            &mut None,
+            &mut Vec::new(),
+            &AppState::SetupComplete,
+            &AppState::SettingUpDefaults,
+            &async_tx,
+        );
+        match res {
+            Some(AppState::LoadingCommitDetail { prev_state, .. }) => {
+                assert!(matches!(*prev_state, AppState::SettingUpDefaults));
+            }
+            _ => panic!("Expected LoadingCommitDetail state, got {res:?}"),
+        }
+        match async_rx.recv().await {
+            Some(AsyncResult::CommitDetailFetched { path, result }) => {
+                assert_eq!(path, "/hub/main");
+                assert_eq!(result.unwrap().hash, "abc1234");
+            }
+            _ => panic!("Expected CommitDetailFetched result"),
+        }
+    }
+
+    #[test]
+    fn test_history_f_lists_commit_files() {
+        let repo = MockRepoBuilder::default().build();
+        let mut commits = sample_commits();
+        let mut selected = 0;
+        let (async_tx, _async_rx) = mpsc::unbounded_channel();
+
+        let res = handle_history_events(
+            &key(KeyCode::Char('f')),
+            &repo,
+            "/hub/main",
+            "main",
+            &mut commits,
+            &mut selected,
+            &mut HistoryQuery::default(),
+            &mut false,
+            &mut false,
+            &mut None,
+            &mut Vec::new(),
+            &AppState::SetupComplete,
+            &AppState::SettingUpDefaults,
+            &async_tx,
+        );
+        match res {
+            Some(AppState::ViewingCommitFiles {
+                commit, prev_state, ..
+            }) => {
+                assert_eq!(commit.hash, "abc1234");
+                assert!(matches!(*prev_state, AppState::SettingUpDefaults));
+            }
+            _ => panic!("Expected ViewingCommitFiles state, got {res:?}"),
+        }
+    }
+
+    #[test]
+    fn test_history_marks_and_ports_oldest_first() {
+        let repo = MockRepoBuilder::default()
+            .with_worktrees(vec![Worktree {
+                path: "/hub/dev".into(),
+                commit: "abcdef1".into(),
+                branch: "dev".into(),
+                is_bare: false,
+                is_detached: false,
+                status_summary: None,
+                size_bytes: 0,
+                metadata: None,
+                upstream: None,
+                contexts: Vec::new(),
+                sync_drift: Vec::new(),
+            }])
+            .build();
+        let mut commits = numbered_commits(3);
+        let mut selected = 0;
+        let mut marked = Vec::new();
+        let (async_tx, _async_rx) = mpsc::unbounded_channel();
+
+        let mut press = |code: KeyCode, selected: &mut usize, marked: &mut Vec<String>| {
+            handle_history_events(
+                &key(code),
+                &repo,
+                "/hub/main",
+                "main",
+                &mut commits,
+                selected,
+                &mut HistoryQuery::default(),
+                &mut false,
+                &mut false,
+                &mut None,
+                marked,
+                &AppState::SetupComplete,
+                &AppState::SettingUpDefaults,
+                &async_tx,
+            )
+        };
+
+        press(KeyCode::Char(' '), &mut selected, &mut marked);
+        selected = 2;
+        press(KeyCode::Char(' '), &mut selected, &mut marked);
+        selected = 1;
+        press(KeyCode::Char(' '), &mut selected, &mut marked);
+        press(KeyCode::Char(' '), &mut selected, &mut marked);
+        assert_eq!(marked, vec!["0000000", "0000002"]);
+
+        match press(KeyCode::Char('p'), &mut selected, &mut marked) {
+            Some(AppState::PickingCherryPickTarget {
+                hashes,
+                source,
+                targets,
+                ..
+            }) => {
+                assert_eq!(hashes, vec!["0000002", "0000000"]);
+                assert_eq!(source, "/hub/main");
+                assert_eq!(targets.len(), 1);
+            }
+            other => panic!("Expected PickingCherryPickTarget state, got {other:?}"),
+        }
+    }
+
+    fn numbered_commits(count: usize) -> Vec<GitCommit> {
+        (0..count)
+            .map(|i| GitCommit {
+                hash: format!("{i:07x}"),
+                author: "Jane".into(),
+                date: "2026-01-01".into(),
+                message: if i % 2 == 0 { "fix" } else { "feat" }.to_string() + &i.to_string(),
+                graph: "*".into(),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_history_loads_next_page_near_the_end() {
+        let repo = MockRepoBuilder::default()
+            .with_history(numbered_commits(60))
+            .build();
+        let mut query = HistoryQuery::latest(20);
+        let mut commits = repo.get_history("/hub/main", &query).unwrap();
+        let mut has_more = true;
+        let mut loading_more = false;
+        let mut selected = 10;
+        let (async_tx, mut async_rx) = mpsc::unbounded_channel();
+
+        let press = |commits: &mut Vec<GitCommit>,
+                     selected: &mut usize,
+                     query: &mut HistoryQuery,
+                     has_more: &mut bool,
+                     loading_more: &mut bool| {
+            handle_history_events(
+                &key(KeyCode::Down),
+                &repo,
+                "/hub/main",
+                "main",
+                commits,
+                selected,
+                query,
+                has_more,
+                loading_more,
+                &mut None,
+                &mut Vec::new(),
+                &AppState::SetupComplete,
+                &AppState::SettingUpDefaults,
+                &async_tx,
+            )
+        };
+
+        // Far from the end nothing is fetched.
+        press(
+            &mut commits,
+            &mut selected,
+            &mut query,
+            &mut has_more,
+            &mut loading_more,
+        );
+        assert_eq!((commits.len(), selected, loading_more), (20, 11, false));
+
+        // Near the end the next page is requested once, without blocking the key.
+        selected = 15;
+        press(
+            &mut commits,
+            &mut selected,
+            &mut query,
+            &mut has_more,
+            &mut loading_more,
+        );
+        assert_eq!((commits.len(), selected, loading_more), (20, 16, true));
+        // At the last row the selection waits for the page instead of wrapping.
+        selected = 19;
+        press(
+            &mut commits,
+            &mut selected,
+            &mut query,
+            &mut has_more,
+            &mut loading_more,
+        );
+        assert_eq!(selected, 19);
+
+        match async_rx.recv().await {
+            Some(AsyncResult::HistoryPageFetched {
+                query: page_query,
+                result,
+                ..
+            }) => {
+                assert_eq!(page_query, query);
+                assert_eq!(page_query.skip, 20);
+                let page = result.unwrap();
+                assert_eq!(page[0].hash, format!("{:07x}", 20));
+                assert_eq!(page.len(), 20);
+            }
+            _ => panic!("Expected HistoryPageFetched result"),
+        }
+        assert!(async_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_history_search_reloads_with_filters() {
+        let repo = MockRepoBuilder::default()
+            .with_history(numbered_commits(10))
+            .build();
+        let mut commits = numbered_commits(10);
+        let mut selected = 3;
+        let mut query = HistoryQuery::default();
+        let mut has_more = false;
+        let mut search = None;
+        let (async_tx, _async_rx) = mpsc::unbounded_channel();
+
+        let mut press =
+            |code: KeyCode, commits: &mut Vec<GitCommit>, search: &mut Option<String>| {
+                handle_history_events(
+                    &key(code),
+                    &repo,
+                    "/hub/main",
+                    "main",
+                    commits,
+                    &mut selected,
+                    &mut query,
+                    &mut has_more,
+                    &mut false,
+                    search,
+                    &mut Vec::new(),
+                    &AppState::SetupComplete,
+                    &AppState::SettingUpDefaults,
+                    &async_tx,
+                )
+            };
+
+        press(KeyCode::Char('/'), &mut commits, &mut search);
+        assert_eq!(search.as_deref(), Some(""));
+        for c in "feat author:jane".chars() {
+            press(KeyCode::Char(c), &mut commits, &mut search);
+        }
+        assert!(press(KeyCode::Enter, &mut commits, &mut search).is_none());
+        assert_eq!(search, None);
+        assert_eq!(commits.len(), 5);
+        assert!(commits.iter().all(|c| c.message.starts_with("feat")));
+
+        // Esc clears the filter before leaving the view.
+        assert!(press(KeyCode::Esc, &mut commits, &mut search).is_none());
+        assert_eq!(commits.len(), 10);
+        assert!(matches!(
+            press(KeyCode::Esc, &mut commits, &mut search),
+            Some(AppState::SetupComplete)
+        ));
+    }
+
+    #[test]
+    fn test_commit_files_open_at_first_changed_line() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "fn main() {}\n").unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        let repo = MockRepoBuilder::default().build();
+        let files = vec![
+            ChangedFile {
+                path: "lib.rs".into(),
+                status: 'M',
+                line: Some(12),
+            },
+            ChangedFile {
+                path: "gone.rs".into(),
+                status: 'D',
+                line: Some(1),
+            },
+        ];
+        let mut selected = 0;
+
+        // `E` always shows the picker, carrying the file and line as the target.
+        let res = handle_commit_files_events(
+            &key(KeyCode::Char('E')),
+            &repo,
+            &path,
+            "main",
+            &files,
+            &mut selected,
+            &AppState::SetupComplete,
+            &AppState::SettingUpDefaults,
+        );
+        match res {
+            Some(AppState::SelectingEditor { target, .. }) => {
+                assert_eq!(target.file, Some(format!("{path}/lib.rs")));
+                assert_eq!(target.line, Some(12));
+            }
+            _ => panic!("Expected SelectingEditor state, got {res:?}"),
+        }
+
+        selected = 1;
+        let res = handle_commit_files_events(
+            &key(KeyCode::Enter),
+            &repo,
+            &path,
+            "main",
+            &files,
+            &mut selected,
+            &AppState::SetupComplete,
+            &AppState::SettingUpDefaults,
+        );
+        assert!(matches!(res, Some(AppState::Error(..))));
+    }
+}
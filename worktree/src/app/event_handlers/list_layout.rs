@@ -0,0 +1,357 @@
+use crate::app::model::{AppState, RefreshType, listed_worktrees};
+use crate::domain::list_layout::{ListLayout, arrange_worktrees, group_to_toggle};
+use crate::domain::repository::{ProjectRepository, Worktree};
+
+/// Applies `change` to the listing's layout, saves it for the hub and re-arranges the list.
+///
+/// `change` also gets the selected worktree. The cursor stays on it, or moves to the next
+/// visible worktree when its group was collapsed. Sorts that ask git for every worktree are
+/// left to the background refresh instead of running on the keypress.
+pub fn change_layout<R: ProjectRepository>(
+    repo: &R,
+    current_state: &AppState,
+    change: impl FnOnce(&mut ListLayout, Option<&Worktree>),
+) -> AppState {
+    let mut new_state = current_state.clone();
+    let AppState::ListingWorktrees {
+        worktrees,
+        filtered_indices,
+        table_state,
+        filter_query,
+        layout,
+        refresh_needed,
+        last_selection_change,
+        ..
+    } = &mut new_state
+    else {
+        return new_state;
+    };
+    let selected = table_state
+        .selected()
+        .and_then(|i| filtered_indices.get(i))
+        .and_then(|&idx| worktrees.get(idx))
+        .cloned();
+    change(layout, selected.as_ref());
+
+    if let Err(e) = repo.save_list_layout(layout) {
+        return AppState::Error(
+            format!("Failed to save the list layout: {e}"),
+            Box::new(current_state.clone()),
+        );
+    }
+
+    let needs_git = layout.sort.needs_repo();
+    if !needs_git {
+        arrange_worktrees(repo, worktrees, layout);
+    }
+    *filtered_indices = listed_worktrees(worktrees, filter_query, layout);
+    let cursor = selected
+        .and_then(|wt| worktrees.iter().position(|w| w.path == wt.path))
+        .and_then(|idx| {
+            filtered_indices
+                .iter()
+                .position(|&i| i >= idx)
+                .or_else(|| filtered_indices.len().checked_sub(1))
+        });
+    table_state.select(cursor.or((!filtered_indices.is_empty()).then_some(0)));
+    *refresh_needed = if needs_git {
+        RefreshType::Full
+    } else {
+        RefreshType::Dashboard
+    };
+    *last_selection_change = std::time::Instant::now();
+    new_state
+}
+
+/// Collapses or expands a group with `z`; see [`group_to_toggle`] for which one.
+pub fn toggle_group<R: ProjectRepository>(repo: &R, current_state: &AppState) -> AppState {
+    let AppState::ListingWorktrees {
+        worktrees,
+        filtered_indices,
+        table_state,
+        layout,
+        ..
+    } = current_state
+    else {
+        return current_state.clone();
+    };
+    let selected = table_state
+        .selected()
+        .and_then(|i| filtered_indices.get(i))
+        .copied();
+    let Some(group) =
+        group_to_toggle(worktrees, filtered_indices, selected, layout).map(str::to_string)
+    else {
+        return current_state.clone();
+    };
+    change_layout(repo, current_state, |layout, _| {
+        layout.toggle_collapsed(&group);
+    })
+}
+
+/// Pins or unpins the worktree; the refresh moves it to or from the top.
+pub fn toggle_pin<R: ProjectRepository>(
+    repo: &R,
+    wt: &Worktree,
+    current_state: &AppState,
+) -> AppState {
+    if wt.is_bare {
+        return current_state.clone();
+    }
+    let saved = repo.worktree_metadata(&wt.path).and_then(|mut metadata| {
+        metadata.pinned = !metadata.pinned;
+        repo.update_worktree_metadata(&wt.path, &metadata)
+    });
+    match saved {
+        Ok(()) => {
+            let mut listing = current_state.clone();
+            listing.request_refresh();
+            listing
+        }
+        Err(e) => AppState::Error(
+            format!("Failed to pin '{}': {e}", wt.branch),
+            Box::new(current_state.clone()),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::model::{AppMode, DashboardState, DashboardTab};
+    use crate::app::test_utils::scaffolding::MockRepoBuilder;
+    use crate::domain::list_layout::{
+        GroupMode, PINNED_GROUP, SortMode, UNGROUPED, expanded_indices, group_of,
+    };
+    use crate::domain::repository::WorktreeMetadata;
+    use ratatui::widgets::TableState;
+
+    fn worktree(branch: &str, size_bytes: u64) -> Worktree {
+        Worktree {
+            path: format!("/hub/{}", branch.replace('/', "-")),
+            commit: "abc1234".into(),
+            branch: branch.into(),
+            is_bare: false,
+            is_detached: false,
+            status_summary: Some("clean".into()),
+            size_bytes,
+            metadata: None,
+            upstream: None,
+            contexts: Vec::new(),
+            sync_drift: Vec::new(),
+        }
+    }
+
+    fn listing(worktrees: Vec<Worktree>, selected: usize) -> AppState {
+        let mut table_state = TableState::default();
+        table_state.select(Some(selected));
+        AppState::ListingWorktrees {
+            filtered_indices: (0..worktrees.len()).collect(),
+            worktrees,
+            table_state,
+            refresh_needed: RefreshType::None,
+            selection_mode: false,
+            dashboard: DashboardState {
+                active_tab: DashboardTab::Info,
+                cached_status: None,
+                cached_history: None,
+                loading: false,
+            },
+            filter_query: String::new(),
+            is_filtering: false,
+            mode: AppMode::Normal,
+            last_selection_change: std::time::Instant::now(),
+            layout: ListLayout::default(),
+        }
+    }
+
+    fn selected_branch(state: &AppState) -> Option<&str> {
+        let AppState::ListingWorktrees {
+            worktrees,
+            filtered_indices,
+            table_state,
+            ..
+        } = state
+        else {
+            return None;
+        };
+        let idx = filtered_indices.get(table_state.selected()?)?;
+        Some(worktrees[*idx].branch.as_str())
+    }
+
+    fn branches(worktrees: &[Worktree]) -> Vec<&str> {
+        worktrees.iter().map(|wt| wt.branch.as_str()).collect()
+    }
+
+    #[test]
+    fn test_arrange_worktrees() {
+        let pinned = Worktree {
+            metadata: Some(WorktreeMetadata {
+                pinned: true,
+                ..Default::default()
+            }),
+            ..worktree("fix/crash", 10)
+        };
+        let with_status = |branch: &str, status: &str, size_bytes: u64| Worktree {
+            status_summary: Some(status.into()),
+            ..worktree(branch, size_bytes)
+        };
+        let mut worktrees = vec![
+            worktree("main", 50),
+            with_status("feat/search", "~1", 30),
+            pinned,
+            worktree("feat/login", 40),
+            with_status("fix/typo", "+2", 20),
+        ];
+        let repo = MockRepoBuilder::default().build();
+
+        let layout = ListLayout {
+            sort: SortMode::Size,
+            ..ListLayout::default()
+        };
+        arrange_worktrees(&repo, &mut worktrees, &layout);
+        assert_eq!(
+            branches(&worktrees),
+            ["fix/crash", "main", "feat/login", "feat/search", "fix/typo"]
+        );
+
+        let layout = ListLayout {
+            sort: SortMode::Dirty,
+            group: GroupMode::Prefix,
+            collapsed: vec!["fix".into()],
+        };
+        arrange_worktrees(&repo, &mut worktrees, &layout);
+        assert_eq!(
+            branches(&worktrees),
+            ["fix/crash", "feat/search", "feat/login", "fix/typo", "main"]
+        );
+        let groups: Vec<_> = worktrees
+            .iter()
+            .map(|wt| group_of(wt, layout.group))
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                Some(PINNED_GROUP),
+                Some("feat"),
+                Some("feat"),
+                Some("fix"),
+                Some(UNGROUPED)
+            ]
+        );
+        assert_eq!(expanded_indices(&worktrees, &layout), [0, 1, 2, 4]);
+    }
+
+    #[test]
+    fn test_change_layout_keeps_the_cursor() {
+        let repo = MockRepoBuilder::default().build();
+        let state = listing(
+            vec![
+                worktree("feat/a", 10),
+                worktree("fix/b", 30),
+                worktree("feat/c", 20),
+            ],
+            2,
+        );
+
+        let state = change_layout(&repo, &state, |layout, _| {
+            layout.sort = SortMode::Size;
+            layout.group = GroupMode::Prefix;
+        });
+        assert_eq!(selected_branch(&state), Some("feat/c"));
+        let AppState::ListingWorktrees { worktrees, .. } = &state else {
+            panic!("Expected the listing");
+        };
+        let order: Vec<_> = worktrees.iter().map(|wt| wt.branch.as_str()).collect();
+        assert_eq!(order, ["feat/c", "feat/a", "fix/b"]);
+
+        // Collapsing the selection's group moves the cursor to the next visible worktree.
+        let state = change_layout(&repo, &state, |layout, selected| {
+            if let Some(group) = selected.and_then(|wt| group_of(wt, layout.group)) {
+                layout.toggle_collapsed(group);
+            }
+        });
+        assert_eq!(selected_branch(&state), Some("fix/b"));
+        let AppState::ListingWorktrees {
+            filtered_indices,
+            layout,
+            ..
+        } = &state
+        else {
+            panic!("Expected the listing");
+        };
+        assert_eq!(filtered_indices, &[2]);
+        assert_eq!(layout.collapsed, ["feat"]);
+    }
+
+    #[test]
+    fn test_toggle_group_collapses_and_reopens() {
+        let repo = MockRepoBuilder::default().build();
+        let mut state = listing(
+            vec![
+                worktree("feat/a", 0),
+                worktree("feat/b", 0),
+                worktree("fix/c", 0),
+            ],
+            1,
+        );
+        if let AppState::ListingWorktrees { layout, .. } = &mut state {
+            layout.group = GroupMode::Prefix;
+        }
+
+        let state = toggle_group(&repo, &state);
+        assert_eq!(selected_branch(&state), Some("fix/c"));
+        let AppState::ListingWorktrees { layout, .. } = &state else {
+            panic!("Expected the listing");
+        };
+        assert_eq!(layout.collapsed, ["feat"]);
+
+        // The cursor landed below the collapsed group, so `z` again reopens it.
+        let state = toggle_group(&repo, &state);
+        assert_eq!(selected_branch(&state), Some("fix/c"));
+        let AppState::ListingWorktrees {
+            layout,
+            filtered_indices,
+            ..
+        } = &state
+        else {
+            panic!("Expected the listing");
+        };
+        assert!(layout.collapsed.is_empty());
+        assert_eq!(filtered_indices, &[0, 1, 2]);
+
+        // Collapsing every group leaves nothing selected; `z` still reopens the first one.
+        let state = toggle_group(&repo, &state);
+        let state = change_layout(&repo, &state, |layout, _| {
+            layout.toggle_collapsed("feat");
+        });
+        assert_eq!(selected_branch(&state), None);
+        let state = toggle_group(&repo, &state);
+        let AppState::ListingWorktrees { layout, .. } = &state else {
+            panic!("Expected the listing");
+        };
+        assert_eq!(layout.collapsed, ["fix"]);
+    }
+
+    #[test]
+    fn test_change_layout_leaves_git_sorts_to_the_refresh() {
+        let repo = MockRepoBuilder::default().build();
+        let state = listing(vec![worktree("b", 10), worktree("a", 30)], 1);
+
+        let state = change_layout(&repo, &state, |layout, _| {
+            layout.sort = SortMode::Activity;
+        });
+        let AppState::ListingWorktrees {
+            worktrees,
+            refresh_needed,
+            ..
+        } = &state
+        else {
+            panic!("Expected the listing");
+        };
+        let order: Vec<_> = worktrees.iter().map(|wt| wt.branch.as_str()).collect();
+        assert_eq!(order, ["b", "a"]);
+        assert_eq!(*refresh_needed, RefreshType::Full);
+        assert_eq!(selected_branch(&state), Some("a"));
+    }
+}
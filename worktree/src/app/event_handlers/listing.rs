@@ -1,16 +1,44 @@
 use crate::app::async_tasks::AsyncResult;
 use crate::app::intent::Intent;
 use crate::app::model::{
-    AppMode, AppState, DashboardState, DashboardTab, RefreshType, filter_worktrees,
+    AppMode, AppState, DashboardState, DashboardTab, EditorTarget, RefreshType, listed_worktrees,
 };
-use crate::domain::repository::{ProjectRepository, Worktree};
+use crate::domain::list_layout::GroupMode;
+use crate::domain::repository::{HistoryQuery, ProjectRepository, Worktree};
+use crate::ui::widgets::worktree_list::{ListRow, list_rows};
 use anyhow::Result;
 use ratatui::{Terminal, backend::Backend, widgets::TableState};
 use tokio::sync::mpsc::UnboundedSender;
 
-use super::helpers::{create_timed_state, move_selection};
+use super::branch_manager::open_branch_manager;
+use super::compare::pick_compare_target;
+use super::editor::open_in_editor;
+use super::helpers::move_selection;
+use super::list_layout::{change_layout, toggle_group, toggle_pin};
+use super::metadata::open_metadata_form;
+use super::stale::open_stale_review;
 use std::borrow::Cow;
 
+/// Directory to hand back to the shell when the TUI exits on a worktree (the hub root for the bare entry).
+fn selected_switch_target<R: ProjectRepository>(
+    repo: &R,
+    worktrees: &[Worktree],
+    filtered_indices: &[usize],
+    table_state: &TableState,
+) -> Option<String> {
+    let wt = table_state
+        .selected()
+        .and_then(|i| filtered_indices.get(i))
+        .and_then(|&idx| worktrees.get(idx))?;
+    if wt.is_bare {
+        repo.get_project_root()
+            .ok()
+            .map(|root| root.to_string_lossy().to_string())
+    } else {
+        Some(wt.path.clone())
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::collapsible_if)]
 pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'static, B: Backend>(
@@ -74,9 +102,7 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
                         changed = true;
                     }
                     KeyCode::Backspace => {
-                        if new_query.pop().is_some() {
-                            changed = true;
-                        }
+                        changed |= new_query.pop().is_some();
                     }
                     KeyCode::Char(c) => {
                         new_query.push(c);
@@ -104,6 +130,7 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
                         mode: m,
                         table_state: ts,
                         last_selection_change,
+                        layout,
                         ..
                     } = &mut new_state
                     {
@@ -114,7 +141,7 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
                                 *m = AppMode::Normal;
                             }
                             // Update the cached filtered list
-                            *state_filtered = filter_worktrees(raw_worktrees, filter_query);
+                            *state_filtered = listed_worktrees(raw_worktrees, filter_query, layout);
                         }
                         if changed || selection_changed {
                             *refresh_needed = RefreshType::Dashboard;
@@ -194,68 +221,51 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
                         }
                         return Ok(Some(new_state));
                     }
-                    KeyCode::Enter => {
+                    KeyCode::Enter
+                        if matches!(
+                            current_state,
+                            AppState::ListingWorktrees {
+                                selection_mode: true,
+                                ..
+                            }
+                        ) =>
+                    {
+                        // `worktree switch` without a name: Enter picks the worktree to jump to.
+                        if let Some(path) =
+                            selected_switch_target(repo, worktrees, &filtered_indices, table_state)
+                        {
+                            return Ok(Some(AppState::Exiting(Some(path))));
+                        }
+                    }
+                    KeyCode::Char('t') => {
+                        if let Some(path) =
+                            selected_switch_target(repo, worktrees, &filtered_indices, table_state)
+                        {
+                            return Ok(Some(AppState::Exiting(Some(path))));
+                        }
+                    }
+                    KeyCode::Enter | KeyCode::Char('e') => {
                         if let Some(i) = table_state.selected()
                             && let Some(idx) = filtered_indices.get(i)
                             && let Some(wt) = worktrees.get(*idx)
+                            && let Some(path) = selected_switch_target(
+                                repo,
+                                worktrees,
+                                &filtered_indices,
+                                table_state,
+                            )
                         {
                             let branch = if wt.is_bare {
                                 "HUB".to_string()
                             } else {
                                 wt.branch.clone()
                             };
-                            let path = if wt.is_bare {
-                                // Offload project root fetching
-                                let repo_clone = repo.clone();
-                                tokio::task::spawn_blocking(move || {
-                                    let root = repo_clone.get_project_root();
-                                    let editor = repo_clone.get_preferred_editor();
-                                    // Send a composite result or just reuse existing mechanisms
-                                    // For simplicity here, let's just trigger the editor logic
-                                    if let Ok(root_path) = root {
-                                        let path_str = root_path.to_string_lossy().to_string();
-                                        if let Ok(Some(editor_cmd)) = editor {
-                                            let _ = std::process::Command::new(&editor_cmd)
-                                                .arg(&path_str)
-                                                .spawn();
-                                        }
-                                    }
-                                });
-                                return Ok(Some(create_timed_state(
-                                    AppState::OpeningEditor {
-                                        branch,
-                                        editor: "Detecting...".into(),
-                                        prev_state: Box::new(current_state.clone()),
-                                    },
-                                    current_state.clone(),
-                                    800,
-                                )));
-                            } else {
-                                wt.path.clone()
-                            };
-
-                            let prev = Box::new(current_state.clone());
-                            // Offload editor config fetch
-                            let repo_clone = repo.clone();
-                            let path_clone = path.clone();
-
-                            tokio::task::spawn_blocking(move || {
-                                if let Ok(Some(editor)) = repo_clone.get_preferred_editor() {
-                                    let _ = std::process::Command::new(&editor)
-                                        .arg(&path_clone)
-                                        .spawn();
-                                    // We could send an AsyncResult to confirm opening, but Timed state handles UI feedback
-                                }
-                            });
-
-                            return Ok(Some(create_timed_state(
-                                AppState::OpeningEditor {
-                                    branch,
-                                    editor: "Opening...".into(),
-                                    prev_state: prev.clone(),
-                                },
-                                *prev,
-                                800,
+                            return Ok(Some(open_in_editor(
+                                repo,
+                                &branch,
+                                EditorTarget::worktree(path),
+                                key_code == KeyCode::Char('e'),
+                                current_state.clone(),
                             )));
                         }
                     }
@@ -293,7 +303,8 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
                             let tx = async_tx.clone();
 
                             tokio::task::spawn_blocking(move || {
-                                let result = repo_clone.get_history(&path_clone, 50);
+                                let result =
+                                    repo_clone.get_history(&path_clone, &HistoryQuery::default());
                                 let _ = tx.send(AsyncResult::HistoryFetched {
                                     path: path_clone,
                                     result,
@@ -317,6 +328,7 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
                             filter_query,
                             is_filtering,
                             mode: m,
+                            layout,
                             ..
                         } = current_state
                         {
@@ -342,6 +354,7 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
                                 is_filtering: *is_filtering,
                                 mode: *m,
                                 last_selection_change: std::time::Instant::now(),
+                                layout: layout.clone(),
                             }));
                         }
                     }
@@ -379,7 +392,7 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
                             let tx = async_tx.clone();
 
                             tokio::task::spawn_blocking(move || {
-                                let result = repo_clone.fetch(&path_clone);
+                                let result = repo_clone.fetch(&path_clone, None);
                                 let _ = tx.send(AsyncResult::FetchCompleted {
                                     branch: branch_clone,
                                     result,
@@ -401,14 +414,37 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
                             return Ok(Some(AppState::Confirming {
                                 title: " PRUNE ".into(),
                                 message: "Are you sure you want to prune stale worktrees?".into(),
-                                action: Box::new(Intent::CleanWorktrees {
-                                    dry_run: false,
-                                    artifacts: false,
-                                }),
+                                action: Box::new(Intent::CleanWorktrees { dry_run: false }),
                                 prev_state: Box::new(current_state.clone()),
                             }));
                         }
                     }
+                    KeyCode::Char('*') => {
+                        if let Some(i) = table_state.selected()
+                            && let Some(idx) = filtered_indices.get(i)
+                            && let Some(wt) = worktrees.get(*idx)
+                        {
+                            return Ok(Some(toggle_pin(repo, wt, current_state)));
+                        }
+                    }
+                    KeyCode::Char('S') => {
+                        return Ok(Some(change_layout(repo, current_state, |layout, _| {
+                            layout.sort = layout.sort.next();
+                        })));
+                    }
+                    KeyCode::Char('G') => {
+                        return Ok(Some(change_layout(repo, current_state, |layout, _| {
+                            layout.group = layout.group.next();
+                        })));
+                    }
+                    KeyCode::Char('z') => {
+                        return Ok(Some(toggle_group(repo, current_state)));
+                    }
+                    KeyCode::Char('Z') => {
+                        return Ok(Some(change_layout(repo, current_state, |layout, _| {
+                            layout.collapsed.clear();
+                        })));
+                    }
                     _ => {}
                 },
                 AppMode::Manage => match key_code {
@@ -466,13 +502,25 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
                             prev_state: Box::new(current_state.clone()),
                         }));
                     }
+                    KeyCode::Char('s') => {
+                        return Ok(Some(open_stale_review(repo, current_state)));
+                    }
+                    KeyCode::Char('e') => {
+                        if let Some(i) = table_state.selected()
+                            && let Some(idx) = filtered_indices.get(i)
+                            && let Some(wt) = worktrees.get(*idx).filter(|wt| !wt.is_bare)
+                        {
+                            return Ok(Some(open_metadata_form(repo, wt, current_state)));
+                        }
+                    }
                     KeyCode::Char('C') => {
                         return Ok(Some(AppState::Confirming {
                             title: " CLEAN ARTIFACTS ".into(),
-                            message: "Remove all build artifacts from INACTIVE worktrees?".into(),
-                            action: Box::new(Intent::CleanWorktrees {
+                            message: "Remove untracked build artifacts from all OTHER worktrees?"
+                                .into(),
+                            action: Box::new(Intent::CleanArtifacts {
                                 dry_run: false,
-                                artifacts: true,
+                                inactive_days: None,
                             }),
                             prev_state: Box::new(current_state.clone()),
                         }));
@@ -528,7 +576,7 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
                             let tx = async_tx.clone();
 
                             tokio::task::spawn_blocking(move || {
-                                let result = repo_clone.pull(&path_clone);
+                                let result = repo_clone.pull(&path_clone, None);
                                 let _ = tx.send(AsyncResult::PullCompleted {
                                     branch: branch_clone,
                                     result,
@@ -555,7 +603,7 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
                             let tx = async_tx.clone();
 
                             tokio::task::spawn_blocking(move || {
-                                let result = repo_clone.push(&path_clone);
+                                let result = repo_clone.push(&path_clone, None);
                                 let _ = tx.send(AsyncResult::PushCompleted {
                                     branch: branch_clone,
                                     result,
@@ -568,6 +616,19 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
                             }));
                         }
                     }
+                    KeyCode::Char('c') => {
+                        if let Some(i) = table_state.selected()
+                            && let Some(idx) = filtered_indices.get(i)
+                            && let Some(wt) = worktrees.get(*idx).filter(|wt| !wt.is_bare)
+                        {
+                            return Ok(Some(pick_compare_target(
+                                repo,
+                                wt,
+                                worktrees,
+                                current_state,
+                            )));
+                        }
+                    }
                     KeyCode::Char('R') => {
                         if let Some(i) = table_state.selected()
                             && let Some(idx) = filtered_indices.get(i)
@@ -582,6 +643,9 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
                             }));
                         }
                     }
+                    KeyCode::Char('b') => {
+                        return Ok(Some(open_branch_manager(repo, current_state)));
+                    }
                     KeyCode::Char('f') => {
                         if let Some(i) = table_state.selected()
                             && let Some(idx) = filtered_indices.get(i)
@@ -593,7 +657,7 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
                             let tx = async_tx.clone();
 
                             tokio::task::spawn_blocking(move || {
-                                let result = repo_clone.fetch(&path_clone);
+                                let result = repo_clone.fetch(&path_clone, None);
                                 let _ = tx.send(AsyncResult::FetchCompleted {
                                     branch: branch_clone,
                                     result,
@@ -621,7 +685,20 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
 
                     if x >= 1 && x <= 1 + list_width && y >= 5 {
                         let row_index = (y as i16 - 5) as usize;
-                        if row_index < filtered_indices.len() {
+                        // Group headers take rows of their own.
+                        let clicked = match current_state {
+                            AppState::ListingWorktrees { layout, .. }
+                                if layout.group != GroupMode::None && filter_query.is_empty() =>
+                            {
+                                match list_rows(worktrees, &filtered_indices, layout).get(row_index)
+                                {
+                                    Some(ListRow::Worktree(pos)) => Some(*pos),
+                                    _ => None,
+                                }
+                            }
+                            _ => (row_index < filtered_indices.len()).then_some(row_index),
+                        };
+                        if let Some(row_index) = clicked {
                             table_state.select(Some(row_index));
                             if let AppState::ListingWorktrees {
                                 filtered_indices: state_filtered,
@@ -630,6 +707,7 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
                                 filter_query,
                                 is_filtering,
                                 mode: m,
+                                layout,
                                 ..
                             } = current_state
                             {
@@ -644,6 +722,7 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
                                     is_filtering: *is_filtering,
                                     mode: *m,
                                     last_selection_change: std::time::Instant::now(),
+                                    layout: layout.clone(),
                                 }));
                             }
                         }
@@ -655,6 +734,7 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
                             filter_query,
                             is_filtering,
                             mode: m,
+                            layout,
                             ..
                         } = current_state
                     {
@@ -685,6 +765,7 @@ pub fn handle_listing_events<R: ProjectRepository + Clone + Send + Sync + 'stati
                                 is_filtering: *is_filtering,
                                 mode: *m,
                                 last_selection_change: std::time::Instant::now(),
+                                layout: layout.clone(),
                             }));
                         }
                     }
@@ -755,6 +836,9 @@ mod tests {
             status_summary: None,
             size_bytes: 0,
             metadata: None,
+            upstream: None,
+            contexts: Vec::new(),
+            sync_drift: Vec::new(),
         };
         let worktrees = vec![hub_wt.clone()];
         let repo = MockRepoBuilder::default()
@@ -783,6 +867,7 @@ mod tests {
             is_filtering: false,
             mode: AppMode::Normal,
             last_selection_change: std::time::Instant::now(),
+            layout: crate::domain::list_layout::ListLayout::default(),
         };
 
         // 2. Test Fetch ('f')
@@ -839,4 +924,94 @@ mod tests {
 
         assert!(res.is_none());
     }
+
+    #[tokio::test]
+    async fn test_editor_picker_targets_filtered_selection() {
+        let worktree = |branch: &str| Worktree {
+            path: format!("/hub/{branch}"),
+            commit: "abcdef1".into(),
+            branch: branch.into(),
+            is_bare: false,
+            is_detached: false,
+            status_summary: None,
+            size_bytes: 0,
+            metadata: None,
+            upstream: None,
+            contexts: Vec::new(),
+            sync_drift: Vec::new(),
+        };
+        let worktrees = vec![worktree("main"), worktree("feat-login")];
+        let repo = MockRepoBuilder::default()
+            .with_worktrees(worktrees.clone())
+            .build();
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        let (async_tx, _async_rx) = mpsc::unbounded_channel();
+
+        // The filter hides "main", so row 0 is "feat-login".
+        let mut table_state = TableState::default();
+        table_state.select(Some(0));
+        let current_state = AppState::ListingWorktrees {
+            filtered_indices: vec![1],
+            worktrees: worktrees.clone(),
+            table_state: table_state.clone(),
+            refresh_needed: RefreshType::None,
+            selection_mode: false,
+            dashboard: DashboardState {
+                active_tab: DashboardTab::Info,
+                cached_status: None,
+                cached_history: None,
+                loading: false,
+            },
+            filter_query: "feat".into(),
+            is_filtering: false,
+            mode: AppMode::Normal,
+            last_selection_change: std::time::Instant::now(),
+            layout: crate::domain::list_layout::ListLayout::default(),
+        };
+
+        let pick_event = Event::Key(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::empty()));
+        let res = handle_listing_events(
+            &pick_event,
+            &repo,
+            &mut terminal,
+            &worktrees,
+            &mut table_state,
+            &current_state,
+            &0,
+            &async_tx,
+        )
+        .unwrap();
+
+        let Some(AppState::SelectingEditor {
+            branch,
+            options,
+            mut selected,
+            target,
+            prev_state,
+            ..
+        }) = res
+        else {
+            panic!("Expected SelectingEditor state, got {res:?}");
+        };
+        assert_eq!(branch, "feat-login");
+        assert_eq!(target.path, "/hub/feat-login");
+
+        let enter = Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        let res = crate::app::event_handlers::handle_editor_events(
+            &enter,
+            &repo,
+            &branch,
+            &options,
+            &mut selected,
+            &target,
+            &prev_state,
+        );
+        match res {
+            Some(AppState::LaunchingEditor { editor, target, .. }) => {
+                assert_eq!(editor.name, options[0].name);
+                assert_eq!(target.path, "/hub/feat-login");
+            }
+            _ => panic!("Expected LaunchingEditor state, got {res:?}"),
+        }
+    }
 }
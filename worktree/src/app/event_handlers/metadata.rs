@@ -0,0 +1,110 @@
+use crate::app::model::{AppState, MetadataForm};
+use crate::domain::repository::{ProjectRepository, Worktree, WorktreeMetadata};
+
+pub fn open_metadata_form<R: ProjectRepository>(
+    repo: &R,
+    wt: &Worktree,
+    current_state: &AppState,
+) -> AppState {
+    match repo.worktree_metadata(&wt.path) {
+        Ok(metadata) => AppState::EditingMetadata {
+            path: wt.path.clone(),
+            branch: wt.branch.clone(),
+            form: MetadataForm::new(&metadata),
+            prev_state: Box::new(current_state.clone()),
+        },
+        Err(e) => AppState::Error(
+            format!("Failed to load metadata: {e}"),
+            Box::new(current_state.clone()),
+        ),
+    }
+}
+
+pub fn handle_metadata_events<R: ProjectRepository>(
+    event: &crossterm::event::Event,
+    repo: &R,
+    path: &str,
+    form: &mut MetadataForm,
+    prev_state: &AppState,
+) -> Option<AppState> {
+    use crossterm::event::{Event, KeyCode};
+    let Event::Key(key) = event else {
+        return None;
+    };
+    let count = form.values.len();
+
+    match key.code {
+        KeyCode::Esc => return Some(prev_state.clone()),
+        KeyCode::Tab | KeyCode::Down => form.focused = (form.focused + 1) % count,
+        KeyCode::BackTab | KeyCode::Up => form.focused = (form.focused + count - 1) % count,
+        KeyCode::Backspace => {
+            form.values[form.focused].pop();
+        }
+        KeyCode::Char(c) => form.values[form.focused].push(c),
+        KeyCode::Enter => {
+            let saved = repo.worktree_metadata(path).and_then(|mut metadata| {
+                for (key, value) in WorktreeMetadata::FIELDS.iter().zip(&form.values) {
+                    metadata.set(key, Some(value.trim().to_string()))?;
+                }
+                repo.update_worktree_metadata(path, &metadata)
+            });
+            match saved {
+                Ok(()) => {
+                    let mut listing = prev_state.clone();
+                    listing.request_refresh();
+                    return Some(listing);
+                }
+                Err(e) => form.error = Some(e.to_string()),
+            }
+        }
+        _ => {}
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::test_utils::scaffolding::MockRepoBuilder;
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+    fn press<R: ProjectRepository>(
+        repo: &R,
+        form: &mut MetadataForm,
+        code: KeyCode,
+    ) -> Option<AppState> {
+        let event = Event::Key(KeyEvent::new(code, KeyModifiers::empty()));
+        handle_metadata_events(&event, repo, "/hub/feat", form, &AppState::Welcome)
+    }
+
+    #[test]
+    fn test_metadata_form_editing() {
+        let repo = MockRepoBuilder::default().build();
+        let mut form = MetadataForm::new(&WorktreeMetadata {
+            purpose: Some("Login".into()),
+            ..WorktreeMetadata::default()
+        });
+        assert_eq!(form.values, ["Login", "", "", ""]);
+
+        press(&repo, &mut form, KeyCode::Backspace);
+        press(&repo, &mut form, KeyCode::BackTab);
+        assert_eq!(form.focused, 3);
+        press(&repo, &mut form, KeyCode::Char('🔑'));
+        assert_eq!(form.values, ["Logi", "", "", "🔑"]);
+
+        // An unknown color keeps the form open with the reason.
+        form.values[2] = "mauve".into();
+        assert!(press(&repo, &mut form, KeyCode::Enter).is_none());
+        assert!(
+            form.error
+                .as_deref()
+                .is_some_and(|e| e.contains("Invalid color"))
+        );
+
+        form.values[2] = "cyan".into();
+        assert!(matches!(
+            press(&repo, &mut form, KeyCode::Enter),
+            Some(AppState::Welcome)
+        ));
+    }
+}
@@ -1,23 +1,34 @@
 pub mod branch;
+pub mod branch_manager;
+pub mod commit_detail;
 pub mod committing;
+pub mod compare;
 pub mod confirm;
 pub mod editor;
 pub mod helpers;
 pub mod history;
 
+pub mod list_layout;
 pub mod listing;
+pub mod metadata;
 pub mod picking;
 pub mod prompt;
+pub mod stale;
 pub mod stash;
 pub mod status;
 
 pub use branch::handle_branch_events;
+pub use branch_manager::handle_branch_manager_events;
+pub use commit_detail::{handle_cherry_pick_target_events, handle_commit_detail_events};
 pub use committing::handle_committing_events;
+pub use compare::{handle_compare_target_events, handle_comparison_events};
 pub use confirm::handle_confirm_events;
 pub use editor::handle_editor_events;
-pub use history::handle_history_events;
+pub use history::{handle_commit_files_events, handle_history_events};
 pub use listing::handle_listing_events;
+pub use metadata::handle_metadata_events;
 pub use picking::handle_picking_ref_events;
 pub use prompt::handle_prompt_events;
+pub use stale::handle_stale_events;
 pub use stash::handle_stash_events;
 pub use status::handle_status_events;
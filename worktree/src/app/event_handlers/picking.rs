@@ -23,15 +23,11 @@ pub fn handle_picking_ref_events(
             // Cancel and return to previous state
             return Some(prev_state.clone());
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if !branches.is_empty() {
-                *selected_index = (*selected_index + 1) % branches.len();
-            }
+        KeyCode::Down | KeyCode::Char('j') if !branches.is_empty() => {
+            *selected_index = (*selected_index + 1) % branches.len();
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            if !branches.is_empty() {
-                *selected_index = (*selected_index + branches.len() - 1) % branches.len();
-            }
+        KeyCode::Up | KeyCode::Char('k') if !branches.is_empty() => {
+            *selected_index = (*selected_index + branches.len() - 1) % branches.len();
         }
         KeyCode::Enter => {
             if let Some(branch) = branches.get(*selected_index) {
@@ -39,6 +35,7 @@ pub fn handle_picking_ref_events(
                 return Some(AppState::Prompting {
                     prompt_type: PromptType::NameNewWorktree {
                         base_ref: branch.clone(),
+                        template: None,
                     },
                     input: String::new(),
                     prev_state: Box::new(prev_state.clone()),
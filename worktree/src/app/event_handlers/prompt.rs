@@ -1,6 +1,11 @@
+use crate::app::event_handlers::branch_manager::reload_branches;
 use crate::app::model::{AppState, PromptType, RefreshType};
 use crate::domain::repository::ProjectRepository;
+use crate::domain::templates;
+use crate::infrastructure::process::run_captured;
+use anyhow::Context;
 use ratatui::widgets::TableState;
+use std::path::Path;
 
 pub fn handle_prompt_events<R: ProjectRepository>(
     event: &crossterm::event::Event,
@@ -22,7 +27,7 @@ pub fn handle_prompt_events<R: ProjectRepository>(
         KeyCode::Enter => {
             let val = input.trim().to_string();
             match prompt_type {
-                PromptType::NameNewWorktree { base_ref } => {
+                PromptType::NameNewWorktree { base_ref, template } => {
                     if !val.is_empty() {
                         let mut adding_state = AppState::AddingWorktree {
                             intent: val.clone(),
@@ -55,10 +60,10 @@ pub fn handle_prompt_events<R: ProjectRepository>(
                             );
                         })?;
 
-                        let res = if val == *base_ref {
-                            repo.add_worktree(&val, base_ref)
-                        } else {
-                            repo.add_new_worktree(&val, &val, base_ref)
+                        let res = match template {
+                            Some(template) => add_with_template(repo, &val, base_ref, template),
+                            None if val == *base_ref => repo.add_worktree(&val, base_ref),
+                            None => repo.add_new_worktree(&val, &val, base_ref),
                         };
 
                         if let Err(e) = res {
@@ -80,6 +85,7 @@ pub fn handle_prompt_events<R: ProjectRepository>(
                                     is_filtering: false,
                                     mode: crate::app::model::AppMode::Normal,
                                     last_selection_change: std::time::Instant::now(),
+                                    layout: repo.list_layout(),
                                 }),
                             )));
                         }
@@ -100,6 +106,7 @@ pub fn handle_prompt_events<R: ProjectRepository>(
                             is_filtering: false,
                             mode: crate::app::model::AppMode::Normal,
                             last_selection_change: std::time::Instant::now(),
+                            layout: repo.list_layout(),
                         }));
                     }
                 }
@@ -131,6 +138,33 @@ pub fn handle_prompt_events<R: ProjectRepository>(
                         }
                     }
                 }
+                PromptType::CreateBranch { base } => {
+                    if !val.is_empty() {
+                        return Ok(Some(branch_change(
+                            repo,
+                            prev_state,
+                            repo.create_branch(&val, base),
+                        )));
+                    }
+                }
+                PromptType::RenameBranch { old } => {
+                    if !val.is_empty() && val != *old {
+                        return Ok(Some(branch_change(
+                            repo,
+                            prev_state,
+                            repo.rename_branch(old, &val),
+                        )));
+                    }
+                }
+                PromptType::SetUpstream { branch } => {
+                    if !val.is_empty() {
+                        return Ok(Some(branch_change(
+                            repo,
+                            prev_state,
+                            repo.set_upstream(branch, &val),
+                        )));
+                    }
+                }
                 PromptType::ApiKey => {
                     if !val.is_empty() {
                         let _ = repo.set_api_key(&val);
@@ -155,6 +189,30 @@ pub fn handle_prompt_events<R: ProjectRepository>(
         KeyCode::Esc => {
             return Ok(Some(prev_state.clone()));
         }
+        KeyCode::Tab => {
+            // Cycles through the templates, then back to none.
+            if let PromptType::NameNewWorktree { base_ref, template } = prompt_type {
+                let names: Vec<String> = repo
+                    .get_project_config()
+                    .map(|config| config.templates.into_keys().collect())
+                    .unwrap_or_default();
+                let next = match template
+                    .as_ref()
+                    .and_then(|t| names.iter().position(|n| n == t))
+                {
+                    Some(i) => names.get(i + 1).cloned(),
+                    None => names.first().cloned(),
+                };
+                return Ok(Some(AppState::Prompting {
+                    prompt_type: PromptType::NameNewWorktree {
+                        base_ref: base_ref.clone(),
+                        template: next,
+                    },
+                    input: input.clone(),
+                    prev_state: Box::new(prev_state.clone()),
+                }));
+            }
+        }
         KeyCode::Char(c) => input.push(c),
         KeyCode::Backspace => {
             input.pop();
@@ -163,3 +221,53 @@ pub fn handle_prompt_events<R: ProjectRepository>(
     }
     Ok(None)
 }
+
+/// Adds `name` from a template, starting at the picked `base_ref` unless the template names
+/// its own base. Hooks run without the terminal, so only a failure's stderr is shown.
+fn add_with_template<R: ProjectRepository>(
+    repo: &R,
+    name: &str,
+    base_ref: &str,
+    template_name: &str,
+) -> anyhow::Result<()> {
+    let config = repo.get_project_config()?;
+    let mut template = templates::find(&config.templates, template_name)?.clone();
+    template.base.get_or_insert_with(|| base_ref.to_string());
+    templates::add_from_template(
+        repo,
+        name,
+        template.branch_for(name).as_deref(),
+        template_name,
+        &template,
+        chrono::Utc::now().timestamp(),
+    )?;
+    if template.hooks.is_empty() {
+        return Ok(());
+    }
+
+    let path = repo
+        .list_worktrees()?
+        .into_iter()
+        .find(|wt| Path::new(&wt.path).file_name().is_some_and(|n| n == name))
+        .map(|wt| wt.path)
+        .with_context(|| format!("Worktree '{name}' was not found after adding it"))?;
+    let env = repo.worktree_env(&path)?;
+    for hook in &template.hooks {
+        run_captured(hook, Path::new(&path), &env).with_context(|| {
+            format!("Hook '{hook}' of template '{template_name}' failed; the worktree was created")
+        })?;
+    }
+    Ok(())
+}
+
+/// Returns to the branch manager with fresh data, or surfaces the git error over it.
+fn branch_change<R: ProjectRepository>(
+    repo: &R,
+    prev_state: &AppState,
+    result: anyhow::Result<()>,
+) -> AppState {
+    match result {
+        Ok(()) => reload_branches(repo, prev_state),
+        Err(e) => AppState::Error(format!("{e:#}"), Box::new(prev_state.clone())),
+    }
+}
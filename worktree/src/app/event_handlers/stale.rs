@@ -0,0 +1,291 @@
+use crate::app::intent::Intent;
+use crate::app::model::AppState;
+use crate::domain::repository::ProjectRepository;
+use crate::domain::stale::{DEFAULT_INACTIVE_DAYS, StaleCriteria, find_stale_worktrees};
+
+pub fn open_stale_review<R: ProjectRepository>(repo: &R, current_state: &AppState) -> AppState {
+    let now = chrono::Utc::now().timestamp();
+    match find_stale_worktrees(repo, &StaleCriteria::all(DEFAULT_INACTIVE_DAYS), now) {
+        Ok(candidates) if candidates.is_empty() => AppState::Error(
+            "No stale worktrees found".into(),
+            Box::new(current_state.clone()),
+        ),
+        Ok(candidates) => AppState::ReviewingStale {
+            checked: candidates.iter().map(|c| !c.dirty).collect(),
+            candidates,
+            selected_index: 0,
+            prev_state: Box::new(current_state.clone()),
+        },
+        Err(e) => AppState::Error(
+            format!("Failed to find stale worktrees: {e}"),
+            Box::new(current_state.clone()),
+        ),
+    }
+}
+
+/// Opens on the expired worktrees so the TUI warns about them at startup; `Esc` goes on to
+/// `listing`. Stays on `listing` when nothing expired or the lookup failed.
+pub fn open_expired_review<R: ProjectRepository>(repo: &R, listing: AppState) -> AppState {
+    let now = chrono::Utc::now().timestamp();
+    match find_stale_worktrees(repo, &StaleCriteria::expired(), now) {
+        Ok(candidates) if !candidates.is_empty() => AppState::ReviewingStale {
+            checked: candidates.iter().map(|c| !c.dirty).collect(),
+            candidates,
+            selected_index: 0,
+            prev_state: Box::new(listing),
+        },
+        _ => listing,
+    }
+}
+
+pub fn handle_stale_events(
+    event: &crossterm::event::Event,
+    candidates: &[crate::domain::stale::StaleWorktree],
+    checked: &mut [bool],
+    selected_index: &mut usize,
+    prev_state: &AppState,
+    current_state: &AppState,
+) -> Option<AppState> {
+    use crossterm::event::{Event, KeyCode};
+    let Event::Key(key) = event else {
+        return None;
+    };
+    let count = candidates.len();
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => return Some(prev_state.clone()),
+        KeyCode::Down | KeyCode::Char('j') if count > 0 => {
+            *selected_index = (*selected_index + 1) % count;
+        }
+        KeyCode::Up | KeyCode::Char('k') if count > 0 => {
+            *selected_index = (*selected_index + count - 1) % count;
+        }
+        KeyCode::Char(' ') => {
+            if let Some(flag) = checked.get_mut(*selected_index) {
+                *flag = !*flag;
+            }
+        }
+        KeyCode::Char('a') => {
+            let all = checked.iter().all(|c| *c);
+            checked.iter_mut().for_each(|c| *c = !all);
+        }
+        KeyCode::Enter | KeyCode::Char('d') => {
+            let selected: Vec<_> = candidates
+                .iter()
+                .zip(checked.iter())
+                .filter_map(|(c, checked)| checked.then_some(c))
+                .collect();
+            if selected.is_empty() {
+                return None;
+            }
+            let dirty = selected.iter().filter(|c| c.dirty).count();
+            let mut message = format!("Remove {} stale worktree(s)?", selected.len());
+            if dirty > 0 {
+                message.push_str(&format!(
+                    " {dirty} of them have uncommitted changes that will be lost."
+                ));
+            }
+            return Some(AppState::Confirming {
+                title: " CLEAN STALE ".into(),
+                message,
+                action: Box::new(Intent::RemoveWorktrees {
+                    paths: selected.iter().map(|c| c.path.clone()).collect(),
+                    force: dirty > 0,
+                }),
+                prev_state: Box::new(current_state.clone()),
+            });
+        }
+        _ => {}
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::test_utils::scaffolding::MockRepoBuilder;
+    use crate::domain::repository::{BranchInfo, Worktree, WorktreeMetadata};
+    use crate::domain::stale::{StaleReason, StaleWorktree, expiry_after};
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+    const DAY: i64 = 86_400;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    fn worktree(branch: &str, status: &str) -> Worktree {
+        Worktree {
+            path: format!("/hub/{branch}"),
+            commit: "abc1234".into(),
+            branch: branch.into(),
+            is_bare: false,
+            is_detached: false,
+            status_summary: Some(status.into()),
+            size_bytes: 2048,
+            metadata: None,
+            upstream: None,
+            contexts: Vec::new(),
+            sync_drift: Vec::new(),
+        }
+    }
+
+    fn branch(name: &str, tip: &str, upstream_gone: bool) -> BranchInfo {
+        BranchInfo {
+            name: name.into(),
+            remote: None,
+            tip: tip.into(),
+            subject: String::new(),
+            date: String::new(),
+            timestamp: 0,
+            upstream: None,
+            ahead: 0,
+            behind: 0,
+            upstream_gone,
+            worktree: None,
+        }
+    }
+
+    fn candidate(branch: &str, dirty: bool) -> StaleWorktree {
+        StaleWorktree {
+            path: format!("/hub/{branch}"),
+            branch: branch.into(),
+            reasons: vec![StaleReason::Merged],
+            size_bytes: 0,
+            dirty,
+            status_summary: Some(if dirty { "~1" } else { "clean" }.into()),
+            idle_days: 0,
+        }
+    }
+
+    #[test]
+    fn test_stale_checklist_confirms_checked_worktrees() {
+        let candidates = vec![candidate("shipped", false), candidate("wip", true)];
+        let mut checked = vec![true, false];
+        let mut selected = 0;
+        let mut press = |code: KeyCode, checked: &mut [bool]| {
+            handle_stale_events(
+                &key(code),
+                &candidates,
+                checked,
+                &mut selected,
+                &AppState::SetupComplete,
+                &AppState::SetupComplete,
+            )
+        };
+
+        match press(KeyCode::Enter, &mut checked) {
+            Some(AppState::Confirming { action, .. }) => assert!(matches!(
+                *action,
+                Intent::RemoveWorktrees { ref paths, force: false } if paths == &["/hub/shipped"]
+            )),
+            other => panic!("Expected confirmation, got {other:?}"),
+        }
+
+        // Checking the dirty worktree forces the removal and warns about it.
+        press(KeyCode::Char('j'), &mut checked);
+        press(KeyCode::Char(' '), &mut checked);
+        match press(KeyCode::Enter, &mut checked) {
+            Some(AppState::Confirming {
+                message, action, ..
+            }) => {
+                assert!(message.contains("1 of them have uncommitted changes"));
+                assert!(matches!(
+                    *action,
+                    Intent::RemoveWorktrees { ref paths, force: true } if paths.len() == 2
+                ));
+            }
+            other => panic!("Expected confirmation, got {other:?}"),
+        }
+
+        press(KeyCode::Char('a'), &mut checked);
+        assert_eq!(checked, vec![false, false]);
+        assert!(press(KeyCode::Enter, &mut checked).is_none());
+    }
+
+    #[test]
+    fn test_find_stale_worktrees() -> anyhow::Result<()> {
+        let repo = MockRepoBuilder::default()
+            .with_worktrees(vec![
+                worktree("main", "clean"),
+                worktree("shipped", "clean"),
+                worktree("fresh", "clean"),
+                worktree("abandoned", "~2"),
+                worktree("active", "clean"),
+            ])
+            .with_branches(vec![
+                branch("main", "1111111", false),
+                branch("shipped", "2222222", false),
+                branch("fresh", "1111111", false),
+                branch("abandoned", "3333333", true),
+                branch("active", "4444444", false),
+            ])
+            .with_merged(vec!["main", "shipped", "fresh"])
+            .build();
+
+        let stale = find_stale_worktrees(&repo, &StaleCriteria::all(30), 40 * DAY)?;
+        let summary: Vec<(&str, String, bool)> = stale
+            .iter()
+            .map(|s| (s.branch.as_str(), s.reasons_label(), s.dirty))
+            .collect();
+        // Every worktree idles 40 days in the mock; `main` is never a candidate.
+        assert_eq!(
+            summary,
+            vec![
+                ("shipped", "merged, inactive 40d".to_string(), false),
+                ("fresh", "inactive 40d".to_string(), false),
+                ("abandoned", "upstream gone, inactive 40d".to_string(), true),
+                ("active", "inactive 40d".to_string(), false),
+            ]
+        );
+
+        let merged_only = StaleCriteria {
+            merged: true,
+            ..StaleCriteria::default()
+        };
+        let stale = find_stale_worktrees(&repo, &merged_only, 40 * DAY)?;
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].reasons, vec![StaleReason::Merged]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_expired_worktrees() -> anyhow::Result<()> {
+        let now = 1_800_000_000;
+        let expiring = |branch: &str, status: &str, expires_at: Option<String>| Worktree {
+            metadata: Some(WorktreeMetadata {
+                expires_at,
+                ..Default::default()
+            }),
+            ..worktree(branch, status)
+        };
+        let repo = MockRepoBuilder::default()
+            .with_worktrees(vec![
+                expiring("spike", "~1", expiry_after(now - 2 * DAY, 1)),
+                expiring("later", "clean", expiry_after(now, 3)),
+                expiring("forever", "clean", None),
+            ])
+            .with_branches(vec![branch("main", "1111111", false)])
+            .build();
+
+        let stale = find_stale_worktrees(&repo, &StaleCriteria::expired(), now)?;
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].branch, "spike");
+        assert_eq!(stale[0].reasons, vec![StaleReason::Expired]);
+        assert!(stale[0].dirty);
+
+        // A worktree whose status could not be read is never removed unasked.
+        let unknown = Worktree {
+            status_summary: None,
+            ..expiring("broken", "", expiry_after(now - 2 * DAY, 1))
+        };
+        let repo = MockRepoBuilder::default()
+            .with_worktrees(vec![unknown])
+            .with_branches(vec![branch("main", "1111111", false)])
+            .build();
+        let stale = find_stale_worktrees(&repo, &StaleCriteria::expired(), now)?;
+        assert!(stale[0].dirty);
+        assert_eq!(stale[0].status_label(), "status unknown");
+        Ok(())
+    }
+}
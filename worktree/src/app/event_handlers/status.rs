@@ -1,6 +1,8 @@
-use crate::app::model::AppState;
+use crate::app::model::{AppState, EditorTarget};
 use crate::domain::repository::ProjectRepository;
 
+use super::editor::open_in_editor;
+
 #[allow(clippy::too_many_arguments)]
 pub fn handle_status_events<R: ProjectRepository + Clone + Send + Sync + 'static>(
     event: &crossterm::event::Event,
@@ -106,6 +108,37 @@ pub fn handle_status_events<R: ProjectRepository + Clone + Send + Sync + 'static
                         });
                     }
                 }
+                KeyCode::Enter | KeyCode::Char('e') => {
+                    let file = status.selected_file()?.to_string();
+                    let absolute = std::path::Path::new(path).join(&file);
+                    if !absolute.exists() {
+                        return Some(AppState::Error(
+                            format!("{file} was deleted; nothing to open"),
+                            Box::new(current_state.clone()),
+                        ));
+                    }
+                    let idx = status.selected_index;
+                    let line = if idx < status.staged.len() + status.unstaged.len() {
+                        repo.first_changed_line(path, &file, idx < status.staged.len())
+                            .ok()
+                            .flatten()
+                    } else {
+                        // Untracked files have no diff; open them at the top.
+                        None
+                    };
+                    let target = EditorTarget {
+                        path: path.to_string(),
+                        file: Some(absolute.to_string_lossy().to_string()),
+                        line,
+                    };
+                    return Some(open_in_editor(
+                        repo,
+                        branch,
+                        target,
+                        key_code == KeyCode::Char('E'),
+                        current_state.clone(),
+                    ));
+                }
                 KeyCode::Char('c') => {
                     return Some(AppState::Committing {
                         path: path.to_string(),
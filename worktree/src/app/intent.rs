@@ -1,4 +1,7 @@
 use crate::app::model::AppMode;
+use crate::domain::list_layout::{GroupMode, SortMode};
+use crate::domain::repository::HistoryQuery;
+use crate::domain::stale::StaleCriteria;
 
 #[derive(Clone, Debug)]
 pub enum Intent {
@@ -8,14 +11,27 @@ pub enum Intent {
         warp: bool,
     },
     AddWorktree {
-        intent: String,
+        /// Derived from `issue` when not given.
+        intent: Option<String>,
         branch: Option<String>,
+        remote: Option<String>,
+        purpose: Option<String>,
+        color: Option<String>,
+        icon: Option<String>,
+        issue: Option<String>,
+        template: Option<String>,
+        /// Days until the worktree expires.
+        ttl: Option<u64>,
     },
     RemoveWorktree {
         intent: String,
         force: bool,
     },
-    ListWorktrees,
+    /// `None` keeps the layout saved for the hub.
+    ListWorktrees {
+        sort: Option<SortMode>,
+        group: Option<GroupMode>,
+    },
     SetupDefaults,
     RunCommand {
         intent: String,
@@ -25,12 +41,34 @@ pub enum Intent {
     SyncConfigurations {
         intent: Option<String>,
     },
-    #[allow(dead_code)]
+    /// Prints a worktree's metadata, or one field of it.
+    ShowMetadata {
+        name: String,
+        key: Option<String>,
+    },
+    /// Sets a metadata field, or clears it when `value` is `None`.
+    SetMetadata {
+        name: String,
+        key: String,
+        value: Option<String>,
+    },
+    /// Runs `command` in one existing worktree, or in all of them when `name` is `None`.
+    Exec {
+        name: Option<String>,
+        command: Vec<String>,
+    },
+    /// `sync --check` / `sync --fix`: report (and optionally repair) drift from the hub.
+    CheckSync {
+        intent: Option<String>,
+        fix: bool,
+    },
     Pull {
         intent: Option<String>,
+        remote: Option<String>,
     },
     Push {
         intent: Option<String>,
+        remote: Option<String>,
     },
     Config {
         key: Option<String>,
@@ -38,7 +76,22 @@ pub enum Intent {
     },
     CleanWorktrees {
         dry_run: bool,
-        artifacts: bool,
+    },
+    /// Removes build artifacts from every worktree but the current one.
+    CleanArtifacts {
+        dry_run: bool,
+        /// Only worktrees idle for at least this many days.
+        inactive_days: Option<u64>,
+    },
+    CleanStale {
+        criteria: StaleCriteria,
+        dry_run: bool,
+        force: bool,
+    },
+    /// Removes the given worktree paths; used by the TUI stale checklist.
+    RemoveWorktrees {
+        paths: Vec<String>,
+        force: bool,
     },
     SwitchWorktree {
         name: String,
@@ -66,6 +119,77 @@ pub enum Intent {
     Teleport {
         target: String,
     },
+    EditWorktree {
+        name: String,
+        editor: Option<String>,
+        file: Option<String>,
+        line: Option<usize>,
+        save: bool,
+    },
+    ShowLog {
+        name: String,
+        query: HistoryQuery,
+    },
+    Compare {
+        left: String,
+        right: String,
+        patch: bool,
+    },
+    Port {
+        range: String,
+        to: String,
+        from: Option<String>,
+        remove: bool,
+    },
+    ListBranches {
+        all: bool,
+    },
+    CreateBranch {
+        name: String,
+        base: String,
+    },
+    RenameBranch {
+        old: String,
+        new: String,
+    },
+    DeleteBranch {
+        name: String,
+        force: bool,
+    },
+    SetUpstream {
+        branch: String,
+        upstream: String,
+    },
+    OpenBranch {
+        branch: String,
+        name: Option<String>,
+    },
+    ListRemotes,
+    CacheStatus,
+    /// `template list`: every `templates:` entry and its problems.
+    ListTemplates,
+    AddRemote {
+        name: String,
+        url: String,
+    },
+    RemoveRemote {
+        name: String,
+    },
+    FetchRemote {
+        remote: Option<String>,
+    },
+    SyncWarpWorkflows,
+    ShellInit {
+        shell: crate::infrastructure::shell_integration::ShellKind,
+    },
+    Prompt {
+        timeout_ms: u64,
+    },
+    Complete {
+        kind: Option<crate::infrastructure::completion::CompletionKind>,
+        cword: Option<usize>,
+        words: Vec<String>,
+    },
     ViewStashes {
         path: String,
         branch: String,
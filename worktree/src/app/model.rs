@@ -1,17 +1,54 @@
 use crate::app::intent::Intent;
-use crate::domain::repository::Worktree;
+use crate::domain::list_layout::{ListLayout, expanded_indices};
+use crate::domain::repository::{Worktree, WorktreeMetadata};
 use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
 use ratatui::widgets::TableState;
 use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone)]
 pub enum PromptType {
-    NameNewWorktree { base_ref: String },
+    /// `template` is a `templates:` entry chosen with Tab, if any.
+    NameNewWorktree {
+        base_ref: String,
+        template: Option<String>,
+    },
+    CreateBranch {
+        base: String,
+    },
+    RenameBranch {
+        old: String,
+    },
+    SetUpstream {
+        branch: String,
+    },
     CommitMessage,
     StashMessage,
     ApiKey,
 }
 
+/// Values being edited in the metadata form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataForm {
+    /// Parallel to `WorktreeMetadata::FIELDS`.
+    pub values: Vec<String>,
+    pub focused: usize,
+    /// Why the last save was rejected, e.g. an unknown color.
+    pub error: Option<String>,
+}
+
+impl MetadataForm {
+    pub fn new(metadata: &WorktreeMetadata) -> Self {
+        Self {
+            values: WorktreeMetadata::FIELDS
+                .iter()
+                .map(|key| metadata.get(key).ok().flatten().unwrap_or_default())
+                .collect(),
+            focused: 0,
+            error: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum AppMode {
     #[default]
@@ -35,50 +72,7 @@ pub enum RefreshType {
     Full,
 }
 
-#[derive(Clone, serde::Serialize, serde::Deserialize, Debug)]
-pub struct EditorConfig {
-    pub name: String,
-    pub command: String,
-}
-
-impl EditorConfig {
-    pub fn defaults() -> Vec<Self> {
-        vec![
-            Self {
-                name: "VS Code".into(),
-                command: "code".into(),
-            },
-            Self {
-                name: "Cursor".into(),
-                command: "cursor".into(),
-            },
-            Self {
-                name: "Zed".into(),
-                command: "zed".into(),
-            },
-            Self {
-                name: "Android Studio".into(),
-                command: "studio".into(),
-            },
-            Self {
-                name: "IntelliJ IDEA".into(),
-                command: "idea".into(),
-            },
-            Self {
-                name: "Vim".into(),
-                command: "vim".into(),
-            },
-            Self {
-                name: "Neovim".into(),
-                command: "nvim".into(),
-            },
-            Self {
-                name: "Antigravity".into(),
-                command: "antigravity".into(),
-            },
-        ]
-    }
-}
+pub use crate::domain::repository::{EditorConfig, EditorTarget};
 
 #[derive(Clone, Debug)]
 pub struct StatusViewState {
@@ -192,7 +186,17 @@ pub enum AppState {
     SelectingEditor {
         branch: String,
         options: Vec<EditorConfig>,
+        /// The first `installed` options were found on `$PATH`.
+        installed: usize,
         selected: usize,
+        target: EditorTarget,
+        prev_state: Box<Self>,
+    },
+    /// Handing the terminal (or a detached process) to the chosen editor.
+    LaunchingEditor {
+        branch: String,
+        editor: EditorConfig,
+        target: EditorTarget,
         prev_state: Box<Self>,
     },
     /// Opening a worktree in the selected editor.
@@ -214,6 +218,8 @@ pub enum AppState {
         is_filtering: bool,
         mode: AppMode,
         last_selection_change: Instant,
+        /// Sort order and grouping; `worktrees` is kept arranged by it.
+        layout: ListLayout,
     },
     /// Detailed Git status view for a specific worktree.
     ViewingStatus {
@@ -244,9 +250,104 @@ pub enum AppState {
     },
     /// Git commit history log view.
     ViewingHistory {
+        path: String,
         branch: String,
         commits: Vec<crate::domain::repository::GitCommit>,
         selected_index: usize,
+        /// Filters of the loaded pages; `skip` is the offset of the last page.
+        query: crate::domain::repository::HistoryQuery,
+        /// Whether the last page was full, so scrolling may load another.
+        has_more: bool,
+        /// Whether the next page is being fetched in the background.
+        loading_more: bool,
+        /// The search line while `/` is being typed.
+        search: Option<String>,
+        /// Hashes marked with `Space` for porting to another worktree.
+        marked: Vec<String>,
+        prev_state: Box<Self>,
+    },
+    /// Full detail (message, people, refs, stats, diff) of a commit from the history view.
+    ViewingCommitDetail {
+        path: String,
+        branch: String,
+        detail: Box<crate::domain::repository::CommitDetail>,
+        scroll: u16,
+        /// Last line `scroll` may reach, counted once when the detail is loaded.
+        max_scroll: u16,
+        prev_state: Box<Self>,
+    },
+    /// Choosing the worktree commits get cherry-picked (or moved) into.
+    PickingCherryPickTarget {
+        /// Oldest first, in the order they are applied.
+        hashes: Vec<String>,
+        /// Worktree the commits come from; moving drops them there.
+        source: String,
+        targets: Vec<Worktree>,
+        selected_index: usize,
+        prev_state: Box<Self>,
+    },
+    /// Running a cherry-pick or revert.
+    ApplyingCommit {
+        message: String,
+        prev_state: Box<Self>,
+    },
+    /// A commit action finished successfully.
+    CommitActionComplete {
+        message: String,
+        #[allow(dead_code)]
+        prev_state: Box<Self>,
+    },
+    /// Choosing what to compare the selected worktree against.
+    PickingCompareTarget {
+        path: String,
+        left: String,
+        /// Other worktrees' refs first, then remaining local branches.
+        options: Vec<String>,
+        selected_index: usize,
+        prev_state: Box<Self>,
+    },
+    LoadingComparison {
+        left: String,
+        right: String,
+        prev_state: Box<Self>,
+    },
+    /// Unique commits, merge base and aggregate diff of two refs.
+    ViewingComparison {
+        path: String,
+        comparison: Box<crate::domain::repository::RefComparison>,
+        scroll: u16,
+        prev_state: Box<Self>,
+    },
+    /// Local and remote branches with their upstream and checkout details.
+    ManagingBranches {
+        branches: Vec<crate::domain::repository::BranchInfo>,
+        selected_index: usize,
+        /// Whether remote-tracking branches are listed too.
+        show_remote: bool,
+        prev_state: Box<Self>,
+    },
+    /// Checklist of stale worktrees offered for removal.
+    ReviewingStale {
+        candidates: Vec<crate::domain::stale::StaleWorktree>,
+        /// Parallel to `candidates`; dirty worktrees start unchecked.
+        checked: Vec<bool>,
+        selected_index: usize,
+        prev_state: Box<Self>,
+    },
+    /// Form for a worktree's purpose, description, color and icon.
+    EditingMetadata {
+        path: String,
+        branch: String,
+        form: MetadataForm,
+        prev_state: Box<Self>,
+    },
+    /// Files changed by a commit selected in the history view.
+    ViewingCommitFiles {
+        path: String,
+        branch: String,
+        commit: crate::domain::repository::GitCommit,
+        files: Vec<crate::domain::repository::ChangedFile>,
+        selected_index: usize,
         prev_state: Box<Self>,
     },
     /// Loading status for a worktree.
@@ -260,6 +361,11 @@ pub enum AppState {
         branch: String,
         prev_state: Box<Self>,
     },
+    /// Loading the detail of a commit selected in the history view.
+    LoadingCommitDetail {
+        branch: String,
+        prev_state: Box<Self>,
+    },
     /// Loading branches for selection.
     LoadingBranches { prev_state: Box<Self> },
     /// Cleaning stale worktrees/artifacts.
@@ -340,11 +446,24 @@ impl AppState {
             | Self::PushComplete { prev_state, .. }
             | Self::SelectingEditor { prev_state, .. }
             | Self::OpeningEditor { prev_state, .. }
+            | Self::LaunchingEditor { prev_state, .. }
             | Self::ViewingStatus { prev_state, .. }
             | Self::ViewingStashes { prev_state, .. }
             | Self::ViewingHistory { prev_state, .. }
+            | Self::ViewingCommitFiles { prev_state, .. }
+            | Self::ViewingCommitDetail { prev_state, .. }
+            | Self::PickingCherryPickTarget { prev_state, .. }
+            | Self::ApplyingCommit { prev_state, .. }
+            | Self::CommitActionComplete { prev_state, .. }
+            | Self::PickingCompareTarget { prev_state, .. }
+            | Self::LoadingComparison { prev_state, .. }
+            | Self::ViewingComparison { prev_state, .. }
+            | Self::ManagingBranches { prev_state, .. }
+            | Self::ReviewingStale { prev_state, .. }
+            | Self::EditingMetadata { prev_state, .. }
             | Self::LoadingStatus { prev_state, .. }
             | Self::LoadingHistory { prev_state, .. }
+            | Self::LoadingCommitDetail { prev_state, .. }
             | Self::LoadingBranches { prev_state, .. }
             | Self::Cleaning { prev_state, .. }
             | Self::Staging { prev_state, .. }
@@ -361,6 +480,15 @@ impl AppState {
     }
 }
 
+/// The rows of the list: every worktree of an expanded group, or the fuzzy matches while filtering.
+pub fn listed_worktrees(worktrees: &[Worktree], query: &str, layout: &ListLayout) -> Vec<usize> {
+    if query.is_empty() {
+        expanded_indices(worktrees, layout)
+    } else {
+        filter_worktrees(worktrees, query)
+    }
+}
+
 pub fn filter_worktrees(worktrees: &[Worktree], query: &str) -> Vec<usize> {
     if query.is_empty() {
         return (0..worktrees.len()).collect();
@@ -385,7 +513,7 @@ pub fn filter_worktrees(worktrees: &[Worktree], query: &str) -> Vec<usize> {
         .collect();
 
     // Sort by score descending
-    scored_indices.sort_by(|a, b| b.0.cmp(&a.0));
+    scored_indices.sort_by_key(|b| std::cmp::Reverse(b.0));
 
     scored_indices.into_iter().map(|(_, i)| i).collect()
 }
@@ -406,6 +534,9 @@ mod tests {
                 status_summary: Some("clean".to_string()),
                 size_bytes: 0,
                 metadata: None,
+                upstream: None,
+                contexts: Vec::new(),
+                sync_drift: Vec::new(),
             },
             Worktree {
                 path: "/path/to/dev".to_string(),
@@ -416,6 +547,9 @@ mod tests {
                 status_summary: Some("+1 ~2".to_string()),
                 size_bytes: 0,
                 metadata: None,
+                upstream: None,
+                contexts: Vec::new(),
+                sync_drift: Vec::new(),
             },
             Worktree {
                 path: "/path/to/feature-login".to_string(),
@@ -426,6 +560,9 @@ mod tests {
                 status_summary: Some("clean".to_string()),
                 size_bytes: 0,
                 metadata: None,
+                upstream: None,
+                contexts: Vec::new(),
+                sync_drift: Vec::new(),
             },
         ];
 
@@ -1,5 +1,7 @@
 use crate::app::model::AppState;
-use crate::domain::repository::Worktree;
+use crate::domain::cache::CacheStatus;
+use crate::domain::list_layout::GroupMode;
+use crate::domain::repository::{BranchInfo, Remote, Worktree};
 use miette::Result;
 
 /// Abstract interface for the UI, allowing us to swap Real UI for a Test Spy.
@@ -7,8 +9,13 @@ pub trait ViewPort: Send + Sync + 'static {
     fn render(&self, state: AppState);
     fn render_json<T: serde::Serialize>(&self, data: &T) -> Result<()>;
     fn render_banner(&self);
-    fn render_listing_table(&self, worktrees: &[Worktree]);
+    fn render_listing_table(&self, worktrees: &[Worktree], group: GroupMode);
+    fn render_branch_table(&self, branches: &[BranchInfo]);
+    fn render_remote_table(&self, remotes: &[Remote]);
+    fn render_cache_table(&self, caches: &[CacheStatus]);
     fn render_feedback_prompt(&self);
+    /// Asks a yes/no question; anything but an explicit yes (or no terminal) means no.
+    fn confirm(&self, question: &str) -> bool;
 }
 
 /// The production implementation that calls your static View
@@ -28,11 +35,27 @@ impl ViewPort for RatatuiView {
         crate::app::view::View::render_banner();
     }
 
-    fn render_listing_table(&self, worktrees: &[Worktree]) {
-        crate::app::view::View::render_listing_table(worktrees);
+    fn render_listing_table(&self, worktrees: &[Worktree], group: GroupMode) {
+        crate::app::view::View::render_listing_table(worktrees, group);
+    }
+
+    fn render_branch_table(&self, branches: &[BranchInfo]) {
+        crate::app::view::View::render_branch_table(branches);
+    }
+
+    fn render_remote_table(&self, remotes: &[Remote]) {
+        crate::app::view::View::render_remote_table(remotes);
+    }
+
+    fn render_cache_table(&self, caches: &[CacheStatus]) {
+        crate::app::view::View::render_cache_table(caches);
     }
 
     fn render_feedback_prompt(&self) {
         crate::app::view::View::render_feedback_prompt();
     }
+
+    fn confirm(&self, question: &str) -> bool {
+        crate::app::view::View::confirm(question)
+    }
 }
@@ -1,7 +1,17 @@
 use crate::app::intent::Intent;
 use crate::app::model::AppState;
 use crate::app::ports::{RatatuiView, ViewPort};
-use crate::domain::repository::{ProjectRepository, Worktree};
+use crate::domain::artifacts::{WorktreeArtifacts, find_worktree_artifacts};
+use crate::domain::issues::IssueLink;
+use crate::domain::list_layout::arrange_worktrees;
+use crate::domain::repository::{
+    ProjectRepository, Worktree, WorktreeMetadata, annotate_worktrees,
+};
+use crate::domain::stale::{expiry_after, find_stale_worktrees};
+use crate::domain::sync::{DriftState, SyncDrift};
+use crate::domain::templates;
+use crate::infrastructure::process::run_forwarding_signals;
+use crate::ui::widgets::worktree_list::format_size;
 use indicatif::{ProgressBar, ProgressStyle};
 use miette::{IntoDiagnostic, Result};
 use owo_colors::{OwoColorize, Stream::Stdout};
@@ -22,20 +32,72 @@ fn get_project_name(url: Option<&String>, name: Option<String>) -> String {
     })
 }
 
+/// Finds a worktree whose branch or directory name is exactly `name` (case-insensitive).
+fn match_worktree_exact<'a>(candidates: &[&'a Worktree], name: &str) -> Option<&'a Worktree> {
+    let needle = name.to_lowercase();
+    candidates
+        .iter()
+        .find(|wt| wt.branch.to_lowercase() == needle)
+        .or_else(|| {
+            candidates.iter().find(|wt| {
+                Path::new(&wt.path)
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.to_lowercase() == needle)
+            })
+        })
+        .copied()
+}
+
+/// Finds a worktree by name. Priority: exact branch → exact dir name → substring branch → substring path.
+fn match_worktree<'a>(candidates: &[&'a Worktree], name: &str) -> Option<&'a Worktree> {
+    let needle = name.to_lowercase();
+    match_worktree_exact(candidates, name).or_else(|| {
+        candidates
+            .iter()
+            .find(|wt| wt.branch.to_lowercase().contains(&needle))
+            .or_else(|| {
+                candidates
+                    .iter()
+                    .find(|wt| wt.path.to_lowercase().contains(&needle))
+            })
+            .copied()
+    })
+}
+
+/// A command run by `exec` failed; the process exits with the same code.
+#[derive(Debug)]
+pub struct CommandExit {
+    pub code: i32,
+}
+
+impl std::fmt::Display for CommandExit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Command exited with status {}", self.code)
+    }
+}
+
+impl std::error::Error for CommandExit {}
+
+impl miette::Diagnostic for CommandExit {}
+
 pub struct Reducer<R: ProjectRepository, V: ViewPort = RatatuiView> {
     repo: R,
     view: V,
     json_mode: bool,
     quiet_mode: bool,
+    /// The CLI definition behind completions, the shell wrapper and Warp workflows.
+    cli: clap::Command,
 }
 
 impl<R: ProjectRepository + Clone + Send + Sync + 'static> Reducer<R, RatatuiView> {
-    pub const fn new(repo: R, json_mode: bool, quiet_mode: bool) -> Self {
+    pub fn new(repo: R, json_mode: bool, quiet_mode: bool) -> Self {
         Self {
             repo,
             view: RatatuiView,
             json_mode,
             quiet_mode,
+            cli: clap::Command::new("worktree"),
         }
     }
 }
@@ -47,9 +109,17 @@ impl<R: ProjectRepository + Clone + Send + Sync + 'static, V: ViewPort> Reducer<
             view,
             json_mode,
             quiet_mode,
+            cli: clap::Command::new("worktree"),
         }
     }
 
+    /// Sets the CLI definition that completions, the shell wrapper and Warp workflows are built from.
+    #[must_use]
+    pub fn with_cli(mut self, cli: clap::Command) -> Self {
+        self.cli = cli;
+        self
+    }
+
     async fn run_blocking<F, T>(&self, f: F) -> Result<T>
     where
         F: FnOnce(R) -> anyhow::Result<T> + Send + 'static,
@@ -62,6 +132,71 @@ impl<R: ProjectRepository + Clone + Send + Sync + 'static, V: ViewPort> Reducer<
             .map_err(|e| miette::miette!(e))
     }
 
+    /// Path of the worktree whose branch or directory is exactly `name`.
+    async fn resolve_worktree_path(&self, name: &str) -> Result<String> {
+        let worktrees = self.run_blocking(|r: R| r.list_worktrees()).await?;
+        let candidates: Vec<&Worktree> = worktrees.iter().filter(|wt| !wt.is_bare).collect();
+        match_worktree_exact(&candidates, name)
+            .map(|wt| wt.path.clone())
+            .ok_or_else(|| {
+                miette::miette!(
+                    "Worktree '{name}' not found. HELP: Run `worktree list` to see available worktrees."
+                )
+            })
+    }
+
+    /// Runs a template's hooks in the new worktree, stopping at the first failure.
+    /// In JSON mode their output goes to stderr so stdout stays parseable.
+    async fn run_template_hooks(
+        &self,
+        name: &str,
+        template: &str,
+        hooks: &[String],
+        json_mode: bool,
+    ) -> Result<()> {
+        let path = self.resolve_worktree_path(name).await?;
+        let path_clone = path.clone();
+        let env = self
+            .run_blocking(move |r: R| r.worktree_env(&path_clone))
+            .await?;
+        for hook in hooks {
+            if !json_mode {
+                println!("{} {}", "▶".cyan().bold(), hook.bold());
+            }
+            let mut cmd = tokio::process::Command::new("sh");
+            cmd.args(["-c", hook])
+                .envs(env.iter().cloned())
+                .current_dir(&path);
+            if json_mode {
+                cmd.stdout(std::io::stderr());
+            }
+            let code = run_forwarding_signals(cmd).await.map_err(|e| {
+                miette::miette!("Failed to run hook '{hook}' of template '{template}': {e}")
+            })?;
+            if code != 0 {
+                return Err(miette::miette!(
+                    "Hook '{hook}' of template '{template}' exited with {code}. HELP: The worktree '{name}' was created; rerun the remaining hooks with 'worktree exec {name} -- <cmd>'."
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn report_stash(&self, action: &str, index: usize) -> Result<()> {
+        if self.json_mode {
+            self.view
+                .render_json(&serde_json::json!({
+                    "status": "success",
+                    "action": action.to_lowercase(),
+                    "stash": index
+                }))
+                .map_err(|e| miette::miette!("{e:?}"))?;
+        } else if !self.quiet_mode {
+            println!("{} {action} stash@{{{index}}}.", "✔".green().bold());
+        }
+        Ok(())
+    }
+
     #[instrument(skip(self))]
     pub async fn handle(&self, intent: Intent) -> Result<()> {
         info!(?intent, "Handling intent");
@@ -112,6 +247,7 @@ impl<R: ProjectRepository + Clone + Send + Sync + 'static, V: ViewPort> Reducer<
                                 if let Err(e) =
                                     crate::infrastructure::warp_integration::generate_warp_workflows(
                                         Path::new(&project_name),
+                                        &self.cli,
                                     )
                                 {
                                     error!(error = %e, "Failed to generate Warp workflows");
@@ -174,8 +310,122 @@ impl<R: ProjectRepository + Clone + Send + Sync + 'static, V: ViewPort> Reducer<
                     }
                 }
             }
-            Intent::AddWorktree { intent, branch } => {
-                let branch_name = branch.unwrap_or_else(|| intent.clone());
+            Intent::AddWorktree {
+                intent,
+                branch,
+                remote,
+                purpose,
+                color,
+                icon,
+                issue,
+                template,
+                ttl,
+            } => {
+                // `--issue` names the branch and worktree after the ticket.
+                let (intent, branch, purpose, issue) = match issue {
+                    Some(id) => {
+                        let id_clone = id.clone();
+                        let link = match self
+                            .run_blocking(move |r: R| r.lookup_issue(&id_clone))
+                            .await
+                        {
+                            Ok(link) => link,
+                            Err(e) => {
+                                if !json_mode {
+                                    println!(
+                                        "{} Could not look up {id}: {e}. Naming the worktree from the id only.",
+                                        "⚠".yellow().bold()
+                                    );
+                                }
+                                IssueLink {
+                                    id,
+                                    url: None,
+                                    title: None,
+                                }
+                            }
+                        };
+                        let config = self.run_blocking(|r: R| r.get_project_config()).await?;
+                        let (issue_branch, issue_name) = config.issues.names(&link);
+                        let purpose = purpose.or_else(|| link.title.clone());
+                        (
+                            intent.unwrap_or(issue_name),
+                            Some(branch.unwrap_or(issue_branch)),
+                            purpose,
+                            Some(link),
+                        )
+                    }
+                    None => (
+                        intent.ok_or_else(|| {
+                            miette::miette!("A worktree name or --issue is required.")
+                        })?,
+                        branch,
+                        purpose,
+                        None,
+                    ),
+                };
+                // `--template` supplies the branch, base and defaults; flags still win.
+                let template = match template {
+                    Some(name) if remote.is_some() => {
+                        return Err(miette::miette!(
+                            "--remote cannot be combined with --template '{name}'. HELP: Set the template's `base:` to a remote branch such as upstream/main instead."
+                        ));
+                    }
+                    Some(name) => {
+                        let config = self.run_blocking(|r: R| r.get_project_config()).await?;
+                        let found = templates::find(&config.templates, &name)
+                            .map_err(|e| miette::miette!("{e}"))?
+                            .clone();
+                        Some((name, found))
+                    }
+                    None => None,
+                };
+                // `None` for a detached template worktree.
+                let template_branch = template
+                    .as_ref()
+                    .and_then(|(_, t)| branch.clone().or_else(|| t.branch_for(&intent)));
+                // Validate up front so a bad color does not leave a half-configured worktree.
+                let mut fields = Vec::new();
+                let mut check = WorktreeMetadata::default();
+                for (key, value) in [("purpose", purpose), ("color", color), ("icon", icon)] {
+                    if let Some(value) = value {
+                        check
+                            .set(key, Some(value.clone()))
+                            .map_err(|e| miette::miette!("{e}"))?;
+                        fields.push((key, value));
+                    }
+                }
+                let mut branch_name = match &template {
+                    Some((_, t)) => template_branch
+                        .clone()
+                        .unwrap_or_else(|| t.base.clone().unwrap_or_else(|| "HEAD".to_string())),
+                    None => branch.unwrap_or_else(|| intent.clone()),
+                };
+                // `--remote upstream` or an `upstream/<branch>` argument tracks that remote.
+                let tracked = if template.is_none()
+                    && (remote.is_some() || branch_name.contains('/'))
+                {
+                    let remotes = self.run_blocking(|r: R| r.list_remotes()).await?;
+                    if let Some(remote) = remote {
+                        if !remotes.iter().any(|r| r.name == remote) {
+                            return Err(miette::miette!(
+                                "Remote '{remote}' is not configured. Add it with 'worktree remote add {remote} <url>'."
+                            ));
+                        }
+                        Some(format!("{remote}/{branch_name}"))
+                    } else if let Some(local) = remotes.iter().find_map(|r| {
+                        branch_name
+                            .strip_prefix(&r.name)
+                            .and_then(|rest| rest.strip_prefix('/'))
+                    }) {
+                        let upstream = branch_name.clone();
+                        branch_name = local.to_string();
+                        Some(upstream)
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                };
                 if !json_mode {
                     self.view.render(AppState::AddingWorktree {
                         intent: intent.clone(),
@@ -200,9 +450,71 @@ impl<R: ProjectRepository + Clone + Send + Sync + 'static, V: ViewPort> Reducer<
 
                 let intent_clone = intent.clone();
                 let branch_name_clone = branch_name.clone();
+                let tracked_clone = tracked.clone();
+                let from_issue = issue.is_some();
+                let template_clone = template.clone();
                 let res = self
-                    .run_blocking(move |r: R| r.add_worktree(&intent_clone, &branch_name_clone))
+                    .run_blocking(move |r: R| {
+                        if let Some((name, template)) = template_clone {
+                            return templates::add_from_template(
+                                &r,
+                                &intent_clone,
+                                template_branch.as_deref(),
+                                &name,
+                                &template,
+                                chrono::Utc::now().timestamp(),
+                            );
+                        }
+                        let Some(upstream) = tracked_clone else {
+                            // A ticket's branch usually does not exist yet; start it from HEAD.
+                            let exists = || -> anyhow::Result<bool> {
+                                Ok(r.list_branch_info()?
+                                    .iter()
+                                    .any(|b| !b.is_remote() && b.name == branch_name_clone))
+                            };
+                            if from_issue && !exists()? {
+                                return r.add_new_worktree(
+                                    &intent_clone,
+                                    &branch_name_clone,
+                                    "HEAD",
+                                );
+                            }
+                            return r.add_worktree(&intent_clone, &branch_name_clone);
+                        };
+                        let exists = r
+                            .list_branch_info()?
+                            .iter()
+                            .any(|b| !b.is_remote() && b.name == branch_name_clone);
+                        if exists {
+                            r.add_worktree(&intent_clone, &branch_name_clone)?;
+                        } else {
+                            r.add_new_worktree(&intent_clone, &branch_name_clone, &upstream)?;
+                        }
+                        r.set_upstream(&branch_name_clone, &upstream)
+                    })
                     .await;
+                let res = match res {
+                    Ok(()) if !fields.is_empty() || issue.is_some() || ttl.is_some() => {
+                        let intent_clone = intent.clone();
+                        let issue = issue.clone();
+                        let now = chrono::Utc::now().timestamp();
+                        self.run_blocking(move |r: R| {
+                            let mut metadata = r.worktree_metadata(&intent_clone)?;
+                            for (key, value) in fields {
+                                metadata.set(key, Some(value))?;
+                            }
+                            if issue.is_some() {
+                                metadata.issue = issue;
+                            }
+                            if let Some(days) = ttl {
+                                metadata.expires_at = expiry_after(now, days);
+                            }
+                            r.update_worktree_metadata(&intent_clone, &metadata)
+                        })
+                        .await
+                    }
+                    other => other,
+                };
 
                 match res {
                     Ok(()) => {
@@ -210,12 +522,21 @@ impl<R: ProjectRepository + Clone + Send + Sync + 'static, V: ViewPort> Reducer<
                             pb.finish_and_clear();
                         }
                         info!(%intent, %branch_name, "Worktree added successfully");
+                        if let Some((name, template)) = &template
+                            && !template.hooks.is_empty()
+                        {
+                            self.run_template_hooks(&intent, name, &template.hooks, json_mode)
+                                .await?;
+                        }
                         if json_mode {
                             self.view
                                 .render_json(&serde_json::json!({
                                     "status": "success",
                                     "intent": intent,
-                                    "branch": branch_name
+                                    "branch": branch_name,
+                                    "upstream": tracked,
+                                    "issue": issue,
+                                    "template": template.as_ref().map(|(name, _)| name)
                                 }))
                                 .map_err(|e| miette::miette!("{e:?}"))?;
                         } else if !quiet_mode {
@@ -284,8 +605,18 @@ impl<R: ProjectRepository + Clone + Send + Sync + 'static, V: ViewPort> Reducer<
                     }
                 }
             }
-            Intent::ListWorktrees => {
-                let worktrees = self.run_blocking(|r: R| r.list_worktrees()).await?;
+            Intent::ListWorktrees { sort, group } => {
+                let (worktrees, layout) = self
+                    .run_blocking(move |r: R| {
+                        let mut layout = r.list_layout();
+                        layout.sort = sort.unwrap_or(layout.sort);
+                        layout.group = group.unwrap_or(layout.group);
+                        let mut worktrees = r.list_worktrees()?;
+                        annotate_worktrees(&r, &mut worktrees);
+                        arrange_worktrees(&r, &mut worktrees, &layout);
+                        Ok((worktrees, layout))
+                    })
+                    .await?;
                 info!(count = worktrees.len(), "Worktrees listed successfully");
                 if json_mode {
                     self.view
@@ -296,7 +627,7 @@ impl<R: ProjectRepository + Clone + Send + Sync + 'static, V: ViewPort> Reducer<
                     if worktrees.is_empty() {
                         self.view.render(AppState::Welcome);
                     }
-                    self.view.render_listing_table(&worktrees);
+                    self.view.render_listing_table(&worktrees, layout.group);
                     let tip = "Tip: Run with 'worktrees list' (no args) for interactive TUI";
                     if !quiet_mode {
                         println!("\n{}", tip.if_supports_color(Stdout, |t| t.dimmed()));
@@ -392,12 +723,18 @@ impl<R: ProjectRepository + Clone + Send + Sync + 'static, V: ViewPort> Reducer<
                     );
                 }
 
-                // 2. Run command
+                // 2. Run command with the worktree's ports and stack settings
+                let intent_clone = intent.clone();
+                let env = self
+                    .run_blocking(move |r: R| r.worktree_env(&intent_clone))
+                    .await
+                    .unwrap_or_default();
                 let command_clone = command.clone();
                 let intent_clone = intent.clone();
                 let status = tokio::task::spawn_blocking(move || {
                     Command::new(&command_clone[0])
                         .args(&command_clone[1..])
+                        .envs(env)
                         .current_dir(&intent_clone)
                         .spawn()
                         .map_err(|e| miette::miette!("Failed to spawn command: {}", e))?
@@ -475,7 +812,276 @@ impl<R: ProjectRepository + Clone + Send + Sync + 'static, V: ViewPort> Reducer<
                         .map_err(|e| miette::miette!("{e:?}"))?;
                 }
             }
-            Intent::Push { intent } => {
+            Intent::Exec { name, command } => {
+                let worktrees = self.run_blocking(|r: R| r.list_worktrees()).await?;
+                let candidates: Vec<&Worktree> =
+                    worktrees.iter().filter(|wt| !wt.is_bare).collect();
+                let targets: Vec<Worktree> = match &name {
+                    Some(name) => vec![
+                        match_worktree(&candidates, name)
+                            .ok_or_else(|| {
+                                miette::miette!(
+                                    "Worktree '{name}' not found. HELP: Run `worktree list` to see available worktrees."
+                                )
+                            })?
+                            .clone(),
+                    ],
+                    None => candidates.into_iter().cloned().collect(),
+                };
+
+                let mut results = Vec::new();
+                for wt in &targets {
+                    if name.is_none() && !json_mode && !quiet_mode {
+                        println!(
+                            "{} {} {}",
+                            "➜".cyan().bold(),
+                            wt.branch.bold(),
+                            command.join(" ").dimmed()
+                        );
+                    }
+                    let path = wt.path.clone();
+                    let env = self.run_blocking(move |r: R| r.worktree_env(&path)).await?;
+                    let mut cmd = tokio::process::Command::new(&command[0]);
+                    cmd.args(&command[1..]).envs(env).current_dir(&wt.path);
+                    let code = run_forwarding_signals(cmd).await.map_err(|e| {
+                        miette::miette!("Failed to run '{}' in {}: {e}", command[0], wt.branch)
+                    })?;
+                    if code != 0 && name.is_none() && !json_mode && !quiet_mode {
+                        println!("   {} exited with status {code}", "❌".red());
+                    }
+                    results.push((wt, code));
+                }
+
+                if json_mode {
+                    self.view
+                        .render_json(&serde_json::json!({
+                            "status": "success",
+                            "results": results
+                                .iter()
+                                .map(|(wt, code)| serde_json::json!({
+                                    "path": wt.path,
+                                    "branch": wt.branch,
+                                    "exit_code": code,
+                                }))
+                                .collect::<Vec<_>>(),
+                        }))
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if name.is_none() && !quiet_mode {
+                    let failed = results.iter().filter(|(_, code)| *code != 0).count();
+                    println!(
+                        "{} {}/{} worktrees succeeded.",
+                        if failed == 0 {
+                            "✔".green().bold().to_string()
+                        } else {
+                            "⚠".yellow().to_string()
+                        },
+                        results.len() - failed,
+                        results.len()
+                    );
+                }
+
+                // The first failure decides the exit code, as with `set -e`.
+                if let Some((_, code)) = results.iter().find(|(_, code)| *code != 0) {
+                    return Err(CommandExit { code: *code }.into());
+                }
+            }
+            Intent::ShowMetadata { name, key } => {
+                let path = self.resolve_worktree_path(&name).await?;
+                let metadata = self
+                    .run_blocking(move |r: R| r.worktree_metadata(&path))
+                    .await?;
+                if let Some(key) = key {
+                    let value = metadata.get(&key).map_err(|e| miette::miette!("{e}"))?;
+                    if json_mode {
+                        self.view
+                            .render_json(&serde_json::json!({ "key": key, "value": value }))
+                            .map_err(|e| miette::miette!("{e:?}"))?;
+                    } else if let Some(value) = value {
+                        println!("{value}");
+                    }
+                } else if json_mode {
+                    self.view
+                        .render_json(&metadata)
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else {
+                    let mut shown = false;
+                    let recorded = ["created_at", "issue", "template", "expires_at", "pinned"];
+                    for key in recorded.into_iter().chain(WorktreeMetadata::FIELDS) {
+                        if let Ok(Some(value)) = metadata.get(key) {
+                            println!("{:<12} {value}", key.dimmed());
+                            shown = true;
+                        }
+                    }
+                    for (key, value) in &metadata.env {
+                        println!("{:<12} {value}", format!("env.{key}").dimmed());
+                        shown = true;
+                    }
+                    if !shown && !quiet_mode {
+                        println!(
+                            "{} No metadata for {name}. Set some with 'worktree meta {name} set purpose \"...\"'.",
+                            "➜".cyan().bold()
+                        );
+                    }
+                }
+            }
+            Intent::SetMetadata { name, key, value } => {
+                let path = self.resolve_worktree_path(&name).await?;
+                let (key_clone, value_clone) = (key.clone(), value.clone());
+                self.run_blocking(move |r: R| {
+                    let mut metadata = r.worktree_metadata(&path)?;
+                    metadata.set(&key_clone, value_clone)?;
+                    r.update_worktree_metadata(&path, &metadata)
+                })
+                .await?;
+                if json_mode {
+                    self.view
+                        .render_json(&serde_json::json!({
+                            "status": "success",
+                            "name": name,
+                            "key": key,
+                            "value": value
+                        }))
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if !quiet_mode {
+                    match value {
+                        Some(value) => println!("{} {name}: {key} = {value}", "✔".green().bold()),
+                        None => println!("{} {name}: {key} cleared", "✔".green().bold()),
+                    }
+                }
+            }
+            Intent::CheckSync { intent, fix } => {
+                let worktrees = self.run_blocking(|r: R| r.list_worktrees()).await?;
+                let targets: Vec<Worktree> = worktrees
+                    .into_iter()
+                    .filter(|wt| !wt.is_bare)
+                    .filter(|wt| {
+                        intent
+                            .as_ref()
+                            .is_none_or(|name| wt.branch == *name || wt.path.ends_with(name))
+                    })
+                    .collect();
+                if targets.is_empty() {
+                    return Err(miette::miette!("No matching worktrees found to check."));
+                }
+
+                let mut report = Vec::new();
+                let mut fixed = Vec::new();
+                let mut skipped = Vec::new();
+                let mut drifted = 0;
+                for wt in targets {
+                    let path = wt.path.clone();
+                    let drift: Vec<SyncDrift> = self
+                        .run_blocking(move |r: R| r.check_sync(&path))
+                        .await?
+                        .into_iter()
+                        .filter(|d| d.state != DriftState::InSync)
+                        .collect();
+                    drifted += drift.len();
+
+                    if !json_mode && !quiet_mode && !drift.is_empty() {
+                        println!(
+                            "{} {} ({} drifted)",
+                            "➜".cyan().bold(),
+                            wt.branch.bold(),
+                            drift.len()
+                        );
+                        for d in &drift {
+                            println!(
+                                "   {} {:<16} {}",
+                                "⚠".yellow(),
+                                d.state.label(),
+                                d.destination
+                            );
+                        }
+                    }
+
+                    if fix {
+                        let mut destinations = Vec::new();
+                        for d in &drift {
+                            // Local edits are only overwritten after seeing what they lose.
+                            if d.state == DriftState::Modified {
+                                if json_mode || quiet_mode {
+                                    skipped.push(format!("{}:{}", wt.branch, d.destination));
+                                    continue;
+                                }
+                                let (path, destination) = (wt.path.clone(), d.destination.clone());
+                                let diff = self
+                                    .run_blocking(move |r: R| r.sync_diff(&path, &destination))
+                                    .await?;
+                                for line in diff.lines() {
+                                    if line.starts_with('+') && !line.starts_with("+++") {
+                                        println!("      {}", line.green());
+                                    } else if line.starts_with('-') && !line.starts_with("---") {
+                                        println!("      {}", line.red());
+                                    } else {
+                                        println!("      {}", line.dimmed());
+                                    }
+                                }
+                                if !self.view.confirm(&format!(
+                                    "Overwrite local changes to {} in '{}'?",
+                                    d.destination, wt.branch
+                                )) {
+                                    println!(
+                                        "   {} Kept local changes to {}.",
+                                        "⚠".yellow(),
+                                        d.destination
+                                    );
+                                    skipped.push(format!("{}:{}", wt.branch, d.destination));
+                                    continue;
+                                }
+                            }
+                            destinations.push(d.destination.clone());
+                        }
+                        if !destinations.is_empty() {
+                            let path = wt.path.clone();
+                            let to_fix = destinations.clone();
+                            self.run_blocking(move |r: R| r.fix_sync(&path, &to_fix))
+                                .await?;
+                            if !json_mode && !quiet_mode {
+                                println!(
+                                    "   {} Re-synced {} entr{}.",
+                                    "✔".green(),
+                                    destinations.len(),
+                                    if destinations.len() == 1 { "y" } else { "ies" }
+                                );
+                            }
+                            fixed.extend(
+                                destinations
+                                    .into_iter()
+                                    .map(|d| format!("{}:{d}", wt.branch)),
+                            );
+                        }
+                    }
+
+                    report.push(serde_json::json!({
+                        "path": wt.path,
+                        "branch": wt.branch,
+                        "drift": drift,
+                    }));
+                }
+
+                if json_mode {
+                    self.view
+                        .render_json(&serde_json::json!({
+                            "status": if drifted == 0 || fix { "success" } else { "drift" },
+                            "worktrees": report,
+                            "fixed": fixed,
+                            "skipped": skipped,
+                        }))
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if !quiet_mode && drifted == 0 {
+                    println!("{} All synced files match the hub.", "✔".green().bold());
+                }
+
+                // A failing exit code lets `sync --check` guard CI and scripts.
+                if !fix && drifted > 0 {
+                    return Err(miette::miette!(
+                        "{drifted} synced entr{} drifted from the hub. Run `worktree sync --fix` to repair.",
+                        if drifted == 1 { "y" } else { "ies" }
+                    ));
+                }
+            }
+            Intent::Push { intent, remote } => {
                 let worktrees = self.run_blocking(|r: R| r.list_worktrees()).await?;
 
                 let target = if let Some(name) = intent {
@@ -490,15 +1096,21 @@ impl<R: ProjectRepository + Clone + Send + Sync + 'static, V: ViewPort> Reducer<
 
                 if let Some(wt) = target {
                     if !json_mode && !quiet_mode {
+                        let target = remote
+                            .as_deref()
+                            .map(|r| format!(" to {}", r.bold()))
+                            .unwrap_or_default();
                         println!(
-                            "{} Pushing worktree: {}",
+                            "{} Pushing worktree: {}{target}",
                             "➜".cyan().bold(),
                             wt.branch.bold()
                         );
                     }
 
                     let path = wt.path.clone();
-                    let res = self.run_blocking(move |r: R| r.push(&path)).await;
+                    let res = self
+                        .run_blocking(move |r: R| r.push(&path, remote.as_deref()))
+                        .await;
 
                     match res {
                         Ok(()) => {
@@ -523,7 +1135,7 @@ impl<R: ProjectRepository + Clone + Send + Sync + 'static, V: ViewPort> Reducer<
                 }
             }
 
-            Intent::Pull { intent } => {
+            Intent::Pull { intent, remote } => {
                 let worktrees = self.run_blocking(|r: R| r.list_worktrees()).await?;
 
                 let target = if let Some(name) = intent {
@@ -538,15 +1150,21 @@ impl<R: ProjectRepository + Clone + Send + Sync + 'static, V: ViewPort> Reducer<
 
                 if let Some(wt) = target {
                     if !json_mode && !quiet_mode {
+                        let target = remote
+                            .as_deref()
+                            .map(|r| format!(" from {}", r.bold()))
+                            .unwrap_or_default();
                         println!(
-                            "{} Pulling worktree: {}",
+                            "{} Pulling worktree: {}{target}",
                             "➜".cyan().bold(),
                             wt.branch.bold()
                         );
                     }
 
                     let path = wt.path.clone();
-                    let res = self.run_blocking(move |r: R| r.pull(&path)).await;
+                    let res = self
+                        .run_blocking(move |r: R| r.pull(&path, remote.as_deref()))
+                        .await;
 
                     match res {
                         Ok(()) => {
@@ -602,21 +1220,9 @@ impl<R: ProjectRepository + Clone + Send + Sync + 'static, V: ViewPort> Reducer<
                     }
                 }
             }
-            Intent::CleanWorktrees { dry_run, artifacts } => {
+            Intent::CleanWorktrees { dry_run } => {
                 if !json_mode {
-                    if artifacts {
-                        if dry_run {
-                            println!(
-                                "{} Scanning for build artifacts in inactive worktrees (dry-run)...",
-                                "➜".cyan().bold()
-                            );
-                        } else {
-                            println!(
-                                "{} Cleaning build artifacts from inactive worktrees...",
-                                "➜".cyan().bold()
-                            );
-                        }
-                    } else if dry_run {
+                    if dry_run {
                         println!(
                             "{} Scanning for stale worktrees (dry-run)...",
                             "➜".cyan().bold()
@@ -627,7 +1233,7 @@ impl<R: ProjectRepository + Clone + Send + Sync + 'static, V: ViewPort> Reducer<
                 }
 
                 let stale_worktrees = self
-                    .run_blocking(move |r: R| r.clean_worktrees(dry_run, artifacts))
+                    .run_blocking(move |r: R| r.clean_worktrees(dry_run, false))
                     .await
                     .map_err(|e| miette::miette!("Failed to clean worktrees: {}", e))?;
 
@@ -677,85 +1283,487 @@ impl<R: ProjectRepository + Clone + Send + Sync + 'static, V: ViewPort> Reducer<
                         .map_err(|e| miette::miette!("{e:?}"))?;
                 }
             }
-            Intent::SwitchWorktree { name, copy } => {
-                let worktrees = self.run_blocking(|r: R| r.list_worktrees()).await?;
-
-                let candidates: Vec<&Worktree> =
-                    worktrees.iter().filter(|wt| !wt.is_bare).collect();
-
-                let needle = name.to_lowercase();
-
-                // Priority: exact branch → exact dir name → substring branch → substring path
-                let matched = candidates
-                    .iter()
-                    .find(|wt| wt.branch.to_lowercase() == needle)
-                    .or_else(|| {
-                        candidates.iter().find(|wt| {
-                            Path::new(&wt.path)
-                                .file_name()
-                                .and_then(|n| n.to_str())
-                                .is_some_and(|n| n.to_lowercase() == needle)
-                        })
-                    })
-                    .or_else(|| {
-                        candidates
-                            .iter()
-                            .find(|wt| wt.branch.to_lowercase().contains(&needle))
+            Intent::CleanArtifacts {
+                dry_run,
+                inactive_days,
+            } => {
+                if !json_mode && !quiet_mode {
+                    println!(
+                        "{} Scanning worktrees for build artifacts{}...",
+                        "➜".cyan().bold(),
+                        inactive_days.map_or_else(String::new, |days| format!(
+                            " (inactive for {days}d or more)"
+                        ))
+                    );
+                }
+                let now = chrono::Utc::now().timestamp();
+                let current_dir = std::env::current_dir()
+                    .and_then(|d| d.canonicalize())
+                    .into_diagnostic()?;
+                let found: Vec<_> = self
+                    .run_blocking(move |r: R| find_worktree_artifacts(&r, inactive_days, now))
+                    .await?
+                    .into_iter()
+                    .filter(|wt| {
+                        !Path::new(&wt.path)
+                            .canonicalize()
+                            .is_ok_and(|p| current_dir.starts_with(p))
                     })
-                    .or_else(|| {
-                        candidates
-                            .iter()
-                            .find(|wt| wt.path.to_lowercase().contains(&needle))
-                    });
+                    .collect();
+                let total: u64 = found.iter().map(WorktreeArtifacts::size_bytes).sum();
 
-                match matched {
-                    Some(wt) => {
-                        if copy {
-                            // Copy path to clipboard using pbcopy on macOS
-                            #[cfg(target_os = "macos")]
-                            {
-                                use std::io::Write;
-                                use std::process::Stdio;
-                                let mut child = Command::new("pbcopy")
-                                    .stdin(Stdio::piped())
-                                    .spawn()
-                                    .map_err(|e| {
-                                        miette::miette!("Failed to spawn pbcopy: {}", e)
-                                    })?;
-
-                                if let Some(mut stdin) = child.stdin.take() {
-                                    stdin.write_all(wt.path.as_bytes()).map_err(|e| {
-                                        miette::miette!("Failed to write to pbcopy: {}", e)
-                                    })?;
-                                }
-                                child.wait().map_err(|e| {
-                                    miette::miette!("Failed to wait for pbcopy: {}", e)
-                                })?;
+                if !json_mode && !quiet_mode {
+                    for wt in &found {
+                        println!(
+                            "   • {:<24} {:>9}  {} artifact(s), idle {}d",
+                            wt.branch.bold(),
+                            format_size(wt.size_bytes()),
+                            wt.artifacts.len(),
+                            wt.idle_days
+                        );
+                        if dry_run {
+                            for artifact in &wt.artifacts {
+                                println!(
+                                    "       {:<40} {:<9} {:>9}",
+                                    artifact.relative.dimmed(),
+                                    artifact.ecosystem.label(),
+                                    format_size(artifact.size_bytes)
+                                );
                             }
                         }
+                    }
+                }
 
-                        if json_mode {
-                            self.view
-                                .render_json(&serde_json::json!({
-                                    "status": "success",
-                                    "path": wt.path,
-                                    "branch": wt.branch,
-                                    "copied": copy
-                                }))
-                                .map_err(|e| miette::miette!("{e:?}"))?;
-                        } else {
-                            // Print ONLY the path to stdout for shell integration
-                            println!("{}", wt.path);
+                let mut cleaned = Vec::new();
+                if !dry_run {
+                    for wt in &found {
+                        let path = wt.path.clone();
+                        let artifacts = wt.artifacts.clone();
+                        match self
+                            .run_blocking(move |r: R| r.remove_artifacts(&path, &artifacts))
+                            .await
+                        {
+                            Ok(()) => cleaned.push(wt.branch.clone()),
+                            Err(e) => {
+                                error!(error = %e, branch = %wt.branch, "Failed to remove artifacts");
+                                if !json_mode {
+                                    println!("   {} {}: {e}", "❌".red(), wt.branch);
+                                }
+                            }
                         }
                     }
-                    None => {
-                        return Err(miette::miette!(
+                }
+
+                if json_mode {
+                    self.view
+                        .render_json(&serde_json::json!({
+                            "status": "success",
+                            "dry_run": dry_run,
+                            "reclaimable_bytes": total,
+                            "worktrees": found,
+                            "cleaned": cleaned
+                        }))
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if !quiet_mode {
+                    if found.is_empty() {
+                        println!("{} No build artifacts found.", "✔".green().bold());
+                    } else if dry_run {
+                        println!(
+                            "\n{} {} reclaimable in {} worktree(s). Run without --dry-run to delete.",
+                            "Tip:".cyan().bold(),
+                            format_size(total),
+                            found.len()
+                        );
+                    } else {
+                        let freed: u64 = found
+                            .iter()
+                            .filter(|wt| cleaned.contains(&wt.branch))
+                            .map(WorktreeArtifacts::size_bytes)
+                            .sum();
+                        println!(
+                            "\n{} Freed {} from {} worktree(s).",
+                            "✔".green().bold(),
+                            format_size(freed),
+                            cleaned.len()
+                        );
+                    }
+                }
+            }
+            Intent::CleanStale {
+                criteria,
+                dry_run,
+                force,
+            } => {
+                if !json_mode && !quiet_mode {
+                    println!("{} Looking for stale worktrees...", "➜".cyan().bold());
+                }
+                let now = chrono::Utc::now().timestamp();
+                let candidates = self
+                    .run_blocking(move |r: R| find_stale_worktrees(&r, &criteria, now))
+                    .await?;
+
+                if !json_mode && !quiet_mode {
+                    if candidates.is_empty() {
+                        println!("{} No stale worktrees found.", "✔".green().bold());
+                    }
+                    for candidate in &candidates {
+                        let dirty = if candidate.dirty {
+                            format!("  {} {}", "dirty".red(), candidate.status_label())
+                        } else {
+                            String::new()
+                        };
+                        println!(
+                            "   • {:<24} {:<32} {:>9}{dirty}",
+                            candidate.branch.bold(),
+                            candidate.reasons_label().yellow(),
+                            format_size(candidate.size_bytes)
+                        );
+                    }
+                }
+
+                let current_dir = std::env::current_dir()
+                    .and_then(|d| d.canonicalize())
+                    .into_diagnostic()?;
+                // Expiry is meant to run unattended, and --json/--quiet cannot ask, so those runs
+                // keep every dirty candidate unless --force is given.
+                let unattended = criteria.expired || json_mode || quiet_mode;
+                let mut removed = Vec::new();
+                let mut skipped = Vec::new();
+                let mut refused = Vec::new();
+                for candidate in &candidates {
+                    let is_current = Path::new(&candidate.path)
+                        .canonicalize()
+                        .is_ok_and(|p| current_dir.starts_with(p));
+                    if candidate.dirty && !force && unattended {
+                        if !dry_run && !json_mode && !quiet_mode {
+                            println!(
+                                "   {} Kept '{}': it has uncommitted changes ({}). Use --force to remove it.",
+                                "⚠".yellow(),
+                                candidate.branch,
+                                candidate.status_label()
+                            );
+                        }
+                        refused.push(candidate.branch.clone());
+                        continue;
+                    }
+                    let confirmed = !candidate.dirty
+                        || force
+                        || (!dry_run
+                            && self.view.confirm(&format!(
+                                "'{}' has uncommitted changes ({}). Remove it anyway?",
+                                candidate.branch,
+                                candidate.status_label()
+                            )));
+                    if dry_run || is_current || !confirmed {
+                        skipped.push(candidate.branch.clone());
+                        continue;
+                    }
+                    let path = candidate.path.clone();
+                    let dirty = candidate.dirty;
+                    match self
+                        .run_blocking(move |r: R| r.remove_worktree(&path, dirty))
+                        .await
+                    {
+                        Ok(()) => removed.push(candidate.branch.clone()),
+                        Err(e) => {
+                            error!(error = %e, branch = %candidate.branch, "Failed to remove stale worktree");
+                            if !json_mode {
+                                println!("   {} {}: {e}", "❌".red(), candidate.branch);
+                            }
+                            skipped.push(candidate.branch.clone());
+                        }
+                    }
+                }
+
+                if json_mode {
+                    self.view
+                        .render_json(&serde_json::json!({
+                            "status": "success",
+                            "dry_run": dry_run,
+                            "candidates": candidates,
+                            "removed": removed,
+                            "skipped": skipped,
+                            "refused": refused
+                        }))
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if !quiet_mode && !candidates.is_empty() {
+                    if dry_run {
+                        println!(
+                            "\n{} Run without --dry-run to remove these worktrees.",
+                            "Tip:".cyan().bold()
+                        );
+                    } else {
+                        println!(
+                            "\n{} Removed {} stale worktree(s), kept {}.",
+                            "✔".green().bold(),
+                            removed.len(),
+                            skipped.len() + refused.len()
+                        );
+                    }
+                }
+            }
+            Intent::RemoveWorktrees { paths, force } => {
+                for path in paths {
+                    let path_clone = path.clone();
+                    self.run_blocking(move |r: R| r.remove_worktree(&path_clone, force))
+                        .await?;
+                    if !json_mode && !quiet_mode {
+                        println!("{} Removed {path}", "✔".green().bold());
+                    }
+                }
+            }
+            Intent::SwitchWorktree { name, copy } => {
+                let worktrees = self.run_blocking(|r: R| r.list_worktrees()).await?;
+
+                let candidates: Vec<&Worktree> =
+                    worktrees.iter().filter(|wt| !wt.is_bare).collect();
+
+                let matched = match_worktree(&candidates, &name);
+
+                match matched {
+                    Some(wt) => {
+                        if copy {
+                            crate::infrastructure::clipboard::copy_to_clipboard(&wt.path)
+                                .map_err(|e| miette::miette!("{e:#}"))?;
+                        }
+
+                        if json_mode {
+                            self.view
+                                .render_json(&serde_json::json!({
+                                    "status": "success",
+                                    "path": wt.path,
+                                    "branch": wt.branch,
+                                    "copied": copy
+                                }))
+                                .map_err(|e| miette::miette!("{e:?}"))?;
+                        } else if !crate::infrastructure::shell_integration::write_cd_target(
+                            &wt.path,
+                        )
+                        .map_err(|e| miette::miette!("{e:?}"))?
+                        {
+                            // Print ONLY the path to stdout for shell integration
+                            println!("{}", wt.path);
+                        }
+                    }
+                    None => {
+                        return Err(miette::miette!(
                             "No worktree found matching '{}'. Run 'worktrees list' to see available worktrees.",
                             name
                         ));
                     }
                 }
             }
+            Intent::EditWorktree {
+                name,
+                editor,
+                file,
+                line,
+                save,
+            } => {
+                let worktrees = self.run_blocking(|r: R| r.list_worktrees()).await?;
+                let candidates: Vec<&Worktree> =
+                    worktrees.iter().filter(|wt| !wt.is_bare).collect();
+                let wt = match_worktree(&candidates, &name).ok_or_else(|| {
+                    miette::miette!(
+                        "No worktree found matching '{}'. Run 'worktrees list' to see available worktrees.",
+                        name
+                    )
+                })?;
+
+                let editors = self.run_blocking(|r: R| r.list_editors()).await?;
+                let preferred = match editor.clone() {
+                    Some(e) => Some(e),
+                    None => self.run_blocking(|r: R| r.get_preferred_editor()).await?,
+                };
+                let resolved = crate::infrastructure::editor::resolve_editor(
+                    preferred.as_deref(),
+                    &editors,
+                )
+                .ok_or_else(|| {
+                    miette::miette!(
+                        "No editor configured. Pass --editor, set $VISUAL or $EDITOR, or pick one in the TUI with 'e'."
+                    )
+                })?;
+
+                if save && let Some(choice) = editor {
+                    self.run_blocking(move |r: R| {
+                        let mut config = r.get_project_config()?;
+                        config.editor = Some(choice);
+                        r.save_project_config(&config)
+                    })
+                    .await?;
+                }
+
+                let target = crate::domain::repository::EditorTarget {
+                    path: wt.path.clone(),
+                    file: file.map(|f| Path::new(&wt.path).join(f).to_string_lossy().to_string()),
+                    line,
+                };
+                let args = target.render_args(&resolved);
+
+                if !json_mode && !quiet_mode {
+                    println!(
+                        "{} Opening {} in {}",
+                        "➜".cyan().bold(),
+                        wt.branch.bold(),
+                        resolved.name.cyan()
+                    );
+                }
+
+                let launched = resolved.clone();
+                self.run_blocking(move |_r: R| {
+                    crate::infrastructure::editor::launch(&launched, &target)
+                })
+                .await?;
+
+                if json_mode {
+                    self.view
+                        .render_json(&serde_json::json!({
+                            "status": "success",
+                            "path": wt.path,
+                            "branch": wt.branch,
+                            "editor": resolved.name,
+                            "command": resolved.command,
+                            "args": args
+                        }))
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                }
+            }
+            Intent::ShowLog { name, query } => {
+                let worktrees = self.run_blocking(|r: R| r.list_worktrees()).await?;
+                let candidates: Vec<&Worktree> =
+                    worktrees.iter().filter(|wt| !wt.is_bare).collect();
+                let wt = match_worktree(&candidates, &name).ok_or_else(|| {
+                    miette::miette!(
+                        "No worktree found matching '{}'. Run 'worktrees list' to see available worktrees.",
+                        name
+                    )
+                })?;
+
+                let path = wt.path.clone();
+                let page_query = query.clone();
+                let commits = self
+                    .run_blocking(move |r: R| r.get_history(&path, &page_query))
+                    .await?;
+
+                if json_mode {
+                    self.view
+                        .render_json(&serde_json::json!({
+                            "status": "success",
+                            "branch": wt.branch,
+                            "path": wt.path,
+                            "query": query,
+                            "commits": commits,
+                            "has_more": commits.len() >= query.limit
+                        }))
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if commits.is_empty() {
+                    if !quiet_mode {
+                        println!("{} No commits match on {}", "⚠".yellow(), wt.branch.bold());
+                    }
+                } else {
+                    for commit in &commits {
+                        println!(
+                            "{} {} {} {}",
+                            commit.hash.yellow(),
+                            commit.date.dimmed(),
+                            commit.author.cyan(),
+                            commit.message
+                        );
+                    }
+                    if !quiet_mode && commits.len() >= query.limit {
+                        println!(
+                            "\n{} More commits available: --skip {}",
+                            "➜".cyan().bold(),
+                            query.skip + commits.len()
+                        );
+                    }
+                }
+            }
+            Intent::Compare { left, right, patch } => {
+                let worktrees = self.run_blocking(|r: R| r.list_worktrees()).await?;
+                let candidates: Vec<&Worktree> =
+                    worktrees.iter().filter(|wt| !wt.is_bare).collect();
+                // Sides name a worktree only on an exact match; anything else is taken as a ref.
+                let left_wt = match_worktree_exact(&candidates, &left);
+                let right_wt = match_worktree_exact(&candidates, &right);
+                let path = left_wt
+                    .or(right_wt)
+                    .or_else(|| candidates.first().copied())
+                    .map_or_else(|| ".".to_string(), |wt| wt.path.clone());
+                let left_ref = left_wt.map_or(left.clone(), |wt| wt.head_ref().to_string());
+                let right_ref = right_wt.map_or(right.clone(), |wt| wt.head_ref().to_string());
+
+                let comparison = self
+                    .run_blocking(move |r: R| r.compare_refs(&path, &left_ref, &right_ref))
+                    .await?;
+
+                if json_mode {
+                    self.view
+                        .render_json(&serde_json::json!({
+                            "status": "success",
+                            "comparison": comparison
+                        }))
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else {
+                    let short = |hash: &str| hash.get(..7).unwrap_or(hash).to_string();
+                    println!(
+                        "{} Comparing {} ⇄ {} (merge base {})",
+                        "➜".cyan().bold(),
+                        comparison.left.bold(),
+                        comparison.right.bold(),
+                        comparison
+                            .merge_base
+                            .as_deref()
+                            .map_or_else(|| "none".to_string(), short)
+                            .yellow()
+                    );
+                    for (arrow, side, commits) in [
+                        ("↑", &comparison.left, &comparison.left_only),
+                        ("↓", &comparison.right, &comparison.right_only),
+                    ] {
+                        println!(
+                            "\n{} {} commit(s) only in {}",
+                            arrow.cyan().bold(),
+                            commits.len(),
+                            side.bold()
+                        );
+                        for commit in commits {
+                            println!(
+                                "  {} {} {} {}",
+                                commit.hash.yellow(),
+                                commit.date.dimmed(),
+                                commit.author.cyan(),
+                                commit.message
+                            );
+                        }
+                    }
+
+                    let (added, removed) = comparison.files.iter().fold((0, 0), |(a, d), f| {
+                        (a + f.additions.unwrap_or(0), d + f.deletions.unwrap_or(0))
+                    });
+                    println!(
+                        "\n{} file(s) changed {} {}",
+                        comparison.files.len(),
+                        format!("+{added}").green(),
+                        format!("-{removed}").red()
+                    );
+                    for file in &comparison.files {
+                        match (file.additions, file.deletions) {
+                            (Some(a), Some(d)) => println!(
+                                "  {:>6} {:>6}  {}",
+                                format!("+{a}").green(),
+                                format!("-{d}").red(),
+                                file.path
+                            ),
+                            _ => println!("  {:>13}  {}", "bin".dimmed(), file.path),
+                        }
+                    }
+                    if patch && !comparison.diff.is_empty() {
+                        println!("\n{}", comparison.diff);
+                    }
+                }
+            }
             Intent::Convert { name, branch } => {
                 if !json_mode && !quiet_mode {
                     println!(
@@ -982,6 +1990,11 @@ impl<R: ProjectRepository + Clone + Send + Sync + 'static, V: ViewPort> Reducer<
                 let mut cmd = crate::cli::Cli::command();
                 let bin_name = cmd.get_name().to_string();
                 clap_complete::generate(shell, &mut cmd, bin_name, &mut std::io::stdout());
+                if let Some(shim) =
+                    crate::infrastructure::completion::dynamic_completion_shim(shell)
+                {
+                    print!("{shim}");
+                }
             }
             Intent::Open => {
                 let worktrees = self.run_blocking(|r: R| r.list_worktrees()).await?;
@@ -1112,28 +2125,7 @@ impl<R: ProjectRepository + Clone + Send + Sync + 'static, V: ViewPort> Reducer<
                 };
 
                 // 2. Resolve Target Worktree (using fuzzy match logic like Switch)
-                let needle = target.to_lowercase();
-                let matched_target = candidates
-                    .iter()
-                    .find(|wt| wt.branch.to_lowercase() == needle)
-                    .or_else(|| {
-                        candidates.iter().find(|wt| {
-                            Path::new(&wt.path)
-                                .file_name()
-                                .and_then(|n| n.to_str())
-                                .is_some_and(|n| n.to_lowercase() == needle)
-                        })
-                    })
-                    .or_else(|| {
-                        candidates
-                            .iter()
-                            .find(|wt| wt.branch.to_lowercase().contains(&needle))
-                    })
-                    .or_else(|| {
-                        candidates
-                            .iter()
-                            .find(|wt| wt.path.to_lowercase().contains(&needle))
-                    });
+                let matched_target = match_worktree(&candidates, &target);
 
                 let target_wt = match matched_target {
                     Some(wt) => wt,
@@ -1218,49 +2210,640 @@ impl<R: ProjectRepository + Clone + Send + Sync + 'static, V: ViewPort> Reducer<
                         .map_err(|e| miette::miette!("{e:?}"))?;
                 }
             }
-            Intent::ApplyStash { path, index } => {
-                let path_clone = path;
-                self.run_blocking(move |r: R| r.apply_stash(&path_clone, index))
-                    .await
-                    .map_err(|e| miette::miette!(e.to_string()))?;
-            }
-            Intent::PopStash { path, index } => {
-                let path_clone = path;
-                self.run_blocking(move |r: R| r.pop_stash(&path_clone, index))
-                    .await
-                    .map_err(|e| miette::miette!(e.to_string()))?;
-            }
-            Intent::DropStash { path, index } => {
-                let path_clone = path;
-                self.run_blocking(move |r: R| r.drop_stash(&path_clone, index))
-                    .await
-                    .map_err(|e| miette::miette!(e.to_string()))?;
-            }
-            Intent::StashSave { path, message } => {
-                let path_clone = path;
-                self.run_blocking(move |r: R| r.stash_save(&path_clone, message.as_deref()))
-                    .await
-                    .map_err(|e| miette::miette!(e.to_string()))?;
-            }
-            Intent::ViewStashes { .. } => {}
-            Intent::ChangeMode(_) => {
-                // This is primarily handled in listing.rs for TUI
-                // but we keep the variant here for intent completeness.
-            }
-        }
-
-        Ok(())
-    }
-}
+            Intent::Port {
+                range,
+                to,
+                from,
+                remove,
+            } => {
+                let worktrees = self.run_blocking(|r: R| r.list_worktrees()).await?;
+                let candidates: Vec<&Worktree> =
+                    worktrees.iter().filter(|wt| !wt.is_bare).collect();
+                let target = match_worktree(&candidates, &to).ok_or_else(|| {
+                    miette::miette!(
+                        "No worktree found matching '{}'. Run 'worktrees list' to see available worktrees.",
+                        to
+                    )
+                })?;
+                let source_path = match &from {
+                    Some(name) => match_worktree(&candidates, name)
+                        .ok_or_else(|| {
+                            miette::miette!(
+                                "No worktree found matching '{}'. Run 'worktrees list' to see available worktrees.",
+                                name
+                            )
+                        })?
+                        .path
+                        .clone(),
+                    None => {
+                        // The worktree containing the current directory, which may be a subdirectory.
+                        let current_dir = std::env::current_dir()
+                            .and_then(|d| d.canonicalize())
+                            .into_diagnostic()?;
+                        candidates
+                            .iter()
+                            .filter(|wt| {
+                                Path::new(&wt.path)
+                                    .canonicalize()
+                                    .is_ok_and(|p| current_dir.starts_with(p))
+                            })
+                            .max_by_key(|wt| wt.path.len())
+                            .ok_or_else(|| {
+                                miette::miette!(
+                                    "The current directory is not inside a worktree. Pass --from <worktree>."
+                                )
+                            })?
+                            .path
+                            .clone()
+                    }
+                };
+                if source_path == target.path {
+                    return Err(miette::miette!(
+                        "Source and target are the same worktree. Pass --from or run this from the source worktree."
+                    ));
+                }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::domain::repository::{ProjectRepository, RepositoryEvent, Worktree};
-    use anyhow::Result;
-    use crossbeam_channel::Receiver;
+                let range_clone = range.clone();
+                let path_clone = source_path.clone();
+                let hashes = self
+                    .run_blocking(move |r: R| r.resolve_commit_range(&path_clone, &range_clone))
+                    .await?;
+                // Refuse before porting anything, so a range that cannot be dropped afterwards
+                // does not end up on both branches.
+                if remove {
+                    let path_clone = source_path.clone();
+                    let to_drop = hashes.clone();
+                    self.run_blocking(move |r: R| r.check_droppable(&path_clone, &to_drop))
+                        .await?;
+                }
 
-    use std::sync::{Arc, Mutex};
+                if !json_mode && !quiet_mode {
+                    println!(
+                        "{} Porting {} commit(s) into {}...",
+                        "➜".cyan().bold(),
+                        hashes.len(),
+                        target.branch.bold()
+                    );
+                }
+
+                let target_path = target.path.clone();
+                let to_port = hashes.clone();
+                let outcome = self
+                    .run_blocking(move |r: R| {
+                        Ok(crate::domain::port::port_commits(
+                            &r,
+                            &target_path,
+                            &to_port,
+                        ))
+                    })
+                    .await?;
+
+                if let Some(conflict) = &outcome.conflict {
+                    error!(error = %conflict.error, "Port stopped on a conflict");
+                    if json_mode {
+                        self.view
+                            .render_json(&serde_json::json!({
+                                "status": "conflict",
+                                "target": target.branch,
+                                "ported": outcome.ported,
+                                "conflict": conflict
+                            }))
+                            .map_err(|e| miette::miette!("{e:?}"))?;
+                        return Err(CommandExit { code: 1 }.into());
+                    }
+                    let short = |hash: &str| hash.get(..7).unwrap_or(hash).to_string();
+                    println!(
+                        "\n{} Cherry-pick of {} into {} stopped on conflicts ({} of {} applied).",
+                        "❌".red().bold(),
+                        short(&conflict.commit).yellow(),
+                        target.branch.bold(),
+                        outcome.ported.len(),
+                        hashes.len()
+                    );
+                    println!(
+                        "{} Analyzing conflicts with Gemini AI...",
+                        "➜".cyan().bold()
+                    );
+                    let diff = conflict.diff.clone();
+                    let explanation_res: Result<String> = self
+                        .run_blocking(move |r: R| r.explain_rebase_conflict(&diff))
+                        .await;
+                    match explanation_res {
+                        Ok(explanation) => {
+                            println!("\n{}", "AI Conflict Explanation:".yellow().bold());
+                            println!("{}", explanation);
+                        }
+                        Err(qe) => {
+                            error!(error = %qe, "Gemini explanation failed");
+                        }
+                    }
+                    println!(
+                        "\n{} Resolve in {}, then run 'git cherry-pick --continue' (or '--abort').",
+                        "⚠".yellow(),
+                        target.path
+                    );
+                    if !conflict.remaining.is_empty() {
+                        let remaining: Vec<String> =
+                            conflict.remaining.iter().map(|h| short(h)).collect();
+                        println!("   Not yet ported: {}", remaining.join(" "));
+                    }
+                    if remove {
+                        println!("   The source branch was left unchanged.");
+                    }
+                    return Err(CommandExit { code: 1 }.into());
+                }
+
+                if remove {
+                    let path_clone = source_path.clone();
+                    let to_drop = hashes.clone();
+                    self.run_blocking(move |r: R| r.drop_commits(&path_clone, &to_drop))
+                        .await?;
+                }
+
+                if json_mode {
+                    self.view
+                        .render_json(&serde_json::json!({
+                            "status": "success",
+                            "target": target.branch,
+                            "ported": outcome.ported,
+                            "removed_from_source": remove
+                        }))
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if !quiet_mode {
+                    println!(
+                        "{} Ported {} commit(s) into {}.",
+                        "✔".green().bold(),
+                        outcome.ported.len(),
+                        target.branch.bold()
+                    );
+                    if remove {
+                        println!("{} Removed them from {}.", "✔".green().bold(), source_path);
+                    }
+                }
+            }
+            Intent::ListBranches { all } => {
+                let mut branches = self.run_blocking(|r: R| r.list_branch_info()).await?;
+                if !all {
+                    branches.retain(|b| !b.is_remote());
+                }
+                if json_mode {
+                    self.view
+                        .render_json(&branches)
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if branches.is_empty() {
+                    println!("{} No branches yet.", "⚠".yellow());
+                } else {
+                    self.view.render_branch_table(&branches);
+                }
+            }
+            Intent::CreateBranch { name, base } => {
+                let (name_clone, base_clone) = (name.clone(), base.clone());
+                self.run_blocking(move |r: R| r.create_branch(&name_clone, &base_clone))
+                    .await?;
+                if json_mode {
+                    self.view
+                        .render_json(&serde_json::json!({
+                            "status": "success",
+                            "branch": name,
+                            "base": base
+                        }))
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if !quiet_mode {
+                    println!(
+                        "{} Created branch {} from {}.",
+                        "✔".green().bold(),
+                        name.bold(),
+                        base
+                    );
+                }
+            }
+            Intent::RenameBranch { old, new } => {
+                let (old_clone, new_clone) = (old.clone(), new.clone());
+                self.run_blocking(move |r: R| r.rename_branch(&old_clone, &new_clone))
+                    .await?;
+                if json_mode {
+                    self.view
+                        .render_json(&serde_json::json!({
+                            "status": "success",
+                            "old": old,
+                            "new": new
+                        }))
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if !quiet_mode {
+                    println!(
+                        "{} Renamed branch {} to {}.",
+                        "✔".green().bold(),
+                        old,
+                        new.bold()
+                    );
+                }
+            }
+            Intent::DeleteBranch { name, force } => {
+                let branches = self.run_blocking(|r: R| r.list_branch_info()).await?;
+                let branch = branches
+                    .into_iter()
+                    .find(|b| !b.is_remote() && b.name == name)
+                    .ok_or_else(|| miette::miette!("No local branch named '{}'.", name))?;
+                if let Some(path) = &branch.worktree {
+                    return Err(miette::miette!(
+                        "Branch '{}' is checked out in {}. Remove that worktree first.",
+                        name,
+                        path
+                    ));
+                }
+                if !force {
+                    let name_clone = name.clone();
+                    let unmerged = self
+                        .run_blocking(move |r: R| r.branch_unmerged_into(&name_clone))
+                        .await?;
+                    if let Some(target) = unmerged {
+                        return Err(miette::miette!(
+                            "Branch '{}' has commits that are not in '{}'. Use --force to delete it anyway.",
+                            name,
+                            target
+                        ));
+                    }
+                }
+                let name_clone = name.clone();
+                self.run_blocking(move |r: R| r.delete_branch(&name_clone))
+                    .await?;
+                if json_mode {
+                    self.view
+                        .render_json(&serde_json::json!({
+                            "status": "success",
+                            "branch": name,
+                            "tip": branch.tip
+                        }))
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if !quiet_mode {
+                    println!(
+                        "{} Deleted branch {} (was {}).",
+                        "✔".green().bold(),
+                        name.bold(),
+                        branch.tip.yellow()
+                    );
+                }
+            }
+            Intent::SetUpstream { branch, upstream } => {
+                let (branch_clone, upstream_clone) = (branch.clone(), upstream.clone());
+                self.run_blocking(move |r: R| r.set_upstream(&branch_clone, &upstream_clone))
+                    .await?;
+                if json_mode {
+                    self.view
+                        .render_json(&serde_json::json!({
+                            "status": "success",
+                            "branch": branch,
+                            "upstream": upstream
+                        }))
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if !quiet_mode {
+                    println!(
+                        "{} {} now tracks {}.",
+                        "✔".green().bold(),
+                        branch.bold(),
+                        upstream
+                    );
+                }
+            }
+            Intent::OpenBranch { branch, name } => {
+                let branches = self.run_blocking(|r: R| r.list_branch_info()).await?;
+                let info = branches
+                    .iter()
+                    .find(|b| b.name == branch)
+                    .ok_or_else(|| miette::miette!("No branch named '{}'.", branch))?;
+                let local = info.local_name().to_string();
+                let local_info = branches.iter().find(|b| !b.is_remote() && b.name == local);
+                if let Some(path) = local_info.and_then(|b| b.worktree.as_ref()) {
+                    return Err(miette::miette!(
+                        "Branch '{}' is already checked out in {}.",
+                        local,
+                        path
+                    ));
+                }
+                let intent = name.unwrap_or_else(|| local.clone());
+                // A remote branch without a local counterpart gets a new tracking branch.
+                let start = (local_info.is_none() && info.is_remote()).then(|| info.name.clone());
+
+                let (intent_clone, local_clone) = (intent.clone(), local.clone());
+                self.run_blocking(move |r: R| match &start {
+                    Some(remote) => r.add_new_worktree(&intent_clone, &local_clone, remote),
+                    None => r.add_worktree(&intent_clone, &local_clone),
+                })
+                .await?;
+                info!(%intent, branch = %local, "Worktree added for branch");
+                if json_mode {
+                    self.view
+                        .render_json(&serde_json::json!({
+                            "status": "success",
+                            "intent": intent,
+                            "branch": local
+                        }))
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if !quiet_mode {
+                    self.view.render(AppState::WorktreeAdded { intent });
+                }
+            }
+            Intent::CacheStatus => {
+                let statuses = self.run_blocking(|r: R| r.cache_status()).await?;
+                if json_mode {
+                    self.view
+                        .render_json(&statuses)
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if statuses.is_empty() {
+                    println!(
+                        "{} Shared caches are turned off. Set 'cache.enabled: true' in .worktrees.yaml to share build output.",
+                        "⚠".yellow()
+                    );
+                } else {
+                    self.view.render_cache_table(&statuses);
+                    for status in &statuses {
+                        if let Some(problem) = &status.problem {
+                            println!("{} {}: {problem}", "⚠".yellow(), status.kind.label());
+                        }
+                    }
+                }
+            }
+            Intent::ListTemplates => {
+                let config = self.run_blocking(|r: R| r.get_project_config()).await?;
+                let invalid = config
+                    .templates
+                    .values()
+                    .filter(|t| !t.problems().is_empty())
+                    .count();
+                if json_mode {
+                    let entries: Vec<_> = config
+                        .templates
+                        .iter()
+                        .map(|(name, template)| {
+                            serde_json::json!({
+                                "name": name,
+                                "template": template,
+                                "problems": template.problems()
+                            })
+                        })
+                        .collect();
+                    self.view
+                        .render_json(&entries)
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if config.templates.is_empty() {
+                    println!(
+                        "{} No templates defined. Add them under 'templates:' in .worktrees.yaml.",
+                        "⚠".yellow()
+                    );
+                } else {
+                    for (name, template) in &config.templates {
+                        let problems = template.problems();
+                        let mark = if problems.is_empty() {
+                            "✔".green().bold().to_string()
+                        } else {
+                            "✘".red().bold().to_string()
+                        };
+                        let mut summary = match template.branch_for("{name}") {
+                            Some(branch) => format!("branch {branch}"),
+                            None => "detached".to_string(),
+                        };
+                        summary.push_str(&format!(
+                            " from {}",
+                            template.base.as_deref().unwrap_or("HEAD")
+                        ));
+                        if !template.sync.is_empty() {
+                            summary.push_str(&format!(", {} sync", template.sync.len()));
+                        }
+                        if !template.hooks.is_empty() {
+                            summary.push_str(&format!(", {} hooks", template.hooks.len()));
+                        }
+                        if let Some(age) = &template.expire_after {
+                            summary.push_str(&format!(", expires after {age}"));
+                        }
+                        println!("{mark} {} {}", name.bold(), summary.dimmed());
+                        if let Some(text) =
+                            template.description.as_ref().or(template.purpose.as_ref())
+                        {
+                            println!("   {text}");
+                        }
+                        for problem in problems {
+                            println!("   {} {problem}", "└─".red());
+                        }
+                    }
+                }
+                if invalid > 0 {
+                    return Err(miette::miette!(
+                        "{invalid} template(s) in .worktrees.yaml are invalid."
+                    ));
+                }
+            }
+            Intent::ListRemotes => {
+                let remotes = self.run_blocking(|r: R| r.list_remotes()).await?;
+                if json_mode {
+                    self.view
+                        .render_json(&remotes)
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if remotes.is_empty() {
+                    println!(
+                        "{} No remotes configured. Add one with 'worktree remote add <name> <url>'.",
+                        "⚠".yellow()
+                    );
+                } else {
+                    self.view.render_remote_table(&remotes);
+                }
+            }
+            Intent::AddRemote { name, url } => {
+                if !json_mode && !quiet_mode {
+                    println!(
+                        "{} Adding remote {} ({url})",
+                        "➜".cyan().bold(),
+                        name.bold()
+                    );
+                }
+                let (name_clone, url_clone) = (name.clone(), url.clone());
+                self.run_blocking(move |r: R| r.add_remote(&name_clone, &url_clone))
+                    .await?;
+                if json_mode {
+                    self.view
+                        .render_json(&serde_json::json!({
+                            "status": "success",
+                            "remote": name,
+                            "url": url
+                        }))
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if !quiet_mode {
+                    println!(
+                        "   {} Fetched {}. Track its branches with 'worktree add <name> {name}/<branch>'.",
+                        "✔".green(),
+                        name.bold()
+                    );
+                }
+            }
+            Intent::RemoveRemote { name } => {
+                let worktrees = self.run_blocking(|r: R| r.list_worktrees()).await?;
+                let prefix = format!("{name}/");
+                let orphaned: Vec<Worktree> = worktrees
+                    .into_iter()
+                    .filter(|wt| {
+                        wt.upstream
+                            .as_deref()
+                            .is_some_and(|u| u.starts_with(&prefix))
+                    })
+                    .collect();
+                let name_clone = name.clone();
+                self.run_blocking(move |r: R| r.remove_remote(&name_clone))
+                    .await?;
+                if json_mode {
+                    self.view
+                        .render_json(&serde_json::json!({
+                            "status": "success",
+                            "remote": name,
+                            "untracked": orphaned.iter().map(|wt| &wt.branch).collect::<Vec<_>>()
+                        }))
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if !quiet_mode {
+                    println!("{} Removed remote {}.", "✔".green().bold(), name.bold());
+                    for wt in orphaned {
+                        println!(
+                            "   {} {} no longer has an upstream (was {}).",
+                            "⚠".yellow(),
+                            wt.branch.bold(),
+                            wt.upstream.unwrap_or_default()
+                        );
+                    }
+                }
+            }
+            Intent::FetchRemote { remote } => {
+                if !json_mode && !quiet_mode {
+                    println!(
+                        "{} Fetching {}",
+                        "➜".cyan().bold(),
+                        remote.as_deref().unwrap_or("all remotes").bold()
+                    );
+                }
+                let remote_clone = remote.clone();
+                self.run_blocking(move |r: R| r.fetch(".", remote_clone.as_deref()))
+                    .await?;
+                if json_mode {
+                    self.view
+                        .render_json(&serde_json::json!({
+                            "status": "success",
+                            "remote": remote
+                        }))
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if !quiet_mode {
+                    println!("   {} Fetch complete.", "✔".green());
+                }
+            }
+            Intent::SyncWarpWorkflows => {
+                let root = self.run_blocking(|r: R| r.get_project_root()).await?;
+                let workflow_path =
+                    crate::infrastructure::warp_integration::generate_warp_workflows(
+                        &root, &self.cli,
+                    )
+                    .map_err(|e| miette::miette!("{e:?}"))?;
+
+                if json_mode {
+                    self.view
+                        .render_json(&serde_json::json!({
+                            "status": "success",
+                            "path": workflow_path.to_string_lossy()
+                        }))
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if !quiet_mode {
+                    println!(
+                        "{} Warp workflows written to {}",
+                        "✔".green().bold(),
+                        workflow_path.display()
+                    );
+                }
+            }
+            Intent::ShellInit { shell } => {
+                print!(
+                    "{}",
+                    crate::infrastructure::shell_integration::init_script(shell, &self.cli)
+                );
+            }
+            Intent::Prompt { timeout_ms } => {
+                let current_dir = std::env::current_dir().into_diagnostic()?;
+                let info = crate::infrastructure::shell_integration::prompt_info(
+                    &current_dir,
+                    Duration::from_millis(timeout_ms),
+                );
+
+                if json_mode {
+                    self.view
+                        .render_json(&serde_json::json!({
+                            "status": "success",
+                            "worktree": info.as_ref().map(|i| &i.name),
+                            "branch": info.as_ref().and_then(|i| i.branch.as_ref()),
+                            "dirty": info.as_ref().is_some_and(|i| i.dirty),
+                            "ahead": info.as_ref().map_or(0, |i| i.ahead),
+                            "behind": info.as_ref().map_or(0, |i| i.behind)
+                        }))
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else if let Some(info) = info {
+                    println!("{}", info.render());
+                }
+            }
+            Intent::Complete { kind, cword, words } => {
+                let candidates = tokio::task::spawn_blocking(move || {
+                    crate::infrastructure::completion::complete(kind, &words, cword)
+                })
+                .await
+                .into_diagnostic()?;
+
+                if json_mode {
+                    self.view
+                        .render_json(&candidates)
+                        .map_err(|e| miette::miette!("{e:?}"))?;
+                } else {
+                    for candidate in candidates {
+                        println!("{candidate}");
+                    }
+                }
+            }
+            Intent::ApplyStash { path, index } => {
+                let path_clone = path;
+                self.run_blocking(move |r: R| r.apply_stash(&path_clone, index))
+                    .await
+                    .map_err(|e| miette::miette!(e.to_string()))?;
+                self.report_stash("Applied", index)?;
+            }
+            Intent::PopStash { path, index } => {
+                let path_clone = path;
+                self.run_blocking(move |r: R| r.pop_stash(&path_clone, index))
+                    .await
+                    .map_err(|e| miette::miette!(e.to_string()))?;
+                self.report_stash("Popped", index)?;
+            }
+            Intent::DropStash { path, index } => {
+                let path_clone = path;
+                self.run_blocking(move |r: R| r.drop_stash(&path_clone, index))
+                    .await
+                    .map_err(|e| miette::miette!(e.to_string()))?;
+                self.report_stash("Dropped", index)?;
+            }
+            Intent::StashSave { path, message } => {
+                let path_clone = path;
+                self.run_blocking(move |r: R| r.stash_save(&path_clone, message.as_deref()))
+                    .await
+                    .map_err(|e| miette::miette!(e.to_string()))?;
+            }
+            Intent::ViewStashes { .. } => {}
+            Intent::ChangeMode(_) => {
+                // This is primarily handled in listing.rs for TUI
+                // but we keep the variant here for intent completeness.
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repository::{ProjectRepository, Remote, RepositoryEvent, Worktree};
+    use anyhow::Result;
+    use crossbeam_channel::Receiver;
+
+    use std::sync::{Arc, Mutex};
 
     #[derive(Default)]
     struct CallTracker {
@@ -1330,6 +2913,9 @@ mod tests {
                         status_summary: Some("clean".to_string()),
                         size_bytes: 0,
                         metadata: None,
+                        upstream: None,
+                        contexts: Vec::new(),
+                        sync_drift: Vec::new(),
                     },
                     Worktree {
                         path: "dev".to_string(),
@@ -1340,6 +2926,9 @@ mod tests {
                         status_summary: Some("~1".to_string()),
                         size_bytes: 0,
                         metadata: None,
+                        upstream: None,
+                        contexts: Vec::new(),
+                        sync_drift: Vec::new(),
                     },
                 ])
             }
@@ -1352,81 +2941,430 @@ mod tests {
                 .push(format!("sync:{path}"));
             Ok(())
         }
-
-        fn get_project_root(&self) -> anyhow::Result<std::path::PathBuf> {
+        fn check_sync(&self, path: &str) -> anyhow::Result<Vec<crate::domain::sync::SyncDrift>> {
+            use crate::domain::sync::{DriftState, SyncAction, SyncDrift};
+            if path != "dev" {
+                return Ok(Vec::new());
+            }
+            Ok(vec![
+                SyncDrift {
+                    destination: ".env".into(),
+                    source: ".env".into(),
+                    action: SyncAction::Copy,
+                    state: DriftState::Modified,
+                },
+                SyncDrift {
+                    destination: "certs".into(),
+                    source: "certs".into(),
+                    action: SyncAction::Copy,
+                    state: DriftState::Missing,
+                },
+                SyncDrift {
+                    destination: "app.env".into(),
+                    source: "app.env.tmpl".into(),
+                    action: SyncAction::Template,
+                    state: DriftState::InSync,
+                },
+            ])
+        }
+
+        fn fix_sync(&self, path: &str, destinations: &[String]) -> anyhow::Result<()> {
+            self.tracker
+                .lock()
+                .unwrap()
+                .calls
+                .push(format!("fix-sync:{path}|{}", destinations.join(",")));
+            Ok(())
+        }
+
+        fn sync_diff(&self, _path: &str, destination: &str) -> anyhow::Result<String> {
+            Ok(format!(
+                "--- a/{destination}\n+++ b/{destination}\n-local\n+hub\n"
+            ))
+        }
+        fn worktree_env(&self, path: &str) -> anyhow::Result<Vec<(String, String)>> {
+            Ok(vec![("WORKTREE_PORT".into(), format!("2{}", path.len()))])
+        }
+        fn add_detached_worktree(&self, path: &str, base: &str) -> anyhow::Result<()> {
+            self.tracker
+                .lock()
+                .unwrap()
+                .calls
+                .push(format!("add_detached:{path}|{base}"));
+            Ok(())
+        }
+        fn sync_entries(&self, path: &str, entries: &[String]) -> anyhow::Result<()> {
+            self.tracker
+                .lock()
+                .unwrap()
+                .calls
+                .push(format!("sync_entries:{path}|{}", entries.join(";")));
+            Ok(())
+        }
+        fn lookup_issue(&self, id: &str) -> anyhow::Result<crate::domain::issues::IssueLink> {
+            // A local stand-in for a tracker.
+            Ok(crate::domain::issues::IssueLink {
+                id: id.to_string(),
+                url: Some(format!("https://tracker.local/{id}")),
+                title: Some("Login page".to_string()),
+            })
+        }
+        fn worktree_metadata(
+            &self,
+            path: &str,
+        ) -> anyhow::Result<crate::domain::repository::WorktreeMetadata> {
+            Ok(crate::domain::repository::WorktreeMetadata {
+                purpose: (path == "dev").then(|| "Integration".to_string()),
+                ..Default::default()
+            })
+        }
+
+        fn list_layout(&self) -> crate::domain::list_layout::ListLayout {
+            crate::domain::list_layout::ListLayout::default()
+        }
+        fn save_list_layout(
+            &self,
+            _layout: &crate::domain::list_layout::ListLayout,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn expired_worktrees(&self, _now: i64) -> anyhow::Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+
+        fn update_worktree_metadata(
+            &self,
+            path: &str,
+            metadata: &crate::domain::repository::WorktreeMetadata,
+        ) -> anyhow::Result<()> {
+            self.tracker.lock().unwrap().calls.push(format!(
+                "meta:{path}|{}",
+                serde_json::to_string(metadata).unwrap()
+            ));
+            Ok(())
+        }
+
+        fn get_project_root(&self) -> anyhow::Result<std::path::PathBuf> {
+            self.tracker
+                .lock()
+                .unwrap()
+                .calls
+                .push("get_project_root".to_string());
+            Ok(std::path::PathBuf::from("/mock/root"))
+        }
+
+        fn pull(&self, path: &str, remote: Option<&str>) -> anyhow::Result<()> {
+            let remote = remote.map(|r| format!("|{r}")).unwrap_or_default();
+            self.tracker
+                .lock()
+                .unwrap()
+                .calls
+                .push(format!("pull:{path}{remote}"));
+            Ok(())
+        }
+        fn detect_context(
+            &self,
+            _base_path: &std::path::Path,
+        ) -> crate::domain::repository::ProjectContext {
+            crate::domain::repository::ProjectContext::default()
+        }
+        fn get_preferred_editor(&self) -> anyhow::Result<Option<String>> {
+            Ok(None)
+        }
+        fn set_preferred_editor(&self, _editor: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn list_editors(&self) -> anyhow::Result<Vec<crate::domain::repository::EditorConfig>> {
+            Ok(crate::domain::repository::EditorConfig::defaults())
+        }
+        fn get_project_config(&self) -> anyhow::Result<crate::domain::repository::ProjectConfig> {
+            Ok(crate::domain::repository::ProjectConfig {
+                templates: serde_yaml::from_str(
+                    "hotfix: {base: main, branch: 'hotfix/{name}', purpose: Hotfix, sync: ['copy .env.prod .env']}\nspike: {detached: true, expire_after: 7d}",
+                )?,
+                ..crate::domain::repository::ProjectConfig::default()
+            })
+        }
+        fn save_project_config(
+            &self,
+            _config: &crate::domain::repository::ProjectConfig,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn fetch(&self, _path: &str, remote: Option<&str>) -> anyhow::Result<()> {
+            self.tracker
+                .lock()
+                .unwrap()
+                .calls
+                .push(format!("fetch:{}", remote.unwrap_or("--all")));
+            Ok(())
+        }
+        fn push(&self, path: &str, remote: Option<&str>) -> anyhow::Result<()> {
+            let remote = remote.map(|r| format!("|{r}")).unwrap_or_default();
+            self.tracker
+                .lock()
+                .unwrap()
+                .calls
+                .push(format!("push:{path}{remote}"));
+            Ok(())
+        }
+        fn list_remotes(&self) -> anyhow::Result<Vec<Remote>> {
+            Ok(["origin", "upstream"]
+                .into_iter()
+                .map(|name| Remote {
+                    name: name.to_string(),
+                    fetch_url: format!("git@example.com:{name}/app.git"),
+                    push_url: format!("git@example.com:{name}/app.git"),
+                })
+                .collect())
+        }
+        fn add_remote(&self, name: &str, url: &str) -> anyhow::Result<()> {
+            self.tracker
+                .lock()
+                .unwrap()
+                .calls
+                .push(format!("add-remote:{name}|{url}"));
+            Ok(())
+        }
+        fn remove_remote(&self, name: &str) -> anyhow::Result<()> {
+            self.tracker
+                .lock()
+                .unwrap()
+                .calls
+                .push(format!("remove-remote:{name}"));
+            Ok(())
+        }
+        fn get_status(&self, path: &str) -> anyhow::Result<crate::domain::repository::GitStatus> {
+            let tracker = self.tracker.lock().unwrap();
+            Ok(tracker.status_map.get(path).cloned().unwrap_or_else(|| {
+                crate::domain::repository::GitStatus {
+                    staged: vec![],
+                    unstaged: vec![],
+                    untracked: vec![],
+                }
+            }))
+        }
+        fn stage_all(&self, _path: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn unstage_all(&self, _path: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn stage_file(&self, _path: &str, _file: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn unstage_file(&self, _path: &str, _file: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn commit(&self, _path: &str, _message: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn get_history(
+            &self,
+            path: &str,
+            query: &crate::domain::repository::HistoryQuery,
+        ) -> anyhow::Result<Vec<crate::domain::repository::GitCommit>> {
+            self.tracker.lock().unwrap().calls.push(format!(
+                "history:{path}|{}|{}|{}",
+                query.limit,
+                query.search_text(),
+                query.graph
+            ));
+            Ok(vec![crate::domain::repository::GitCommit {
+                hash: "abc1234".into(),
+                author: "Jane".into(),
+                date: "2026-01-01".into(),
+                message: "Fix bug".into(),
+                graph: String::new(),
+            }])
+        }
+        fn get_commit_files(
+            &self,
+            _path: &str,
+            _hash: &str,
+        ) -> anyhow::Result<Vec<crate::domain::repository::ChangedFile>> {
+            Ok(vec![])
+        }
+        fn get_commit_detail(
+            &self,
+            _path: &str,
+            hash: &str,
+        ) -> anyhow::Result<crate::domain::repository::CommitDetail> {
+            Ok(crate::domain::repository::CommitDetail {
+                hash: hash.to_string(),
+                author: String::new(),
+                author_date: String::new(),
+                committer: String::new(),
+                committer_date: String::new(),
+                parents: vec![],
+                refs: vec![],
+                subject: String::new(),
+                body: String::new(),
+                files: vec![],
+                diff: String::new(),
+            })
+        }
+        fn cherry_pick(&self, path: &str, hash: &str) -> anyhow::Result<()> {
+            self.tracker
+                .lock()
+                .unwrap()
+                .calls
+                .push(format!("cherry-pick:{path}|{hash}"));
+            if hash == "conflict" {
+                return Err(anyhow::anyhow!("could not apply {hash}"));
+            }
+            Ok(())
+        }
+        fn revert_commit(&self, _path: &str, _hash: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn resolve_commit_range(&self, _path: &str, range: &str) -> anyhow::Result<Vec<String>> {
+            Ok(range.split("..").map(String::from).collect())
+        }
+        fn check_droppable(&self, _path: &str, hashes: &[String]) -> anyhow::Result<()> {
+            if hashes.iter().any(|h| h == "merge") {
+                return Err(anyhow::anyhow!("The commits are not a contiguous run"));
+            }
+            Ok(())
+        }
+        fn drop_commits(&self, path: &str, hashes: &[String]) -> anyhow::Result<()> {
             self.tracker
                 .lock()
                 .unwrap()
                 .calls
-                .push("get_project_root".to_string());
-            Ok(std::path::PathBuf::from("/mock/root"))
+                .push(format!("drop:{path}|{}", hashes.join(",")));
+            Ok(())
         }
-
-        fn pull(&self, path: &str) -> anyhow::Result<()> {
+        fn compare_refs(
+            &self,
+            _path: &str,
+            left: &str,
+            right: &str,
+        ) -> anyhow::Result<crate::domain::repository::RefComparison> {
             self.tracker
                 .lock()
                 .unwrap()
                 .calls
-                .push(format!("pull:{path}"));
-            Ok(())
+                .push(format!("compare:{left}|{right}"));
+            Ok(crate::domain::repository::RefComparison {
+                left: left.to_string(),
+                right: right.to_string(),
+                merge_base: None,
+                left_only: vec![],
+                right_only: vec![],
+                files: vec![],
+                diff: String::new(),
+            })
         }
-        fn detect_context(
+        fn first_changed_line(
             &self,
-            _base_path: &std::path::Path,
-        ) -> crate::domain::repository::ProjectContext {
-            crate::domain::repository::ProjectContext::Standard
-        }
-        fn get_preferred_editor(&self) -> anyhow::Result<Option<String>> {
+            _path: &str,
+            _file: &str,
+            _staged: bool,
+        ) -> anyhow::Result<Option<usize>> {
             Ok(None)
         }
-        fn set_preferred_editor(&self, _editor: &str) -> anyhow::Result<()> {
+        fn list_branches(&self) -> anyhow::Result<Vec<String>> {
+            Ok(vec!["main".to_string(), "dev".to_string()])
+        }
+        fn list_branch_info(&self) -> anyhow::Result<Vec<crate::domain::repository::BranchInfo>> {
+            let branch = |name: &str, remote: Option<&str>, worktree: Option<&str>| {
+                crate::domain::repository::BranchInfo {
+                    name: name.to_string(),
+                    remote: remote.map(String::from),
+                    tip: "abc1234".to_string(),
+                    subject: "Initial commit".to_string(),
+                    date: "2026-01-01".to_string(),
+                    timestamp: 1_767_225_600,
+                    upstream: None,
+                    ahead: 0,
+                    behind: 0,
+                    upstream_gone: false,
+                    worktree: worktree.map(String::from),
+                }
+            };
+            Ok(vec![
+                branch("main", None, Some("main")),
+                branch("wip", None, None),
+                branch("origin/feature", Some("origin"), None),
+            ])
+        }
+        fn create_branch(&self, name: &str, base: &str) -> anyhow::Result<()> {
+            self.tracker
+                .lock()
+                .unwrap()
+                .calls
+                .push(format!("create-branch:{name}|{base}"));
             Ok(())
         }
-        fn fetch(&self, _path: &str) -> anyhow::Result<()> {
+        fn rename_branch(&self, from: &str, to: &str) -> anyhow::Result<()> {
+            self.tracker
+                .lock()
+                .unwrap()
+                .calls
+                .push(format!("rename-branch:{from}|{to}"));
             Ok(())
         }
-        fn push(&self, path: &str) -> anyhow::Result<()> {
+        fn delete_branch(&self, name: &str) -> anyhow::Result<()> {
             self.tracker
                 .lock()
                 .unwrap()
                 .calls
-                .push(format!("push:{path}"));
+                .push(format!("delete-branch:{name}"));
             Ok(())
         }
-        fn get_status(&self, path: &str) -> anyhow::Result<crate::domain::repository::GitStatus> {
-            let tracker = self.tracker.lock().unwrap();
-            Ok(tracker.status_map.get(path).cloned().unwrap_or_else(|| {
-                crate::domain::repository::GitStatus {
-                    staged: vec![],
-                    unstaged: vec![],
-                    untracked: vec![],
-                }
-            }))
+        fn branch_unmerged_into(&self, branch: &str) -> anyhow::Result<Option<String>> {
+            Ok((branch == "wip").then(|| "main".to_string()))
         }
-        fn stage_all(&self, _path: &str) -> anyhow::Result<()> {
+        fn set_upstream(&self, branch: &str, upstream: &str) -> anyhow::Result<()> {
+            self.tracker
+                .lock()
+                .unwrap()
+                .calls
+                .push(format!("set-upstream:{branch}|{upstream}"));
             Ok(())
         }
-        fn unstage_all(&self, _path: &str) -> anyhow::Result<()> {
-            Ok(())
+        fn default_branch(&self) -> anyhow::Result<String> {
+            Ok("main".to_string())
         }
-        fn stage_file(&self, _path: &str, _file: &str) -> anyhow::Result<()> {
-            Ok(())
+        fn merged_branches(&self, _target: &str) -> anyhow::Result<Vec<String>> {
+            Ok(vec!["main".to_string()])
         }
-        fn unstage_file(&self, _path: &str, _file: &str) -> anyhow::Result<()> {
-            Ok(())
+        fn last_activity(&self, _path: &str) -> anyhow::Result<i64> {
+            Ok(0)
         }
-        fn commit(&self, _path: &str, _message: &str) -> anyhow::Result<()> {
-            Ok(())
+        fn scan_artifacts(
+            &self,
+            path: &str,
+        ) -> anyhow::Result<Vec<crate::domain::artifacts::Artifact>> {
+            Ok(vec![crate::domain::artifacts::Artifact {
+                path: format!("{path}/target"),
+                relative: "target".to_string(),
+                ecosystem: crate::domain::artifacts::Ecosystem::Cargo,
+                size_bytes: 2048,
+            }])
         }
-        fn get_history(
+        fn remove_artifacts(
             &self,
-            _path: &str,
-            _limit: usize,
-        ) -> anyhow::Result<Vec<crate::domain::repository::GitCommit>> {
-            Ok(vec![])
+            path: &str,
+            artifacts: &[crate::domain::artifacts::Artifact],
+        ) -> anyhow::Result<()> {
+            self.tracker
+                .lock()
+                .unwrap()
+                .calls
+                .push(format!("remove-artifacts:{path}|{}", artifacts.len()));
+            Ok(())
         }
-        fn list_branches(&self) -> anyhow::Result<Vec<String>> {
-            Ok(vec!["main".to_string(), "dev".to_string()])
+        fn cache_status(&self) -> anyhow::Result<Vec<crate::domain::cache::CacheStatus>> {
+            Ok(vec![crate::domain::cache::CacheStatus {
+                kind: crate::domain::cache::CacheKind::CargoTarget,
+                location: "/hub/.cache/cargo-target".to_string(),
+                configured_by: "/hub/.cargo/config.toml".to_string(),
+                size_bytes: 4096,
+                worktrees: vec!["main".to_string(), "dev".to_string()],
+                problem: None,
+            }])
         }
         fn switch_branch(&self, path: &str, branch: &str) -> anyhow::Result<()> {
             self.tracker
@@ -1573,146 +3511,695 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_reducer_handle_init_fresh() -> Result<()> {
+    async fn test_reducer_handle_init_fresh() -> Result<()> {
+        let tracker = Arc::new(Mutex::new(CallTracker::default()));
+        let repo = MockRepo::new(tracker.clone());
+        let reducer = Reducer::new(repo, false, false);
+
+        reducer
+            .handle(Intent::Initialize {
+                url: None,
+                name: Some("fresh-project".to_string()),
+                warp: false,
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let calls = tracker.lock().unwrap().calls.clone();
+        assert_eq!(calls[0], "init:None|fresh-project");
+        assert_eq!(calls[1], "add_new:main|main|HEAD");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reducer_handle_init() -> Result<()> {
+        let tracker = Arc::new(Mutex::new(CallTracker::default()));
+        let repo = MockRepo::new(tracker.clone());
+        let reducer = Reducer::new(repo, false, false);
+
+        reducer
+            .handle(Intent::Initialize {
+                url: Some("https://github.com/user/repo.git".to_string()),
+                name: None,
+                warp: false,
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        assert_eq!(
+            tracker.lock().unwrap().calls,
+            vec!["init:Some(\"https://github.com/user/repo.git\")|repo"]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reducer_handle_add() -> Result<()> {
+        let tracker = Arc::new(Mutex::new(CallTracker::default()));
+        let repo = MockRepo::new(tracker.clone());
+        let reducer = Reducer::new(repo, false, false);
+
+        reducer
+            .handle(Intent::AddWorktree {
+                intent: Some("feat-x".to_string()),
+                branch: Some("feature/x".to_string()),
+                remote: None,
+                purpose: None,
+                color: None,
+                icon: None,
+                issue: None,
+                template: None,
+                ttl: None,
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        assert_eq!(tracker.lock().unwrap().calls, vec!["add:feat-x|feature/x"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reducer_handle_add_from_template() -> Result<()> {
+        let tracker = Arc::new(Mutex::new(CallTracker::default()));
+        let reducer = Reducer::new(MockRepo::new(tracker.clone()), false, true);
+        let add = |name: &str, template: &str| Intent::AddWorktree {
+            intent: Some(name.to_string()),
+            branch: None,
+            remote: None,
+            purpose: None,
+            color: None,
+            icon: None,
+            issue: None,
+            template: Some(template.to_string()),
+            ttl: None,
+        };
+
+        reducer
+            .handle(add("crash", "hotfix"))
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        reducer
+            .handle(add("try", "spike"))
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let calls = tracker.lock().unwrap().calls.clone();
+        assert_eq!(
+            calls[..4],
+            [
+                "add_new:crash|hotfix/crash|main",
+                r#"meta:crash|{"created_at":null,"purpose":"Hotfix","description":null,"color":null,"icon":null,"template":"hotfix"}"#,
+                "sync_entries:crash|copy .env.prod .env",
+                "add_detached:try|HEAD",
+            ]
+        );
+        assert!(calls[4].starts_with(r#"meta:try|{"created_at":null,"purpose":null,"description":null,"color":null,"icon":null,"template":"spike","expires_at":"20"#));
+
+        let res = reducer.handle(add("x", "nope")).await;
+        assert!(res.is_err_and(|e| e.to_string().contains("Available templates: hotfix, spike")));
+
+        let mut with_remote = add("y", "hotfix");
+        if let Intent::AddWorktree { remote, .. } = &mut with_remote {
+            *remote = Some("upstream".to_string());
+        }
+        let res = reducer.handle(with_remote).await;
+        assert!(res.is_err_and(|e| e.to_string().contains("--remote cannot be combined")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reducer_handle_add_from_issue() -> Result<()> {
+        let tracker = Arc::new(Mutex::new(CallTracker::default()));
+        let reducer = Reducer::new(MockRepo::new(tracker.clone()), false, true);
+
+        reducer
+            .handle(Intent::AddWorktree {
+                intent: None,
+                branch: None,
+                remote: None,
+                purpose: None,
+                color: None,
+                icon: None,
+                issue: Some("PROJ-123".to_string()),
+                template: None,
+                ttl: None,
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        assert_eq!(
+            tracker.lock().unwrap().calls,
+            vec![
+                "add_new:proj-123|feat/PROJ-123-login-page|HEAD",
+                r#"meta:proj-123|{"created_at":null,"purpose":"Login page","description":null,"color":null,"icon":null,"issue":{"id":"PROJ-123","url":"https://tracker.local/PROJ-123","title":"Login page"}}"#,
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reducer_handle_metadata() -> Result<()> {
+        use crate::app::test_utils::scaffolding::TestSpyView;
+        let tracker = Arc::new(Mutex::new(CallTracker::default()));
+        let reducer = Reducer::new(MockRepo::new(tracker.clone()), false, true);
+
+        reducer
+            .handle(Intent::AddWorktree {
+                intent: Some("feat-x".to_string()),
+                branch: None,
+                remote: None,
+                purpose: Some("Login".to_string()),
+                color: Some("cyan".to_string()),
+                icon: None,
+                issue: None,
+                template: None,
+                ttl: None,
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        reducer
+            .handle(Intent::SetMetadata {
+                name: "dev".to_string(),
+                key: "env.API".to_string(),
+                value: Some("local".to_string()),
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        assert_eq!(
+            tracker.lock().unwrap().calls,
+            vec![
+                "add:feat-x|feat-x",
+                r#"meta:feat-x|{"created_at":null,"purpose":"Login","description":null,"color":"cyan","icon":null}"#,
+                "list",
+                r#"meta:dev|{"created_at":null,"purpose":"Integration","description":null,"color":null,"icon":null,"env":{"API":"local"}}"#,
+            ]
+        );
+
+        // A bad color is rejected before anything is created.
+        let res = reducer
+            .handle(Intent::AddWorktree {
+                intent: Some("feat-y".to_string()),
+                branch: None,
+                remote: None,
+                purpose: None,
+                color: Some("mauve".to_string()),
+                icon: None,
+                issue: None,
+                template: None,
+                ttl: None,
+            })
+            .await;
+        assert!(res.is_err_and(|e| e.to_string().contains("Invalid color")));
+        let res = reducer
+            .handle(Intent::SetMetadata {
+                name: "de".to_string(),
+                key: "purpose".to_string(),
+                value: None,
+            })
+            .await;
+        assert!(res.is_err_and(|e| e.to_string().contains("not found")));
+        assert_eq!(tracker.lock().unwrap().calls.len(), 5);
+
+        let view = TestSpyView::default();
+        let jsons = view.jsons.clone();
+        let reducer = Reducer::new_with_view(MockRepo::new(tracker), view, true, false);
+        reducer
+            .handle(Intent::ShowMetadata {
+                name: "dev".to_string(),
+                key: Some("purpose".to_string()),
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        assert_eq!(jsons.lock().unwrap()[0]["value"], "Integration");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reducer_handle_setup() -> Result<()> {
+        let tracker = Arc::new(Mutex::new(CallTracker::default()));
+        let repo = MockRepo::new(tracker.clone());
+        let reducer = Reducer::new(repo, false, false);
+
+        reducer
+            .handle(Intent::SetupDefaults)
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let calls = tracker.lock().unwrap().calls.clone();
+        assert!(calls.contains(&"add:main|main".to_string()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reducer_handle_run() -> Result<()> {
+        let tracker = Arc::new(Mutex::new(CallTracker::default()));
+        let repo = MockRepo::new(tracker.clone());
+        let reducer = Reducer::new(repo, false, false);
+
+        // Create a dummy directory to satisfy .current_dir(&intent)
+        let temp_dir = "temp-run-test";
+        let _ = std::fs::create_dir(temp_dir);
+
+        let res = reducer
+            .handle(Intent::RunCommand {
+                intent: temp_dir.to_string(),
+                branch: Some("main".to_string()),
+                command: vec!["echo".to_string(), "hello".to_string()],
+            })
+            .await;
+
+        // Cleanup the dummy directory
+        let _ = std::fs::remove_dir(temp_dir);
+
+        res.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let calls = tracker.lock().unwrap().calls.clone();
+        assert_eq!(calls[0], format!("add:{temp_dir}|main"));
+        assert_eq!(calls[1], format!("remove:{temp_dir}|force:true"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reducer_handle_sync() -> Result<()> {
+        let tracker = Arc::new(Mutex::new(CallTracker::default()));
+        let repo = MockRepo::new(tracker.clone());
+        let reducer = Reducer::new(repo, false, false);
+
+        reducer
+            .handle(Intent::SyncConfigurations {
+                intent: Some("main".to_string()),
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let calls = tracker.lock().unwrap().calls.clone();
+        assert!(calls.contains(&"list".to_string()));
+        assert!(calls.contains(&"sync:main".to_string()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reducer_handle_push() -> Result<()> {
         let tracker = Arc::new(Mutex::new(CallTracker::default()));
         let repo = MockRepo::new(tracker.clone());
         let reducer = Reducer::new(repo, false, false);
 
         reducer
-            .handle(Intent::Initialize {
-                url: None,
-                name: Some("fresh-project".to_string()),
-                warp: false,
+            .handle(Intent::Push {
+                intent: Some("main".to_string()),
+                remote: None,
             })
             .await
             .map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
         let calls = tracker.lock().unwrap().calls.clone();
-        assert_eq!(calls[0], "init:None|fresh-project");
-        assert_eq!(calls[1], "add_new:main|main|HEAD");
+        assert!(calls.contains(&"list".to_string()));
+        assert!(calls.contains(&"push:main".to_string()));
         Ok(())
     }
 
     #[tokio::test]
-    async fn test_reducer_handle_init() -> Result<()> {
+    async fn test_reducer_handle_pull_from_remote() -> Result<()> {
         let tracker = Arc::new(Mutex::new(CallTracker::default()));
         let repo = MockRepo::new(tracker.clone());
         let reducer = Reducer::new(repo, false, false);
 
         reducer
-            .handle(Intent::Initialize {
-                url: Some("https://github.com/user/repo.git".to_string()),
-                name: None,
-                warp: false,
+            .handle(Intent::Pull {
+                intent: Some("main".to_string()),
+                remote: Some("upstream".to_string()),
             })
             .await
             .map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
-        assert_eq!(
-            tracker.lock().unwrap().calls,
-            vec!["init:Some(\"https://github.com/user/repo.git\")|repo"]
-        );
+        let calls = tracker.lock().unwrap().calls.clone();
+        assert!(calls.contains(&"pull:main|upstream".to_string()));
         Ok(())
     }
 
     #[tokio::test]
-    async fn test_reducer_handle_add() -> Result<()> {
+    async fn test_reducer_handle_add_tracks_remote() -> Result<()> {
         let tracker = Arc::new(Mutex::new(CallTracker::default()));
         let repo = MockRepo::new(tracker.clone());
         let reducer = Reducer::new(repo, false, false);
 
+        // A remote-prefixed branch creates a local branch tracking that remote.
         reducer
             .handle(Intent::AddWorktree {
-                intent: "feat-x".to_string(),
-                branch: Some("feature/x".to_string()),
+                intent: Some("fix-docs".to_string()),
+                branch: Some("upstream/fix-docs".to_string()),
+                remote: None,
+                purpose: None,
+                color: None,
+                icon: None,
+                issue: None,
+                template: None,
+                ttl: None,
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        // An existing local branch is checked out and re-pointed at the remote.
+        reducer
+            .handle(Intent::AddWorktree {
+                intent: Some("wip".to_string()),
+                branch: None,
+                remote: Some("upstream".to_string()),
+                purpose: None,
+                color: None,
+                icon: None,
+                issue: None,
+                template: None,
+                ttl: None,
             })
             .await
             .map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
-        assert_eq!(tracker.lock().unwrap().calls, vec!["add:feat-x|feature/x"]);
+        assert_eq!(
+            tracker.lock().unwrap().calls,
+            vec![
+                "add_new:fix-docs|fix-docs|upstream/fix-docs",
+                "set-upstream:fix-docs|upstream/fix-docs",
+                "add:wip|wip",
+                "set-upstream:wip|upstream/wip",
+            ]
+        );
+
+        let res = reducer
+            .handle(Intent::AddWorktree {
+                intent: Some("wip".to_string()),
+                branch: None,
+                remote: Some("fork".to_string()),
+                purpose: None,
+                color: None,
+                icon: None,
+                issue: None,
+                template: None,
+                ttl: None,
+            })
+            .await;
+        assert!(res.is_err_and(|e| e.to_string().contains("'fork' is not configured")));
         Ok(())
     }
 
     #[tokio::test]
-    async fn test_reducer_handle_setup() -> Result<()> {
+    async fn test_reducer_handle_remote_management() -> Result<()> {
         let tracker = Arc::new(Mutex::new(CallTracker::default()));
         let repo = MockRepo::new(tracker.clone());
         let reducer = Reducer::new(repo, false, false);
 
         reducer
-            .handle(Intent::SetupDefaults)
+            .handle(Intent::AddRemote {
+                name: "fork".to_string(),
+                url: "git@example.com:me/app.git".to_string(),
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        reducer
+            .handle(Intent::FetchRemote {
+                remote: Some("fork".to_string()),
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        reducer
+            .handle(Intent::RemoveRemote {
+                name: "fork".to_string(),
+            })
             .await
             .map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
-        let calls = tracker.lock().unwrap().calls.clone();
-        assert!(calls.contains(&"add:main|main".to_string()));
+        assert_eq!(
+            tracker.lock().unwrap().calls,
+            vec![
+                "add-remote:fork|git@example.com:me/app.git",
+                "fetch:fork",
+                "list",
+                "remove-remote:fork",
+            ]
+        );
         Ok(())
     }
 
     #[tokio::test]
-    async fn test_reducer_handle_run() -> Result<()> {
+    async fn test_reducer_handle_cache_status() -> Result<()> {
+        use crate::app::test_utils::scaffolding::TestSpyView;
+
         let tracker = Arc::new(Mutex::new(CallTracker::default()));
-        let repo = MockRepo::new(tracker.clone());
-        let reducer = Reducer::new(repo, false, false);
+        let view = TestSpyView::default();
+        let tables = view.cache_tables.clone();
+        let reducer = Reducer::new_with_view(MockRepo::new(tracker), view, false, false);
 
-        // Create a dummy directory to satisfy .current_dir(&intent)
-        let temp_dir = "temp-run-test";
-        let _ = std::fs::create_dir(temp_dir);
+        reducer
+            .handle(Intent::CacheStatus)
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
-        let res = reducer
-            .handle(Intent::RunCommand {
-                intent: temp_dir.to_string(),
-                branch: Some("main".to_string()),
-                command: vec!["echo".to_string(), "hello".to_string()],
-            })
-            .await;
+        let tables = tables.lock().unwrap();
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0][0].worktrees, vec!["main", "dev"]);
+        Ok(())
+    }
 
-        // Cleanup the dummy directory
-        let _ = std::fs::remove_dir(temp_dir);
+    #[tokio::test]
+    async fn test_reducer_handle_check_sync() -> Result<()> {
+        use crate::app::test_utils::scaffolding::TestSpyView;
 
-        res.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        let tracker = Arc::new(Mutex::new(CallTracker::default()));
+        let view = TestSpyView::default();
+        let jsons = view.jsons.clone();
+        let reducer = Reducer::new_with_view(MockRepo::new(tracker.clone()), view, true, false);
+
+        let err = reducer
+            .handle(Intent::CheckSync {
+                intent: None,
+                fix: false,
+            })
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("2 synced entries drifted"));
+        let report = jsons.lock().unwrap()[0].clone();
+        assert_eq!(report["status"], "drift");
+        assert_eq!(report["worktrees"][1]["drift"][0]["state"], "modified");
 
-        let calls = tracker.lock().unwrap().calls.clone();
-        assert_eq!(calls[0], format!("add:{temp_dir}|main"));
-        assert_eq!(calls[1], format!("remove:{temp_dir}|force:true"));
+        // Without a terminal, local edits are skipped rather than overwritten.
+        reducer
+            .handle(Intent::CheckSync {
+                intent: Some("dev".into()),
+                fix: true,
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        assert!(
+            tracker
+                .lock()
+                .unwrap()
+                .calls
+                .contains(&"fix-sync:dev|certs".to_string())
+        );
+        assert_eq!(jsons.lock().unwrap()[1]["skipped"][0], "dev:.env");
         Ok(())
     }
 
     #[tokio::test]
-    async fn test_reducer_handle_sync() -> Result<()> {
+    async fn test_reducer_handle_clean_artifacts() -> Result<()> {
+        use crate::app::test_utils::scaffolding::TestSpyView;
+
         let tracker = Arc::new(Mutex::new(CallTracker::default()));
-        let repo = MockRepo::new(tracker.clone());
-        let reducer = Reducer::new(repo, false, false);
+        let view = TestSpyView::default();
+        let jsons = view.jsons.clone();
+        let reducer = Reducer::new_with_view(MockRepo::new(tracker.clone()), view, true, false);
 
         reducer
-            .handle(Intent::SyncConfigurations {
-                intent: Some("main".to_string()),
+            .handle(Intent::CleanArtifacts {
+                dry_run: true,
+                inactive_days: Some(30),
             })
             .await
             .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        assert_eq!(jsons.lock().unwrap()[0]["reclaimable_bytes"], 4096);
+        assert!(
+            !tracker
+                .lock()
+                .unwrap()
+                .calls
+                .iter()
+                .any(|c| c.starts_with("remove-artifacts:"))
+        );
 
+        reducer
+            .handle(Intent::CleanArtifacts {
+                dry_run: false,
+                inactive_days: None,
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
         let calls = tracker.lock().unwrap().calls.clone();
-        assert!(calls.contains(&"list".to_string()));
-        assert!(calls.contains(&"sync:main".to_string()));
+        assert!(calls.contains(&"remove-artifacts:main|1".to_string()));
+        assert!(calls.contains(&"remove-artifacts:dev|1".to_string()));
         Ok(())
     }
 
     #[tokio::test]
-    async fn test_reducer_handle_push() -> Result<()> {
+    async fn test_reducer_handle_clean_stale_confirms_dirty_worktrees() -> Result<()> {
+        use crate::app::test_utils::scaffolding::TestSpyView;
+        use crate::domain::stale::StaleCriteria;
+
+        // `dev` has a local change and has been idle since the epoch in the mock.
+        for answer in [false, true] {
+            let tracker = Arc::new(Mutex::new(CallTracker::default()));
+            let view = TestSpyView {
+                confirm_answer: answer,
+                ..TestSpyView::default()
+            };
+            let confirmations = view.confirmations.clone();
+            let reducer =
+                Reducer::new_with_view(MockRepo::new(tracker.clone()), view, false, false);
+
+            reducer
+                .handle(Intent::CleanStale {
+                    criteria: StaleCriteria {
+                        expired: false,
+                        ..StaleCriteria::all(30)
+                    },
+                    dry_run: false,
+                    force: false,
+                })
+                .await
+                .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+            assert_eq!(confirmations.lock().unwrap().len(), 1);
+            let removed = tracker
+                .lock()
+                .unwrap()
+                .calls
+                .contains(&"remove:dev|force:true".to_string());
+            assert_eq!(removed, answer);
+        }
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_reducer_handle_clean_stale_refuses_dirty_worktrees_unattended() -> Result<()> {
+        use crate::app::test_utils::scaffolding::TestSpyView;
+        use crate::domain::stale::StaleCriteria;
+
+        // `dev` is dirty and idle but not expired: including --expired, or running with
+        // --json, still keeps it without asking.
+        for (criteria, json) in [
+            (StaleCriteria::all(30), false),
+            (
+                StaleCriteria {
+                    inactive_days: Some(30),
+                    ..StaleCriteria::default()
+                },
+                true,
+            ),
+        ] {
+            let tracker = Arc::new(Mutex::new(CallTracker::default()));
+            let view = TestSpyView {
+                confirm_answer: true,
+                ..TestSpyView::default()
+            };
+            let confirmations = view.confirmations.clone();
+            let jsons = view.jsons.clone();
+            let reducer = Reducer::new_with_view(MockRepo::new(tracker.clone()), view, json, false);
+
+            reducer
+                .handle(Intent::CleanStale {
+                    criteria,
+                    dry_run: false,
+                    force: false,
+                })
+                .await
+                .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+            assert!(confirmations.lock().unwrap().is_empty());
+            let calls = tracker.lock().unwrap().calls.clone();
+            assert!(!calls.iter().any(|c| c.starts_with("remove:dev|")));
+            if json {
+                let output = jsons.lock().unwrap()[0].clone();
+                assert_eq!(output["refused"], serde_json::json!(["dev"]));
+            }
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reducer_handle_clean_expired_keeps_dirty_worktrees() -> Result<()> {
+        use crate::app::test_utils::scaffolding::TestSpyView;
+        use crate::domain::repository::WorktreeMetadata;
+        use crate::domain::stale::StaleCriteria;
+
+        let expired = |path: &str, status: &str| Worktree {
+            path: path.to_string(),
+            commit: "abcdef0".to_string(),
+            branch: path.to_string(),
+            is_bare: false,
+            is_detached: false,
+            status_summary: Some(status.to_string()),
+            size_bytes: 0,
+            metadata: Some(WorktreeMetadata {
+                expires_at: Some("2020-01-01T00:00:00Z".to_string()),
+                ..Default::default()
+            }),
+            upstream: None,
+            contexts: Vec::new(),
+            sync_drift: Vec::new(),
+        };
+        for force in [false, true] {
+            let tracker = Arc::new(Mutex::new(CallTracker {
+                worktrees: Some(vec![expired("dev", "~1"), expired("spike", "clean")]),
+                ..CallTracker::default()
+            }));
+            let view = TestSpyView {
+                confirm_answer: true,
+                ..TestSpyView::default()
+            };
+            let confirmations = view.confirmations.clone();
+            let reducer =
+                Reducer::new_with_view(MockRepo::new(tracker.clone()), view, false, false);
+
+            reducer
+                .handle(Intent::CleanStale {
+                    criteria: StaleCriteria::expired(),
+                    dry_run: false,
+                    force,
+                })
+                .await
+                .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+            let calls = tracker.lock().unwrap().calls.clone();
+            assert!(calls.contains(&"remove:spike|force:false".to_string()));
+            assert_eq!(calls.iter().any(|c| c.starts_with("remove:dev|")), force);
+            assert!(confirmations.lock().unwrap().is_empty());
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reducer_handle_add_with_ttl() -> Result<()> {
         let tracker = Arc::new(Mutex::new(CallTracker::default()));
-        let repo = MockRepo::new(tracker.clone());
-        let reducer = Reducer::new(repo, false, false);
+        let reducer = Reducer::new(MockRepo::new(tracker.clone()), false, true);
 
         reducer
-            .handle(Intent::Push {
-                intent: Some("main".to_string()),
+            .handle(Intent::AddWorktree {
+                intent: Some("try".to_string()),
+                branch: None,
+                remote: None,
+                purpose: None,
+                color: None,
+                icon: None,
+                issue: None,
+                template: None,
+                ttl: Some(3),
             })
             .await
             .map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
         let calls = tracker.lock().unwrap().calls.clone();
-        assert!(calls.contains(&"list".to_string()));
-        assert!(calls.contains(&"push:main".to_string()));
+        assert!(
+            calls
+                .iter()
+                .any(|c| c.starts_with("meta:try|") && c.contains(r#""expires_at":"20"#))
+        );
         Ok(())
     }
 
@@ -1723,10 +4210,7 @@ mod tests {
         let reducer = Reducer::new(repo, false, false);
 
         reducer
-            .handle(Intent::CleanWorktrees {
-                dry_run: true,
-                artifacts: false,
-            })
+            .handle(Intent::CleanWorktrees { dry_run: true })
             .await
             .map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
@@ -1768,6 +4252,192 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_reducer_handle_show_log() -> Result<()> {
+        let tracker = Arc::new(Mutex::new(CallTracker::default()));
+        let repo = MockRepo::new(tracker.clone());
+        let reducer = Reducer::new(repo, true, false);
+
+        let mut query = crate::domain::repository::HistoryQuery {
+            limit: 5,
+            graph: false,
+            ..Default::default()
+        };
+        query.apply_search("fix author:jane");
+        reducer
+            .handle(Intent::ShowLog {
+                name: "dev".to_string(),
+                query,
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let calls = tracker.lock().unwrap().calls.clone();
+        assert!(
+            calls
+                .iter()
+                .any(|c| c.starts_with("history:") && c.ends_with("|5|fix author:jane|false"))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reducer_handle_compare_resolves_worktrees_and_refs() -> Result<()> {
+        let tracker = Arc::new(Mutex::new(CallTracker::default()));
+        let repo = MockRepo::new(tracker.clone());
+        let reducer = Reducer::new(repo, true, false);
+
+        reducer
+            .handle(Intent::Compare {
+                left: "dev".to_string(),
+                right: "v1.0".to_string(),
+                patch: false,
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let calls = tracker.lock().unwrap().calls.clone();
+        assert!(calls.contains(&"compare:dev|v1.0".to_string()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reducer_handle_port_and_remove() -> Result<()> {
+        let tracker = Arc::new(Mutex::new(CallTracker::default()));
+        let repo = MockRepo::new(tracker.clone());
+        let reducer = Reducer::new(repo, true, false);
+
+        reducer
+            .handle(Intent::Port {
+                range: "aaa..bbb".to_string(),
+                to: "dev".to_string(),
+                from: Some("main".to_string()),
+                remove: true,
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let calls = tracker.lock().unwrap().calls.clone();
+        assert!(calls.contains(&"cherry-pick:dev|aaa".to_string()));
+        assert!(calls.contains(&"cherry-pick:dev|bbb".to_string()));
+        assert!(calls.contains(&"drop:main|aaa,bbb".to_string()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reducer_handle_port_stops_on_conflict() -> Result<()> {
+        let tracker = Arc::new(Mutex::new(CallTracker::default()));
+        let repo = MockRepo::new(tracker.clone());
+        let reducer = Reducer::new(repo, true, false);
+
+        let result = reducer
+            .handle(Intent::Port {
+                range: "aaa..conflict..ccc".to_string(),
+                to: "dev".to_string(),
+                from: Some("main".to_string()),
+                remove: true,
+            })
+            .await;
+        let exit = result
+            .as_ref()
+            .err()
+            .and_then(|e| e.downcast_ref::<CommandExit>());
+        assert_eq!(exit.map(|e| e.code), Some(1));
+
+        let calls = tracker.lock().unwrap().calls.clone();
+        assert!(calls.contains(&"cherry-pick:dev|conflict".to_string()));
+        assert!(!calls.iter().any(|c| c.ends_with("|ccc")));
+        assert!(!calls.iter().any(|c| c.starts_with("drop:")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reducer_handle_port_checks_removal_before_porting() {
+        let tracker = Arc::new(Mutex::new(CallTracker::default()));
+        let repo = MockRepo::new(tracker.clone());
+        let reducer = Reducer::new(repo, true, false);
+
+        let result = reducer
+            .handle(Intent::Port {
+                range: "aaa..merge..ccc".to_string(),
+                to: "dev".to_string(),
+                from: Some("main".to_string()),
+                remove: true,
+            })
+            .await;
+        assert!(result.is_err());
+        let calls = tracker.lock().unwrap().calls.clone();
+        assert!(
+            !calls.iter().any(|c| c.starts_with("cherry-pick:")),
+            "{calls:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reducer_handle_port_same_worktree_fails() {
+        let tracker = Arc::new(Mutex::new(CallTracker::default()));
+        let repo = MockRepo::new(tracker.clone());
+        let reducer = Reducer::new(repo, true, false);
+
+        let result = reducer
+            .handle(Intent::Port {
+                range: "aaa".to_string(),
+                to: "dev".to_string(),
+                from: Some("dev".to_string()),
+                remove: false,
+            })
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reducer_handle_delete_branch_checks_merged() {
+        let tracker = Arc::new(Mutex::new(CallTracker::default()));
+        let repo = MockRepo::new(tracker.clone());
+        let reducer = Reducer::new(repo, true, false);
+
+        let delete = |name: &str, force: bool| Intent::DeleteBranch {
+            name: name.to_string(),
+            force,
+        };
+        assert!(reducer.handle(delete("wip", false)).await.is_err());
+        assert!(reducer.handle(delete("main", true)).await.is_err());
+        assert!(reducer.handle(delete("missing", true)).await.is_err());
+        assert!(tracker.lock().unwrap().calls.is_empty());
+
+        assert!(reducer.handle(delete("wip", true)).await.is_ok());
+        assert_eq!(tracker.lock().unwrap().calls, vec!["delete-branch:wip"]);
+    }
+
+    #[tokio::test]
+    async fn test_reducer_handle_open_remote_branch() -> Result<()> {
+        let tracker = Arc::new(Mutex::new(CallTracker::default()));
+        let repo = MockRepo::new(tracker.clone());
+        let reducer = Reducer::new(repo, true, false);
+
+        reducer
+            .handle(Intent::OpenBranch {
+                branch: "origin/feature".to_string(),
+                name: None,
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        reducer
+            .handle(Intent::OpenBranch {
+                branch: "wip".to_string(),
+                name: Some("scratch".to_string()),
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let calls = tracker.lock().unwrap().calls.clone();
+        assert_eq!(
+            calls,
+            vec!["add_new:feature|feature|origin/feature", "add:scratch|wip"]
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_reducer_handle_checkout() -> Result<()> {
         let tracker = Arc::new(Mutex::new(CallTracker::default()));
@@ -2,7 +2,8 @@
 mod tests {
     use crate::app::intent::Intent;
     use crate::app::model::AppState;
-    use crate::app::test_utils::scaffolding::ReducerTestKit;
+    use crate::app::test_utils::scaffolding::{MockRepoBuilder, ReducerTestKit};
+    use crate::domain::repository::BranchInfo;
 
     #[tokio::test]
     async fn test_initialize_flow_happy_path() {
@@ -44,7 +45,13 @@ mod tests {
     async fn test_list_worktrees_empty() {
         let kit = ReducerTestKit::new();
 
-        kit.reducer.handle(Intent::ListWorktrees).await.unwrap();
+        kit.reducer
+            .handle(Intent::ListWorktrees {
+                sort: None,
+                group: None,
+            })
+            .await
+            .unwrap();
 
         // Assert: Banner -> Welcome Screen (implied by empty list) -> Listing Table
         // The View implementation for ListWorktrees:
@@ -72,4 +79,95 @@ mod tests {
         assert_eq!(listings.len(), 1, "Should have rendered listing table");
         assert!(listings[0].is_empty(), "Listing should be empty");
     }
+
+    #[tokio::test]
+    async fn test_list_worktrees_sorted() {
+        use crate::domain::list_layout::SortMode;
+        use crate::domain::repository::Worktree;
+
+        let worktree = |name: &str, size_bytes: u64| Worktree {
+            path: format!("/hub/{name}"),
+            commit: "abc1234".into(),
+            branch: name.into(),
+            is_bare: false,
+            is_detached: false,
+            status_summary: Some("clean".into()),
+            size_bytes,
+            metadata: None,
+            upstream: None,
+            contexts: Vec::new(),
+            sync_drift: Vec::new(),
+        };
+        let repo = MockRepoBuilder::default()
+            .with_worktrees(vec![
+                worktree("alpha", 1),
+                worktree("charlie", 3),
+                worktree("bravo", 2),
+            ])
+            .build();
+        let kit = ReducerTestKit::with_repo(repo);
+
+        for sort in [SortMode::Size, SortMode::Name] {
+            kit.reducer
+                .handle(Intent::ListWorktrees {
+                    sort: Some(sort),
+                    group: None,
+                })
+                .await
+                .unwrap();
+        }
+
+        let listings = kit.view.listings.lock().unwrap();
+        let names: Vec<Vec<&str>> = listings
+            .iter()
+            .map(|l| l.iter().map(|wt| wt.branch.as_str()).collect())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                vec!["charlie", "bravo", "alpha"],
+                vec!["alpha", "bravo", "charlie"]
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_branches_hides_remotes_unless_all() {
+        let branch = |name: &str, remote: Option<&str>| BranchInfo {
+            name: name.into(),
+            remote: remote.map(String::from),
+            tip: "abc1234".into(),
+            subject: "Initial commit".into(),
+            date: "2026-01-01".into(),
+            timestamp: 0,
+            upstream: None,
+            ahead: 0,
+            behind: 0,
+            upstream_gone: false,
+            worktree: None,
+        };
+        let repo = MockRepoBuilder::default()
+            .with_branches(vec![
+                branch("main", None),
+                branch("origin/main", Some("origin")),
+            ])
+            .build();
+        let kit = ReducerTestKit::with_repo(repo);
+
+        kit.reducer
+            .handle(Intent::ListBranches { all: false })
+            .await
+            .unwrap();
+        kit.reducer
+            .handle(Intent::ListBranches { all: true })
+            .await
+            .unwrap();
+
+        let tables = kit.view.branch_tables.lock().unwrap();
+        let names: Vec<Vec<&str>> = tables
+            .iter()
+            .map(|t| t.iter().map(|b| b.name.as_str()).collect())
+            .collect();
+        assert_eq!(names, vec![vec!["main"], vec!["main", "origin/main"]]);
+    }
 }
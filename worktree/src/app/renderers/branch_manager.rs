@@ -0,0 +1,149 @@
+use crate::app::event_handlers::branch_manager::visible_branches;
+use crate::domain::repository::BranchInfo;
+use crate::ui::theme::CyberTheme;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use super::helpers::centered_rect;
+use super::history::history_scroll_offset;
+
+pub fn render_branch_manager(
+    f: &mut Frame,
+    branches: &[BranchInfo],
+    selected_index: usize,
+    show_remote: bool,
+) {
+    let theme = CyberTheme::default();
+    let area = centered_rect(85, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let visible = visible_branches(branches, show_remote);
+    let scope = if show_remote { "ALL" } else { "LOCAL" };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.primary))
+        .title(Span::styled(
+            format!("  BRANCHES: {scope} ({}) ", visible.len()),
+            Style::default()
+                .fg(theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ));
+    let inner_area = block.inner(area);
+    f.render_widget(block, area);
+
+    let name_width = visible
+        .iter()
+        .map(|b| b.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(12, 40);
+
+    let mut items = Vec::new();
+    if visible.is_empty() {
+        items.push(Line::from(Span::styled(
+            "  No branches",
+            Style::default().fg(theme.subtle),
+        )));
+    }
+    for (i, branch) in visible.iter().enumerate() {
+        let is_selected = i == selected_index;
+        let row_style = if is_selected {
+            Style::default()
+                .bg(theme.selection_bg)
+                .fg(theme.text)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let name_color = if branch.is_remote() {
+            theme.subtle
+        } else {
+            theme.primary
+        };
+
+        let mut entry = vec![
+            Span::styled(
+                if is_selected { " ▶ " } else { "   " },
+                row_style.fg(theme.primary),
+            ),
+            Span::styled(
+                format!("{:<name_width$} ", branch.name),
+                row_style.fg(name_color),
+            ),
+            Span::styled(format!(" {} ", branch.tip), row_style.fg(theme.warning)),
+            Span::styled(format!(" {:<10} ", branch.date), row_style.fg(theme.subtle)),
+        ];
+        if branch.upstream_gone {
+            entry.push(Span::styled(" upstream gone ", row_style.fg(theme.error)));
+        } else if let Some(upstream) = &branch.upstream {
+            entry.push(Span::styled(
+                format!(" {upstream} "),
+                row_style.fg(theme.accent),
+            ));
+            if branch.ahead > 0 || branch.behind > 0 {
+                entry.push(Span::styled(
+                    format!("⇡{} ⇣{} ", branch.ahead, branch.behind),
+                    row_style.fg(theme.warning),
+                ));
+            }
+        }
+        if let Some(path) = &branch.worktree {
+            let dir = std::path::Path::new(path)
+                .file_name()
+                .map_or_else(|| path.clone(), |n| n.to_string_lossy().to_string());
+            entry.push(Span::styled(
+                format!(" ● {dir} "),
+                row_style.fg(theme.success),
+            ));
+        }
+        entry.push(Span::styled(&branch.subject, row_style));
+        items.push(Line::from(entry));
+    }
+
+    let offset = history_scroll_offset(selected_index, inner_area.height);
+    let p = Paragraph::new(items).scroll((u16::try_from(offset).unwrap_or(u16::MAX), 0));
+    f.render_widget(p, inner_area);
+
+    let footer_area = Rect::new(area.x + 2, area.y + area.height - 1, area.width - 4, 1);
+    let key = |k: &'static str| {
+        Span::styled(
+            k,
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let help_text = Paragraph::new(Line::from(vec![
+        key(" [N]"),
+        Span::raw(" NEW  "),
+        key(" [R]"),
+        Span::raw(" RENAME  "),
+        key(" [U]"),
+        Span::raw(" UPSTREAM  "),
+        key(" [D]"),
+        Span::raw(" DELETE  "),
+        key(" [ENT]"),
+        Span::raw(" WORKTREE  "),
+        key(" [A]"),
+        Span::raw(if show_remote {
+            " HIDE REMOTES  "
+        } else {
+            " SHOW REMOTES  "
+        }),
+        Span::styled(
+            " [ESC/Q]",
+            Style::default()
+                .fg(theme.subtle)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" BACK "),
+    ]))
+    .alignment(Alignment::Center);
+    f.render_widget(help_text, footer_area);
+}
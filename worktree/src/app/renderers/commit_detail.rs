@@ -0,0 +1,210 @@
+use crate::domain::repository::{CommitDetail, FileStat};
+use crate::ui::theme::CyberTheme;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use super::helpers::centered_rect;
+
+fn short(hash: &str) -> &str {
+    hash.get(..7).unwrap_or(hash)
+}
+
+/// Style for one line of a unified diff.
+fn diff_line_style(line: &str, theme: &CyberTheme) -> Style {
+    if line.starts_with("diff --git") {
+        Style::default()
+            .fg(theme.warning)
+            .add_modifier(Modifier::BOLD)
+    } else if line.starts_with("+++") || line.starts_with("---") {
+        Style::default().fg(theme.text).add_modifier(Modifier::BOLD)
+    } else if line.starts_with("@@") {
+        Style::default().fg(theme.primary)
+    } else if line.starts_with('+') {
+        Style::default().fg(theme.success)
+    } else if line.starts_with('-') {
+        Style::default().fg(theme.error)
+    } else {
+        Style::default().fg(theme.subtle)
+    }
+}
+
+/// A `N files changed +A -D` summary followed by one line per file.
+pub fn file_stat_lines<'a>(files: &'a [FileStat], theme: &CyberTheme) -> Vec<Line<'a>> {
+    let (added, removed) = files.iter().fold((0, 0), |(a, d), f| {
+        (a + f.additions.unwrap_or(0), d + f.deletions.unwrap_or(0))
+    });
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            format!("{} files changed ", files.len()),
+            Style::default().fg(theme.text),
+        ),
+        Span::styled(format!("+{added} "), Style::default().fg(theme.success)),
+        Span::styled(format!("-{removed}"), Style::default().fg(theme.error)),
+    ])];
+    for file in files {
+        let mut spans = match (file.additions, file.deletions) {
+            (Some(a), Some(d)) => vec![
+                Span::styled(format!("{a:>5} "), Style::default().fg(theme.success)),
+                Span::styled(format!("{d:>5}  "), Style::default().fg(theme.error)),
+            ],
+            _ => vec![Span::styled(
+                format!("{:>11}  ", "bin"),
+                Style::default().fg(theme.subtle),
+            )],
+        };
+        spans.push(Span::styled(
+            file.path.as_str(),
+            Style::default().fg(theme.text),
+        ));
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+/// A unified diff, one styled line per diff line.
+pub fn diff_lines<'a>(diff: &'a str, theme: &CyberTheme) -> Vec<Line<'a>> {
+    diff.lines()
+        .map(|l| Line::from(Span::styled(l, diff_line_style(l, theme))))
+        .collect()
+}
+
+/// All lines of the detail pane; the handler uses the count to bound scrolling.
+pub fn commit_detail_lines<'a>(detail: &'a CommitDetail, theme: &CyberTheme) -> Vec<Line<'a>> {
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(theme.subtle));
+    let mut lines = vec![
+        Line::from(vec![
+            label("commit     "),
+            Span::styled(detail.hash.as_str(), Style::default().fg(theme.warning)),
+        ]),
+        Line::from(vec![
+            label("author     "),
+            Span::styled(detail.author.as_str(), Style::default().fg(theme.accent)),
+            Span::styled(
+                format!("  {}", detail.author_date),
+                Style::default().fg(theme.subtle),
+            ),
+        ]),
+        Line::from(vec![
+            label("committer  "),
+            Span::styled(detail.committer.as_str(), Style::default().fg(theme.accent)),
+            Span::styled(
+                format!("  {}", detail.committer_date),
+                Style::default().fg(theme.subtle),
+            ),
+        ]),
+        Line::from(vec![
+            label("parents    "),
+            Span::styled(
+                detail
+                    .parents
+                    .iter()
+                    .map(|p| short(p))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                Style::default().fg(theme.warning),
+            ),
+        ]),
+    ];
+    if !detail.refs.is_empty() {
+        lines.push(Line::from(vec![
+            label("refs       "),
+            Span::styled(
+                detail.refs.join(", "),
+                Style::default()
+                    .fg(theme.secondary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        detail.subject.as_str(),
+        Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+    )));
+    if !detail.body.is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(
+            detail
+                .body
+                .lines()
+                .map(|l| Line::from(Span::styled(l, Style::default().fg(theme.text)))),
+        );
+    }
+
+    lines.push(Line::from(""));
+    lines.extend(file_stat_lines(&detail.files, theme));
+    lines.push(Line::from(""));
+    lines.extend(diff_lines(&detail.diff, theme));
+    lines
+}
+
+/// Last line the detail can be scrolled to. Builds every line once, so call it on load.
+pub fn commit_detail_max_scroll(detail: &CommitDetail) -> u16 {
+    u16::try_from(
+        commit_detail_lines(detail, &CyberTheme::default())
+            .len()
+            .saturating_sub(1),
+    )
+    .unwrap_or(u16::MAX)
+}
+
+pub fn render_commit_detail(f: &mut Frame, detail: &CommitDetail, scroll: u16) {
+    let theme = CyberTheme::default();
+    let area = centered_rect(90, 85, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.secondary))
+        .title(Span::styled(
+            format!(" 󰜘 COMMIT {} ", short(&detail.hash)),
+            Style::default()
+                .fg(theme.secondary)
+                .add_modifier(Modifier::BOLD),
+        ));
+    let inner_area = block.inner(area);
+    f.render_widget(block, area);
+
+    let lines = commit_detail_lines(detail, &theme);
+    f.render_widget(Paragraph::new(lines).scroll((scroll, 0)), inner_area);
+
+    let footer_area = Rect::new(area.x + 2, area.y + area.height - 1, area.width - 4, 1);
+    let key = |k: &'static str| {
+        Span::styled(
+            k,
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let help_text = Paragraph::new(Line::from(vec![
+        key(" [j/k/PG]"),
+        Span::raw(" SCROLL "),
+        key(" [Y]"),
+        Span::raw(" COPY "),
+        key(" [P]"),
+        Span::raw(" CHERRY-PICK "),
+        key(" [R]"),
+        Span::raw(" REVERT "),
+        key(" [W]"),
+        Span::raw(" WORKTREE "),
+        key(" [F]"),
+        Span::raw(" FILES "),
+        Span::styled(
+            " [ESC/Q]",
+            Style::default()
+                .fg(theme.subtle)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" BACK "),
+    ]))
+    .alignment(Alignment::Center);
+    f.render_widget(help_text, footer_area);
+}
@@ -0,0 +1,127 @@
+use crate::domain::repository::{GitCommit, RefComparison};
+use crate::ui::theme::CyberTheme;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use super::commit_detail::{diff_lines, file_stat_lines};
+use super::helpers::centered_rect;
+
+fn commit_lines<'a>(
+    arrow: &'static str,
+    side: &'a str,
+    commits: &'a [GitCommit],
+    theme: &CyberTheme,
+) -> Vec<Line<'a>> {
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            format!("{arrow} {} commits only in ", commits.len()),
+            Style::default().fg(theme.primary),
+        ),
+        Span::styled(
+            side,
+            Style::default()
+                .fg(theme.secondary)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ])];
+    lines.extend(commits.iter().map(|c| {
+        Line::from(vec![
+            Span::styled(format!("  {} ", c.hash), Style::default().fg(theme.warning)),
+            Span::styled(
+                format!("{:<10} ", c.date),
+                Style::default().fg(theme.subtle),
+            ),
+            Span::styled(
+                format!("{:<15} ", c.author),
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(c.message.as_str(), Style::default().fg(theme.text)),
+        ])
+    }));
+    lines
+}
+
+/// All lines of the comparison pane; the handler uses the count to bound scrolling.
+pub fn comparison_lines<'a>(comparison: &'a RefComparison, theme: &CyberTheme) -> Vec<Line<'a>> {
+    let merge_base = comparison
+        .merge_base
+        .as_deref()
+        .map_or("none (unrelated histories)", |h| h.get(..7).unwrap_or(h));
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("merge base  ", Style::default().fg(theme.subtle)),
+            Span::styled(merge_base, Style::default().fg(theme.warning)),
+        ]),
+        Line::from(""),
+    ];
+    lines.extend(commit_lines(
+        "↑",
+        &comparison.left,
+        &comparison.left_only,
+        theme,
+    ));
+    lines.push(Line::from(""));
+    lines.extend(commit_lines(
+        "↓",
+        &comparison.right,
+        &comparison.right_only,
+        theme,
+    ));
+    lines.push(Line::from(""));
+    lines.extend(file_stat_lines(&comparison.files, theme));
+    lines.push(Line::from(""));
+    lines.extend(diff_lines(&comparison.diff, theme));
+    lines
+}
+
+pub fn render_comparison(f: &mut Frame, comparison: &RefComparison, scroll: u16) {
+    let theme = CyberTheme::default();
+    let area = centered_rect(90, 85, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.secondary))
+        .title(Span::styled(
+            format!(" 󰕚 COMPARE {} ⇄ {} ", comparison.left, comparison.right),
+            Style::default()
+                .fg(theme.secondary)
+                .add_modifier(Modifier::BOLD),
+        ));
+    let inner_area = block.inner(area);
+    f.render_widget(block, area);
+
+    let lines = comparison_lines(comparison, &theme);
+    f.render_widget(Paragraph::new(lines).scroll((scroll, 0)), inner_area);
+
+    let footer_area = Rect::new(area.x + 2, area.y + area.height - 1, area.width - 4, 1);
+    let key = |k: &'static str| {
+        Span::styled(
+            k,
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let help_text = Paragraph::new(Line::from(vec![
+        key(" [j/k/PG]"),
+        Span::raw(" SCROLL "),
+        key(" [S]"),
+        Span::raw(" SWAP SIDES "),
+        Span::styled(
+            " [ESC/Q]",
+            Style::default()
+                .fg(theme.subtle)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" BACK "),
+    ]))
+    .alignment(Alignment::Center);
+    f.render_widget(help_text, footer_area);
+}
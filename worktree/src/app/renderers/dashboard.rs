@@ -8,7 +8,7 @@ pub fn render_dashboard(
     f: &mut Frame,
     worktree: Option<&Worktree>,
     all_worktrees: &[Worktree],
-    context: ProjectContext,
+    context: &ProjectContext,
     active_tab: DashboardTab,
     status: Option<&GitStatus>,
     history: Option<&[GitCommit]>,
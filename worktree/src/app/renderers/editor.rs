@@ -13,6 +13,7 @@ pub fn render_editor_selection(
     f: &mut Frame,
     branch: &str,
     options: &[EditorConfig],
+    installed: usize,
     selected: usize,
 ) {
     let theme = CyberTheme::default();
@@ -46,8 +47,14 @@ pub fn render_editor_selection(
         };
 
         let prefix = if is_selected { " ▶ " } else { "   " };
+        let (marker, marker_style) = if i < installed {
+            ("● ", Style::default().fg(theme.success))
+        } else {
+            ("○ ", Style::default().fg(theme.subtle))
+        };
         items.push(Line::from(vec![
             Span::styled(prefix, style),
+            Span::styled(marker, marker_style),
             Span::styled(&option.name, style),
             Span::raw(" "),
             Span::styled(
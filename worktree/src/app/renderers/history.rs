@@ -1,4 +1,4 @@
-use crate::domain::repository::GitCommit;
+use crate::domain::repository::{ChangedFile, GitCommit, HistoryQuery};
 use crate::ui::theme::CyberTheme;
 use ratatui::{
     Frame,
@@ -10,26 +10,106 @@ use ratatui::{
 
 use super::helpers::centered_rect;
 
-pub fn render_history(f: &mut Frame, branch: &str, commits: &[GitCommit], selected_index: usize) {
+/// The rows the commit list occupies for a history popup on `screen`.
+///
+/// Shared with the event handler so mouse clicks map to the same rows.
+pub fn history_list_area(screen: Rect, show_search: bool) -> Rect {
+    let inner = Block::default()
+        .borders(Borders::ALL)
+        .inner(centered_rect(85, 80, screen));
+    if show_search {
+        Rect {
+            y: inner.y + 1,
+            height: inner.height.saturating_sub(1),
+            ..inner
+        }
+    } else {
+        inner
+    }
+}
+
+/// First visible row so that `selected_index` stays on screen.
+pub fn history_scroll_offset(selected_index: usize, height: u16) -> usize {
+    selected_index.saturating_sub(usize::from(height.max(1)) - 1)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn render_history(
+    f: &mut Frame,
+    branch: &str,
+    commits: &[GitCommit],
+    selected_index: usize,
+    query: &HistoryQuery,
+    has_more: bool,
+    search: Option<&str>,
+    marked: &[String],
+) {
     let theme = CyberTheme::default();
     let area = centered_rect(85, 80, f.area());
     f.render_widget(Clear, area);
 
+    let count = commits.iter().filter(|c| !c.hash.is_empty()).count();
+    let more = if has_more { "+" } else { "" };
+    let marks = if marked.is_empty() {
+        String::new()
+    } else {
+        format!(" · {} marked", marked.len())
+    };
     let outer_block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(theme.secondary))
         .title(Span::styled(
-            format!(" 󰊚 COMMIT LOG: {branch} "),
+            format!(" 󰊚 COMMIT LOG: {branch} ({count}{more}{marks}) "),
             Style::default()
                 .fg(theme.secondary)
                 .add_modifier(Modifier::BOLD),
         ));
-
-    let inner_area = outer_block.inner(area);
     f.render_widget(outer_block, area);
 
+    let show_search = search.is_some() || query.is_filtered();
+    let list_area = history_list_area(f.area(), show_search);
+    if show_search {
+        let search_area = Rect {
+            y: list_area.y - 1,
+            height: 1,
+            ..list_area
+        };
+        let line = match search {
+            Some(input) => Line::from(vec![
+                Span::styled(
+                    " / ",
+                    Style::default()
+                        .fg(theme.warning)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(input, Style::default().fg(theme.text)),
+                Span::styled("█", Style::default().fg(theme.primary)),
+                Span::styled(
+                    "   author: path: since: until:",
+                    Style::default().fg(theme.subtle),
+                ),
+            ]),
+            None => Line::from(vec![
+                Span::styled(
+                    " FILTER ",
+                    Style::default()
+                        .fg(theme.warning)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(query.search_text(), Style::default().fg(theme.text)),
+            ]),
+        };
+        f.render_widget(Paragraph::new(line), search_area);
+    }
+
     let mut items = Vec::new();
+    if count == 0 {
+        items.push(Line::from(Span::styled(
+            "  No commits match",
+            Style::default().fg(theme.subtle),
+        )));
+    }
     for (i, commit) in commits.iter().enumerate() {
         let is_selected = i == selected_index;
         let row_style = if is_selected && !commit.hash.is_empty() {
@@ -46,10 +126,16 @@ pub fn render_history(f: &mut Frame, branch: &str, commits: &[GitCommit], select
         } else {
             "  "
         };
+        let mark = if !commit.hash.is_empty() && marked.contains(&commit.hash) {
+            "●"
+        } else {
+            " "
+        };
 
         let mut entry = vec![
             Span::styled(format!("{:<15}", commit.graph), row_style.fg(theme.primary)),
             Span::styled(prefix, row_style.fg(theme.primary)),
+            Span::styled(mark, row_style.fg(theme.success)),
         ];
 
         if !commit.hash.is_empty() {
@@ -67,19 +153,136 @@ pub fn render_history(f: &mut Frame, branch: &str, commits: &[GitCommit], select
         items.push(Line::from(entry));
     }
 
-    let p = Paragraph::new(items).alignment(Alignment::Left);
-    f.render_widget(p, inner_area);
+    let offset = history_scroll_offset(selected_index, list_area.height);
+    let p = Paragraph::new(items)
+        .alignment(Alignment::Left)
+        .scroll((u16::try_from(offset).unwrap_or(u16::MAX), 0));
+    f.render_widget(p, list_area);
 
     // Footer
+    let footer_area = Rect::new(area.x + 2, area.y + area.height - 1, area.width - 4, 1);
+    let key = |k: &'static str| {
+        Span::styled(
+            k,
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let back = |k: &'static str| {
+        Span::styled(
+            k,
+            Style::default()
+                .fg(theme.subtle)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let spans = if search.is_some() {
+        vec![
+            key(" [ENT]"),
+            Span::raw(" APPLY  "),
+            back(" [ESC]"),
+            Span::raw(" CANCEL "),
+        ]
+    } else {
+        let mut spans = vec![
+            key(" [UP/DOWN]"),
+            Span::raw(" NAVIGATE  "),
+            key(" [ENT]"),
+            Span::raw(" DETAIL  "),
+            key(" [F]"),
+            Span::raw(" FILES  "),
+            key(" [SPC]"),
+            Span::raw(" MARK  "),
+            key(" [P]"),
+            Span::raw(" PORT  "),
+            key(" [/]"),
+            Span::raw(" SEARCH  "),
+        ];
+        if query.is_filtered() {
+            spans.extend([back(" [ESC]"), Span::raw(" CLEAR  "), back(" [Q]")]);
+        } else {
+            spans.push(back(" [ESC/Q]"));
+        }
+        spans.push(Span::raw(" BACK "));
+        spans
+    };
+    let help_text = Paragraph::new(Line::from(spans)).alignment(Alignment::Center);
+    f.render_widget(help_text, footer_area);
+}
+
+pub fn render_commit_files(
+    f: &mut Frame,
+    commit: &GitCommit,
+    files: &[ChangedFile],
+    selected_index: usize,
+) {
+    let theme = CyberTheme::default();
+    let area = centered_rect(70, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.secondary))
+        .title(Span::styled(
+            format!(" {} {} ", commit.hash, commit.message),
+            Style::default()
+                .fg(theme.secondary)
+                .add_modifier(Modifier::BOLD),
+        ));
+
+    let inner_area = block.inner(area);
+    f.render_widget(block, area);
+
+    let items: Vec<Line> = if files.is_empty() {
+        vec![Line::from(Span::styled(
+            "  No file changes in this commit",
+            Style::default().fg(theme.subtle),
+        ))]
+    } else {
+        files
+            .iter()
+            .enumerate()
+            .map(|(i, file)| {
+                let is_selected = i == selected_index;
+                let row_style = if is_selected {
+                    Style::default()
+                        .bg(theme.selection_bg)
+                        .fg(theme.text)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                let status_color = match file.status {
+                    'A' => theme.success,
+                    'D' => theme.error,
+                    'R' => theme.accent,
+                    _ => theme.warning,
+                };
+                Line::from(vec![
+                    Span::styled(if is_selected { " ▶ " } else { "   " }, row_style),
+                    Span::styled(format!("{} ", file.status), row_style.fg(status_color)),
+                    Span::styled(&file.path, row_style),
+                    Span::styled(
+                        file.line.map(|l| format!(":{l}")).unwrap_or_default(),
+                        row_style.fg(theme.subtle),
+                    ),
+                ])
+            })
+            .collect()
+    };
+    f.render_widget(Paragraph::new(items), inner_area);
+
     let footer_area = Rect::new(area.x + 2, area.y + area.height - 1, area.width - 4, 1);
     let help_text = Paragraph::new(Line::from(vec![
         Span::styled(
-            " [UP/DOWN]",
+            " [ENT/E]",
             Style::default()
                 .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::raw(" NAVIGATE  "),
+        Span::raw(" OPEN  "),
         Span::styled(
             " [ESC/Q]",
             Style::default()
@@ -1,5 +1,6 @@
 use crate::app::model::DashboardTab;
 use crate::app::renderers::dashboard::render_dashboard;
+use crate::domain::list_layout::ListLayout;
 use crate::domain::repository::{GitCommit, GitStatus, ProjectContext, Worktree};
 use crate::ui::widgets::worktree_list::WorktreeListWidget;
 use ratatui::{
@@ -15,7 +16,7 @@ pub fn render_listing(
     worktrees: &[Worktree],
     filtered_indices: &[usize],
     table_state: &mut TableState,
-    context: ProjectContext,
+    context: &ProjectContext,
     area: Rect,
     active_tab: DashboardTab,
     status: Option<&GitStatus>,
@@ -23,6 +24,7 @@ pub fn render_listing(
     filter_query: &str,
     is_filtering: bool,
     mode: crate::app::model::AppMode,
+    layout: &ListLayout,
     spinner_tick: usize,
 ) {
     let chunks = Layout::default()
@@ -51,7 +53,8 @@ pub fn render_listing(
         } else {
             None
         })
-        .with_mode(mode);
+        .with_mode(mode)
+        .grouped(filter_query.is_empty().then_some(layout));
 
     f.render_stateful_widget(table, list_area, table_state);
 
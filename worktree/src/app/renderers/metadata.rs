@@ -0,0 +1,97 @@
+use crate::app::model::MetadataForm;
+use crate::domain::repository::WorktreeMetadata;
+use crate::ui::theme::CyberTheme;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+
+use super::helpers::centered_rect;
+
+pub fn render_metadata_form(f: &mut Frame, branch: &str, form: &MetadataForm) {
+    let theme = CyberTheme::default();
+    let area = centered_rect(60, 40, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.accent))
+        .title(Span::styled(
+            format!(" EDIT {branch} "),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ));
+    let inner_area = block.inner(area);
+    f.render_widget(block, area);
+
+    let mut lines = vec![Line::from("")];
+    for (i, (key, value)) in WorktreeMetadata::FIELDS
+        .iter()
+        .zip(&form.values)
+        .enumerate()
+    {
+        let focused = i == form.focused;
+        let mut line = vec![
+            Span::styled(
+                format!(" {:<12}", key.to_uppercase()),
+                Style::default().fg(if focused {
+                    theme.accent
+                } else {
+                    theme.secondary
+                }),
+            ),
+            Span::styled(value.as_str(), Style::default().fg(theme.text)),
+        ];
+        if focused {
+            line.push(Span::styled(
+                "█",
+                Style::default()
+                    .fg(theme.primary)
+                    .add_modifier(Modifier::SLOW_BLINK),
+            ));
+        }
+        lines.push(Line::from(line));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        " Colors: a name such as cyan or lightblue, or #rrggbb. Empty fields are cleared.",
+        Style::default().fg(theme.subtle),
+    )));
+    if let Some(error) = &form.error {
+        lines.push(Line::from(Span::styled(
+            format!(" {error}"),
+            Style::default().fg(theme.error),
+        )));
+    }
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner_area);
+
+    let footer_area = Rect::new(area.x + 2, area.y + area.height - 1, area.width - 4, 1);
+    let key = |k: &'static str| {
+        Span::styled(
+            k,
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let help_text = Paragraph::new(Line::from(vec![
+        key(" [TAB]"),
+        Span::raw(" NEXT  "),
+        key(" [ENT]"),
+        Span::raw(" SAVE  "),
+        Span::styled(
+            " [ESC]",
+            Style::default()
+                .fg(theme.subtle)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" CANCEL "),
+    ]))
+    .alignment(Alignment::Center);
+    f.render_widget(help_text, footer_area);
+}
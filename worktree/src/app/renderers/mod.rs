@@ -1,19 +1,29 @@
 pub mod branch;
+pub mod branch_manager;
 pub mod commit;
+pub mod commit_detail;
+pub mod compare;
 pub mod dashboard;
 pub mod editor;
 pub mod helpers;
 pub mod history;
 pub mod listing;
+pub mod metadata;
 pub mod modals;
 pub mod prompt;
+pub mod stale;
 pub mod status;
 
 pub use branch::render_branch_selection;
+pub use branch_manager::render_branch_manager;
 pub use commit::render_commit_menu;
+pub use commit_detail::render_commit_detail;
+pub use compare::render_comparison;
 pub use editor::render_editor_selection;
-pub use history::render_history;
+pub use history::{render_commit_files, render_history};
 pub use listing::render_listing;
+pub use metadata::render_metadata_form;
 pub use modals::render_modals;
 pub use prompt::render_prompt;
+pub use stale::render_stale_review;
 pub use status::render_status;
@@ -90,6 +90,50 @@ pub fn render_modals<R: ProjectRepository>(
                 Style::default(),
             );
         }
+        AppState::ApplyingCommit { message, .. } => {
+            let theme = CyberTheme::default();
+            render_info_modal(
+                f,
+                standard_area,
+                Line::from(vec![Span::styled(
+                    format!(" {spinner} APPLYING COMMIT "),
+                    Style::default()
+                        .fg(theme.primary)
+                        .add_modifier(Modifier::BOLD),
+                )]),
+                vec![Line::from(message.as_str())],
+                Style::default().fg(theme.primary),
+            );
+        }
+        AppState::LoadingComparison { left, right, .. } => {
+            let theme = CyberTheme::default();
+            render_info_modal(
+                f,
+                standard_area,
+                Line::from(vec![Span::styled(
+                    format!(" {spinner} COMPARING "),
+                    Style::default()
+                        .fg(theme.primary)
+                        .add_modifier(Modifier::BOLD),
+                )]),
+                vec![Line::from(format!("{left} ⇄ {right}"))],
+                Style::default().fg(theme.primary),
+            );
+        }
+        AppState::CommitActionComplete { message, .. } => {
+            render_info_modal(
+                f,
+                standard_area,
+                Line::from(vec![Span::styled(
+                    " ✅ DONE ",
+                    Style::default()
+                        .fg(RatatuiColor::Green)
+                        .add_modifier(Modifier::BOLD),
+                )]),
+                vec![Line::from(message.as_str())],
+                Style::default(),
+            );
+        }
         AppState::Pushing { branch, .. } => {
             let theme = CyberTheme::default();
             render_info_modal(
@@ -220,13 +264,22 @@ pub fn render_modals<R: ProjectRepository>(
                 ]),
                 Line::from(vec![
                     Span::styled(
-                        " [O] ",
+                        " [ENT] ",
                         Style::default()
                             .fg(theme.accent)
                             .add_modifier(Modifier::BOLD),
                     ),
                     Span::raw(" Open the selected worktree in your preferred editor"),
                 ]),
+                Line::from(vec![
+                    Span::styled(
+                        " [E] ",
+                        Style::default()
+                            .fg(theme.accent)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(" Pick an editor and remember it for this project"),
+                ]),
                 Line::from(vec![
                     Span::styled(
                         " [G] ",
@@ -245,6 +298,15 @@ pub fn render_modals<R: ProjectRepository>(
                     ),
                     Span::raw(" View recent commit history for the worktree"),
                 ]),
+                Line::from(vec![
+                    Span::styled(
+                        " [T] ",
+                        Style::default()
+                            .fg(theme.secondary)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(" Exit and cd into the selected worktree (needs shell-init)"),
+                ]),
                 Line::from(vec![
                     Span::styled(
                         " [B] ",
@@ -290,6 +352,53 @@ pub fn render_modals<R: ProjectRepository>(
                     ),
                     Span::raw(" Pull changes from the remote repository"),
                 ]),
+                Line::from(vec![
+                    Span::styled(
+                        " [G] [C] ",
+                        Style::default()
+                            .fg(theme.secondary)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(" Compare with another worktree or branch"),
+                ]),
+                Line::from(vec![
+                    Span::styled(
+                        " [*] ",
+                        Style::default()
+                            .fg(theme.accent)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(" Pin or unpin the selected worktree at the top of the list"),
+                ]),
+                Line::from(vec![
+                    Span::styled(
+                        " [Shift+S] ",
+                        Style::default()
+                            .fg(theme.primary)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(
+                        " Cycle the sort order: name, activity, size, dirty first, ahead/behind",
+                    ),
+                ]),
+                Line::from(vec![
+                    Span::styled(
+                        " [Shift+G] ",
+                        Style::default()
+                            .fg(theme.primary)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(" Group by branch prefix, by purpose or not at all"),
+                ]),
+                Line::from(vec![
+                    Span::styled(
+                        " [Z] / [Shift+Z] ",
+                        Style::default()
+                            .fg(theme.primary)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(" Collapse the selected worktree's group / expand all groups"),
+                ]),
                 Line::from(vec![
                     Span::styled(
                         " [U] ",
@@ -497,12 +606,12 @@ pub fn render_modals<R: ProjectRepository>(
                 )
                 .alignment(Alignment::Center);
             f.render_widget(p, main_chunks[1]);
-            f.render_widget(DetailsWidget::new(None, &[], context), main_chunks[2]);
+            f.render_widget(DetailsWidget::new(None, &[], &context), main_chunks[2]);
         }
         AppState::Error(msg, _) => {
             let table = WorktreeListWidget::new(&[], None);
             f.render_stateful_widget(table, main_chunks[1], &mut TableState::default());
-            f.render_widget(DetailsWidget::new(None, &[], context), main_chunks[2]);
+            f.render_widget(DetailsWidget::new(None, &[], &context), main_chunks[2]);
 
             let area = centered_rect(70, 50, f.area());
             f.render_widget(Clear, area);
@@ -592,6 +701,7 @@ pub fn render_modals<R: ProjectRepository>(
         | AppState::SettingUpDefaults
         | AppState::LoadingStatus { .. }
         | AppState::LoadingHistory { .. }
+        | AppState::LoadingCommitDetail { .. }
         | AppState::LoadingBranches { .. }
         | AppState::Cleaning { .. } => {
             let theme = CyberTheme::default();
@@ -601,6 +711,7 @@ pub fn render_modals<R: ProjectRepository>(
                 AppState::SettingUpDefaults => "CONFIGURING DEFAULTS",
                 AppState::LoadingStatus { .. } => "FETCHING GIT STATUS",
                 AppState::LoadingHistory { .. } => "FETCHING COMMIT HISTORY",
+                AppState::LoadingCommitDetail { .. } => "LOADING COMMIT",
                 AppState::LoadingBranches { .. } => "LISTING BRANCHES",
                 AppState::Cleaning { .. } => "CLEANING WORKTREES",
                 _ => "PROCESSING",
@@ -19,7 +19,10 @@ pub fn render_prompt(f: &mut Frame, prompt_type: &PromptType, input: &str) {
         PromptType::CommitMessage => " COMMIT MESSAGE ",
         PromptType::ApiKey => " GEMINI API KEY ",
         PromptType::StashMessage => " STASH MESSAGE ",
-        PromptType::NameNewWorktree { base_ref } => {
+        PromptType::CreateBranch { base } => &format!(" NEW BRANCH (FROM {base}) "),
+        PromptType::RenameBranch { old } => &format!(" RENAME BRANCH {old} "),
+        PromptType::SetUpstream { branch } => &format!(" UPSTREAM FOR {branch} "),
+        PromptType::NameNewWorktree { base_ref, .. } => {
             &format!(" NAME NEW WORKTREE (FROM {base_ref}) ")
         }
     };
@@ -38,7 +41,7 @@ pub fn render_prompt(f: &mut Frame, prompt_type: &PromptType, input: &str) {
     let inner_area = block.inner(area);
     f.render_widget(block, area);
 
-    let p = Paragraph::new(vec![
+    let mut lines = vec![
         Line::from(""),
         Line::from(vec![
             Span::styled(
@@ -55,8 +58,18 @@ pub fn render_prompt(f: &mut Frame, prompt_type: &PromptType, input: &str) {
                     .add_modifier(Modifier::SLOW_BLINK),
             ),
         ]),
-    ])
-    .alignment(Alignment::Left);
+    ];
+    if let PromptType::NameNewWorktree { template, .. } = prompt_type {
+        lines.push(Line::from(vec![
+            Span::styled(" TEMPLATE: ", Style::default().fg(theme.secondary)),
+            Span::styled(
+                template.as_deref().unwrap_or("none"),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  [Tab] change", Style::default().fg(theme.subtle)),
+        ]));
+    }
+    let p = Paragraph::new(lines).alignment(Alignment::Left);
 
     f.render_widget(p, inner_area);
 }
@@ -0,0 +1,131 @@
+use crate::domain::stale::StaleWorktree;
+use crate::ui::theme::CyberTheme;
+use crate::ui::widgets::worktree_list::format_size;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use super::helpers::centered_rect;
+use super::history::history_scroll_offset;
+
+pub fn render_stale_review(
+    f: &mut Frame,
+    candidates: &[StaleWorktree],
+    checked: &[bool],
+    selected_index: usize,
+) {
+    let theme = CyberTheme::default();
+    let area = centered_rect(80, 70, f.area());
+    f.render_widget(Clear, area);
+
+    let selected_count = checked.iter().filter(|c| **c).count();
+    let reclaimable: u64 = candidates
+        .iter()
+        .zip(checked)
+        .filter(|(_, checked)| **checked)
+        .map(|(c, _)| c.size_bytes)
+        .sum();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.warning))
+        .title(Span::styled(
+            format!(
+                " STALE WORKTREES: {selected_count}/{} SELECTED ({}) ",
+                candidates.len(),
+                format_size(reclaimable)
+            ),
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ));
+    let inner_area = block.inner(area);
+    f.render_widget(block, area);
+
+    let name_width = candidates
+        .iter()
+        .map(|c| c.branch.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(12, 40);
+
+    let mut items = Vec::new();
+    for (i, candidate) in candidates.iter().enumerate() {
+        let is_selected = i == selected_index;
+        let row_style = if is_selected {
+            Style::default()
+                .bg(theme.selection_bg)
+                .fg(theme.text)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let mark = if checked.get(i).copied().unwrap_or(false) {
+            "[x]"
+        } else {
+            "[ ]"
+        };
+
+        let mut entry = vec![
+            Span::styled(
+                if is_selected { " ▶ " } else { "   " },
+                row_style.fg(theme.primary),
+            ),
+            Span::styled(format!("{mark} "), row_style.fg(theme.accent)),
+            Span::styled(
+                format!("{:<name_width$} ", candidate.branch),
+                row_style.fg(theme.primary),
+            ),
+            Span::styled(
+                format!(" {:>9} ", format_size(candidate.size_bytes)),
+                row_style.fg(theme.subtle),
+            ),
+            Span::styled(
+                format!(" {} ", candidate.reasons_label()),
+                row_style.fg(theme.warning),
+            ),
+        ];
+        if candidate.dirty {
+            entry.push(Span::styled(
+                format!(" dirty {} ", candidate.status_label()),
+                row_style.fg(theme.error),
+            ));
+        }
+        items.push(Line::from(entry));
+    }
+
+    let offset = history_scroll_offset(selected_index, inner_area.height);
+    let p = Paragraph::new(items).scroll((u16::try_from(offset).unwrap_or(u16::MAX), 0));
+    f.render_widget(p, inner_area);
+
+    let footer_area = Rect::new(area.x + 2, area.y + area.height - 1, area.width - 4, 1);
+    let key = |k: &'static str| {
+        Span::styled(
+            k,
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let help_text = Paragraph::new(Line::from(vec![
+        key(" [SPACE]"),
+        Span::raw(" TOGGLE  "),
+        key(" [A]"),
+        Span::raw(" ALL  "),
+        key(" [ENT]"),
+        Span::raw(" REMOVE  "),
+        Span::styled(
+            " [ESC/Q]",
+            Style::default()
+                .fg(theme.subtle)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" BACK "),
+    ]))
+    .alignment(Alignment::Center);
+    f.render_widget(help_text, footer_area);
+}
@@ -335,6 +335,13 @@ pub fn render_status(
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw(" Diff  "),
+        Span::styled(
+            "[E]",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" Edit  "),
         Span::styled(
             "[C]",
             Style::default()
@@ -3,9 +3,11 @@ pub mod scaffolding {
     use super::super::model::AppState;
     use super::super::ports::ViewPort;
     use super::super::reducer::Reducer;
+    use crate::domain::cache::CacheStatus;
+    use crate::domain::list_layout::GroupMode;
     use crate::domain::repository::{
-        GitCommit, GitStatus, ProjectContext, ProjectRepository, RepoStatus, RepositoryEvent,
-        StashEntry, Worktree,
+        BranchInfo, GitCommit, GitStatus, ProjectContext, ProjectRepository, Remote, RepoStatus,
+        RepositoryEvent, StashEntry, Worktree,
     };
     use crossbeam_channel::Receiver;
     use miette::Result;
@@ -21,7 +23,13 @@ pub mod scaffolding {
         pub jsons: Arc<Mutex<Vec<Value>>>,
         pub banners: Arc<Mutex<usize>>,
         pub listings: Arc<Mutex<Vec<Vec<Worktree>>>>,
+        pub branch_tables: Arc<Mutex<Vec<Vec<BranchInfo>>>>,
+        pub remote_tables: Arc<Mutex<Vec<Vec<Remote>>>>,
+        pub cache_tables: Arc<Mutex<Vec<Vec<CacheStatus>>>>,
         pub prompts: Arc<Mutex<usize>>,
+        /// Questions passed to `confirm`, each answered with `confirm_answer`.
+        pub confirmations: Arc<Mutex<Vec<String>>>,
+        pub confirm_answer: bool,
     }
 
     impl ViewPort for TestSpyView {
@@ -40,13 +48,33 @@ pub mod scaffolding {
             *self.banners.lock().unwrap() += 1;
         }
 
-        fn render_listing_table(&self, worktrees: &[Worktree]) {
+        fn render_listing_table(&self, worktrees: &[Worktree], _group: GroupMode) {
             self.listings.lock().unwrap().push(worktrees.to_vec());
         }
 
+        fn render_branch_table(&self, branches: &[BranchInfo]) {
+            self.branch_tables.lock().unwrap().push(branches.to_vec());
+        }
+
+        fn render_remote_table(&self, remotes: &[Remote]) {
+            self.remote_tables.lock().unwrap().push(remotes.to_vec());
+        }
+
+        fn render_cache_table(&self, caches: &[CacheStatus]) {
+            self.cache_tables.lock().unwrap().push(caches.to_vec());
+        }
+
         fn render_feedback_prompt(&self) {
             *self.prompts.lock().unwrap() += 1;
         }
+
+        fn confirm(&self, question: &str) -> bool {
+            self.confirmations
+                .lock()
+                .unwrap()
+                .push(question.to_string());
+            self.confirm_answer
+        }
     }
 
     // --- 2. The Mock Repo Builder ---
@@ -54,6 +82,9 @@ pub mod scaffolding {
     #[derive(Clone, Default)]
     pub struct MockRepoBuilder {
         worktrees: Vec<Worktree>,
+        history: Vec<GitCommit>,
+        branches: Vec<BranchInfo>,
+        merged: Vec<String>,
     }
 
     impl MockRepoBuilder {
@@ -62,9 +93,30 @@ pub mod scaffolding {
             self
         }
 
+        /// Commits served by `get_history`, filtered by `grep` and paged by `skip`/`limit`.
+        pub fn with_history(mut self, history: Vec<GitCommit>) -> Self {
+            self.history = history;
+            self
+        }
+
+        /// Branches served by `list_branch_info`; names starting with `wip` count as unmerged.
+        pub fn with_branches(mut self, branches: Vec<BranchInfo>) -> Self {
+            self.branches = branches;
+            self
+        }
+
+        /// Branches `merged_branches` reports for any target.
+        pub fn with_merged(mut self, merged: Vec<&str>) -> Self {
+            self.merged = merged.into_iter().map(String::from).collect();
+            self
+        }
+
         pub fn build(self) -> MockRepo {
             MockRepo {
                 worktrees: self.worktrees,
+                history: self.history,
+                branches: self.branches,
+                merged: self.merged,
             }
         }
     }
@@ -72,6 +124,9 @@ pub mod scaffolding {
     #[derive(Clone)]
     pub struct MockRepo {
         worktrees: Vec<Worktree>,
+        history: Vec<GitCommit>,
+        branches: Vec<BranchInfo>,
+        merged: Vec<String>,
     }
 
     impl ProjectRepository for MockRepo {
@@ -93,8 +148,58 @@ pub mod scaffolding {
         fn sync_configs(&self, _path: &str) -> anyhow::Result<()> {
             Ok(())
         }
+        fn check_sync(&self, _path: &str) -> anyhow::Result<Vec<crate::domain::sync::SyncDrift>> {
+            Ok(Vec::new())
+        }
+        fn fix_sync(&self, _path: &str, _destinations: &[String]) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn sync_diff(&self, _path: &str, _destination: &str) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+        fn worktree_env(&self, _path: &str) -> anyhow::Result<Vec<(String, String)>> {
+            Ok(Vec::new())
+        }
+        fn add_detached_worktree(&self, _path: &str, _base: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn sync_entries(&self, _path: &str, _entries: &[String]) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn lookup_issue(&self, id: &str) -> anyhow::Result<crate::domain::issues::IssueLink> {
+            Ok(crate::domain::issues::IssueLink {
+                id: id.to_string(),
+                url: None,
+                title: None,
+            })
+        }
+        fn worktree_metadata(
+            &self,
+            _path: &str,
+        ) -> anyhow::Result<crate::domain::repository::WorktreeMetadata> {
+            Ok(crate::domain::repository::WorktreeMetadata::default())
+        }
+        fn update_worktree_metadata(
+            &self,
+            _path: &str,
+            _metadata: &crate::domain::repository::WorktreeMetadata,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn list_layout(&self) -> crate::domain::list_layout::ListLayout {
+            crate::domain::list_layout::ListLayout::default()
+        }
+        fn save_list_layout(
+            &self,
+            _layout: &crate::domain::list_layout::ListLayout,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn expired_worktrees(&self, _now: i64) -> anyhow::Result<Vec<String>> {
+            Ok(Vec::new())
+        }
         fn detect_context(&self, _base_path: &Path) -> ProjectContext {
-            ProjectContext::Standard
+            ProjectContext::default()
         }
         fn get_preferred_editor(&self) -> anyhow::Result<Option<String>> {
             Ok(None)
@@ -102,13 +207,34 @@ pub mod scaffolding {
         fn set_preferred_editor(&self, _editor: &str) -> anyhow::Result<()> {
             Ok(())
         }
-        fn fetch(&self, _path: &str) -> anyhow::Result<()> {
+        fn list_editors(&self) -> anyhow::Result<Vec<crate::domain::repository::EditorConfig>> {
+            Ok(crate::domain::repository::EditorConfig::defaults())
+        }
+        fn get_project_config(&self) -> anyhow::Result<crate::domain::repository::ProjectConfig> {
+            Ok(crate::domain::repository::ProjectConfig::default())
+        }
+        fn save_project_config(
+            &self,
+            _config: &crate::domain::repository::ProjectConfig,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn fetch(&self, _path: &str, _remote: Option<&str>) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn pull(&self, _path: &str, _remote: Option<&str>) -> anyhow::Result<()> {
             Ok(())
         }
-        fn pull(&self, _path: &str) -> anyhow::Result<()> {
+        fn push(&self, _path: &str, _remote: Option<&str>) -> anyhow::Result<()> {
             Ok(())
         }
-        fn push(&self, _path: &str) -> anyhow::Result<()> {
+        fn list_remotes(&self) -> anyhow::Result<Vec<Remote>> {
+            Ok(vec![])
+        }
+        fn add_remote(&self, _name: &str, _url: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn remove_remote(&self, _name: &str) -> anyhow::Result<()> {
             Ok(())
         }
         fn get_status(&self, _path: &str) -> anyhow::Result<GitStatus> {
@@ -139,12 +265,134 @@ pub mod scaffolding {
         fn generate_commit_message(&self, _diff: &str, _branch: &str) -> anyhow::Result<String> {
             Ok("mock commit".into())
         }
-        fn get_history(&self, _path: &str, _limit: usize) -> anyhow::Result<Vec<GitCommit>> {
+        fn get_history(
+            &self,
+            _path: &str,
+            query: &crate::domain::repository::HistoryQuery,
+        ) -> anyhow::Result<Vec<GitCommit>> {
+            Ok(self
+                .history
+                .iter()
+                .filter(|c| query.grep.as_ref().is_none_or(|g| c.message.contains(g)))
+                .skip(query.skip)
+                .take(query.limit)
+                .cloned()
+                .collect())
+        }
+        fn get_commit_files(
+            &self,
+            _path: &str,
+            _hash: &str,
+        ) -> anyhow::Result<Vec<crate::domain::repository::ChangedFile>> {
             Ok(vec![])
         }
+        fn get_commit_detail(
+            &self,
+            _path: &str,
+            hash: &str,
+        ) -> anyhow::Result<crate::domain::repository::CommitDetail> {
+            Ok(crate::domain::repository::CommitDetail {
+                hash: hash.to_string(),
+                author: String::new(),
+                author_date: String::new(),
+                committer: String::new(),
+                committer_date: String::new(),
+                parents: vec![],
+                refs: vec![],
+                subject: String::new(),
+                body: String::new(),
+                files: vec![],
+                diff: String::new(),
+            })
+        }
+        fn cherry_pick(&self, _path: &str, hash: &str) -> anyhow::Result<()> {
+            if hash == "conflict" {
+                anyhow::bail!("could not apply {hash}");
+            }
+            Ok(())
+        }
+        fn revert_commit(&self, _path: &str, _hash: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn resolve_commit_range(&self, _path: &str, range: &str) -> anyhow::Result<Vec<String>> {
+            Ok(range.split("..").map(String::from).collect())
+        }
+        fn check_droppable(&self, _path: &str, _hashes: &[String]) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn drop_commits(&self, _path: &str, _hashes: &[String]) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn compare_refs(
+            &self,
+            _path: &str,
+            left: &str,
+            right: &str,
+        ) -> anyhow::Result<crate::domain::repository::RefComparison> {
+            Ok(crate::domain::repository::RefComparison {
+                left: left.to_string(),
+                right: right.to_string(),
+                merge_base: None,
+                left_only: vec![],
+                right_only: vec![],
+                files: vec![],
+                diff: String::new(),
+            })
+        }
+        fn first_changed_line(
+            &self,
+            _path: &str,
+            _file: &str,
+            _staged: bool,
+        ) -> anyhow::Result<Option<usize>> {
+            Ok(None)
+        }
         fn list_branches(&self) -> anyhow::Result<Vec<String>> {
             Ok(vec![])
         }
+        fn list_branch_info(&self) -> anyhow::Result<Vec<BranchInfo>> {
+            Ok(self.branches.clone())
+        }
+        fn create_branch(&self, _name: &str, _base: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn rename_branch(&self, _from: &str, _to: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn delete_branch(&self, _name: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn branch_unmerged_into(&self, branch: &str) -> anyhow::Result<Option<String>> {
+            Ok(branch.starts_with("wip").then(|| "main".to_string()))
+        }
+        fn set_upstream(&self, _branch: &str, _upstream: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn default_branch(&self) -> anyhow::Result<String> {
+            Ok("main".to_string())
+        }
+        fn merged_branches(&self, _target: &str) -> anyhow::Result<Vec<String>> {
+            Ok(self.merged.clone())
+        }
+        fn last_activity(&self, _path: &str) -> anyhow::Result<i64> {
+            Ok(0)
+        }
+        fn scan_artifacts(
+            &self,
+            _path: &str,
+        ) -> anyhow::Result<Vec<crate::domain::artifacts::Artifact>> {
+            Ok(vec![])
+        }
+        fn remove_artifacts(
+            &self,
+            _path: &str,
+            _artifacts: &[crate::domain::artifacts::Artifact],
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn cache_status(&self) -> anyhow::Result<Vec<crate::domain::cache::CacheStatus>> {
+            Ok(vec![])
+        }
         fn switch_branch(&self, _path: &str, _branch: &str) -> anyhow::Result<()> {
             Ok(())
         }
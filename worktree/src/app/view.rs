@@ -2,16 +2,26 @@ use crate::app::async_tasks::AsyncResult;
 use crate::app::cli_renderer::CliRenderer;
 use crate::app::event_handlers::helpers::create_timed_state;
 use crate::app::event_handlers::{
-    handle_branch_events, handle_committing_events, handle_confirm_events, handle_editor_events,
-    handle_history_events, handle_listing_events, handle_picking_ref_events, handle_prompt_events,
-    handle_stash_events, handle_status_events,
+    handle_branch_events, handle_branch_manager_events, handle_cherry_pick_target_events,
+    handle_commit_detail_events, handle_commit_files_events, handle_committing_events,
+    handle_compare_target_events, handle_comparison_events, handle_confirm_events,
+    handle_editor_events, handle_history_events, handle_listing_events, handle_metadata_events,
+    handle_picking_ref_events, handle_prompt_events, handle_stale_events, handle_stash_events,
+    handle_status_events,
 };
-use crate::app::model::{AppState, RefreshType};
+use crate::app::model::{AppState, EditorConfig, EditorTarget, RefreshType};
+use crate::app::renderers::commit_detail::commit_detail_max_scroll;
 use crate::app::renderers::{
-    render_branch_selection, render_commit_menu, render_editor_selection, render_history,
-    render_listing, render_modals, render_prompt, render_status,
+    render_branch_manager, render_branch_selection, render_commit_detail, render_commit_files,
+    render_commit_menu, render_comparison, render_editor_selection, render_history, render_listing,
+    render_metadata_form, render_modals, render_prompt, render_stale_review, render_status,
+};
+use crate::domain::cache::CacheStatus;
+use crate::domain::list_layout::{GroupMode, arrange_worktrees};
+use crate::domain::repository::{
+    BranchInfo, HistoryQuery, ProjectRepository, Remote, RepositoryEvent, Worktree,
+    annotate_worktrees,
 };
-use crate::domain::repository::{ProjectRepository, RepositoryEvent, Worktree};
 use crate::ui::widgets::{footer::FooterWidget, header::HeaderWidget, stash_list::StashListWidget};
 use anyhow::Result;
 use crossbeam_channel::Receiver;
@@ -45,18 +55,38 @@ impl View {
         CliRenderer::render_json(data)
     }
 
-    pub fn render_listing_table(worktrees: &[Worktree]) {
-        CliRenderer::render_listing_table(worktrees);
+    pub fn render_listing_table(worktrees: &[Worktree], group: GroupMode) {
+        CliRenderer::render_listing_table(worktrees, group);
+    }
+
+    pub fn render_branch_table(branches: &[BranchInfo]) {
+        CliRenderer::render_branch_table(branches);
+    }
+
+    pub fn render_remote_table(remotes: &[Remote]) {
+        CliRenderer::render_remote_table(remotes);
+    }
+
+    pub fn render_cache_table(caches: &[CacheStatus]) {
+        CliRenderer::render_cache_table(caches);
     }
 
     pub fn render_feedback_prompt() {
         CliRenderer::render_feedback_prompt();
     }
 
+    pub fn render_expired_warning(branches: &[String]) {
+        CliRenderer::render_expired_warning(branches);
+    }
+
+    pub fn confirm(question: &str) -> bool {
+        CliRenderer::confirm(question)
+    }
+
     fn render_background(
         f: &mut Frame,
         state: &AppState,
-        context: crate::domain::repository::ProjectContext,
+        context: &crate::domain::repository::ProjectContext,
         area: ratatui::layout::Rect,
         spinner_tick: usize,
     ) {
@@ -69,6 +99,7 @@ impl View {
                 filter_query,
                 is_filtering,
                 mode,
+                layout,
                 ..
             } => {
                 render_listing(
@@ -84,6 +115,7 @@ impl View {
                     filter_query,
                     *is_filtering,
                     *mode,
+                    layout,
                     spinner_tick,
                 );
             }
@@ -95,14 +127,124 @@ impl View {
             } => {
                 render_status(f, branch, status, prev_state, area);
             }
+            AppState::ViewingHistory {
+                branch,
+                commits,
+                selected_index,
+                query,
+                has_more,
+                search,
+                marked,
+                prev_state,
+                ..
+            } => {
+                Self::render_background(f, prev_state, context, area, spinner_tick);
+                render_history(
+                    f,
+                    branch,
+                    commits,
+                    *selected_index,
+                    query,
+                    *has_more,
+                    search.as_deref(),
+                    marked,
+                );
+            }
+            AppState::ViewingComparison {
+                comparison,
+                scroll,
+                prev_state,
+                ..
+            } => {
+                Self::render_background(f, prev_state, context, area, spinner_tick);
+                render_comparison(f, comparison, *scroll);
+            }
+            AppState::PickingCompareTarget {
+                left,
+                options,
+                selected_index,
+                prev_state,
+                ..
+            } => {
+                Self::render_background(f, prev_state, context, area, spinner_tick);
+                render_branch_selection(
+                    f,
+                    options,
+                    *selected_index,
+                    Some(&format!("COMPARE {left} WITH")),
+                );
+            }
+            AppState::ViewingCommitDetail {
+                detail,
+                scroll,
+                prev_state,
+                ..
+            } => {
+                Self::render_background(f, prev_state, context, area, spinner_tick);
+                render_commit_detail(f, detail, *scroll);
+            }
+            AppState::PickingCherryPickTarget {
+                hashes,
+                targets,
+                selected_index,
+                prev_state,
+                ..
+            } => {
+                Self::render_background(f, prev_state, context, area, spinner_tick);
+                let names: Vec<String> = targets.iter().map(|wt| wt.branch.clone()).collect();
+                let title = match hashes.len() {
+                    1 => "CHERRY-PICK INTO".to_string(),
+                    n => format!("PORT {n} COMMITS INTO"),
+                };
+                render_branch_selection(f, &names, *selected_index, Some(&title));
+            }
+            AppState::ManagingBranches {
+                branches,
+                selected_index,
+                show_remote,
+                prev_state,
+            } => {
+                Self::render_background(f, prev_state, context, area, spinner_tick);
+                render_branch_manager(f, branches, *selected_index, *show_remote);
+            }
+            AppState::ReviewingStale {
+                candidates,
+                checked,
+                selected_index,
+                prev_state,
+            } => {
+                Self::render_background(f, prev_state, context, area, spinner_tick);
+                render_stale_review(f, candidates, checked, *selected_index);
+            }
+            AppState::EditingMetadata {
+                branch,
+                form,
+                prev_state,
+                ..
+            } => {
+                Self::render_background(f, prev_state, context, area, spinner_tick);
+                render_metadata_form(f, branch, form);
+            }
+            AppState::ViewingCommitFiles {
+                commit,
+                files,
+                selected_index,
+                prev_state,
+                ..
+            } => {
+                Self::render_background(f, prev_state, context, area, spinner_tick);
+                render_commit_files(f, commit, files, *selected_index);
+            }
             AppState::LoadingStatus { prev_state, .. }
             | AppState::LoadingHistory { prev_state, .. }
+            | AppState::LoadingCommitDetail { prev_state, .. }
             | AppState::LoadingBranches { prev_state, .. }
             | AppState::Cleaning { prev_state, .. }
             | AppState::Staging { prev_state, .. }
             | AppState::Unstaging { prev_state, .. }
             | AppState::SwitchingBranchTask { prev_state, .. }
             | AppState::GeneratingCommitMessage { prev_state, .. }
+            | AppState::LoadingComparison { prev_state, .. }
             | AppState::LoadingDiff { prev_state, .. } => {
                 Self::render_background(f, prev_state, context, area, spinner_tick);
             }
@@ -255,10 +397,20 @@ impl View {
                         {
                             match result {
                                 Ok(commits) => {
+                                    let query = HistoryQuery::default();
+                                    let has_more =
+                                        commits.iter().filter(|c| !c.hash.is_empty()).count()
+                                            >= query.limit;
                                     *state = AppState::ViewingHistory {
+                                        path,
                                         branch: branch.clone(),
                                         commits,
                                         selected_index: 0,
+                                        query,
+                                        has_more,
+                                        loading_more: false,
+                                        search: None,
+                                        marked: Vec::new(),
                                         prev_state: prev_state.clone(),
                                     };
                                 }
@@ -459,6 +611,102 @@ impl View {
                             }
                         }
                     }
+                    AsyncResult::HistoryPageFetched {
+                        path: page_path,
+                        query: page_query,
+                        result,
+                    } => {
+                        // Pages of a search that has since been replaced are dropped.
+                        if let AppState::ViewingHistory {
+                            path,
+                            commits,
+                            query,
+                            has_more,
+                            loading_more,
+                            ..
+                        } = state
+                            && *path == page_path
+                            && *query == page_query
+                            && *loading_more
+                        {
+                            *loading_more = false;
+                            match result {
+                                Ok(page) => {
+                                    *has_more = page.iter().filter(|c| !c.hash.is_empty()).count()
+                                        >= query.limit;
+                                    commits.extend(page);
+                                }
+                                Err(e) => {
+                                    *has_more = false;
+                                    *state = AppState::Error(
+                                        format!("Failed to fetch history: {e}"),
+                                        Box::new(state.clone()),
+                                    );
+                                }
+                            }
+                        }
+                    }
+                    AsyncResult::CommitDetailFetched { path, result } => {
+                        if let AppState::LoadingCommitDetail { branch, prev_state } = state {
+                            match result {
+                                Ok(detail) => {
+                                    *state = AppState::ViewingCommitDetail {
+                                        path,
+                                        branch: branch.clone(),
+                                        max_scroll: commit_detail_max_scroll(&detail),
+                                        detail: Box::new(detail),
+                                        scroll: 0,
+                                        prev_state: prev_state.clone(),
+                                    };
+                                }
+                                Err(e) => {
+                                    *state = AppState::Error(
+                                        format!("Failed to load commit: {e}"),
+                                        prev_state.clone(),
+                                    );
+                                }
+                            }
+                        }
+                    }
+                    AsyncResult::ComparisonFetched { path, result } => {
+                        if let AppState::LoadingComparison { prev_state, .. } = state {
+                            match result {
+                                Ok(comparison) => {
+                                    *state = AppState::ViewingComparison {
+                                        path,
+                                        comparison: Box::new(comparison),
+                                        scroll: 0,
+                                        prev_state: prev_state.clone(),
+                                    };
+                                }
+                                Err(e) => {
+                                    *state = AppState::Error(
+                                        format!("Comparison failed: {e}"),
+                                        prev_state.clone(),
+                                    );
+                                }
+                            }
+                        }
+                    }
+                    AsyncResult::CommitApplied { result } => {
+                        if let AppState::ApplyingCommit { prev_state, .. } = state {
+                            match result {
+                                Ok(message) => {
+                                    *state = create_timed_state(
+                                        AppState::CommitActionComplete {
+                                            message,
+                                            prev_state: prev_state.clone(),
+                                        },
+                                        *prev_state.clone(),
+                                        1200,
+                                    );
+                                }
+                                Err(e) => {
+                                    *state = AppState::Error(format!("{e}"), prev_state.clone());
+                                }
+                            }
+                        }
+                    }
                     AsyncResult::CommitMessageGenerated { result } => {
                         if let AppState::GeneratingCommitMessage { prev_state, .. } = state {
                             match result {
@@ -561,13 +809,30 @@ impl View {
                             table_state,
                             dashboard,
                             filter_query,
+                            layout,
                             ..
                         } = state
                         {
                             if let Ok(new_worktrees) = result {
+                                // Keep the cursor on the same worktree when the order changed.
+                                let selected_path = table_state
+                                    .selected()
+                                    .and_then(|i| filtered_indices.get(i))
+                                    .and_then(|&idx| worktrees.get(idx))
+                                    .map(|wt| wt.path.clone());
                                 *worktrees = new_worktrees;
-                                *filtered_indices =
-                                    crate::app::model::filter_worktrees(worktrees, filter_query);
+                                *filtered_indices = crate::app::model::listed_worktrees(
+                                    worktrees,
+                                    filter_query,
+                                    layout,
+                                );
+                                if let Some(i) = selected_path.and_then(|path| {
+                                    filtered_indices
+                                        .iter()
+                                        .position(|&idx| worktrees[idx].path == path)
+                                }) {
+                                    table_state.select(Some(i));
+                                }
                                 if table_state.selected().is_none() && !worktrees.is_empty() {
                                     table_state.select(Some(0));
                                 }
@@ -580,6 +845,27 @@ impl View {
                 }
             }
 
+            if let AppState::LaunchingEditor {
+                branch,
+                editor,
+                target,
+                prev_state,
+            } = state
+            {
+                *state = match Self::launch_editor(terminal, editor, target) {
+                    Ok(()) => create_timed_state(
+                        AppState::OpeningEditor {
+                            branch: branch.clone(),
+                            editor: editor.name.clone(),
+                            prev_state: prev_state.clone(),
+                        },
+                        *prev_state.clone(),
+                        800,
+                    ),
+                    Err(e) => AppState::Error(format!("{e:#}"), prev_state.clone()),
+                };
+            }
+
             if let AppState::Timed {
                 target_state,
                 start_time,
@@ -603,14 +889,19 @@ impl View {
                 is_filtering: _,
                 mode: _,
                 last_selection_change: _,
-                ..
+                layout,
             } = state
             {
                 if *refresh_needed == RefreshType::Full {
                     let repo_clone = repo.clone();
                     let tx = async_tx.clone();
+                    let layout = layout.clone();
                     tokio::task::spawn_blocking(move || {
-                        let res = repo_clone.list_worktrees();
+                        let res = repo_clone.list_worktrees().map(|mut worktrees| {
+                            annotate_worktrees(&repo_clone, &mut worktrees);
+                            arrange_worktrees(&repo_clone, &mut worktrees, &layout);
+                            worktrees
+                        });
                         let _ = tx.send(AsyncResult::WorktreesListed { result: res });
                     });
                     *refresh_needed = RefreshType::None;
@@ -699,14 +990,158 @@ impl View {
                         );
                     }
                     AppState::ViewingHistory {
-                        branch: _,
+                        path,
+                        branch,
                         commits,
                         selected_index,
+                        query,
+                        has_more,
+                        loading_more,
+                        search,
+                        marked,
+                        prev_state,
+                    } => {
+                        new_state = handle_history_events(
+                            &event,
+                            repo,
+                            path,
+                            branch,
+                            commits,
+                            selected_index,
+                            query,
+                            has_more,
+                            loading_more,
+                            search,
+                            marked,
+                            prev_state,
+                            &current_state_clone,
+                            &async_tx,
+                        );
+                    }
+                    AppState::PickingCompareTarget {
+                        path,
+                        left,
+                        options,
+                        selected_index,
+                        prev_state,
+                    } => {
+                        new_state = handle_compare_target_events(
+                            &event,
+                            repo,
+                            path,
+                            left,
+                            options,
+                            selected_index,
+                            prev_state,
+                            &async_tx,
+                        );
+                    }
+                    AppState::ViewingComparison {
+                        path,
+                        comparison,
+                        scroll,
+                        prev_state,
+                    } => {
+                        new_state = handle_comparison_events(
+                            &event, repo, path, comparison, scroll, prev_state, &async_tx,
+                        );
+                    }
+                    AppState::ViewingCommitDetail {
+                        path,
+                        branch,
+                        detail,
+                        scroll,
+                        max_scroll,
+                        prev_state,
+                    } => {
+                        new_state = handle_commit_detail_events(
+                            &event,
+                            repo,
+                            path,
+                            branch,
+                            detail,
+                            scroll,
+                            *max_scroll,
+                            prev_state,
+                            &current_state_clone,
+                            &async_tx,
+                        );
+                    }
+                    AppState::PickingCherryPickTarget {
+                        hashes,
+                        source,
+                        targets,
+                        selected_index,
+                        prev_state,
+                    } => {
+                        new_state = handle_cherry_pick_target_events(
+                            &event,
+                            repo,
+                            hashes,
+                            source,
+                            targets,
+                            selected_index,
+                            prev_state,
+                            &async_tx,
+                        );
+                    }
+                    AppState::ManagingBranches {
+                        branches,
+                        selected_index,
+                        show_remote,
+                        prev_state,
+                    } => {
+                        new_state = handle_branch_manager_events(
+                            &event,
+                            repo,
+                            branches,
+                            selected_index,
+                            show_remote,
+                            prev_state,
+                            &current_state_clone,
+                        );
+                    }
+                    AppState::ReviewingStale {
+                        candidates,
+                        checked,
+                        selected_index,
+                        prev_state,
+                    } => {
+                        new_state = handle_stale_events(
+                            &event,
+                            candidates,
+                            checked,
+                            selected_index,
+                            prev_state,
+                            &current_state_clone,
+                        );
+                    }
+                    AppState::EditingMetadata {
+                        path,
+                        form,
                         prev_state,
                         ..
                     } => {
-                        new_state =
-                            handle_history_events(&event, commits, selected_index, prev_state);
+                        new_state = handle_metadata_events(&event, repo, path, form, prev_state);
+                    }
+                    AppState::ViewingCommitFiles {
+                        path,
+                        branch,
+                        files,
+                        selected_index,
+                        prev_state,
+                        ..
+                    } => {
+                        new_state = handle_commit_files_events(
+                            &event,
+                            repo,
+                            path,
+                            branch,
+                            files,
+                            selected_index,
+                            prev_state,
+                            &current_state_clone,
+                        );
                     }
                     AppState::SwitchingBranch {
                         path,
@@ -738,11 +1173,12 @@ impl View {
                         branch,
                         options,
                         selected,
+                        target,
                         prev_state,
                         ..
                     } => {
                         new_state = handle_editor_events(
-                            &event, repo, branch, options, selected, prev_state,
+                            &event, repo, branch, options, selected, target, prev_state,
                         );
                     }
                     AppState::Prompting {
@@ -805,6 +1241,7 @@ impl View {
                     }
                     AppState::LoadingStatus { .. }
                     | AppState::LoadingHistory { .. }
+                    | AppState::LoadingCommitDetail { .. }
                     | AppState::LoadingBranches { .. }
                     | AppState::Cleaning { .. }
                     | AppState::Staging { .. }
@@ -813,7 +1250,9 @@ impl View {
                     | AppState::GeneratingCommitMessage { .. }
                     | AppState::LoadingDiff { .. }
                     | AppState::LoadingStashes { .. }
-                    | AppState::StashAction { .. } => {
+                    | AppState::StashAction { .. }
+                    | AppState::ApplyingCommit { .. }
+                    | AppState::LoadingComparison { .. } => {
                         // Background loading states don't have secondary event handlers
                         // But can still be exited via global q/Esc handled below
                     }
@@ -837,6 +1276,36 @@ impl View {
         }
     }
 
+    /// Runs the editor; terminal editors get the TTY while the TUI is suspended.
+    fn launch_editor(
+        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+        editor: &EditorConfig,
+        target: &EditorTarget,
+    ) -> Result<()> {
+        if !editor.terminal {
+            return crate::infrastructure::editor::launch(editor, target);
+        }
+
+        disable_raw_mode()?;
+        execute!(
+            terminal.backend_mut(),
+            LeaveAlternateScreen,
+            DisableMouseCapture
+        )?;
+        terminal.show_cursor()?;
+
+        let result = crate::infrastructure::editor::launch(editor, target);
+
+        enable_raw_mode()?;
+        execute!(
+            terminal.backend_mut(),
+            EnterAlternateScreen,
+            EnableMouseCapture
+        )?;
+        terminal.clear()?;
+        result
+    }
+
     fn fetch_dashboard_data<R: ProjectRepository + Clone + Send + Sync + 'static>(
         repo: &R,
         worktrees: &[Worktree],
@@ -858,29 +1327,29 @@ impl View {
                 // If we already have data or are loading, don't fetch again
                 // But if we switched tabs, we might need different data
                 match dashboard.active_tab {
-                    crate::app::model::DashboardTab::Status => {
-                        if dashboard.cached_status.is_none() && !dashboard.loading {
-                            dashboard.loading = true;
-                            let repo_clone = repo.clone();
-                            let path = wt.path.clone();
-                            let tx = async_tx.clone();
-                            tokio::task::spawn_blocking(move || {
-                                let result = repo_clone.get_status(&path);
-                                let _ = tx.send(AsyncResult::StatusFetched { path, result });
-                            });
-                        }
+                    crate::app::model::DashboardTab::Status
+                        if dashboard.cached_status.is_none() && !dashboard.loading =>
+                    {
+                        dashboard.loading = true;
+                        let repo_clone = repo.clone();
+                        let path = wt.path.clone();
+                        let tx = async_tx.clone();
+                        tokio::task::spawn_blocking(move || {
+                            let result = repo_clone.get_status(&path);
+                            let _ = tx.send(AsyncResult::StatusFetched { path, result });
+                        });
                     }
-                    crate::app::model::DashboardTab::Log => {
-                        if dashboard.cached_history.is_none() && !dashboard.loading {
-                            dashboard.loading = true;
-                            let repo_clone = repo.clone();
-                            let path = wt.path.clone();
-                            let tx = async_tx.clone();
-                            tokio::task::spawn_blocking(move || {
-                                let result = repo_clone.get_history(&path, 10);
-                                let _ = tx.send(AsyncResult::HistoryFetched { path, result });
-                            });
-                        }
+                    crate::app::model::DashboardTab::Log
+                        if dashboard.cached_history.is_none() && !dashboard.loading =>
+                    {
+                        dashboard.loading = true;
+                        let repo_clone = repo.clone();
+                        let path = wt.path.clone();
+                        let tx = async_tx.clone();
+                        tokio::task::spawn_blocking(move || {
+                            let result = repo_clone.get_history(&path, &HistoryQuery::latest(10));
+                            let _ = tx.send(AsyncResult::HistoryFetched { path, result });
+                        });
                     }
                     _ => {}
                 }
@@ -914,7 +1383,7 @@ impl View {
             )
             .split(f.area());
 
-        let context = render_context.context;
+        let context = &render_context.context;
         f.render_widget(
             HeaderWidget {
                 context,
@@ -934,6 +1403,7 @@ impl View {
                 filter_query,
                 is_filtering,
                 mode,
+                layout,
                 ..
             } => {
                 render_listing(
@@ -949,6 +1419,7 @@ impl View {
                     filter_query,
                     *is_filtering,
                     *mode,
+                    layout,
                     spinner_tick,
                 );
             }
@@ -964,11 +1435,109 @@ impl View {
                 branch,
                 commits,
                 selected_index,
+                query,
+                has_more,
+                search,
+                marked,
                 prev_state,
                 ..
             } => {
                 Self::render_background(f, prev_state, context, chunks[1], spinner_tick);
-                render_history(f, branch, commits, *selected_index);
+                render_history(
+                    f,
+                    branch,
+                    commits,
+                    *selected_index,
+                    query,
+                    *has_more,
+                    search.as_deref(),
+                    marked,
+                );
+            }
+            AppState::ViewingComparison {
+                comparison,
+                scroll,
+                prev_state,
+                ..
+            } => {
+                Self::render_background(f, prev_state, context, chunks[1], spinner_tick);
+                render_comparison(f, comparison, *scroll);
+            }
+            AppState::PickingCompareTarget {
+                left,
+                options,
+                selected_index,
+                prev_state,
+                ..
+            } => {
+                Self::render_background(f, prev_state, context, chunks[1], spinner_tick);
+                render_branch_selection(
+                    f,
+                    options,
+                    *selected_index,
+                    Some(&format!("COMPARE {left} WITH")),
+                );
+            }
+            AppState::ViewingCommitDetail {
+                detail,
+                scroll,
+                prev_state,
+                ..
+            } => {
+                Self::render_background(f, prev_state, context, chunks[1], spinner_tick);
+                render_commit_detail(f, detail, *scroll);
+            }
+            AppState::PickingCherryPickTarget {
+                hashes,
+                targets,
+                selected_index,
+                prev_state,
+                ..
+            } => {
+                Self::render_background(f, prev_state, context, chunks[1], spinner_tick);
+                let names: Vec<String> = targets.iter().map(|wt| wt.branch.clone()).collect();
+                let title = match hashes.len() {
+                    1 => "CHERRY-PICK INTO".to_string(),
+                    n => format!("PORT {n} COMMITS INTO"),
+                };
+                render_branch_selection(f, &names, *selected_index, Some(&title));
+            }
+            AppState::ManagingBranches {
+                branches,
+                selected_index,
+                show_remote,
+                prev_state,
+            } => {
+                Self::render_background(f, prev_state, context, chunks[1], spinner_tick);
+                render_branch_manager(f, branches, *selected_index, *show_remote);
+            }
+            AppState::ReviewingStale {
+                candidates,
+                checked,
+                selected_index,
+                prev_state,
+            } => {
+                Self::render_background(f, prev_state, context, chunks[1], spinner_tick);
+                render_stale_review(f, candidates, checked, *selected_index);
+            }
+            AppState::EditingMetadata {
+                branch,
+                form,
+                prev_state,
+                ..
+            } => {
+                Self::render_background(f, prev_state, context, chunks[1], spinner_tick);
+                render_metadata_form(f, branch, form);
+            }
+            AppState::ViewingCommitFiles {
+                commit,
+                files,
+                selected_index,
+                prev_state,
+                ..
+            } => {
+                Self::render_background(f, prev_state, context, chunks[1], spinner_tick);
+                render_commit_files(f, commit, files, *selected_index);
             }
             AppState::SwitchingBranch {
                 branches,
@@ -991,12 +1560,13 @@ impl View {
             AppState::SelectingEditor {
                 branch,
                 options,
+                installed,
                 selected,
                 prev_state,
                 ..
             } => {
                 Self::render_background(f, prev_state, context, chunks[1], spinner_tick);
-                render_editor_selection(f, branch, options, *selected);
+                render_editor_selection(f, branch, options, *installed, *selected);
             }
             AppState::Prompting {
                 prompt_type,
@@ -1019,8 +1589,15 @@ impl View {
             AppState::ViewingStashes { .. } => {
                 StashListWidget::render(f, chunks[1], display_state);
             }
+            AppState::ApplyingCommit { prev_state, .. }
+            | AppState::CommitActionComplete { prev_state, .. }
+            | AppState::LoadingComparison { prev_state, .. } => {
+                Self::render_background(f, prev_state, context, chunks[1], spinner_tick);
+                render_modals(f, repo, display_state, spinner_tick);
+            }
             AppState::LoadingStatus { .. }
             | AppState::LoadingHistory { .. }
+            | AppState::LoadingCommitDetail { .. }
             | AppState::LoadingBranches { .. }
             | AppState::Cleaning { .. }
             | AppState::Staging { .. }
@@ -1087,8 +1664,62 @@ mod tests {
             Ok(())
         }
 
+        fn check_sync(&self, _path: &str) -> anyhow::Result<Vec<crate::domain::sync::SyncDrift>> {
+            Ok(Vec::new())
+        }
+
+        fn fix_sync(&self, _path: &str, _destinations: &[String]) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn sync_diff(&self, _path: &str, _destination: &str) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+        fn worktree_env(&self, _path: &str) -> anyhow::Result<Vec<(String, String)>> {
+            Ok(Vec::new())
+        }
+        fn add_detached_worktree(&self, _path: &str, _base: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn sync_entries(&self, _path: &str, _entries: &[String]) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn lookup_issue(&self, id: &str) -> anyhow::Result<crate::domain::issues::IssueLink> {
+            Ok(crate::domain::issues::IssueLink {
+                id: id.to_string(),
+                url: None,
+                title: None,
+            })
+        }
+        fn worktree_metadata(
+            &self,
+            _path: &str,
+        ) -> anyhow::Result<crate::domain::repository::WorktreeMetadata> {
+            Ok(crate::domain::repository::WorktreeMetadata::default())
+        }
+
+        fn update_worktree_metadata(
+            &self,
+            _path: &str,
+            _metadata: &crate::domain::repository::WorktreeMetadata,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn list_layout(&self) -> crate::domain::list_layout::ListLayout {
+            crate::domain::list_layout::ListLayout::default()
+        }
+        fn save_list_layout(
+            &self,
+            _layout: &crate::domain::list_layout::ListLayout,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn expired_worktrees(&self, _now: i64) -> anyhow::Result<Vec<String>> {
+            Ok(Vec::new())
+        }
         fn detect_context(&self, _base_path: &std::path::Path) -> ProjectContext {
-            ProjectContext::Standard
+            ProjectContext::default()
         }
 
         fn get_preferred_editor(&self) -> anyhow::Result<Option<String>> {
@@ -1098,16 +1729,37 @@ mod tests {
         fn set_preferred_editor(&self, _editor: &str) -> anyhow::Result<()> {
             Ok(())
         }
+        fn list_editors(&self) -> anyhow::Result<Vec<crate::domain::repository::EditorConfig>> {
+            Ok(crate::domain::repository::EditorConfig::defaults())
+        }
+        fn get_project_config(&self) -> anyhow::Result<crate::domain::repository::ProjectConfig> {
+            Ok(crate::domain::repository::ProjectConfig::default())
+        }
+        fn save_project_config(
+            &self,
+            _config: &crate::domain::repository::ProjectConfig,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
 
-        fn fetch(&self, _path: &str) -> anyhow::Result<()> {
+        fn fetch(&self, _path: &str, _remote: Option<&str>) -> anyhow::Result<()> {
             Ok(())
         }
 
-        fn pull(&self, _path: &str) -> anyhow::Result<()> {
+        fn pull(&self, _path: &str, _remote: Option<&str>) -> anyhow::Result<()> {
             Ok(())
         }
 
-        fn push(&self, _path: &str) -> anyhow::Result<()> {
+        fn push(&self, _path: &str, _remote: Option<&str>) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn list_remotes(&self) -> anyhow::Result<Vec<crate::domain::repository::Remote>> {
+            Ok(vec![])
+        }
+        fn add_remote(&self, _name: &str, _url: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn remove_remote(&self, _name: &str) -> anyhow::Result<()> {
             Ok(())
         }
 
@@ -1147,13 +1799,125 @@ mod tests {
             Ok("feat: test commit".to_string())
         }
 
-        fn get_history(&self, _path: &str, _limit: usize) -> anyhow::Result<Vec<GitCommit>> {
+        fn get_history(
+            &self,
+            _path: &str,
+            _query: &crate::domain::repository::HistoryQuery,
+        ) -> anyhow::Result<Vec<GitCommit>> {
+            Ok(vec![])
+        }
+        fn get_commit_files(
+            &self,
+            _path: &str,
+            _hash: &str,
+        ) -> anyhow::Result<Vec<crate::domain::repository::ChangedFile>> {
             Ok(vec![])
         }
+        fn get_commit_detail(
+            &self,
+            _path: &str,
+            hash: &str,
+        ) -> anyhow::Result<crate::domain::repository::CommitDetail> {
+            Ok(crate::domain::repository::CommitDetail {
+                hash: hash.to_string(),
+                author: String::new(),
+                author_date: String::new(),
+                committer: String::new(),
+                committer_date: String::new(),
+                parents: vec![],
+                refs: vec![],
+                subject: String::new(),
+                body: String::new(),
+                files: vec![],
+                diff: String::new(),
+            })
+        }
+        fn cherry_pick(&self, _path: &str, _hash: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn revert_commit(&self, _path: &str, _hash: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn resolve_commit_range(&self, _path: &str, range: &str) -> anyhow::Result<Vec<String>> {
+            Ok(range.split("..").map(String::from).collect())
+        }
+        fn check_droppable(&self, _path: &str, _hashes: &[String]) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn drop_commits(&self, _path: &str, _hashes: &[String]) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn compare_refs(
+            &self,
+            _path: &str,
+            left: &str,
+            right: &str,
+        ) -> anyhow::Result<crate::domain::repository::RefComparison> {
+            Ok(crate::domain::repository::RefComparison {
+                left: left.to_string(),
+                right: right.to_string(),
+                merge_base: None,
+                left_only: vec![],
+                right_only: vec![],
+                files: vec![],
+                diff: String::new(),
+            })
+        }
+        fn first_changed_line(
+            &self,
+            _path: &str,
+            _file: &str,
+            _staged: bool,
+        ) -> anyhow::Result<Option<usize>> {
+            Ok(None)
+        }
 
         fn list_branches(&self) -> anyhow::Result<Vec<String>> {
             Ok(vec![])
         }
+        fn list_branch_info(&self) -> anyhow::Result<Vec<crate::domain::repository::BranchInfo>> {
+            Ok(vec![])
+        }
+        fn create_branch(&self, _name: &str, _base: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn rename_branch(&self, _from: &str, _to: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn delete_branch(&self, _name: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn branch_unmerged_into(&self, _branch: &str) -> anyhow::Result<Option<String>> {
+            Ok(None)
+        }
+        fn set_upstream(&self, _branch: &str, _upstream: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn default_branch(&self) -> anyhow::Result<String> {
+            Ok("main".to_string())
+        }
+        fn merged_branches(&self, _target: &str) -> anyhow::Result<Vec<String>> {
+            Ok(vec!["main".to_string()])
+        }
+        fn last_activity(&self, _path: &str) -> anyhow::Result<i64> {
+            Ok(0)
+        }
+        fn scan_artifacts(
+            &self,
+            _path: &str,
+        ) -> anyhow::Result<Vec<crate::domain::artifacts::Artifact>> {
+            Ok(vec![])
+        }
+        fn remove_artifacts(
+            &self,
+            _path: &str,
+            _artifacts: &[crate::domain::artifacts::Artifact],
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn cache_status(&self) -> anyhow::Result<Vec<crate::domain::cache::CacheStatus>> {
+            Ok(vec![])
+        }
 
         fn switch_branch(&self, _path: &str, _branch: &str) -> anyhow::Result<()> {
             Ok(())
@@ -1244,7 +2008,7 @@ mod tests {
         let mut state = AppState::Welcome;
         let render_context = RenderContext {
             project_name: "test-project".to_string(),
-            context: ProjectContext::Standard,
+            context: ProjectContext::default(),
         };
 
         terminal
@@ -1289,6 +2053,9 @@ mod tests {
                 status_summary: Some("clean".to_string()),
                 size_bytes: 1024,
                 metadata: None,
+                upstream: None,
+                contexts: Vec::new(),
+                sync_drift: Vec::new(),
             },
             Worktree {
                 path: "/test/dev".to_string(),
@@ -1299,6 +2066,9 @@ mod tests {
                 status_summary: Some("+2 ~1".to_string()),
                 size_bytes: 2048,
                 metadata: None,
+                upstream: None,
+                contexts: Vec::new(),
+                sync_drift: Vec::new(),
             },
         ];
 
@@ -1321,10 +2091,11 @@ mod tests {
             is_filtering: false,
             mode: crate::app::model::AppMode::Normal,
             last_selection_change: std::time::Instant::now(),
+            layout: crate::domain::list_layout::ListLayout::default(),
         };
         let render_context = RenderContext {
             project_name: "test-project".to_string(),
-            context: ProjectContext::Standard,
+            context: ProjectContext::default(),
         };
 
         terminal
@@ -1360,7 +2131,7 @@ mod tests {
         let mut state = AppState::Error("Test error message".to_string(), prev_state);
         let render_context = RenderContext {
             project_name: "test-project".to_string(),
-            context: ProjectContext::Standard,
+            context: ProjectContext::default(),
         };
 
         terminal
@@ -1403,7 +2174,7 @@ mod tests {
         };
         let render_context = RenderContext {
             project_name: "test-project".to_string(),
-            context: ProjectContext::Standard,
+            context: ProjectContext::default(),
         };
 
         terminal
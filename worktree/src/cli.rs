@@ -1,6 +1,10 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use clap_complete::Shell;
 
+use crate::domain::list_layout::{GroupMode, SortMode};
+use crate::infrastructure::completion::CompletionKind;
+use crate::infrastructure::shell_integration::ShellKind;
+
 #[derive(Parser)]
 #[command(name = "worktree")]
 #[command(
@@ -39,11 +43,37 @@ pub enum Commands {
     /// Add a new worktree for a specific intent/feature
     ///
     /// Example: worktree add feature-login
+    /// Example: worktree add fix-docs --remote upstream
+    /// Example: worktree add --issue PROJ-123
+    /// Example: worktree add --template hotfix login-crash
+    /// Example: worktree add spike-cache --ttl 3d
     Add {
         /// The name/intent of the worktree (e.g., dev, main, feat-x)
-        intent: String,
-        /// The branch to track (defaults to intent name)
+        #[arg(required_unless_present = "issue")]
+        intent: Option<String>,
+        /// The branch to track (defaults to intent name); `upstream/<branch>` tracks that remote
         branch: Option<String>,
+        /// Track the branch on this remote (e.g. upstream) instead of letting git pick one
+        #[arg(short, long)]
+        remote: Option<String>,
+        /// What the worktree is for, shown in the list instead of its directory name
+        #[arg(long)]
+        purpose: Option<String>,
+        /// Color for the worktree in the TUI: a name such as `cyan` or `#rrggbb`
+        #[arg(long)]
+        color: Option<String>,
+        /// Icon (any short text or emoji) shown next to the worktree in the TUI
+        #[arg(long)]
+        icon: Option<String>,
+        /// Ticket to work on; names the branch and worktree from the `issues` templates
+        #[arg(long)]
+        issue: Option<String>,
+        /// Set the worktree up from a `templates:` entry in .worktrees.yaml
+        #[arg(long, conflicts_with_all = ["issue", "remote"])]
+        template: Option<String>,
+        /// Expire the worktree after this long (e.g. 3d, 2w); see `clean --expired`
+        #[arg(long, value_name = "AGE", value_parser = crate::domain::stale::parse_days)]
+        ttl: Option<u64>,
     },
     /// Remove an existing worktree and its associated files
     ///
@@ -56,7 +86,18 @@ pub enum Commands {
         force: bool,
     },
     /// List all active worktrees and their status
-    List,
+    ///
+    /// Uses the sort order and grouping saved from the TUI unless overridden.
+    ///
+    /// Example: worktree list --sort activity --group prefix
+    List {
+        /// Order within each group (pinned worktrees always come first)
+        #[arg(long, value_enum)]
+        sort: Option<SortArg>,
+        /// Group by branch prefix (`feat/`, `fix/`) or purpose
+        #[arg(long, value_enum)]
+        group: Option<GroupArg>,
+    },
     /// Run a command in a temporary worktree and remove it afterward
     ///
     /// Example: worktree run temp-check "cargo test"
@@ -70,12 +111,35 @@ pub enum Commands {
         #[arg(required = true, num_args = 1..)]
         command: Vec<String>,
     },
+    /// Run a command inside an existing worktree with its environment
+    ///
+    /// The environment merges the hub `.env`, shared cache settings, allocated ports, the
+    /// worktree's own `env` metadata and `WORKTREE_NAME`, `WORKTREE_BRANCH`, `WORKTREE_ROOT`.
+    ///
+    /// Example: worktree exec feature-login -- npm run dev
+    Exec {
+        /// The worktree to run in
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        name: Option<String>,
+        /// Run in every worktree, one after another
+        #[arg(long)]
+        all: bool,
+        /// The command and its arguments, after `--`
+        #[arg(last = true, required = true)]
+        command: Vec<String>,
+    },
     /// Synchronize configuration files to a specific worktree or all worktrees
     ///
-    /// Example: worktree sync feature-login
+    /// Example: worktree sync feature-login --check
     Sync {
         /// The name of the worktree to sync (omit to sync all)
         intent: Option<String>,
+        /// Report synced entries that are missing, outdated, modified locally or broken
+        #[arg(long)]
+        check: bool,
+        /// Re-sync drifted entries, showing a diff before overwriting local changes
+        #[arg(long)]
+        fix: bool,
     },
     /// Push changes to the remote repository
     ///
@@ -83,6 +147,19 @@ pub enum Commands {
     Push {
         /// The name of the worktree to push (defaults to current directory if valid worktree, or fails)
         intent: Option<String>,
+        /// Push the current branch to this remote instead of its upstream
+        #[arg(short, long)]
+        remote: Option<String>,
+    },
+    /// Pull changes into a worktree
+    ///
+    /// Example: worktree pull main --remote upstream
+    Pull {
+        /// The name of the worktree to pull
+        intent: Option<String>,
+        /// Pull the same-named branch from this remote instead of the upstream
+        #[arg(short, long)]
+        remote: Option<String>,
     },
     /// Configuration management
     Config {
@@ -95,14 +172,39 @@ pub enum Commands {
     Setup,
     /// Clean up stale worktrees (directories with missing metadata or deleted branches)
     ///
+    /// With --merged, --gone, --expired or --older-than, removes worktrees matching any of those
+    /// checks instead. Worktrees with uncommitted changes are only removed after confirmation;
+    /// with --expired, --json or --quiet they are kept unless --force is given.
+    ///
+    /// With --artifacts, deletes untracked build output found by the Cargo, npm/pnpm, Gradle,
+    /// Maven, Python, .NET and Go detectors plus `artifacts.include` globs in .worktrees.yaml.
+    ///
     /// Example: worktree clean --dry-run
+    /// Example: worktree clean --merged --older-than 30d
+    /// Example: worktree clean --expired
+    /// Example: worktree clean --artifacts --older-than 14d --dry-run
     Clean {
         /// Show what would be deleted without actually removing anything
         #[arg(long)]
         dry_run: bool,
-        /// Remove build artifacts (`node_modules`, target, build, etc.) from inactive worktrees
-        #[arg(long)]
+        /// Remove build artifacts (`node_modules`, target, .venv, etc.) from the other worktrees
+        #[arg(long, conflicts_with_all = ["merged", "gone", "expired", "force"])]
         artifacts: bool,
+        /// Worktrees whose branch is merged into the default branch
+        #[arg(long)]
+        merged: bool,
+        /// Worktrees whose upstream branch was deleted on the remote
+        #[arg(long)]
+        gone: bool,
+        /// Worktrees past the expiry set by `add --ttl` or a template; dirty ones need --force
+        #[arg(long)]
+        expired: bool,
+        /// Worktrees untouched for this long (e.g. 30d, 2w); limits --artifacts too
+        #[arg(long, value_name = "AGE", value_parser = crate::domain::stale::parse_days)]
+        older_than: Option<u64>,
+        /// Remove candidates with uncommitted changes without asking
+        #[arg(short, long)]
+        force: bool,
     },
     /// Switch to a worktree by name (prints path to stdout for shell integration)
     ///
@@ -114,6 +216,72 @@ pub enum Commands {
         #[arg(short, long)]
         copy: bool,
     },
+    /// Open a worktree (or a file inside it) in an editor
+    ///
+    /// Uses the project's preferred editor, then the global one, then $VISUAL/$EDITOR.
+    ///
+    /// Example: worktree edit feat-login --file src/main.rs --line 42
+    Edit {
+        /// The name or branch of the worktree to open
+        name: String,
+        /// Editor name from the editor list, or a command line such as "code --wait"
+        #[arg(short, long)]
+        editor: Option<String>,
+        /// File to open, relative to the worktree root
+        #[arg(short, long)]
+        file: Option<String>,
+        /// Line to jump to (requires --file)
+        #[arg(short, long, requires = "file")]
+        line: Option<usize>,
+        /// Remember --editor as this project's preferred editor
+        #[arg(long, requires = "editor")]
+        save: bool,
+    },
+    /// Show a worktree's commit history, optionally filtered
+    ///
+    /// Filters follow `git log` semantics and can be combined.
+    ///
+    /// Example: worktree log feat-login --author jane --since "2 weeks ago" --json
+    Log {
+        /// The name or branch of the worktree
+        name: String,
+        /// Only commits whose message matches this pattern (case-insensitive)
+        #[arg(short, long)]
+        grep: Option<String>,
+        /// Only commits by a matching author
+        #[arg(short, long)]
+        author: Option<String>,
+        /// Only commits touching this path, relative to the worktree root
+        #[arg(short, long)]
+        path: Option<String>,
+        /// Only commits after this date (e.g. 2026-01-01 or "2 weeks ago")
+        #[arg(long)]
+        since: Option<String>,
+        /// Only commits before this date
+        #[arg(long)]
+        until: Option<String>,
+        /// Maximum number of commits to show
+        #[arg(short = 'n', long, default_value_t = 50)]
+        limit: usize,
+        /// Number of matching commits to skip (for paging)
+        #[arg(long, default_value_t = 0)]
+        skip: usize,
+    },
+    /// Compare two worktrees (or a worktree and any ref)
+    ///
+    /// Lists the commits unique to each side, their merge base, and what merging the first
+    /// into the second would change.
+    ///
+    /// Example: worktree compare feature-x dev
+    Compare {
+        /// Worktree name, branch, tag or commit
+        left: String,
+        /// Worktree name, branch, tag or commit to compare against
+        right: String,
+        /// Print the aggregate patch after the summary
+        #[arg(short, long)]
+        patch: bool,
+    },
     /// Convert an existing standard repository into a bare hub structure
     ///
     /// This will move the .git folder to .bare and create a new hub directory.
@@ -176,6 +344,277 @@ pub enum Commands {
         /// The name or branch of the target worktree
         target: String,
     },
+    /// Cherry-pick committed work into another worktree
+    ///
+    /// Accepts a single commit or a range (`A..B`, `A^!`); merges are skipped. On conflict the
+    /// cherry-pick is left in progress in the target and explained.
+    ///
+    /// Example: worktree port main..HEAD --to dev --remove
+    Port {
+        /// Commit or range to port, resolved in the source worktree
+        range: String,
+        /// The name or branch of the worktree to apply the commits to
+        #[arg(short, long)]
+        to: String,
+        /// The worktree the commits come from (defaults to the current directory)
+        #[arg(short, long)]
+        from: Option<String>,
+        /// Remove the commits from the source branch once all of them applied cleanly
+        #[arg(long)]
+        remove: bool,
+    },
+    /// Manage local and remote branches
+    ///
+    /// Without a subcommand, lists local branches with their tip, upstream, ahead/behind, last
+    /// commit date and the worktree that has them checked out.
+    ///
+    /// Example: worktree branch delete feat-old
+    Branch {
+        #[command(subcommand)]
+        action: Option<BranchAction>,
+    },
+    /// Show or edit a worktree's metadata (purpose, description, color, icon, env)
+    ///
+    /// Without a subcommand, prints all of it.
+    ///
+    /// Example: worktree meta feature-login set purpose "Login redesign"
+    Meta {
+        /// The worktree to inspect or edit
+        name: String,
+        #[command(subcommand)]
+        action: Option<MetaAction>,
+    },
+    /// Manage the hub's remotes
+    ///
+    /// Without a subcommand, lists remotes with their fetch and push URLs.
+    ///
+    /// Example: worktree remote add upstream git@github.com:org/project.git
+    Remote {
+        #[command(subcommand)]
+        action: Option<RemoteAction>,
+    },
+    /// Inspect the build caches shared between worktrees
+    ///
+    /// Caches are set up when a worktree is added: a shared Cargo target dir (or sccache), a
+    /// shared pnpm store and the Gradle build cache. Configure or opt out with `cache:` in
+    /// .worktrees.yaml.
+    ///
+    /// Example: worktree cache status
+    Cache {
+        #[command(subcommand)]
+        action: Option<CacheAction>,
+    },
+    /// List the worktree templates defined in .worktrees.yaml
+    ///
+    /// Each template is validated; problems are listed under it and make the command fail.
+    ///
+    /// Example: worktree template list
+    Template {
+        #[command(subcommand)]
+        action: Option<TemplateAction>,
+    },
+    /// Print the shell wrapper that lets `worktree switch` / `wt` change directory
+    ///
+    /// Example: eval "$(worktree shell-init zsh)"
+    ShellInit {
+        /// The shell to generate the integration for
+        #[arg(value_enum)]
+        shell: ShellKind,
+    },
+    /// Print a compact prompt segment (name, dirty marker, ahead/behind) for the current worktree
+    ///
+    /// Example: worktree prompt --timeout-ms 100
+    Prompt {
+        /// Give up on git status after this many milliseconds and print only the name
+        #[arg(long, default_value_t = 150)]
+        timeout_ms: u64,
+    },
+    /// Apply, pop or drop a stash of the current worktree
+    ///
+    /// Example: worktree stash pop 1
+    Stash {
+        #[command(subcommand)]
+        action: StashAction,
+    },
+    /// Warp terminal integration
+    Warp {
+        #[command(subcommand)]
+        action: WarpAction,
+    },
+}
+
+/// Name of the hidden dynamic-completion entry point.
+///
+/// It is parsed separately from [`Cli`] because clap's bash generator uses `__` as its
+/// subcommand path separator and cannot cope with a subcommand of this name.
+pub const COMPLETE_COMMAND: &str = "__complete";
+
+/// Arguments of `worktree __complete` (used by the shell completion scripts).
+#[derive(Parser, Debug)]
+#[command(name = "__complete")]
+pub struct CompleteArgs {
+    /// Enumerate this kind directly instead of inferring it from the command line
+    #[arg(long, value_enum)]
+    pub kind: Option<CompletionKind>,
+    /// Index of the word under the cursor (defaults to the last word)
+    #[arg(long)]
+    pub cword: Option<usize>,
+    /// The command line being completed, program name first
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    pub words: Vec<String>,
+}
+
+#[derive(Subcommand)]
+pub enum WarpAction {
+    /// Regenerate .warp/workflows/worktrees.yaml from the current CLI definition
+    ///
+    /// Example: worktree warp sync
+    Sync,
+}
+
+#[derive(Subcommand)]
+pub enum BranchAction {
+    /// List branches with tip, upstream, ahead/behind, date and worktree
+    ///
+    /// Example: worktree branch list --all --json
+    List {
+        /// Include remote-tracking branches
+        #[arg(short, long)]
+        all: bool,
+    },
+    /// Create a branch without checking it out
+    ///
+    /// Example: worktree branch create feat-x --base main
+    Create {
+        /// Name of the new branch
+        name: String,
+        /// Commit or branch to start from (defaults to HEAD)
+        #[arg(short, long, default_value = "HEAD")]
+        base: String,
+    },
+    /// Rename a local branch
+    ///
+    /// Example: worktree branch rename feat-x feat-login
+    Rename {
+        /// The current name
+        old: String,
+        /// The new name
+        new: String,
+    },
+    /// Delete a local branch (refuses unmerged branches unless forced)
+    ///
+    /// Example: worktree branch delete feat-old
+    Delete {
+        /// The branch to delete
+        name: String,
+        /// Delete even if it has commits missing from its upstream or the default branch
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Set the upstream a local branch tracks
+    ///
+    /// Example: worktree branch set-upstream feat-x origin/feat-x
+    SetUpstream {
+        /// The local branch
+        branch: String,
+        /// The remote-tracking branch to track, e.g. origin/feat-x
+        upstream: String,
+    },
+    /// Check a branch out in a new worktree (remote branches get a local tracking branch)
+    ///
+    /// Example: worktree branch open origin/feat-x
+    Open {
+        /// The local or remote-tracking branch
+        branch: String,
+        /// Name of the worktree (defaults to the branch name without its remote)
+        #[arg(short, long)]
+        name: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum StashAction {
+    /// Apply a stash and keep it
+    Apply {
+        /// The stash index, as in `stash@{1}` (defaults to the latest)
+        #[arg(default_value_t = 0)]
+        index: usize,
+    },
+    /// Apply a stash and drop it
+    Pop {
+        /// The stash index, as in `stash@{1}` (defaults to the latest)
+        #[arg(default_value_t = 0)]
+        index: usize,
+    },
+    /// Delete a stash
+    Drop {
+        /// The stash index, as in `stash@{1}` (defaults to the latest)
+        #[arg(default_value_t = 0)]
+        index: usize,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CacheAction {
+    /// Show each shared cache, its size and the worktrees using it
+    Status,
+}
+
+#[derive(Subcommand)]
+pub enum TemplateAction {
+    /// Show each template and what is wrong with it, if anything
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum MetaAction {
+    /// Print one field, or all of them
+    Get {
+        /// purpose, description, color, icon, pinned, created_at or env.<KEY>
+        key: Option<String>,
+    },
+    /// Set a field
+    ///
+    /// Example: worktree meta feature-login set env.API_URL http://localhost:8080
+    Set {
+        /// purpose, description, color, icon, pinned (true/false) or env.<KEY>
+        key: String,
+        value: String,
+    },
+    /// Clear a field
+    Unset {
+        /// purpose, description, color, icon, pinned or env.<KEY>
+        key: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RemoteAction {
+    /// List remotes with their fetch and push URLs
+    List,
+    /// Add a remote and fetch its branches
+    ///
+    /// Example: worktree remote add upstream git@github.com:org/project.git
+    Add {
+        /// Name of the remote, e.g. upstream
+        name: String,
+        /// URL to fetch from and push to
+        url: String,
+    },
+    /// Remove a remote and its remote-tracking branches
+    ///
+    /// Example: worktree remote remove upstream
+    Remove {
+        /// The remote to remove
+        name: String,
+    },
+    /// Fetch one remote (or all of them) and prune deleted branches
+    ///
+    /// Example: worktree remote fetch upstream
+    Fetch {
+        /// The remote to fetch (defaults to all remotes)
+        name: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -189,6 +628,56 @@ pub enum ConfigAction {
     GetKey,
 }
 
+/// `list --sort` values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SortArg {
+    /// The order of `git worktree list`
+    Default,
+    /// Worktree name, A to Z
+    Name,
+    /// Most recently committed, checked out or staged first
+    Activity,
+    /// Largest first
+    Size,
+    /// Uncommitted changes first
+    Dirty,
+    /// Most commits ahead of and behind the upstream first
+    AheadBehind,
+}
+
+impl From<SortArg> for SortMode {
+    fn from(arg: SortArg) -> Self {
+        match arg {
+            SortArg::Default => Self::Default,
+            SortArg::Name => Self::Name,
+            SortArg::Activity => Self::Activity,
+            SortArg::Size => Self::Size,
+            SortArg::Dirty => Self::Dirty,
+            SortArg::AheadBehind => Self::AheadBehind,
+        }
+    }
+}
+
+/// `list --group` values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GroupArg {
+    None,
+    /// The branch name up to the first `/`
+    Prefix,
+    /// The `purpose` metadata field
+    Purpose,
+}
+
+impl From<GroupArg> for GroupMode {
+    fn from(arg: GroupArg) -> Self {
+        match arg {
+            GroupArg::None => Self::None,
+            GroupArg::Prefix => Self::Prefix,
+            GroupArg::Purpose => Self::Purpose,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -218,8 +707,8 @@ mod tests {
             .command
             .ok_or_else(|| anyhow::anyhow!("Missing command"))?
         {
-            Commands::Add { intent, branch } => {
-                assert_eq!(intent, "feat");
+            Commands::Add { intent, branch, .. } => {
+                assert_eq!(intent.as_deref(), Some("feat"));
                 assert_eq!(branch, Some("branch".to_string()));
             }
             _ => anyhow::bail!("Expected Add"),
@@ -231,7 +720,28 @@ mod tests {
         assert!(matches!(
             cli.command
                 .ok_or_else(|| anyhow::anyhow!("Missing command"))?,
-            Commands::List
+            Commands::List {
+                sort: None,
+                group: None
+            }
+        ));
+
+        let cli = Cli::try_parse_from([
+            "worktree",
+            "list",
+            "--sort",
+            "ahead-behind",
+            "--group",
+            "prefix",
+        ])
+        .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        assert!(matches!(
+            cli.command
+                .ok_or_else(|| anyhow::anyhow!("Missing command"))?,
+            Commands::List {
+                sort: Some(SortArg::AheadBehind),
+                group: Some(GroupArg::Prefix)
+            }
         ));
         Ok(())
     }
@@ -245,10 +755,7 @@ mod tests {
             .command
             .ok_or_else(|| anyhow::anyhow!("Missing command"))?
         {
-            Commands::Clean {
-                dry_run,
-                artifacts: _,
-            } => {
+            Commands::Clean { dry_run, .. } => {
                 assert!(dry_run);
             }
             _ => anyhow::bail!("Expected Clean"),
@@ -261,28 +768,53 @@ mod tests {
             .command
             .ok_or_else(|| anyhow::anyhow!("Missing command"))?
         {
-            Commands::Clean {
-                dry_run,
-                artifacts: _,
-            } => {
+            Commands::Clean { dry_run, .. } => {
                 assert!(!dry_run);
             }
             _ => anyhow::bail!("Expected Clean"),
         }
 
         // Test clean with artifacts
-        let cli = Cli::try_parse_from(["worktree", "clean", "--artifacts"])
+        let cli = Cli::try_parse_from(["worktree", "clean", "--artifacts", "--older-than", "14d"])
             .map_err(|e| anyhow::anyhow!(e.to_string()))?;
         match cli
             .command
             .ok_or_else(|| anyhow::anyhow!("Missing command"))?
         {
-            Commands::Clean { dry_run, artifacts } => {
+            Commands::Clean {
+                dry_run,
+                artifacts,
+                older_than,
+                ..
+            } => {
                 assert!(!dry_run);
                 assert!(artifacts);
+                assert_eq!(older_than, Some(14));
             }
             _ => anyhow::bail!("Expected Clean"),
         }
+        assert!(Cli::try_parse_from(["worktree", "clean", "--artifacts", "--merged"]).is_err());
+
+        // Test stale checks
+        let cli = Cli::try_parse_from(["worktree", "clean", "--merged", "--older-than", "2w"])
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        match cli
+            .command
+            .ok_or_else(|| anyhow::anyhow!("Missing command"))?
+        {
+            Commands::Clean {
+                merged,
+                gone,
+                older_than,
+                ..
+            } => {
+                assert!(merged);
+                assert!(!gone);
+                assert_eq!(older_than, Some(14));
+            }
+            _ => anyhow::bail!("Expected Clean"),
+        }
+        assert!(Cli::try_parse_from(["worktree", "clean", "--older-than", "soon"]).is_err());
 
         Ok(())
     }
@@ -333,4 +865,187 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_cli_parsing_stash() -> Result<()> {
+        let cli = Cli::try_parse_from(["worktree", "stash", "pop", "2"])
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Stash {
+                action: StashAction::Pop { index: 2 }
+            })
+        ));
+        let cli = Cli::try_parse_from(["worktree", "stash", "drop"])
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Stash {
+                action: StashAction::Drop { index: 0 }
+            })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_parsing_warp_sync() -> Result<()> {
+        let cli = Cli::try_parse_from(["worktree", "warp", "sync"])
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        assert!(matches!(
+            cli.command
+                .ok_or_else(|| anyhow::anyhow!("Missing command"))?,
+            Commands::Warp {
+                action: WarpAction::Sync
+            }
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_parsing_exec() -> Result<()> {
+        let cli = Cli::try_parse_from(["worktree", "exec", "feat", "--", "npm", "run", "dev"])
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        match cli.command {
+            Some(Commands::Exec { name, all, command }) => {
+                assert_eq!(name.as_deref(), Some("feat"));
+                assert!(!all);
+                assert_eq!(command, ["npm", "run", "dev"]);
+            }
+            _ => return Err(anyhow::anyhow!("Expected Exec command")),
+        }
+
+        let cli = Cli::try_parse_from(["worktree", "exec", "--all", "--", "git", "status"])
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Exec {
+                name: None,
+                all: true,
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["worktree", "exec", "--", "ls"]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_parsing_meta() -> Result<()> {
+        let cli = Cli::try_parse_from([
+            "worktree",
+            "add",
+            "feat",
+            "--purpose",
+            "Login",
+            "--color",
+            "cyan",
+            "--icon",
+            "🔑",
+        ])
+        .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Add { purpose: Some(p), color: Some(c), icon: Some(_), .. })
+                if p == "Login" && c == "cyan"
+        ));
+
+        let cli = Cli::try_parse_from(["worktree", "add", "--issue", "PROJ-1"])
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Add {
+                intent: None,
+                issue: Some(_),
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["worktree", "add"]).is_err());
+        let cli = Cli::try_parse_from(["worktree", "add", "--template", "hotfix", "crash"])
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Add {
+                intent: Some(_),
+                template: Some(_),
+                ..
+            })
+        ));
+        assert!(
+            Cli::try_parse_from(["worktree", "add", "--template", "t", "--issue", "X-1"]).is_err()
+        );
+        assert!(
+            Cli::try_parse_from(["worktree", "add", "--template", "t", "x", "--remote", "up"])
+                .is_err()
+        );
+        assert!(matches!(
+            Cli::try_parse_from(["worktree", "template", "list"])
+                .map_err(|e| anyhow::anyhow!(e.to_string()))?
+                .command,
+            Some(Commands::Template {
+                action: Some(TemplateAction::List)
+            })
+        ));
+
+        let cli = Cli::try_parse_from(["worktree", "meta", "feat", "set", "purpose", "Login UI"])
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        match cli.command {
+            Some(Commands::Meta {
+                name,
+                action: Some(MetaAction::Set { key, value }),
+            }) => {
+                assert_eq!(name, "feat");
+                assert_eq!(key, "purpose");
+                assert_eq!(value, "Login UI");
+            }
+            _ => return Err(anyhow::anyhow!("Expected Meta set")),
+        }
+
+        let cli = Cli::try_parse_from(["worktree", "meta", "feat"])
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Meta { action: None, .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_parsing_shell_init_and_prompt() -> Result<()> {
+        let cli = Cli::try_parse_from(["worktree", "shell-init", "nushell"])
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        assert!(matches!(
+            cli.command
+                .ok_or_else(|| anyhow::anyhow!("Missing command"))?,
+            Commands::ShellInit {
+                shell: ShellKind::Nushell
+            }
+        ));
+
+        let cli = Cli::try_parse_from(["worktree", "prompt"])
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        match cli
+            .command
+            .ok_or_else(|| anyhow::anyhow!("Missing command"))?
+        {
+            Commands::Prompt { timeout_ms } => assert_eq!(timeout_ms, 150),
+            _ => anyhow::bail!("Expected Prompt"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_complete_args_parsing() {
+        let args = CompleteArgs::parse_from([
+            "__complete",
+            "--cword",
+            "3",
+            "--",
+            "worktree",
+            "remove",
+            "-f",
+            "fe",
+        ]);
+        assert_eq!(args.kind, None);
+        assert_eq!(args.cword, Some(3));
+        assert_eq!(args.words, vec!["worktree", "remove", "-f", "fe"]);
+    }
 }
@@ -0,0 +1,330 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use super::repository::ProjectRepository;
+use super::stale::idle_days;
+
+/// Custom artifact rules in `.worktrees.yaml`, as globs relative to the worktree root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ArtifactConfig {
+    /// Extra paths to clean, e.g. `**/.turbo` or `coverage`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    /// Paths that are never cleaned, even when a detector matches them.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+}
+
+impl ArtifactConfig {
+    pub const fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Ecosystem {
+    Cargo,
+    Node,
+    Gradle,
+    Maven,
+    Python,
+    DotNet,
+    Go,
+    /// Matched by an `artifacts.include` glob.
+    Custom,
+}
+
+impl Ecosystem {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Cargo => "cargo",
+            Self::Node => "npm/pnpm",
+            Self::Gradle => "gradle",
+            Self::Maven => "maven",
+            Self::Python => "python",
+            Self::DotNet => ".net",
+            Self::Go => "go",
+            Self::Custom => "custom",
+        }
+    }
+}
+
+/// Build output next to an ecosystem's marker file.
+pub struct Detector {
+    ecosystem: Ecosystem,
+    /// File names marking a project; a leading `*` matches by suffix (`*.csproj`).
+    markers: &'static [&'static str],
+    /// Directories next to the marker.
+    artifacts: &'static [&'static str],
+    /// Directories matched anywhere below the marker.
+    nested: &'static [&'static str],
+}
+
+const DETECTORS: &[Detector] = &[
+    Detector {
+        ecosystem: Ecosystem::Cargo,
+        markers: &["Cargo.toml"],
+        artifacts: &["target"],
+        nested: &[],
+    },
+    Detector {
+        ecosystem: Ecosystem::Node,
+        markers: &["package.json"],
+        artifacts: &[
+            "node_modules",
+            "dist",
+            ".next",
+            ".nuxt",
+            ".turbo",
+            ".parcel-cache",
+        ],
+        nested: &[],
+    },
+    Detector {
+        ecosystem: Ecosystem::Gradle,
+        markers: &[
+            "build.gradle",
+            "build.gradle.kts",
+            "settings.gradle",
+            "settings.gradle.kts",
+        ],
+        artifacts: &["build", ".gradle", ".kotlin"],
+        nested: &[],
+    },
+    Detector {
+        ecosystem: Ecosystem::Maven,
+        markers: &["pom.xml"],
+        artifacts: &["target"],
+        nested: &[],
+    },
+    Detector {
+        ecosystem: Ecosystem::Python,
+        markers: &["pyproject.toml", "setup.py", "requirements.txt", "Pipfile"],
+        artifacts: &[
+            ".venv",
+            "venv",
+            ".tox",
+            ".pytest_cache",
+            ".mypy_cache",
+            ".ruff_cache",
+        ],
+        nested: &["__pycache__"],
+    },
+    Detector {
+        ecosystem: Ecosystem::DotNet,
+        markers: &["*.csproj", "*.fsproj", "*.vbproj", "*.sln"],
+        artifacts: &["bin", "obj"],
+        nested: &[],
+    },
+    Detector {
+        ecosystem: Ecosystem::Go,
+        markers: &["go.mod"],
+        artifacts: &["vendor", "bin"],
+        nested: &[],
+    },
+];
+
+impl Detector {
+    fn matches_marker(&self, file_name: &str) -> bool {
+        self.markers.iter().any(|marker| {
+            marker
+                .strip_prefix('*')
+                .map_or(file_name == *marker, |suffix| file_name.ends_with(suffix))
+        })
+    }
+
+    /// Whether the detector claims directories below its own, like `__pycache__`.
+    pub const fn has_nested(&self) -> bool {
+        !self.nested.is_empty()
+    }
+}
+
+/// Detectors whose marker is among `names`, the entries of one directory.
+pub fn detectors_for<'a>(names: impl IntoIterator<Item = &'a str>) -> Vec<&'static Detector> {
+    let names: Vec<&str> = names.into_iter().collect();
+    DETECTORS
+        .iter()
+        .filter(|d| names.iter().any(|name| d.matches_marker(name)))
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Artifact {
+    pub path: String,
+    /// Path relative to the worktree root, with `/` separators.
+    pub relative: String,
+    pub ecosystem: Ecosystem,
+    pub size_bytes: u64,
+}
+
+/// The `artifacts.include`/`exclude` globs, compiled once per scan.
+pub struct ArtifactRules {
+    include: Vec<glob::Pattern>,
+    exclude: Vec<glob::Pattern>,
+}
+
+impl ArtifactRules {
+    const OPTIONS: glob::MatchOptions = glob::MatchOptions {
+        case_sensitive: true,
+        require_literal_separator: true,
+        require_literal_leading_dot: false,
+    };
+
+    pub fn new(config: &ArtifactConfig) -> Self {
+        let compile = |globs: &[String]| -> Vec<glob::Pattern> {
+            globs
+                .iter()
+                .filter_map(|g| glob::Pattern::new(g.trim_end_matches('/')).ok())
+                .collect()
+        };
+        Self {
+            include: compile(&config.include),
+            exclude: compile(&config.exclude),
+        }
+    }
+
+    pub fn is_excluded(&self, relative: &str) -> bool {
+        self.exclude
+            .iter()
+            .any(|p| p.matches_with(relative, Self::OPTIONS))
+    }
+
+    /// The ecosystem whose build output `relative` is, if any.
+    ///
+    /// `here` are the detectors matching its parent directory and `inherited` those active
+    /// further up. Only directories match a detector; files and directories can match an
+    /// include glob.
+    pub fn classify(
+        &self,
+        relative: &str,
+        is_dir: bool,
+        here: &[&Detector],
+        inherited: &[&Detector],
+    ) -> Option<Ecosystem> {
+        let name = relative.rsplit('/').next().unwrap_or(relative);
+        is_dir
+            .then(|| {
+                here.iter()
+                    .find(|d| d.artifacts.contains(&name))
+                    .or_else(|| {
+                        here.iter()
+                            .chain(inherited)
+                            .find(|d| d.nested.contains(&name))
+                    })
+                    .map(|d| d.ecosystem)
+            })
+            .flatten()
+            .or_else(|| {
+                self.include
+                    .iter()
+                    .any(|p| p.matches_with(relative, Self::OPTIONS))
+                    .then_some(Ecosystem::Custom)
+            })
+    }
+}
+
+/// Artifacts found in one worktree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WorktreeArtifacts {
+    pub path: String,
+    pub branch: String,
+    pub idle_days: u64,
+    pub artifacts: Vec<Artifact>,
+}
+
+impl WorktreeArtifacts {
+    pub fn size_bytes(&self) -> u64 {
+        self.artifacts.iter().map(|a| a.size_bytes).sum()
+    }
+}
+
+/// Scans every non-bare worktree, optionally only those idle for at least `inactive_days`.
+///
+/// Worktrees without artifacts are left out.
+pub fn find_worktree_artifacts<R: ProjectRepository + ?Sized>(
+    repo: &R,
+    inactive_days: Option<u64>,
+    now: i64,
+) -> anyhow::Result<Vec<WorktreeArtifacts>> {
+    let mut found = Vec::new();
+    for wt in repo.list_worktrees()?.iter().filter(|wt| !wt.is_bare) {
+        let last_activity = repo.last_activity(&wt.path).unwrap_or(0);
+        let idle_days = idle_days(now, last_activity);
+        if inactive_days.is_some_and(|days| idle_days < days) {
+            continue;
+        }
+        let artifacts = repo.scan_artifacts(&wt.path)?;
+        if !artifacts.is_empty() {
+            found.push(WorktreeArtifacts {
+                path: wt.path.clone(),
+                branch: wt.head_ref().to_string(),
+                idle_days,
+                artifacts,
+            });
+        }
+    }
+    Ok(found)
+}
+
+/// Directories containing at least one of `files` (git's `ls-files` output), plus the files.
+pub fn tracked_paths<'a>(files: impl IntoIterator<Item = &'a str>) -> HashSet<String> {
+    let mut tracked = HashSet::new();
+    for file in files {
+        let mut path = file;
+        while tracked.insert(path.to_string()) {
+            match path.rfind('/') {
+                Some(i) => path = &path[..i],
+                None => break,
+            }
+        }
+    }
+    tracked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracked_paths() {
+        let tracked = tracked_paths(["bin/deploy.sh", "bin/lib/util.sh", "README.md"]);
+        for path in ["bin", "bin/lib", "bin/deploy.sh", "README.md"] {
+            assert!(tracked.contains(path), "{path}");
+        }
+        assert!(!tracked.contains("lib"));
+    }
+
+    #[test]
+    fn test_classify_by_marker_and_rules() {
+        let root = detectors_for(["package.json", "README.md"]);
+        let python = detectors_for(["pyproject.toml"]);
+        assert_eq!(root.len(), 1);
+        assert!(python.iter().all(|d| d.has_nested()));
+        assert_eq!(detectors_for(["App.csproj"]).len(), 1);
+
+        let rules = ArtifactRules::new(&ArtifactConfig {
+            include: vec!["coverage/".into()],
+            exclude: vec!["api/obj".into()],
+        });
+        assert_eq!(
+            rules.classify("node_modules", true, &root, &[]),
+            Some(Ecosystem::Node)
+        );
+        // Files never match a detector, and stray directories need a marker next to them.
+        assert_eq!(rules.classify("node_modules", false, &root, &[]), None);
+        assert_eq!(rules.classify("docs/build", true, &[], &[]), None);
+        // Nested artifacts match below the marker's directory.
+        assert_eq!(
+            rules.classify("tools/src/__pycache__", true, &[], &python),
+            Some(Ecosystem::Python)
+        );
+        assert_eq!(
+            rules.classify("coverage", true, &[], &[]),
+            Some(Ecosystem::Custom)
+        );
+        assert!(rules.is_excluded("api/obj"));
+        assert!(!rules.is_excluded("api/bin"));
+    }
+}
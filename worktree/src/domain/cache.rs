@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// How Rust worktrees share compiled output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RustCacheMode {
+    /// One `target-dir` for every worktree in the hub.
+    #[default]
+    Target,
+    /// `rustc-wrapper = "sccache"` with a hub-local cache directory.
+    Sccache,
+    Off,
+}
+
+/// Shared build cache settings in `.worktrees.yaml`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Set to `false` to leave every worktree's build setup untouched.
+    pub enabled: bool,
+    pub rust: RustCacheMode,
+    pub pnpm: bool,
+    pub gradle: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            rust: RustCacheMode::default(),
+            pnpm: true,
+            gradle: true,
+        }
+    }
+}
+
+impl CacheConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Caches that are switched on, regardless of which worktrees need them.
+    pub fn enabled_kinds(&self) -> Vec<CacheKind> {
+        if !self.enabled {
+            return Vec::new();
+        }
+        let mut kinds = Vec::new();
+        match self.rust {
+            RustCacheMode::Target => kinds.push(CacheKind::CargoTarget),
+            RustCacheMode::Sccache => kinds.push(CacheKind::Sccache),
+            RustCacheMode::Off => {}
+        }
+        if self.pnpm {
+            kinds.push(CacheKind::PnpmStore);
+        }
+        if self.gradle {
+            kinds.push(CacheKind::GradleBuildCache);
+        }
+        kinds
+    }
+
+    /// Enabled caches whose ecosystem is present at the root of `worktree`.
+    pub fn kinds_for(&self, worktree: &Path) -> Vec<CacheKind> {
+        self.enabled_kinds()
+            .into_iter()
+            .filter(|kind| kind.markers().iter().any(|m| worktree.join(m).exists()))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheKind {
+    CargoTarget,
+    Sccache,
+    PnpmStore,
+    GradleBuildCache,
+}
+
+impl CacheKind {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::CargoTarget => "cargo target",
+            Self::Sccache => "sccache",
+            Self::PnpmStore => "pnpm store",
+            Self::GradleBuildCache => "gradle build cache",
+        }
+    }
+
+    /// Directory under the hub's `.cache/`; Gradle keeps its cache in `GRADLE_USER_HOME`.
+    pub const fn dir_name(self) -> Option<&'static str> {
+        match self {
+            Self::CargoTarget => Some("cargo-target"),
+            Self::Sccache => Some("sccache"),
+            Self::PnpmStore => Some("pnpm-store"),
+            Self::GradleBuildCache => None,
+        }
+    }
+
+    const fn markers(self) -> &'static [&'static str] {
+        match self {
+            Self::CargoTarget | Self::Sccache => &["Cargo.toml"],
+            Self::PnpmStore => &["pnpm-lock.yaml", "pnpm-workspace.yaml"],
+            Self::GradleBuildCache => &[
+                "build.gradle",
+                "build.gradle.kts",
+                "settings.gradle",
+                "settings.gradle.kts",
+            ],
+        }
+    }
+}
+
+/// What `worktree cache status` reports for one cache.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CacheStatus {
+    pub kind: CacheKind,
+    pub location: String,
+    /// File or setting that points the tools at `location`.
+    pub configured_by: String,
+    pub size_bytes: u64,
+    /// Names of the worktrees using the cache.
+    pub worktrees: Vec<String>,
+    /// Why the cache is not in effect, e.g. a missing `sccache` binary.
+    pub problem: Option<String>,
+}
+
+/// Shell exports equivalent to the generated tool config, for `.cache/cache.env`.
+pub fn cache_env(kinds: &[CacheKind], cache_root: &Path) -> Vec<(&'static str, String)> {
+    let dir = |kind: CacheKind| {
+        kind.dir_name()
+            .map(|name| cache_root.join(name).to_string_lossy().to_string())
+            .unwrap_or_default()
+    };
+    let mut env = Vec::new();
+    for kind in kinds {
+        match kind {
+            CacheKind::CargoTarget => env.push(("CARGO_TARGET_DIR", dir(*kind))),
+            CacheKind::Sccache => {
+                env.push(("RUSTC_WRAPPER", "sccache".to_string()));
+                env.push(("SCCACHE_DIR", dir(*kind)));
+            }
+            CacheKind::PnpmStore => env.push(("npm_config_store_dir", dir(*kind))),
+            CacheKind::GradleBuildCache => {}
+        }
+    }
+    env
+}
+
+/// First line of a hub `.cargo/config.toml` written by [`cargo_config`]; other files are left alone.
+pub const CARGO_CONFIG_HEADER: &str = "# Generated by worktree: shares build output between worktrees.\n\
+     # Set `cache.rust: off` in .worktrees.yaml and delete this file to opt out.\n";
+
+/// The hub-wide `.cargo/config.toml` for `kind`, or `None` when Rust caching is off.
+pub fn cargo_config(kind: CacheKind, cache_root: &Path) -> Option<String> {
+    let dir = cache_root.join(kind.dir_name()?);
+    let dir = dir.to_string_lossy().replace('\\', "/");
+    let header = CARGO_CONFIG_HEADER;
+    match kind {
+        CacheKind::CargoTarget => Some(format!("{header}[build]\ntarget-dir = \"{dir}\"\n")),
+        CacheKind::Sccache => Some(format!(
+            "{header}[build]\nrustc-wrapper = \"sccache\"\n\n[env]\nSCCACHE_DIR = \"{dir}\"\n"
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_config_kinds() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+        std::fs::write(dir.path().join("pnpm-lock.yaml"), "").unwrap();
+
+        let config = CacheConfig::default();
+        assert_eq!(
+            config.kinds_for(dir.path()),
+            vec![CacheKind::CargoTarget, CacheKind::PnpmStore]
+        );
+
+        let config: CacheConfig = serde_yaml::from_str("rust: sccache\npnpm: false").unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.kinds_for(dir.path()), vec![CacheKind::Sccache]);
+
+        let config: CacheConfig = serde_yaml::from_str("enabled: false").unwrap();
+        assert!(config.kinds_for(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_cache_env_and_cargo_config() {
+        let root = Path::new("/hub/.cache");
+        assert_eq!(
+            cache_env(&[CacheKind::Sccache, CacheKind::PnpmStore], root),
+            vec![
+                ("RUSTC_WRAPPER", "sccache".to_string()),
+                ("SCCACHE_DIR", "/hub/.cache/sccache".to_string()),
+                ("npm_config_store_dir", "/hub/.cache/pnpm-store".to_string()),
+            ]
+        );
+        let config = cargo_config(CacheKind::CargoTarget, root).unwrap();
+        assert!(config.contains("target-dir = \"/hub/.cache/cargo-target\""));
+        assert!(cargo_config(CacheKind::PnpmStore, root).is_none());
+    }
+}
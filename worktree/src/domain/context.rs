@@ -0,0 +1,283 @@
+use serde::Serialize;
+use std::path::Path;
+
+/// A technology stack recognized at a project root; several can apply at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextKind {
+    Node,
+    Rust,
+    Python,
+    Go,
+    Xcode,
+    Flutter,
+    /// Kotlin Multiplatform or Android (Gradle).
+    Kmp,
+}
+
+impl ContextKind {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Node => "NODE",
+            Self::Rust => "RUST",
+            Self::Python => "PYTHON",
+            Self::Go => "GO",
+            Self::Xcode => "IOS",
+            Self::Flutter => "FLUTTER",
+            Self::Kmp => "KMP",
+        }
+    }
+}
+
+/// Stack-specific behavior applied when a worktree is created or used.
+pub trait ContextHandler: Sync {
+    fn kind(&self) -> ContextKind;
+    /// Whether the project rooted at `root` uses this stack.
+    fn detect(&self, root: &Path) -> bool;
+    /// Local, usually untracked files copied from the source worktree when missing in a new one.
+    fn sync_files(&self) -> &'static [&'static str] {
+        &[]
+    }
+    /// Environment for commands run inside `worktree`.
+    fn env(&self, _worktree: &Path) -> Vec<(String, String)> {
+        Vec::new()
+    }
+    /// Artifact globs on top of the ecosystem detectors used by `clean --artifacts`.
+    fn artifact_globs(&self) -> &'static [&'static str] {
+        &[]
+    }
+}
+
+fn has_any(root: &Path, names: &[&str]) -> bool {
+    names.iter().any(|name| root.join(name).exists())
+}
+
+fn has_extension(root: &Path, extensions: &[&str]) -> bool {
+    std::fs::read_dir(root).is_ok_and(|entries| {
+        entries.flatten().any(|entry| {
+            Path::new(&entry.file_name())
+                .extension()
+                .is_some_and(|ext| extensions.iter().any(|e| ext == *e))
+        })
+    })
+}
+
+/// Reads `key=value` from a Java-style properties file.
+fn property(file: &Path, key: &str) -> Option<String> {
+    std::fs::read_to_string(file)
+        .ok()?
+        .lines()
+        .find_map(|line| {
+            let (k, v) = line.split_once('=')?;
+            (k.trim() == key).then(|| v.trim().replace("\\:", ":").replace("\\\\", "\\"))
+        })
+}
+
+struct NodeHandler;
+
+impl ContextHandler for NodeHandler {
+    fn kind(&self) -> ContextKind {
+        ContextKind::Node
+    }
+    fn detect(&self, root: &Path) -> bool {
+        has_any(root, &["package.json"])
+    }
+    fn sync_files(&self) -> &'static [&'static str] {
+        &[".env", ".env.local"]
+    }
+}
+
+struct RustHandler;
+
+impl ContextHandler for RustHandler {
+    fn kind(&self) -> ContextKind {
+        ContextKind::Rust
+    }
+    fn detect(&self, root: &Path) -> bool {
+        has_any(root, &["Cargo.toml"])
+    }
+}
+
+struct PythonHandler;
+
+impl ContextHandler for PythonHandler {
+    fn kind(&self) -> ContextKind {
+        ContextKind::Python
+    }
+    fn detect(&self, root: &Path) -> bool {
+        has_any(
+            root,
+            &["pyproject.toml", "setup.py", "requirements.txt", "Pipfile"],
+        )
+    }
+    fn sync_files(&self) -> &'static [&'static str] {
+        &[".env"]
+    }
+    fn env(&self, worktree: &Path) -> Vec<(String, String)> {
+        let venv = worktree.join(".venv");
+        if venv.is_dir() {
+            vec![("VIRTUAL_ENV".into(), venv.to_string_lossy().to_string())]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+struct GoHandler;
+
+impl ContextHandler for GoHandler {
+    fn kind(&self) -> ContextKind {
+        ContextKind::Go
+    }
+    fn detect(&self, root: &Path) -> bool {
+        has_any(root, &["go.mod", "go.work"])
+    }
+}
+
+struct XcodeHandler;
+
+impl ContextHandler for XcodeHandler {
+    fn kind(&self) -> ContextKind {
+        ContextKind::Xcode
+    }
+    fn detect(&self, root: &Path) -> bool {
+        has_any(root, &["Podfile"]) || has_extension(root, &["xcodeproj", "xcworkspace"])
+    }
+    fn sync_files(&self) -> &'static [&'static str] {
+        &["GoogleService-Info.plist"]
+    }
+    fn artifact_globs(&self) -> &'static [&'static str] {
+        &["**/DerivedData", "Pods", ".build"]
+    }
+}
+
+struct FlutterHandler;
+
+impl ContextHandler for FlutterHandler {
+    fn kind(&self) -> ContextKind {
+        ContextKind::Flutter
+    }
+    fn detect(&self, root: &Path) -> bool {
+        has_any(root, &["pubspec.yaml"])
+    }
+    fn sync_files(&self) -> &'static [&'static str] {
+        &[".env", "android/local.properties", "android/key.properties"]
+    }
+    fn env(&self, worktree: &Path) -> Vec<(String, String)> {
+        property(&worktree.join("android/local.properties"), "flutter.sdk")
+            .map(|sdk| vec![("FLUTTER_ROOT".into(), sdk)])
+            .unwrap_or_default()
+    }
+    fn artifact_globs(&self) -> &'static [&'static str] {
+        &[".dart_tool", "build", "ios/Pods", "android/.gradle"]
+    }
+}
+
+struct KmpHandler;
+
+impl ContextHandler for KmpHandler {
+    fn kind(&self) -> ContextKind {
+        ContextKind::Kmp
+    }
+    fn detect(&self, root: &Path) -> bool {
+        has_any(
+            root,
+            &[
+                "build.gradle",
+                "build.gradle.kts",
+                "settings.gradle",
+                "settings.gradle.kts",
+                "local.properties",
+            ],
+        )
+    }
+    fn sync_files(&self) -> &'static [&'static str] {
+        &["local.properties", "gradle.properties"]
+    }
+    fn env(&self, worktree: &Path) -> Vec<(String, String)> {
+        property(&worktree.join("local.properties"), "sdk.dir")
+            .map(|sdk| vec![("ANDROID_HOME".into(), sdk)])
+            .unwrap_or_default()
+    }
+    fn artifact_globs(&self) -> &'static [&'static str] {
+        &["iosApp/Pods", "**/DerivedData"]
+    }
+}
+
+/// Every known handler, in the order their effects are applied.
+pub static HANDLERS: &[&dyn ContextHandler] = &[
+    &NodeHandler,
+    &RustHandler,
+    &PythonHandler,
+    &GoHandler,
+    &XcodeHandler,
+    &FlutterHandler,
+    &KmpHandler,
+];
+
+/// Handlers whose stack is present at `root`.
+pub fn detect_handlers(root: &Path) -> Vec<&'static dyn ContextHandler> {
+    HANDLERS
+        .iter()
+        .copied()
+        .filter(|h| h.detect(root))
+        .collect()
+}
+
+pub fn detect_contexts(root: &Path) -> Vec<ContextKind> {
+    detect_handlers(root).iter().map(|h| h.kind()).collect()
+}
+
+/// Environment contributed by every handler detected in `worktree`.
+pub fn context_env(worktree: &Path) -> Vec<(String, String)> {
+    detect_handlers(worktree)
+        .iter()
+        .flat_map(|h| h.env(worktree))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_stacked_contexts() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        assert!(detect_contexts(root).is_empty());
+
+        std::fs::write(root.join("package.json"), "{}").unwrap();
+        std::fs::write(root.join("Cargo.toml"), "").unwrap();
+        std::fs::create_dir(root.join("App.xcodeproj")).unwrap();
+        assert_eq!(
+            detect_contexts(root),
+            vec![ContextKind::Node, ContextKind::Rust, ContextKind::Xcode]
+        );
+    }
+
+    #[test]
+    fn test_context_env() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("settings.gradle.kts"), "").unwrap();
+        std::fs::write(
+            root.join("local.properties"),
+            "# SDK\nsdk.dir=C\\:\\\\Android\\\\sdk\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("pyproject.toml"), "").unwrap();
+        std::fs::create_dir(root.join(".venv")).unwrap();
+
+        let env = context_env(root);
+        assert_eq!(
+            env,
+            vec![
+                (
+                    "VIRTUAL_ENV".to_string(),
+                    root.join(".venv").to_string_lossy().to_string()
+                ),
+                ("ANDROID_HOME".to_string(), "C:\\Android\\sdk".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+
+/// Parses a `.env` file: `KEY=value` lines, optionally prefixed with `export` and quoted.
+///
+/// Blank lines, `#` comments and lines without a valid key are skipped.
+pub fn parse_dotenv(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line.split_once('=')?;
+            let key = key.trim();
+            let valid = key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                && key.chars().next().is_some_and(|c| !c.is_ascii_digit());
+            if !valid {
+                return None;
+            }
+            let value = value.trim();
+            let value = ['"', '\'']
+                .iter()
+                .find_map(|q| value.strip_prefix(*q)?.strip_suffix(*q))
+                .unwrap_or_else(|| value.split(" #").next().unwrap_or_default().trim_end());
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Where each part of a worktree's environment comes from, lowest precedence first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EnvLayers {
+    /// The hub's `.env`.
+    pub hub: Vec<(String, String)>,
+    /// `.cache/cache.env`, the shared build cache settings.
+    pub cache: Vec<(String, String)>,
+    /// Stack settings such as `ANDROID_HOME`.
+    pub context: Vec<(String, String)>,
+    /// Allocated ports and service names.
+    pub resources: Vec<(String, String)>,
+    /// `env` in the worktree's metadata.
+    pub worktree: BTreeMap<String, String>,
+    pub name: String,
+    pub branch: String,
+    pub root: String,
+}
+
+impl EnvLayers {
+    /// Later layers win; `WORKTREE_NAME`, `WORKTREE_BRANCH` and `WORKTREE_ROOT` always apply.
+    pub fn merge(self) -> Vec<(String, String)> {
+        let mut env = BTreeMap::new();
+        let layers = self
+            .hub
+            .into_iter()
+            .chain(self.cache)
+            .chain(self.context)
+            .chain(self.resources)
+            .chain(self.worktree);
+        for (key, value) in layers {
+            env.insert(key, value);
+        }
+        env.insert("WORKTREE_NAME".to_string(), self.name);
+        env.insert("WORKTREE_BRANCH".to_string(), self.branch);
+        env.insert("WORKTREE_ROOT".to_string(), self.root);
+        env.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dotenv() {
+        let env = parse_dotenv(
+            "# comment\nA=1\nexport B=\"two words\"\nC='x # y'\nD=plain # trailing\n\
+             1BAD=x\nnot a pair\nE=\n",
+        );
+        assert_eq!(
+            env,
+            vec![
+                ("A".to_string(), "1".to_string()),
+                ("B".to_string(), "two words".to_string()),
+                ("C".to_string(), "x # y".to_string()),
+                ("D".to_string(), "plain".to_string()),
+                ("E".to_string(), String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_env_layers_precedence() {
+        let layers = EnvLayers {
+            hub: vec![("PORT".into(), "3000".into()), ("API".into(), "hub".into())],
+            resources: vec![("PORT".into(), "20110".into())],
+            worktree: BTreeMap::from([("API".into(), "local".into())]),
+            name: "feat".into(),
+            branch: "feature/x".into(),
+            root: "/hub/feat".into(),
+            ..EnvLayers::default()
+        };
+        let env: BTreeMap<_, _> = layers.merge().into_iter().collect();
+        assert_eq!(env["PORT"], "20110");
+        assert_eq!(env["API"], "local");
+        assert_eq!(env["WORKTREE_BRANCH"], "feature/x");
+        assert_eq!(env["WORKTREE_ROOT"], "/hub/feat");
+    }
+}
@@ -0,0 +1,174 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Issue tracker settings in `.worktrees.yaml`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IssueConfig {
+    /// Regexes that find a ticket id in a branch name; the first capture group wins if present.
+    pub patterns: Vec<String>,
+    /// Ticket URL with `{id}`, e.g. `https://jira.example.com/browse/{id}`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Shell command with `{id}` that prints the ticket title, e.g. `gh issue view {id} --json title -q .title`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Branch name for `add --issue`, from `{id}`, `{id_lower}` and `{slug}` (the title).
+    pub branch: String,
+    /// Worktree name for `add --issue`, same variables as `branch`.
+    pub name: String,
+}
+
+impl Default for IssueConfig {
+    fn default() -> Self {
+        Self {
+            patterns: vec![r"[A-Z][A-Z0-9]+-\d+".to_string()],
+            url: None,
+            command: None,
+            branch: "feat/{id}-{slug}".to_string(),
+            name: "{id_lower}".to_string(),
+        }
+    }
+}
+
+impl IssueConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The ticket id in `branch`, if any pattern matches.
+    pub fn extract_id(&self, branch: &str) -> Result<Option<String>> {
+        for pattern in &self.patterns {
+            let regex = Regex::new(pattern)
+                .with_context(|| format!("Invalid issues.patterns entry '{pattern}'"))?;
+            if let Some(captures) = regex.captures(branch) {
+                let id = captures.get(1).or_else(|| captures.get(0));
+                return Ok(id.map(|m| m.as_str().to_string()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Branch and worktree names for a ticket, from the `branch` and `name` templates.
+    pub fn names(&self, issue: &IssueLink) -> (String, String) {
+        let slug = issue.title.as_deref().map(slugify).unwrap_or_default();
+        let render = |template: &str| {
+            tidy(
+                &template
+                    .replace("{id_lower}", &issue.id.to_lowercase())
+                    .replace("{id}", &issue.id)
+                    .replace("{slug}", &slug),
+            )
+        };
+        (render(&self.branch), render(&self.name))
+    }
+}
+
+/// A worktree's ticket; stored in its metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueLink {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// Resolves ticket ids to links. Implementations decide how much they ask the tracker.
+pub trait IssueProvider {
+    /// The link for `id` without contacting the tracker.
+    fn link(&self, id: &str) -> IssueLink;
+    /// The link with details such as the title.
+    fn lookup(&self, id: &str) -> Result<IssueLink> {
+        Ok(self.link(id))
+    }
+}
+
+/// Builds URLs from `issues.url`; knows nothing else about the ticket.
+pub struct UrlProvider {
+    pub template: Option<String>,
+}
+
+impl IssueProvider for UrlProvider {
+    fn link(&self, id: &str) -> IssueLink {
+        IssueLink {
+            id: id.to_string(),
+            url: self.template.as_ref().map(|t| t.replace("{id}", id)),
+            title: None,
+        }
+    }
+}
+
+/// Lowercase words joined by `-`, at most five of them.
+fn slugify(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .take(5)
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Drops separators left dangling by an empty `{slug}`, e.g. `feat/PROJ-1-` becomes `feat/PROJ-1`.
+fn tidy(name: &str) -> String {
+    name.split('/')
+        .map(|part| part.trim_matches(['-', '_', '.']))
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_issue_id() {
+        let config = IssueConfig::default();
+        assert_eq!(
+            config.extract_id("feat/PROJ-123-login").unwrap().as_deref(),
+            Some("PROJ-123")
+        );
+        assert_eq!(config.extract_id("main").unwrap(), None);
+
+        let github = IssueConfig {
+            patterns: vec![r"(?:^|/)(\d+)-".into()],
+            ..IssueConfig::default()
+        };
+        assert_eq!(
+            github.extract_id("fix/42-crash").unwrap().as_deref(),
+            Some("42")
+        );
+
+        let broken = IssueConfig {
+            patterns: vec!["(".into()],
+            ..IssueConfig::default()
+        };
+        assert!(broken.extract_id("x").is_err());
+    }
+
+    #[test]
+    fn test_issue_names_from_templates() {
+        let config = IssueConfig::default();
+        let provider = UrlProvider {
+            template: Some("https://jira.example.com/browse/{id}".into()),
+        };
+        let mut issue = provider.link("PROJ-123");
+        assert_eq!(
+            issue.url.as_deref(),
+            Some("https://jira.example.com/browse/PROJ-123")
+        );
+        assert_eq!(
+            config.names(&issue),
+            ("feat/PROJ-123".to_string(), "proj-123".to_string())
+        );
+
+        issue.title = Some("Login page: add SSO (Okta) & more words".into());
+        assert_eq!(
+            config.names(&issue).0,
+            "feat/PROJ-123-login-page-add-sso-okta"
+        );
+    }
+}
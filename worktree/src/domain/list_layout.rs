@@ -0,0 +1,381 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::HashMap;
+
+use super::repository::{ProjectRepository, Worktree};
+
+/// Group of pinned worktrees, listed before every other group.
+pub const PINNED_GROUP: &str = "pinned";
+/// Group of worktrees without a branch prefix or purpose, listed last.
+pub const UNGROUPED: &str = "ungrouped";
+
+/// Order of the worktrees within their group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortMode {
+    /// The order of `git worktree list`.
+    #[default]
+    Default,
+    /// Worktree name, A to Z.
+    Name,
+    /// Most recently committed, checked out or staged first.
+    Activity,
+    /// Largest first.
+    Size,
+    /// Uncommitted changes first.
+    Dirty,
+    /// Most commits ahead of and behind the upstream first.
+    AheadBehind,
+}
+
+impl SortMode {
+    const ALL: [Self; 6] = [
+        Self::Default,
+        Self::Name,
+        Self::Activity,
+        Self::Size,
+        Self::Dirty,
+        Self::AheadBehind,
+    ];
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Name => "name",
+            Self::Activity => "activity",
+            Self::Size => "size",
+            Self::Dirty => "dirty first",
+            Self::AheadBehind => "ahead/behind",
+        }
+    }
+
+    /// Whether sorting asks git about every worktree, which is too slow for a keypress.
+    pub const fn needs_repo(self) -> bool {
+        matches!(self, Self::Activity | Self::AheadBehind)
+    }
+
+    /// The mode after this one, for cycling in the TUI.
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|&m| m == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+/// What the worktrees are grouped by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum GroupMode {
+    #[default]
+    None,
+    /// The branch name up to the first `/`, e.g. `feat` for `feat/login`.
+    Prefix,
+    /// The `purpose` metadata field.
+    Purpose,
+}
+
+impl GroupMode {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Prefix => "prefix",
+            Self::Purpose => "purpose",
+        }
+    }
+
+    pub const fn next(self) -> Self {
+        match self {
+            Self::None => Self::Prefix,
+            Self::Prefix => Self::Purpose,
+            Self::Purpose => Self::None,
+        }
+    }
+}
+
+/// How the worktree list is ordered, saved per hub under `list` in `.worktree.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ListLayout {
+    pub sort: SortMode,
+    pub group: GroupMode,
+    /// Groups folded away in the TUI.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub collapsed: Vec<String>,
+}
+
+impl ListLayout {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn is_collapsed(&self, group: &str) -> bool {
+        self.collapsed.iter().any(|g| g == group)
+    }
+
+    /// Folds `group` away, or unfolds it when it already is.
+    pub fn toggle_collapsed(&mut self, group: &str) {
+        if self.is_collapsed(group) {
+            self.collapsed.retain(|g| g != group);
+        } else {
+            self.collapsed.push(group.to_string());
+        }
+    }
+}
+
+pub fn is_pinned(wt: &Worktree) -> bool {
+    wt.metadata.as_ref().is_some_and(|m| m.pinned)
+}
+
+/// The group a worktree is listed under; `None` when not grouping and for the hub.
+pub fn group_of(wt: &Worktree, mode: GroupMode) -> Option<&str> {
+    if mode == GroupMode::None || wt.is_bare {
+        return None;
+    }
+    if is_pinned(wt) {
+        return Some(PINNED_GROUP);
+    }
+    let group = match mode {
+        GroupMode::Prefix if !wt.is_detached => wt.branch.split_once('/').map(|(prefix, _)| prefix),
+        GroupMode::Purpose => wt
+            .metadata
+            .as_ref()
+            .and_then(|m| m.purpose.as_deref())
+            .filter(|p| !p.trim().is_empty()),
+        _ => None,
+    };
+    Some(group.unwrap_or(UNGROUPED))
+}
+
+/// Pinned first, then groups A to Z, then the ungrouped ones.
+fn group_rank(group: Option<&str>) -> (u8, &str) {
+    match group {
+        None => (0, ""),
+        Some(PINNED_GROUP) => (1, ""),
+        Some(UNGROUPED) => (3, ""),
+        Some(name) => (2, name),
+    }
+}
+
+fn worktree_name(wt: &Worktree) -> String {
+    std::path::Path::new(&wt.path)
+        .file_name()
+        .map_or_else(|| wt.branch.clone(), |n| n.to_string_lossy().to_string())
+        .to_lowercase()
+}
+
+/// Reorders `worktrees` for display: the hub first, then pinned worktrees, then by group and
+/// `layout.sort`. Worktrees that compare equal keep the order `git worktree list` gave them.
+pub fn arrange_worktrees<R: ProjectRepository + ?Sized>(
+    repo: &R,
+    worktrees: &mut [Worktree],
+    layout: &ListLayout,
+) {
+    let activity: HashMap<String, i64> = if layout.sort == SortMode::Activity {
+        worktrees
+            .iter()
+            .map(|wt| (wt.path.clone(), repo.last_activity(&wt.path).unwrap_or(0)))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+    let divergence: HashMap<String, usize> = if layout.sort == SortMode::AheadBehind {
+        repo.list_branch_info()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|b| !b.is_remote())
+            .map(|b| (b.name, b.ahead + b.behind))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+
+    let by_sort = |a: &Worktree, b: &Worktree| -> Ordering {
+        match layout.sort {
+            SortMode::Default => Ordering::Equal,
+            SortMode::Name => worktree_name(a).cmp(&worktree_name(b)),
+            SortMode::Activity => {
+                Reverse(activity.get(&a.path)).cmp(&Reverse(activity.get(&b.path)))
+            }
+            SortMode::Size => b.size_bytes.cmp(&a.size_bytes),
+            SortMode::Dirty => is_dirty(b).cmp(&is_dirty(a)),
+            SortMode::AheadBehind => {
+                let diverged = |wt: &Worktree| divergence.get(&wt.branch).copied().unwrap_or(0);
+                diverged(b).cmp(&diverged(a))
+            }
+        }
+    };
+    worktrees.sort_by(|a, b| {
+        b.is_bare
+            .cmp(&a.is_bare)
+            .then_with(|| is_pinned(b).cmp(&is_pinned(a)))
+            .then_with(|| {
+                group_rank(group_of(a, layout.group)).cmp(&group_rank(group_of(b, layout.group)))
+            })
+            .then_with(|| by_sort(a, b))
+    });
+}
+
+fn is_dirty(wt: &Worktree) -> bool {
+    wt.status_summary.as_deref().is_some_and(|s| s != "clean")
+}
+
+/// Positions in `worktrees` left once collapsed groups are folded away.
+pub fn expanded_indices(worktrees: &[Worktree], layout: &ListLayout) -> Vec<usize> {
+    worktrees
+        .iter()
+        .enumerate()
+        .filter(|(_, wt)| group_of(wt, layout.group).is_none_or(|g| !layout.is_collapsed(g)))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// The group `z` toggles for the worktree at `selected`, a position in `worktrees`.
+///
+/// A collapsed group listed between the selection and the visible worktree above it (or below
+/// it, on the last visible row) is the one the cursor just left, so it is reopened first.
+/// Otherwise it is the selection's own group. With nothing selected, because every group is
+/// collapsed, it is the first collapsed group.
+pub fn group_to_toggle<'a>(
+    worktrees: &'a [Worktree],
+    visible: &[usize],
+    selected: Option<usize>,
+    layout: &ListLayout,
+) -> Option<&'a str> {
+    let collapsed =
+        |wt: &'a Worktree| group_of(wt, layout.group).filter(|g| layout.is_collapsed(g));
+    let Some((sel, current)) = selected.and_then(|i| Some((i, worktrees.get(i)?))) else {
+        return worktrees.iter().find_map(collapsed);
+    };
+    let above = worktrees[..sel]
+        .iter()
+        .enumerate()
+        .rev()
+        .take_while(|(i, _)| !visible.contains(i))
+        .find_map(|(_, wt)| collapsed(wt));
+    let below = || {
+        visible
+            .iter()
+            .all(|&i| i <= sel)
+            .then(|| worktrees[sel + 1..].iter().find_map(collapsed))
+            .flatten()
+    };
+    above
+        .or_else(below)
+        .or_else(|| group_of(current, layout.group))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repository::WorktreeMetadata;
+
+    fn worktree(branch: &str) -> Worktree {
+        Worktree {
+            path: format!("/hub/{}", branch.replace('/', "-")),
+            commit: "abc1234".into(),
+            branch: branch.into(),
+            is_bare: false,
+            is_detached: false,
+            status_summary: Some("clean".into()),
+            size_bytes: 0,
+            metadata: None,
+            upstream: None,
+            contexts: Vec::new(),
+            sync_drift: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_group_of_and_expanded_indices() {
+        let pinned = Worktree {
+            metadata: Some(WorktreeMetadata {
+                pinned: true,
+                ..Default::default()
+            }),
+            ..worktree("fix/crash")
+        };
+        let worktrees = vec![
+            pinned,
+            worktree("feat/login"),
+            worktree("fix/typo"),
+            worktree("main"),
+        ];
+        let groups: Vec<_> = worktrees
+            .iter()
+            .map(|wt| group_of(wt, GroupMode::Prefix))
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                Some(PINNED_GROUP),
+                Some("feat"),
+                Some("fix"),
+                Some(UNGROUPED)
+            ]
+        );
+        assert_eq!(group_of(&worktrees[1], GroupMode::None), None);
+
+        let layout = ListLayout {
+            group: GroupMode::Prefix,
+            collapsed: vec!["fix".into()],
+            ..ListLayout::default()
+        };
+        // Collapsing `fix` hides its member but never the pinned worktree.
+        assert_eq!(expanded_indices(&worktrees, &layout), [0, 1, 3]);
+    }
+
+    #[test]
+    fn test_group_to_toggle_reopens_the_adjacent_collapsed_group() {
+        let worktrees = vec![
+            worktree("feat/a"),
+            worktree("feat/b"),
+            worktree("fix/c"),
+            worktree("main"),
+        ];
+        let mut layout = ListLayout {
+            group: GroupMode::Prefix,
+            ..ListLayout::default()
+        };
+        let visible = expanded_indices(&worktrees, &layout);
+        assert_eq!(
+            group_to_toggle(&worktrees, &visible, Some(1), &layout),
+            Some("feat")
+        );
+
+        layout.toggle_collapsed("fix");
+        let visible = expanded_indices(&worktrees, &layout);
+        // `main` is listed right after the collapsed `fix` header; `feat/b` is not.
+        assert_eq!(
+            group_to_toggle(&worktrees, &visible, Some(3), &layout),
+            Some("fix")
+        );
+        assert_eq!(
+            group_to_toggle(&worktrees, &visible, Some(1), &layout),
+            Some("feat")
+        );
+
+        // On the last visible row, a collapsed group below it counts too.
+        layout.toggle_collapsed("fix");
+        layout.toggle_collapsed(UNGROUPED);
+        let visible = expanded_indices(&worktrees, &layout);
+        assert_eq!(
+            group_to_toggle(&worktrees, &visible, Some(2), &layout),
+            Some(UNGROUPED)
+        );
+
+        layout.toggle_collapsed("feat");
+        layout.toggle_collapsed("fix");
+        let visible = expanded_indices(&worktrees, &layout);
+        assert!(visible.is_empty());
+        assert_eq!(
+            group_to_toggle(&worktrees, &visible, None, &layout),
+            Some("feat")
+        );
+    }
+
+    #[test]
+    fn test_cycle_modes() {
+        assert_eq!(SortMode::AheadBehind.next(), SortMode::Default);
+        assert_eq!(SortMode::Default.next(), SortMode::Name);
+        assert_eq!(GroupMode::Purpose.next(), GroupMode::None);
+    }
+}
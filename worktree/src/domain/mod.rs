@@ -1 +1,12 @@
+pub mod artifacts;
+pub mod cache;
+pub mod context;
+pub mod env;
+pub mod issues;
+pub mod list_layout;
+pub mod port;
 pub mod repository;
+pub mod resources;
+pub mod stale;
+pub mod sync;
+pub mod templates;
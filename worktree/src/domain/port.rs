@@ -0,0 +1,52 @@
+use serde::Serialize;
+
+use super::repository::ProjectRepository;
+
+/// Where a port stopped because a cherry-pick could not be applied cleanly.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PortConflict {
+    /// The commit whose cherry-pick is left in progress in the target worktree.
+    pub commit: String,
+    /// Commits after `commit` that were not attempted.
+    pub remaining: Vec<String>,
+    pub error: String,
+    /// Diff of the conflicted files, for explanation.
+    pub diff: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PortOutcome {
+    /// Commits applied to the target, in order.
+    pub ported: Vec<String>,
+    pub conflict: Option<PortConflict>,
+}
+
+/// Cherry-picks `hashes` (oldest first) onto the branch checked out at `target`.
+///
+/// Stops at the first commit that fails and leaves that cherry-pick in progress so it can be
+/// resolved with `git cherry-pick --continue` or dropped with `--abort`.
+pub fn port_commits<R: ProjectRepository + ?Sized>(
+    repo: &R,
+    target: &str,
+    hashes: &[String],
+) -> PortOutcome {
+    let mut ported = Vec::new();
+    for (i, hash) in hashes.iter().enumerate() {
+        if let Err(e) = repo.cherry_pick(target, hash) {
+            return PortOutcome {
+                ported,
+                conflict: Some(PortConflict {
+                    commit: hash.clone(),
+                    remaining: hashes[i + 1..].to_vec(),
+                    error: format!("{e:#}"),
+                    diff: repo.get_conflict_diff(target).unwrap_or_default(),
+                }),
+            };
+        }
+        ported.push(hash.clone());
+    }
+    PortOutcome {
+        ported,
+        conflict: None,
+    }
+}
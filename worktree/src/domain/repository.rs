@@ -1,5 +1,14 @@
-use anyhow::Result;
+use super::artifacts::{Artifact, ArtifactConfig};
+use super::cache::{CacheConfig, CacheStatus};
+use super::context::ContextKind;
+use super::issues::{IssueConfig, IssueLink};
+use super::list_layout::ListLayout;
+use super::resources::{ResourceConfig, Resources};
+use super::sync::{DriftState, SyncDrift};
+use super::templates::Templates;
+use anyhow::{Result, anyhow};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
 /// Custom attributes for a worktree.
@@ -10,6 +19,134 @@ pub struct WorktreeMetadata {
     pub description: Option<String>,
     pub color: Option<String>,
     pub icon: Option<String>,
+    /// Ports and service names allocated by the hub; released on removal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resources: Option<Resources>,
+    /// Extra environment for `worktree exec` and `run` in this worktree.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// The ticket this worktree is for, found in its branch name or given to `add --issue`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issue: Option<IssueLink>,
+    /// The `templates:` entry the worktree was created from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// When the worktree is due for removal (RFC 3339).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    /// Pinned worktrees are listed first.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+}
+
+/// Colors accepted for `color`, besides `#rrggbb`.
+const COLOR_NAMES: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "gray",
+    "darkgray",
+    "lightred",
+    "lightgreen",
+    "lightyellow",
+    "lightblue",
+    "lightmagenta",
+    "lightcyan",
+    "white",
+];
+
+impl WorktreeMetadata {
+    /// Fields that `worktree meta` and the TUI edit form can change.
+    pub const FIELDS: [&'static str; 4] = ["purpose", "description", "color", "icon"];
+
+    /// Reads a field; `env.<KEY>` reads one variable of `env`.
+    pub fn get(&self, key: &str) -> Result<Option<String>> {
+        if let Some(var) = key.strip_prefix("env.") {
+            return Ok(self.env.get(var).cloned());
+        }
+        Ok(match key {
+            "created_at" => self.created_at.clone(),
+            "issue" => self.issue.as_ref().map(|issue| issue.id.clone()),
+            "template" => self.template.clone(),
+            "expires_at" => self.expires_at.clone(),
+            "pinned" => self.pinned.then(|| "true".to_string()),
+            "purpose" => self.purpose.clone(),
+            "description" => self.description.clone(),
+            "color" => self.color.clone(),
+            "icon" => self.icon.clone(),
+            _ => return Err(unknown_field(key)),
+        })
+    }
+
+    /// Sets or, with `None`, clears a field. Blank values clear it too.
+    pub fn set(&mut self, key: &str, value: Option<String>) -> Result<()> {
+        let value = value.filter(|v| !v.trim().is_empty());
+        if let Some(var) = key.strip_prefix("env.") {
+            if var.is_empty() {
+                return Err(anyhow!("Missing variable name after 'env.'"));
+            }
+            match value {
+                Some(value) => self.env.insert(var.to_string(), value),
+                None => self.env.remove(var),
+            };
+            return Ok(());
+        }
+        if key == "pinned" {
+            self.pinned = match value.as_deref().map(str::trim) {
+                None | Some("false") => false,
+                Some("true") => true,
+                Some(other) => {
+                    return Err(anyhow!(
+                        "Invalid value '{other}' for 'pinned'. HELP: Use true or false."
+                    ));
+                }
+            };
+            return Ok(());
+        }
+        let field = match key {
+            "purpose" => &mut self.purpose,
+            "description" => &mut self.description,
+            "icon" => &mut self.icon,
+            "color" => {
+                if let Some(color) = &value
+                    && !is_valid_color(color)
+                {
+                    return Err(anyhow!(
+                        "Invalid color '{color}'. HELP: Use #rrggbb or one of: {}.",
+                        COLOR_NAMES.join(", ")
+                    ));
+                }
+                &mut self.color
+            }
+            "created_at" | "issue" | "template" | "expires_at" => {
+                return Err(anyhow!("'{key}' is recorded automatically."));
+            }
+            _ => return Err(unknown_field(key)),
+        };
+        *field = value;
+        Ok(())
+    }
+}
+
+fn unknown_field(key: &str) -> anyhow::Error {
+    anyhow!(
+        "Unknown metadata field '{key}'. HELP: Use one of {}, pinned, created_at, issue, template, expires_at or env.<KEY>.",
+        WorktreeMetadata::FIELDS.join(", ")
+    )
+}
+
+/// `#rrggbb` or a terminal color name such as `cyan` or `lightblue`.
+pub fn is_valid_color(color: &str) -> bool {
+    let color = color.to_ascii_lowercase();
+    color
+        .strip_prefix('#')
+        .map_or(COLOR_NAMES.contains(&color.as_str()), |hex| {
+            hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit())
+        })
 }
 
 /// Represents a Git worktree and its current state.
@@ -31,6 +168,42 @@ pub struct Worktree {
     pub size_bytes: u64,
     /// Custom metadata for this worktree.
     pub metadata: Option<WorktreeMetadata>,
+    /// The branch's configured upstream, e.g. `upstream/main`.
+    pub upstream: Option<String>,
+    /// Stacks detected at the worktree root; filled in by [`annotate_worktrees`].
+    pub contexts: Vec<ContextKind>,
+    /// `.worktrees.sync` entries that no longer match the hub; filled in by
+    /// [`annotate_worktrees`].
+    pub sync_drift: Vec<SyncDrift>,
+}
+
+impl Worktree {
+    /// The ref to compare against: the branch, or the commit when HEAD is detached.
+    pub fn head_ref(&self) -> &str {
+        if self.is_detached || self.branch.is_empty() {
+            &self.commit
+        } else {
+            &self.branch
+        }
+    }
+}
+
+/// Detects stacks and sync drift for the dashboard and `worktree list`. Drift hashes every
+/// synced file, so `list_worktrees`, which also resolves names for other commands, leaves both
+/// empty.
+pub fn annotate_worktrees<R: ProjectRepository + ?Sized>(repo: &R, worktrees: &mut [Worktree]) {
+    for wt in worktrees
+        .iter_mut()
+        .filter(|wt| !wt.is_bare && !wt.path.is_empty())
+    {
+        wt.contexts = super::context::detect_contexts(Path::new(&wt.path));
+        wt.sync_drift = repo
+            .check_sync(&wt.path)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|drift| drift.state != DriftState::InSync)
+            .collect();
+    }
 }
 
 /// Detailed git status of a specific worktree.
@@ -51,13 +224,244 @@ pub struct GitCommit {
     pub graph: String,
 }
 
-/// The architectural context of the project.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
-pub enum ProjectContext {
-    /// A standard project with no specialized sync logic.
-    Standard,
-    /// A Kotlin Multiplatform or Android project requiring specific properties sync.
-    KmpAndroid,
+/// Which slice of the log to load and how to filter it (`git log` semantics).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HistoryQuery {
+    /// Maximum number of commits to return.
+    pub limit: usize,
+    /// Number of matching commits to skip, for pagination.
+    pub skip: usize,
+    /// Only commits whose message matches (`--grep`, case-insensitive).
+    pub grep: Option<String>,
+    /// Only commits by a matching author (`--author`).
+    pub author: Option<String>,
+    /// Only commits touching this path (`-- <path>`).
+    pub path: Option<String>,
+    /// Only commits after this date (`--since`, e.g. `2026-01-01` or `2 weeks ago`).
+    pub since: Option<String>,
+    /// Only commits before this date (`--until`).
+    pub until: Option<String>,
+    /// Include the ASCII commit graph.
+    pub graph: bool,
+}
+
+impl Default for HistoryQuery {
+    fn default() -> Self {
+        Self {
+            limit: Self::PAGE_SIZE,
+            skip: 0,
+            grep: None,
+            author: None,
+            path: None,
+            since: None,
+            until: None,
+            graph: true,
+        }
+    }
+}
+
+impl HistoryQuery {
+    /// Commits loaded per page in the history view.
+    pub const PAGE_SIZE: usize = 50;
+
+    /// The latest `limit` commits, unfiltered.
+    pub fn latest(limit: usize) -> Self {
+        Self {
+            limit,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_filtered(&self) -> bool {
+        self.grep.is_some()
+            || self.author.is_some()
+            || self.path.is_some()
+            || self.since.is_some()
+            || self.until.is_some()
+    }
+
+    /// Parses a search line like `fix login author:jane path:src/ since:2026-01-01`.
+    ///
+    /// `author:`, `path:`, `since:` and `until:` set their filter; the remaining words form the
+    /// message search. Limit, skip and graph are left untouched.
+    pub fn apply_search(&mut self, input: &str) {
+        self.grep = None;
+        self.author = None;
+        self.path = None;
+        self.since = None;
+        self.until = None;
+        let mut words = Vec::new();
+        for word in input.split_whitespace() {
+            let (slot, value) = match word.split_once(':') {
+                Some(("author", v)) => (&mut self.author, v),
+                Some(("path", v)) => (&mut self.path, v),
+                Some(("since", v)) => (&mut self.since, v),
+                Some(("until", v)) => (&mut self.until, v),
+                _ => {
+                    words.push(word);
+                    continue;
+                }
+            };
+            if !value.is_empty() {
+                *slot = Some(value.to_string());
+            }
+        }
+        if !words.is_empty() {
+            self.grep = Some(words.join(" "));
+        }
+    }
+
+    /// The inverse of [`Self::apply_search`], used to pre-fill the search prompt.
+    pub fn search_text(&self) -> String {
+        let filters = [
+            ("author:", &self.author),
+            ("path:", &self.path),
+            ("since:", &self.since),
+            ("until:", &self.until),
+        ];
+        self.grep
+            .iter()
+            .cloned()
+            .chain(
+                filters
+                    .iter()
+                    .filter_map(|(key, value)| value.as_ref().map(|v| format!("{key}{v}"))),
+            )
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// A file touched by a diff, with the first line it changed on the new side.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChangedFile {
+    /// Path relative to the worktree root (the new path for renames).
+    pub path: String,
+    /// Change kind: `A`, `M`, `D` or `R`.
+    pub status: char,
+    /// First changed line, if the diff has a hunk for this file.
+    pub line: Option<usize>,
+}
+
+/// Line counts for one file in a commit (`None` for binary files).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileStat {
+    pub path: String,
+    pub additions: Option<usize>,
+    pub deletions: Option<usize>,
+}
+
+/// Everything the commit detail view shows about a single commit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommitDetail {
+    /// Full object name.
+    pub hash: String,
+    /// `Name <email>` of the author.
+    pub author: String,
+    pub author_date: String,
+    /// `Name <email>` of the committer.
+    pub committer: String,
+    pub committer_date: String,
+    /// Full hashes of the parent commits.
+    pub parents: Vec<String>,
+    /// Branches and tags pointing at the commit (e.g. `origin/main`, `tag: v1.0`).
+    pub refs: Vec<String>,
+    pub subject: String,
+    pub body: String,
+    pub files: Vec<FileStat>,
+    /// The patch, uncolored.
+    pub diff: String,
+}
+
+/// What two refs (usually the branches of two worktrees) have that the other does not.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RefComparison {
+    pub left: String,
+    pub right: String,
+    /// Full hash of the best common ancestor, if the histories are related.
+    pub merge_base: Option<String>,
+    /// Commits reachable from `left` but not `right` (`right..left`).
+    pub left_only: Vec<GitCommit>,
+    /// Commits reachable from `right` but not `left` (`left..right`).
+    pub right_only: Vec<GitCommit>,
+    /// Per-file stats of `right...left`, i.e. what merging `left` would bring into `right`.
+    pub files: Vec<FileStat>,
+    /// The aggregate patch of `right...left`, uncolored.
+    pub diff: String,
+}
+
+/// A configured git remote.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Remote {
+    pub name: String,
+    pub fetch_url: String,
+    pub push_url: String,
+}
+
+/// A local or remote-tracking branch as listed by the branch manager.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BranchInfo {
+    /// Short name, e.g. `main` or `origin/main`.
+    pub name: String,
+    /// The remote of a remote-tracking branch; `None` for local branches.
+    pub remote: Option<String>,
+    /// Short hash of the tip commit.
+    pub tip: String,
+    pub subject: String,
+    /// Committer date of the tip (`YYYY-MM-DD`).
+    pub date: String,
+    /// Committer date of the tip as a Unix timestamp.
+    pub timestamp: i64,
+    /// The configured upstream, e.g. `origin/main`.
+    pub upstream: Option<String>,
+    /// Commits not on the upstream yet.
+    pub ahead: usize,
+    /// Commits on the upstream not merged yet.
+    pub behind: usize,
+    /// Whether the upstream is configured but was deleted from the remote.
+    pub upstream_gone: bool,
+    /// The worktree that has the branch checked out.
+    pub worktree: Option<String>,
+}
+
+impl BranchInfo {
+    pub fn is_remote(&self) -> bool {
+        self.remote.is_some()
+    }
+
+    /// The branch name without its remote, i.e. what a local tracking branch would be called.
+    pub fn local_name(&self) -> &str {
+        self.remote
+            .as_deref()
+            .and_then(|remote| self.name.strip_prefix(remote))
+            .and_then(|rest| rest.strip_prefix('/'))
+            .unwrap_or(&self.name)
+    }
+}
+
+/// The stacks detected at the project root; empty for a standard project.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Default)]
+pub struct ProjectContext {
+    pub kinds: Vec<ContextKind>,
+}
+
+impl ProjectContext {
+    pub fn has(&self, kind: ContextKind) -> bool {
+        self.kinds.contains(&kind)
+    }
+
+    /// Short label for the header, e.g. `STD_GIT` or `NODE+RUST`.
+    pub fn label(&self) -> String {
+        if self.kinds.is_empty() {
+            "STD_GIT".to_string()
+        } else {
+            self.kinds
+                .iter()
+                .map(|k| k.label())
+                .collect::<Vec<_>>()
+                .join("+")
+        }
+    }
 }
 
 /// Represents a Git stash entry.
@@ -68,6 +472,168 @@ pub struct StashEntry {
     pub branch: String,
 }
 
+/// An editor the worktree (or a file inside it) can be opened with.
+///
+/// Arguments are templates: `{path}` is the worktree root, `{file}` the file to open and
+/// `{line}` the line to jump to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditorConfig {
+    pub name: String,
+    /// The executable to run.
+    pub command: String,
+    /// Arguments used to open a worktree.
+    #[serde(default = "EditorConfig::default_args")]
+    pub args: Vec<String>,
+    /// Arguments used to open a file at a line. Falls back to `args` when empty.
+    #[serde(default)]
+    pub goto_args: Vec<String>,
+    /// Whether the editor runs inside the terminal (the TUI is suspended while it runs).
+    #[serde(default)]
+    pub terminal: bool,
+}
+
+impl EditorConfig {
+    fn default_args() -> Vec<String> {
+        vec!["{path}".into()]
+    }
+
+    fn new(name: &str, command: &str, goto_args: &[&str], terminal: bool) -> Self {
+        Self {
+            name: name.into(),
+            command: command.into(),
+            args: Self::default_args(),
+            goto_args: goto_args.iter().map(|a| (*a).to_string()).collect(),
+            terminal,
+        }
+    }
+
+    /// Built-in editors, offered when installed.
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::new("VS Code", "code", &["{path}", "-g", "{file}:{line}"], false),
+            Self::new(
+                "Cursor",
+                "cursor",
+                &["{path}", "-g", "{file}:{line}"],
+                false,
+            ),
+            Self::new("Zed", "zed", &["{file}:{line}"], false),
+            Self::new(
+                "Android Studio",
+                "studio",
+                &["--line", "{line}", "{file}"],
+                false,
+            ),
+            Self::new(
+                "IntelliJ IDEA",
+                "idea",
+                &["--line", "{line}", "{file}"],
+                false,
+            ),
+            Self::new("Vim", "vim", &["+{line}", "{file}"], true),
+            Self::new("Neovim", "nvim", &["+{line}", "{file}"], true),
+            Self::new("Helix", "hx", &["{file}:{line}"], true),
+            Self::new(
+                "Antigravity",
+                "antigravity",
+                &["{path}", "-g", "{file}:{line}"],
+                false,
+            ),
+        ]
+    }
+
+    /// Editor for a raw command line such as `$EDITOR` (`"code --wait"`), assumed to be a
+    /// terminal editor that understands `+{line}` unless it matches a known editor.
+    pub fn from_command_line(command_line: &str, known: &[Self]) -> Option<Self> {
+        let mut parts = command_line.split_whitespace();
+        let program = parts.next()?;
+        let extra: Vec<String> = parts.map(str::to_string).collect();
+        let base = Path::new(program)
+            .file_name()
+            .map_or(program.to_string(), |n| n.to_string_lossy().to_string());
+
+        let mut editor = known
+            .iter()
+            .find(|e| e.command == base)
+            .cloned()
+            .unwrap_or_else(|| Self::new(&base, program, &["+{line}", "{file}"], true));
+        editor.command = program.to_string();
+        if !extra.is_empty() {
+            editor.args = extra.iter().cloned().chain(editor.args).collect();
+            editor.goto_args = extra.into_iter().chain(editor.goto_args).collect();
+        }
+        Some(editor)
+    }
+}
+
+/// What to open in an editor.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EditorTarget {
+    /// The worktree root.
+    pub path: String,
+    /// Absolute path of a file to open, if any.
+    pub file: Option<String>,
+    /// Line to jump to inside `file`.
+    pub line: Option<usize>,
+}
+
+impl EditorTarget {
+    pub fn worktree(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Expands the editor's argument templates for this target.
+    pub fn render_args(&self, editor: &EditorConfig) -> Vec<String> {
+        let (template, file) = match &self.file {
+            Some(file) if !editor.goto_args.is_empty() => (&editor.goto_args, file.as_str()),
+            Some(file) => (&editor.args, file.as_str()),
+            None => (&editor.args, self.path.as_str()),
+        };
+        // Without a goto template the file itself stands in for `{path}`.
+        let path = if editor.goto_args.is_empty() {
+            file
+        } else {
+            self.path.as_str()
+        };
+        let line = self.line.unwrap_or(1).to_string();
+        template
+            .iter()
+            .map(|a| {
+                a.replace("{path}", path)
+                    .replace("{file}", file)
+                    .replace("{line}", &line)
+            })
+            .collect()
+    }
+}
+
+/// Per-project settings stored in the hub root (`.worktrees.yaml`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ProjectConfig {
+    /// Preferred editor (name or command) for this project.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+    /// Extra and excluded paths for `clean --artifacts`.
+    #[serde(skip_serializing_if = "ArtifactConfig::is_empty")]
+    pub artifacts: ArtifactConfig,
+    /// Build caches shared between worktrees.
+    #[serde(skip_serializing_if = "CacheConfig::is_default")]
+    pub cache: CacheConfig,
+    /// Port blocks and service names allocated to each worktree.
+    #[serde(skip_serializing_if = "ResourceConfig::is_default")]
+    pub resources: ResourceConfig,
+    /// Ticket ids in branch names, their URLs and the `add --issue` naming templates.
+    #[serde(skip_serializing_if = "IssueConfig::is_default")]
+    pub issues: IssueConfig,
+    /// Named setups for `worktree add --template`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub templates: Templates,
+}
+
 /// The core abstraction for interacting with Git and project-specific configurations.
 pub trait ProjectRepository {
     /// Initializes a new bare repository at the specified project name directory.
@@ -76,27 +642,65 @@ pub trait ProjectRepository {
     fn add_worktree(&self, path: &str, branch: &str) -> Result<()>;
     /// Creates a new branch from a base and adds it as a worktree.
     fn add_new_worktree(&self, path: &str, branch: &str, base: &str) -> Result<()>;
+    /// Adds a worktree with `base` checked out and no branch.
+    fn add_detached_worktree(&self, path: &str, base: &str) -> Result<()>;
     /// Removes a worktree and its associated files.
     fn remove_worktree(&self, path: &str, force: bool) -> Result<()>;
     /// Lists all worktrees managed by the current bare repository.
     fn list_worktrees(&self) -> Result<Vec<Worktree>>;
     /// Synchronizes configuration files (symlinks/copies) to the target worktree.
     fn sync_configs(&self, path: &str) -> Result<()>;
+    /// Applies extra `.worktrees.sync` lines, such as a template's `sync`, to the worktree.
+    fn sync_entries(&self, path: &str, entries: &[String]) -> Result<()>;
+    /// Compares each `.worktrees.sync` entry in the worktree with its hub source.
+    fn check_sync(&self, path: &str) -> Result<Vec<SyncDrift>>;
+    /// Re-syncs only the given destinations (relative to the worktree), overwriting them.
+    fn fix_sync(&self, path: &str, destinations: &[String]) -> Result<()>;
+    /// Unified diff from the worktree's copy of `destination` to what a sync would write.
+    fn sync_diff(&self, path: &str, destination: &str) -> Result<String>;
+    /// Environment for commands run inside the worktree: the hub `.env`, shared cache settings,
+    /// stack settings, allocated ports, the worktree's own `env` and `WORKTREE_NAME/BRANCH/ROOT`.
+    fn worktree_env(&self, path: &str) -> Result<Vec<(String, String)>>;
+    /// Looks up a ticket with the configured issue provider.
+    fn lookup_issue(&self, id: &str) -> Result<IssueLink>;
+    /// The worktree's stored metadata (empty when it has none).
+    fn worktree_metadata(&self, path: &str) -> Result<WorktreeMetadata>;
+    /// Replaces the worktree's stored metadata.
+    fn update_worktree_metadata(&self, path: &str, metadata: &WorktreeMetadata) -> Result<()>;
+    /// The worktree list's sort order, grouping and collapsed groups saved for the hub.
+    fn list_layout(&self) -> ListLayout;
+    /// Saves the layout chosen in the TUI next to the worktree metadata.
+    fn save_list_layout(&self, layout: &ListLayout) -> Result<()>;
+    /// Branches (or directory names) of worktrees whose expiry passed before `now`. Reads only
+    /// the metadata store, so it is cheap enough to run before every command.
+    fn expired_worktrees(&self, now: i64) -> Result<Vec<String>>;
     /// Detects the project context (e.g., Standard vs KMP).
     fn detect_context(&self, base_path: &Path) -> ProjectContext;
     /// Retrieves the user's preferred editor for opening worktrees.
     fn get_preferred_editor(&self) -> Result<Option<String>>;
     /// Persists the user's preferred editor command.
     fn set_preferred_editor(&self, editor: &str) -> Result<()>;
+    /// Lists built-in and user-defined editors (user definitions override by name).
+    fn list_editors(&self) -> Result<Vec<EditorConfig>>;
+    /// Loads the project configuration from the hub root (defaults when absent).
+    fn get_project_config(&self) -> Result<ProjectConfig>;
+    /// Persists the project configuration to the hub root.
+    fn save_project_config(&self, config: &ProjectConfig) -> Result<()>;
 
     // --- Git Operations ---
 
-    /// Fetches all remotes and prunes stale branches.
-    fn fetch(&self, path: &str) -> Result<()>;
-    /// Pulls changes from the remote repository.
-    fn pull(&self, path: &str) -> Result<()>;
-    /// Pushes committed changes to the remote repository.
-    fn push(&self, path: &str) -> Result<()>;
+    /// Fetches `remote` (or every remote) and prunes stale branches.
+    fn fetch(&self, path: &str, remote: Option<&str>) -> Result<()>;
+    /// Pulls the current branch, from its upstream or from the same branch on `remote`.
+    fn pull(&self, path: &str, remote: Option<&str>) -> Result<()>;
+    /// Pushes the current branch to its upstream or to the same branch on `remote`.
+    fn push(&self, path: &str, remote: Option<&str>) -> Result<()>;
+    /// Lists the configured remotes.
+    fn list_remotes(&self) -> Result<Vec<Remote>>;
+    /// Adds a remote and fetches its branches.
+    fn add_remote(&self, name: &str, url: &str) -> Result<()>;
+    /// Removes a remote along with its remote-tracking branches.
+    fn remove_remote(&self, name: &str) -> Result<()>;
     /// Retrieves the porcelain status for the given worktree path.
     fn get_status(&self, path: &str) -> Result<GitStatus>;
     /// Stages all changes (modified and untracked).
@@ -113,10 +717,55 @@ pub trait ProjectRepository {
     fn get_diff(&self, path: &str) -> Result<String>;
     /// Generates a conventional commit message using AI (Gemini).
     fn generate_commit_message(&self, diff: &str, branch: &str) -> Result<String>;
-    /// Retrieves the recent commit history.
-    fn get_history(&self, path: &str, limit: usize) -> Result<Vec<GitCommit>>;
+    /// Retrieves a page of commit history matching the query.
+    fn get_history(&self, path: &str, query: &HistoryQuery) -> Result<Vec<GitCommit>>;
+    /// Lists the files changed by a commit.
+    fn get_commit_files(&self, path: &str, hash: &str) -> Result<Vec<ChangedFile>>;
+    /// Loads the full message, people, parents, refs, stats and patch of a commit.
+    fn get_commit_detail(&self, path: &str, hash: &str) -> Result<CommitDetail>;
+    /// Cherry-picks a commit onto the branch checked out at `path`.
+    fn cherry_pick(&self, path: &str, hash: &str) -> Result<()>;
+    /// Creates a commit reverting `hash` on the branch checked out at `path`.
+    fn revert_commit(&self, path: &str, hash: &str) -> Result<()>;
+    /// Expands a commit or range (`A..B`, `A^!`) into commit hashes, oldest first, skipping merges.
+    fn resolve_commit_range(&self, path: &str, range: &str) -> Result<Vec<String>>;
+    /// Checks that `hashes` (oldest first) are a contiguous first-parent run above the root of
+    /// the branch checked out at `path`, and that the worktree has no uncommitted changes, so
+    /// [`Self::drop_commits`] can remove them.
+    fn check_droppable(&self, path: &str, hashes: &[String]) -> Result<()>;
+    /// Removes a contiguous run of commits (oldest first) from the branch checked out at `path`.
+    fn drop_commits(&self, path: &str, hashes: &[String]) -> Result<()>;
+    /// Compares two refs: unique commits on each side, merge base and aggregate diff.
+    fn compare_refs(&self, path: &str, left: &str, right: &str) -> Result<RefComparison>;
+    /// Finds the first changed line of a file in the working tree (or the index when `staged`).
+    fn first_changed_line(&self, path: &str, file: &str, staged: bool) -> Result<Option<usize>>;
     /// Lists all available local branches.
     fn list_branches(&self) -> Result<Vec<String>>;
+    /// Lists local and remote-tracking branches with tip, upstream and checkout details.
+    fn list_branch_info(&self) -> Result<Vec<BranchInfo>>;
+    /// Creates a branch at `base` without checking it out.
+    fn create_branch(&self, name: &str, base: &str) -> Result<()>;
+    /// Renames a local branch (worktrees that have it checked out follow).
+    fn rename_branch(&self, from: &str, to: &str) -> Result<()>;
+    /// Deletes a local branch whether or not it is merged; see [`Self::branch_unmerged_into`].
+    fn delete_branch(&self, name: &str) -> Result<()>;
+    /// The ref `branch` still has commits missing from (its upstream, else the default
+    /// branch), or `None` when it is fully merged.
+    fn branch_unmerged_into(&self, branch: &str) -> Result<Option<String>>;
+    /// Sets the upstream a local branch tracks.
+    fn set_upstream(&self, branch: &str, upstream: &str) -> Result<()>;
+    /// The branch the hub's HEAD points at (usually `main`).
+    fn default_branch(&self) -> Result<String>;
+    /// Local branches whose tip is reachable from `target`.
+    fn merged_branches(&self, target: &str) -> Result<Vec<String>>;
+    /// Unix time of the last commit, checkout or staging in the worktree at `path`.
+    fn last_activity(&self, path: &str) -> Result<i64>;
+    /// Finds untracked build artifacts in the worktree at `path` using the project's rules.
+    fn scan_artifacts(&self, path: &str) -> Result<Vec<Artifact>>;
+    /// Deletes `artifacts` found in the worktree at `path`; each must lie inside it.
+    fn remove_artifacts(&self, path: &str, artifacts: &[Artifact]) -> Result<()>;
+    /// Reports the shared build caches enabled for the project and the worktrees using them.
+    fn cache_status(&self) -> Result<Vec<CacheStatus>>;
     /// Switches the worktree to a different branch.
     fn switch_branch(&self, path: &str, branch: &str) -> Result<()>;
     /// Rebases the worktree onto the specified upstream branch.
@@ -216,4 +865,54 @@ mod tests {
         assert_eq!(decoded.purpose, Some("Feature: Login UI".to_string()));
         assert_eq!(decoded.created_at, Some("2023-10-27".to_string()));
     }
+
+    #[test]
+    fn test_worktree_metadata_set_and_get() {
+        let mut meta = WorktreeMetadata::default();
+        meta.set("purpose", Some("Login UI".into())).unwrap();
+        meta.set("color", Some("#FF8800".into())).unwrap();
+        meta.set("env.API_URL", Some("http://localhost".into()))
+            .unwrap();
+        assert_eq!(meta.get("purpose").unwrap().as_deref(), Some("Login UI"));
+        assert_eq!(meta.get("color").unwrap().as_deref(), Some("#FF8800"));
+        assert_eq!(
+            meta.get("env.API_URL").unwrap().as_deref(),
+            Some("http://localhost")
+        );
+
+        assert!(meta.set("color", Some("mauve".into())).is_err());
+        assert!(meta.set("created_at", Some("now".into())).is_err());
+        assert!(meta.get("owner").is_err());
+
+        meta.set("pinned", Some("true".into())).unwrap();
+        assert_eq!(meta.get("pinned").unwrap().as_deref(), Some("true"));
+        assert!(meta.set("pinned", Some("maybe".into())).is_err());
+        meta.set("pinned", None).unwrap();
+        assert!(!meta.pinned);
+
+        meta.set("purpose", None).unwrap();
+        meta.set("env.API_URL", Some("  ".into())).unwrap();
+        assert_eq!(meta.get("purpose").unwrap(), None);
+        assert!(meta.env.is_empty());
+    }
+
+    #[test]
+    fn test_history_query_search_round_trip() {
+        let mut query = HistoryQuery::latest(20);
+        query.apply_search("fix  login author:jane path:src/app since:2026-01-01 until:");
+        assert_eq!(query.grep.as_deref(), Some("fix login"));
+        assert_eq!(query.author.as_deref(), Some("jane"));
+        assert_eq!(query.path.as_deref(), Some("src/app"));
+        assert_eq!(query.since.as_deref(), Some("2026-01-01"));
+        assert_eq!(query.until, None);
+        assert_eq!(query.limit, 20);
+        assert!(query.is_filtered());
+        assert_eq!(
+            query.search_text(),
+            "fix login author:jane path:src/app since:2026-01-01"
+        );
+
+        query.apply_search("  ");
+        assert!(!query.is_filtered());
+    }
 }
@@ -0,0 +1,202 @@
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+/// Port and service allocation settings in `.worktrees.yaml`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResourceConfig {
+    /// Set to `false` to stop allocating resources to new worktrees.
+    pub enabled: bool,
+    /// First port handed out; blocks are carved from `port_start..port_end`.
+    pub port_start: u16,
+    pub port_end: u16,
+    /// Consecutive ports reserved per worktree.
+    pub block_size: u16,
+    /// Derive a database name (`<project>_<worktree>`) for each worktree.
+    pub db_name: bool,
+    /// Derive a docker compose project name (`<project>-<worktree>`) for each worktree.
+    pub compose_project: bool,
+}
+
+impl Default for ResourceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            port_start: 20_000,
+            port_end: 30_000,
+            block_size: 10,
+            db_name: false,
+            compose_project: false,
+        }
+    }
+}
+
+impl ResourceConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// What the hub allocated to one worktree; stored in its metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Resources {
+    pub port_base: u16,
+    pub port_count: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub db_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compose_project: Option<String>,
+}
+
+impl Resources {
+    /// Last port of the block.
+    pub const fn port_end(&self) -> u16 {
+        self.port_base + self.port_count.saturating_sub(1)
+    }
+
+    const fn overlaps(&self, other: &Self) -> bool {
+        self.port_base <= other.port_end() && other.port_base <= self.port_end()
+    }
+
+    /// e.g. `20110-20119`.
+    pub fn ports_label(&self) -> String {
+        format!("{}-{}", self.port_base, self.port_end())
+    }
+
+    /// Environment for commands run in the worktree.
+    pub fn env(&self) -> Vec<(String, String)> {
+        let mut env = vec![
+            ("PORT".to_string(), self.port_base.to_string()),
+            ("WORKTREE_PORT".to_string(), self.port_base.to_string()),
+            ("WORKTREE_PORT_END".to_string(), self.port_end().to_string()),
+        ];
+        if let Some(db) = &self.db_name {
+            env.push(("WORKTREE_DB_NAME".to_string(), db.clone()));
+        }
+        if let Some(project) = &self.compose_project {
+            // Read by docker compose itself, so containers and volumes stay per worktree.
+            env.push(("COMPOSE_PROJECT_NAME".to_string(), project.clone()));
+        }
+        env
+    }
+}
+
+/// Lowercase ASCII alphanumerics with every other run of characters replaced by `separator`.
+fn identifier(parts: &[&str], separator: char) -> String {
+    let mut out = String::new();
+    for c in parts.join(&separator.to_string()).chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+        } else if !out.is_empty() && !out.ends_with(separator) {
+            out.push(separator);
+        }
+    }
+    out.trim_end_matches(separator).to_string()
+}
+
+/// Picks a free port block for `name`, preferring the same block every time.
+///
+/// `taken` are the allocations of the other worktrees in the hub.
+pub fn allocate(
+    config: &ResourceConfig,
+    project: &str,
+    name: &str,
+    taken: &[Resources],
+) -> Result<Resources> {
+    let block = config.block_size.max(1);
+    let slots = config.port_end.saturating_sub(config.port_start) / block;
+    if slots == 0 {
+        return Err(anyhow!(
+            "resources.port_start..port_end leaves no room for a block of {block} ports"
+        ));
+    }
+    let preferred = super::sync::name_hash(name) % u64::from(slots);
+    let preferred = u16::try_from(preferred).unwrap_or(0);
+
+    (0..slots)
+        .map(|i| (preferred + i) % slots)
+        .map(|slot| Resources {
+            port_base: config.port_start + slot * block,
+            port_count: block,
+            db_name: config.db_name.then(|| identifier(&[project, name], '_')),
+            compose_project: config
+                .compose_project
+                .then(|| identifier(&[project, name], '-')),
+        })
+        .find(|candidate| !taken.iter().any(|t| t.overlaps(candidate)))
+        .ok_or_else(|| {
+            anyhow!(
+                "All {slots} port blocks between {} and {} are allocated. HELP: Remove unused worktrees or widen resources.port_end.",
+                config.port_start,
+                config.port_end
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allocate_is_stable_and_avoids_collisions() {
+        let config = ResourceConfig {
+            db_name: true,
+            compose_project: true,
+            ..ResourceConfig::default()
+        };
+        let first = allocate(&config, "My App", "feature/Login", &[]).unwrap();
+        assert_eq!(
+            first,
+            allocate(&config, "My App", "feature/Login", &[]).unwrap()
+        );
+        assert_eq!(first.port_count, 10);
+        assert_eq!((first.port_base - 20_000) % 10, 0);
+        assert_eq!(first.db_name.as_deref(), Some("my_app_feature_login"));
+        assert_eq!(
+            first.compose_project.as_deref(),
+            Some("my-app-feature-login")
+        );
+
+        let second = allocate(
+            &config,
+            "My App",
+            "feature/Login",
+            std::slice::from_ref(&first),
+        )
+        .unwrap();
+        assert_ne!(second.port_base, first.port_base);
+        assert!(!second.overlaps(&first));
+    }
+
+    #[test]
+    fn test_allocate_reports_exhaustion() {
+        let config = ResourceConfig {
+            port_start: 3000,
+            port_end: 3020,
+            ..ResourceConfig::default()
+        };
+        let a = allocate(&config, "p", "a", &[]).unwrap();
+        let b = allocate(&config, "p", "b", std::slice::from_ref(&a)).unwrap();
+        assert!(allocate(&config, "p", "c", &[a, b]).is_err());
+    }
+
+    #[test]
+    fn test_resources_env() {
+        let resources = Resources {
+            port_base: 20_110,
+            port_count: 10,
+            db_name: None,
+            compose_project: Some("app-feat".into()),
+        };
+        assert_eq!(resources.ports_label(), "20110-20119");
+        assert_eq!(
+            resources.env(),
+            vec![
+                ("PORT".to_string(), "20110".to_string()),
+                ("WORKTREE_PORT".to_string(), "20110".to_string()),
+                ("WORKTREE_PORT_END".to_string(), "20119".to_string()),
+                ("COMPOSE_PROJECT_NAME".to_string(), "app-feat".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,287 @@
+use serde::Serialize;
+use std::collections::HashSet;
+
+use super::repository::{ProjectRepository, Worktree, WorktreeMetadata};
+
+/// Inactivity threshold used by the TUI checklist.
+pub const DEFAULT_INACTIVE_DAYS: u64 = 30;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Whole days between `last_activity` and `now`, both Unix times.
+pub fn idle_days(now: i64, last_activity: i64) -> u64 {
+    u64::try_from((now - last_activity) / SECONDS_PER_DAY).unwrap_or(0)
+}
+
+/// Why a worktree is offered for cleanup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StaleReason {
+    /// The branch is fully contained in the default branch (or its upstream).
+    Merged,
+    /// The branch's upstream was deleted on the remote and pruned by `fetch --prune`.
+    UpstreamGone,
+    /// Nothing was committed, checked out or staged for longer than the threshold.
+    Inactive,
+    /// Its `expires_at` (from `add --ttl` or a template's `expire_after`) has passed.
+    Expired,
+}
+
+impl StaleReason {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Merged => "merged",
+            Self::UpstreamGone => "upstream gone",
+            Self::Inactive => "inactive",
+            Self::Expired => "expired",
+        }
+    }
+}
+
+/// Which checks to run; a worktree is a candidate when any selected check matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StaleCriteria {
+    pub merged: bool,
+    pub upstream_gone: bool,
+    pub inactive_days: Option<u64>,
+    pub expired: bool,
+}
+
+impl StaleCriteria {
+    pub const fn all(inactive_days: u64) -> Self {
+        Self {
+            merged: true,
+            upstream_gone: true,
+            inactive_days: Some(inactive_days),
+            expired: true,
+        }
+    }
+
+    /// Only worktrees past their expiry.
+    pub const fn expired() -> Self {
+        Self {
+            merged: false,
+            upstream_gone: false,
+            inactive_days: None,
+            expired: true,
+        }
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        !self.merged && !self.upstream_gone && self.inactive_days.is_none() && !self.expired
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StaleWorktree {
+    pub path: String,
+    pub branch: String,
+    pub reasons: Vec<StaleReason>,
+    pub size_bytes: u64,
+    /// Whether removing the worktree would discard uncommitted changes. A status that could
+    /// not be read counts as dirty.
+    pub dirty: bool,
+    pub status_summary: Option<String>,
+    pub idle_days: u64,
+}
+
+impl StaleWorktree {
+    /// The git status summary, or a note that it could not be read.
+    pub fn status_label(&self) -> &str {
+        self.status_summary.as_deref().unwrap_or("status unknown")
+    }
+
+    /// Joins the reasons for display, e.g. `merged, inactive 45d`.
+    pub fn reasons_label(&self) -> String {
+        self.reasons
+            .iter()
+            .map(|reason| match reason {
+                StaleReason::Inactive => format!("inactive {}d", self.idle_days),
+                other => other.label().to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Parses an age such as `30d`, `2w` or a bare number of days.
+pub fn parse_days(input: &str) -> Result<u64, String> {
+    let input = input.trim();
+    let (number, multiplier) = match input.char_indices().last() {
+        Some((i, 'd')) => (&input[..i], 1),
+        Some((i, 'w')) => (&input[..i], 7),
+        _ => (input, 1),
+    };
+    number
+        .parse::<u64>()
+        .map(|n| n * multiplier)
+        .map_err(|_| format!("invalid age '{input}', expected e.g. 30d or 2w"))
+}
+
+/// The `expires_at` value for a worktree that should live `days` from `now`.
+pub fn expiry_after(now: i64, days: u64) -> Option<String> {
+    let seconds = i64::try_from(days)
+        .unwrap_or(i64::MAX)
+        .saturating_mul(SECONDS_PER_DAY);
+    chrono::DateTime::from_timestamp(now.saturating_add(seconds), 0)
+        .map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+}
+
+/// Seconds until the worktree expires, negative once it has; `None` without an expiry.
+pub fn seconds_until_expiry(wt: &Worktree, now: i64) -> Option<i64> {
+    expires_in(wt.metadata.as_ref()?, now)
+}
+
+/// [`seconds_until_expiry`] for metadata that is not attached to a listed worktree.
+pub fn expires_in(metadata: &WorktreeMetadata, now: i64) -> Option<i64> {
+    let expires_at = metadata.expires_at.as_deref()?;
+    chrono::DateTime::parse_from_rfc3339(expires_at)
+        .ok()
+        .map(|t| t.timestamp() - now)
+}
+
+pub fn is_expired(wt: &Worktree, now: i64) -> bool {
+    seconds_until_expiry(wt, now).is_some_and(|left| left <= 0)
+}
+
+/// Time left in its largest unit, e.g. `3d`, `5h` or `12m`; `expired` once it ran out.
+pub fn format_countdown(seconds: i64) -> String {
+    match seconds {
+        ..=0 => "expired".to_string(),
+        1..3600 => format!("{}m", (seconds + 59) / 60),
+        3600..SECONDS_PER_DAY => format!("{}h", seconds / 3600),
+        _ => format!("{}d", seconds / SECONDS_PER_DAY),
+    }
+}
+
+/// Finds worktrees matching `criteria`, skipping the hub and the default branch's worktree.
+///
+/// A branch whose tip is the merge target's tip has nothing to merge yet (typically a fresh
+/// branch), so it is not reported as merged.
+pub fn find_stale_worktrees<R: ProjectRepository + ?Sized>(
+    repo: &R,
+    criteria: &StaleCriteria,
+    now: i64,
+) -> anyhow::Result<Vec<StaleWorktree>> {
+    let worktrees = repo.list_worktrees()?;
+    let branches = repo.list_branch_info()?;
+    let default_branch = repo.default_branch()?;
+    let tip_of = |name: &str| {
+        branches
+            .iter()
+            .find(|b| b.name == name)
+            .map(|b| b.tip.as_str())
+    };
+
+    let mut merged = HashSet::new();
+    let mut target_tips = Vec::new();
+    if criteria.merged {
+        let upstream = branches
+            .iter()
+            .find(|b| !b.is_remote() && b.name == default_branch)
+            .and_then(|b| b.upstream.clone());
+        for target in std::iter::once(default_branch.clone()).chain(upstream) {
+            merged.extend(repo.merged_branches(&target)?);
+            target_tips.extend(tip_of(&target));
+        }
+    }
+
+    let mut stale = Vec::new();
+    for wt in worktrees
+        .iter()
+        .filter(|wt| !wt.is_bare && wt.branch != default_branch)
+    {
+        let branch = (!wt.is_detached)
+            .then(|| {
+                branches
+                    .iter()
+                    .find(|b| !b.is_remote() && b.name == wt.branch)
+            })
+            .flatten();
+        let last_activity = repo.last_activity(&wt.path).unwrap_or(0);
+        let idle_days = idle_days(now, last_activity);
+
+        let mut reasons = Vec::new();
+        if criteria.merged
+            && let Some(branch) = branch
+            && merged.contains(&branch.name)
+            && !target_tips.contains(&branch.tip.as_str())
+        {
+            reasons.push(StaleReason::Merged);
+        }
+        if criteria.upstream_gone && branch.is_some_and(|b| b.upstream_gone) {
+            reasons.push(StaleReason::UpstreamGone);
+        }
+        if criteria.inactive_days.is_some_and(|days| idle_days >= days) {
+            reasons.push(StaleReason::Inactive);
+        }
+        if criteria.expired && is_expired(wt, now) {
+            reasons.push(StaleReason::Expired);
+        }
+        if reasons.is_empty() {
+            continue;
+        }
+
+        stale.push(StaleWorktree {
+            path: wt.path.clone(),
+            branch: wt.head_ref().to_string(),
+            reasons,
+            size_bytes: wt.size_bytes,
+            dirty: wt.status_summary.as_deref() != Some("clean"),
+            status_summary: wt.status_summary.clone(),
+            idle_days,
+        });
+    }
+    Ok(stale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repository::Worktree;
+
+    fn worktree(branch: &str, status: &str) -> Worktree {
+        Worktree {
+            path: format!("/hub/{branch}"),
+            commit: "abc1234".into(),
+            branch: branch.into(),
+            is_bare: false,
+            is_detached: false,
+            status_summary: Some(status.into()),
+            size_bytes: 2048,
+            metadata: None,
+            upstream: None,
+            contexts: Vec::new(),
+            sync_drift: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("30d"), Ok(30));
+        assert_eq!(parse_days("2w"), Ok(14));
+        assert_eq!(parse_days("7"), Ok(7));
+        assert!(parse_days("soon").is_err());
+    }
+
+    #[test]
+    fn test_expiry_countdown() {
+        let now = 1_800_000_000;
+        let later = Worktree {
+            metadata: Some(crate::domain::repository::WorktreeMetadata {
+                expires_at: expiry_after(now, 3),
+                ..Default::default()
+            }),
+            ..worktree("later", "clean")
+        };
+        assert_eq!(seconds_until_expiry(&later, now), Some(3 * SECONDS_PER_DAY));
+        assert_eq!(
+            seconds_until_expiry(&worktree("forever", "clean"), now),
+            None
+        );
+        assert_eq!(format_countdown(3 * SECONDS_PER_DAY), "3d");
+        assert_eq!(format_countdown(5 * 3600 + 10), "5h");
+        assert_eq!(format_countdown(61), "2m");
+        assert_eq!(format_countdown(0), "expired");
+    }
+}
@@ -0,0 +1,581 @@
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+/// What a `.worktrees.sync` entry does with its source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncAction {
+    Symlink,
+    /// Copies a file or, recursively, a directory.
+    Copy,
+    /// Copies a text file with `{{name}}`, `{{branch}}`, `{{port}}`, `{{path}}`, `{{hub}}`,
+    /// `{{db_name}}` and `{{compose_project}}` substituted.
+    Template,
+}
+
+/// `if branch=<glob>` / `if branch!=<glob>` at the end of an entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchCondition {
+    pub pattern: String,
+    pub negate: bool,
+}
+
+impl BranchCondition {
+    pub fn matches(&self, branch: &str) -> bool {
+        let matched = glob::Pattern::new(&self.pattern)
+            .is_ok_and(|p| p.matches(branch) || p.as_str() == branch);
+        matched != self.negate
+    }
+}
+
+/// One line of `.worktrees.sync`: `<action> <source> [destination] [if branch=<glob>]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncEntry {
+    pub action: SyncAction,
+    /// Path or glob relative to the hub root.
+    pub source: String,
+    /// Path relative to the worktree; a directory when `source` is a glob.
+    pub destination: Option<String>,
+    pub condition: Option<BranchCondition>,
+}
+
+impl SyncEntry {
+    /// Parses one manifest line such as `copy .env.example .env if branch!=main`.
+    pub fn parse(line: &str) -> Result<Self> {
+        let mut words: Vec<&str> = line.split_whitespace().collect();
+        let condition = match words.iter().position(|w| *w == "if") {
+            Some(at) => {
+                let rest = words.split_off(at);
+                let [_, condition] = rest.as_slice() else {
+                    return Err(anyhow!("expected `if branch=<glob>`"));
+                };
+                let (pattern, negate) = if let Some(p) = condition.strip_prefix("branch!=") {
+                    (p, true)
+                } else if let Some(p) = condition.strip_prefix("branch=") {
+                    (p, false)
+                } else {
+                    return Err(anyhow!("unknown condition `{condition}`"));
+                };
+                Some(BranchCondition {
+                    pattern: pattern.to_string(),
+                    negate,
+                })
+            }
+            None => None,
+        };
+
+        let (action, source, destination) = match words.as_slice() {
+            [action, source] => (*action, *source, None),
+            [action, source, destination] => (*action, *source, Some(destination.to_string())),
+            _ => return Err(anyhow!("expected `<action> <source> [destination]`")),
+        };
+        let action = match action {
+            "symlink" => SyncAction::Symlink,
+            "copy" => SyncAction::Copy,
+            "template" => SyncAction::Template,
+            other => return Err(anyhow!("unknown action `{other}`")),
+        };
+        Ok(Self {
+            action,
+            source: source.to_string(),
+            destination,
+            condition,
+        })
+    }
+
+    fn is_glob(&self) -> bool {
+        self.source.contains(['*', '?', '['])
+    }
+
+    /// Where a literal source lands when no destination is given; templates drop `.tmpl`.
+    fn default_destination(&self) -> &str {
+        if self.action == SyncAction::Template {
+            self.source
+                .strip_suffix(".tmpl")
+                .or_else(|| self.source.strip_suffix(".template"))
+                .unwrap_or(&self.source)
+        } else {
+            &self.source
+        }
+    }
+}
+
+/// Parses a manifest, skipping blank lines and `#` comments.
+pub fn parse_manifest(content: &str) -> Result<Vec<SyncEntry>> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| {
+            SyncEntry::parse(line).map_err(|e| anyhow!(".worktrees.sync line {}: {e}", i + 1))
+        })
+        .collect()
+}
+
+/// Values substituted into `template` entries.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SyncVars {
+    pub name: String,
+    pub branch: String,
+    pub port: u16,
+    pub path: String,
+    pub hub: String,
+    pub db_name: String,
+    pub compose_project: String,
+}
+
+impl SyncVars {
+    /// Replaces `{{var}}` (spaces inside the braces allowed); unknown names are left as is.
+    pub fn render(&self, template: &str) -> String {
+        let mut out = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find("{{") {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            let Some(end) = after.find("}}") else {
+                rest = &rest[start..];
+                break;
+            };
+            let value = match after[..end].trim() {
+                "name" => Some(self.name.clone()),
+                "branch" => Some(self.branch.clone()),
+                "port" => Some(self.port.to_string()),
+                "path" => Some(self.path.clone()),
+                "hub" => Some(self.hub.clone()),
+                "db_name" => Some(self.db_name.clone()),
+                "compose_project" => Some(self.compose_project.clone()),
+                _ => None,
+            };
+            match value {
+                Some(value) => out.push_str(&value),
+                None => out.push_str(&rest[start..start + end + 4]),
+            }
+            rest = &after[end + 2..];
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+/// A resolved entry: absolute source and destination.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncOp {
+    pub action: SyncAction,
+    pub source: PathBuf,
+    pub destination: PathBuf,
+}
+
+fn is_safe_relative(path: &Path) -> bool {
+    !path.as_os_str().is_empty()
+        && path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// Resolves `entries` for the worktree at `worktree` checked out on `branch`.
+///
+/// Sources are relative to `hub`; missing sources, unsafe paths (absolute or with `..`) and
+/// anything inside the worktree itself are skipped.
+pub fn plan(entries: &[SyncEntry], hub: &Path, worktree: &Path, branch: &str) -> Vec<SyncOp> {
+    let mut ops = Vec::new();
+    for entry in entries {
+        if entry.condition.as_ref().is_some_and(|c| !c.matches(branch)) {
+            continue;
+        }
+        if !is_safe_relative(Path::new(&entry.source))
+            || entry
+                .destination
+                .as_deref()
+                .is_some_and(|d| !is_safe_relative(Path::new(d)))
+        {
+            continue;
+        }
+
+        if entry.is_glob() {
+            let pattern = hub.join(&entry.source);
+            let Ok(paths) = glob::glob(&pattern.to_string_lossy()) else {
+                continue;
+            };
+            let mut matches: Vec<PathBuf> = paths.flatten().collect();
+            matches.sort();
+            for source in matches {
+                if source.starts_with(worktree) {
+                    continue;
+                }
+                let Ok(relative) = source.strip_prefix(hub) else {
+                    continue;
+                };
+                let destination = match (&entry.destination, source.file_name()) {
+                    (Some(dir), Some(file_name)) => worktree.join(dir).join(file_name),
+                    _ => worktree.join(relative),
+                };
+                ops.push(SyncOp {
+                    action: entry.action,
+                    source,
+                    destination,
+                });
+            }
+        } else {
+            let source = hub.join(&entry.source);
+            if !source.exists() || source.starts_with(worktree) {
+                continue;
+            }
+            let destination = entry
+                .destination
+                .as_deref()
+                .unwrap_or_else(|| entry.default_destination());
+            ops.push(SyncOp {
+                action: entry.action,
+                source,
+                destination: worktree.join(destination),
+            });
+        }
+    }
+    ops
+}
+
+/// 64-bit FNV-1a: stable across runs and platforms, unlike `DefaultHasher`.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, b| {
+        (hash ^ u64::from(*b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Stable hash of a worktree name, used to pick its preferred port block.
+pub fn name_hash(name: &str) -> u64 {
+    fnv1a(FNV_OFFSET, name.as_bytes())
+}
+
+/// A stable port per worktree name in `20000..30000`, spaced 10 apart, for worktrees
+/// without an allocated block.
+pub fn default_port(name: &str) -> u16 {
+    20_000 + u16::try_from(name_hash(name) % 1000).unwrap_or(0) * 10
+}
+
+/// Hash of a file, or of a directory tree (relative paths, contents and link targets).
+pub fn content_hash(path: &Path) -> Option<String> {
+    fn walk(hash: u64, root: &Path, dir: &Path) -> Option<u64> {
+        let mut entries: Vec<_> = std::fs::read_dir(dir).ok()?.flatten().collect();
+        entries.sort_by_key(std::fs::DirEntry::file_name);
+        let mut hash = hash;
+        for entry in entries {
+            let path = entry.path();
+            let relative = path.strip_prefix(root).ok()?;
+            hash = fnv1a(hash, relative.to_string_lossy().as_bytes());
+            let file_type = entry.file_type().ok()?;
+            hash = if file_type.is_symlink() {
+                fnv1a(
+                    hash,
+                    std::fs::read_link(&path).ok()?.to_string_lossy().as_bytes(),
+                )
+            } else if file_type.is_dir() {
+                walk(hash, root, &path)?
+            } else {
+                fnv1a(hash, &std::fs::read(&path).ok()?)
+            };
+        }
+        Some(hash)
+    }
+
+    let hash = if path.is_dir() {
+        walk(FNV_OFFSET, path, path)?
+    } else {
+        fnv1a(FNV_OFFSET, &std::fs::read(path).ok()?)
+    };
+    Some(format!("{hash:016x}"))
+}
+
+/// What a sync would write to `op.destination`, or `None` for symlinks.
+pub fn expected_content(op: &SyncOp, vars: &SyncVars) -> Option<Vec<u8>> {
+    match op.action {
+        SyncAction::Symlink => None,
+        SyncAction::Copy => std::fs::read(&op.source).ok(),
+        SyncAction::Template => std::fs::read_to_string(&op.source)
+            .ok()
+            .map(|t| vars.render(&t).into_bytes()),
+    }
+}
+
+fn expected_hash(op: &SyncOp, vars: &SyncVars) -> Option<String> {
+    if op.action == SyncAction::Copy && op.source.is_dir() {
+        return content_hash(&op.source);
+    }
+    expected_content(op, vars).map(|c| format!("{:016x}", fnv1a(FNV_OFFSET, &c)))
+}
+
+/// Hashes of what the last sync wrote, keyed by destination relative to the worktree.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SyncRecord {
+    pub entries: BTreeMap<String, String>,
+}
+
+impl SyncRecord {
+    /// Remembers the content just written for `op`; symlinks need no record.
+    pub fn record(&mut self, op: &SyncOp, worktree: &Path) {
+        let key = relative_destination(op, worktree);
+        match content_hash(&op.destination) {
+            Some(hash) if op.action != SyncAction::Symlink => {
+                self.entries.insert(key, hash);
+            }
+            _ => {
+                self.entries.remove(&key);
+            }
+        }
+    }
+}
+
+fn relative_destination(op: &SyncOp, worktree: &Path) -> String {
+    op.destination
+        .strip_prefix(worktree)
+        .unwrap_or(&op.destination)
+        .to_string_lossy()
+        .to_string()
+}
+
+/// How a synced entry in a worktree compares to its hub source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftState {
+    InSync,
+    Missing,
+    /// The hub source changed since the last sync; the worktree copy is untouched.
+    Outdated,
+    /// The worktree copy was edited locally; a resync would discard that.
+    Modified,
+    BrokenSymlink,
+}
+
+impl DriftState {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::InSync => "in sync",
+            Self::Missing => "missing",
+            Self::Outdated => "outdated",
+            Self::Modified => "modified",
+            Self::BrokenSymlink => "broken symlink",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SyncDrift {
+    /// Relative to the worktree.
+    pub destination: String,
+    pub source: String,
+    pub action: SyncAction,
+    pub state: DriftState,
+}
+
+fn drift_state(op: &SyncOp, vars: &SyncVars, recorded: Option<&String>) -> DriftState {
+    let Ok(meta) = op.destination.symlink_metadata() else {
+        return DriftState::Missing;
+    };
+    if meta.file_type().is_symlink() && !op.destination.exists() {
+        return DriftState::BrokenSymlink;
+    }
+    if op.action == SyncAction::Symlink {
+        return match std::fs::read_link(&op.destination) {
+            Ok(target) if target == op.source => DriftState::InSync,
+            Ok(_) => DriftState::Outdated,
+            Err(_) => DriftState::Modified,
+        };
+    }
+    let actual = content_hash(&op.destination);
+    if actual.is_some() && actual == expected_hash(op, vars) {
+        DriftState::InSync
+    } else if actual.is_some() && actual.as_ref() == recorded {
+        DriftState::Outdated
+    } else {
+        DriftState::Modified
+    }
+}
+
+/// Compares every planned entry with what is in the worktree.
+pub fn check(ops: &[SyncOp], vars: &SyncVars, record: &SyncRecord, hub: &Path) -> Vec<SyncDrift> {
+    let worktree = Path::new(&vars.path);
+    ops.iter()
+        .map(|op| {
+            let destination = relative_destination(op, worktree);
+            SyncDrift {
+                state: drift_state(op, vars, record.entries.get(&destination)),
+                source: op
+                    .source
+                    .strip_prefix(hub)
+                    .unwrap_or(&op.source)
+                    .to_string_lossy()
+                    .to_string(),
+                destination,
+                action: op.action,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let entries = parse_manifest(
+            "# shared\nsymlink .env\ncopy config/*.json config\n\ntemplate app.env.tmpl if branch!=main\n",
+        )
+        .unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[1].action, SyncAction::Copy);
+        assert_eq!(entries[1].destination.as_deref(), Some("config"));
+        let condition = entries[2].condition.as_ref().unwrap();
+        assert!(condition.negate);
+        assert!(!condition.matches("main"));
+        assert!(condition.matches("feature/x"));
+
+        let err = parse_manifest("copy a\nmove b").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+        assert!(parse_manifest("copy a if tag=x").is_err());
+    }
+
+    #[test]
+    fn test_render_template() {
+        let vars = SyncVars {
+            name: "feat".into(),
+            branch: "feature/x".into(),
+            port: 20110,
+            ..SyncVars::default()
+        };
+        assert_eq!(
+            vars.render("PORT={{port}}\nNAME={{ name }} {{branch}} {{unknown}} {{"),
+            "PORT=20110\nNAME=feat feature/x {{unknown}} {{"
+        );
+    }
+
+    #[test]
+    fn test_plan_resolves_from_hub() {
+        let hub = tempfile::tempdir().unwrap();
+        let hub = hub.path();
+        let worktree = hub.join("feat");
+        std::fs::create_dir_all(hub.join("config")).unwrap();
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::write(hub.join(".env"), "").unwrap();
+        std::fs::write(hub.join("config/a.json"), "").unwrap();
+        std::fs::write(hub.join("config/b.json"), "").unwrap();
+        std::fs::write(hub.join("app.env.tmpl"), "").unwrap();
+
+        let entries = parse_manifest(
+            "copy .env\ncopy config/*.json settings\ntemplate app.env.tmpl\n\
+             copy .env .env.release if branch=release/*\ncopy missing\ncopy ../escape\n\
+             copy feat/x",
+        )
+        .unwrap();
+        let ops = plan(&entries, hub, &worktree, "feature/x");
+        let destinations: Vec<_> = ops
+            .iter()
+            .map(|op| {
+                op.destination
+                    .strip_prefix(&worktree)
+                    .unwrap()
+                    .to_path_buf()
+            })
+            .collect();
+        assert_eq!(
+            destinations,
+            vec![
+                PathBuf::from(".env"),
+                PathBuf::from("settings/a.json"),
+                PathBuf::from("settings/b.json"),
+                PathBuf::from("app.env"),
+            ]
+        );
+        assert_eq!(ops[0].source, hub.join(".env"));
+
+        assert_eq!(plan(&entries, hub, &worktree, "release/1.0").len(), 5);
+    }
+
+    #[test]
+    fn test_check_reports_drift() {
+        let hub = tempfile::tempdir().unwrap();
+        let hub = hub.path();
+        let worktree = hub.join("feat");
+        std::fs::create_dir_all(&worktree).unwrap();
+        for file in [".env", "a", "b", "c"] {
+            std::fs::write(hub.join(file), file).unwrap();
+        }
+        std::fs::write(hub.join("app.tmpl"), "port={{port}}").unwrap();
+        let entries =
+            parse_manifest("copy .env\ncopy a\ncopy b\ncopy c\ntemplate app.tmpl app.env").unwrap();
+        let vars = SyncVars {
+            port: 20_010,
+            path: worktree.to_string_lossy().to_string(),
+            ..SyncVars::default()
+        };
+        let ops = plan(&entries, hub, &worktree, "");
+
+        let mut record = SyncRecord::default();
+        for op in &ops[1..] {
+            std::fs::write(&op.destination, expected_content(op, &vars).unwrap()).unwrap();
+            record.record(op, &worktree);
+        }
+        assert_eq!(
+            record.entries["app.env"],
+            content_hash(&worktree.join("app.env")).unwrap()
+        );
+
+        std::fs::write(hub.join("b"), "new").unwrap();
+        std::fs::write(worktree.join("c"), "local edit").unwrap();
+
+        let states: Vec<_> = check(&ops, &vars, &record, hub)
+            .into_iter()
+            .map(|d| (d.destination, d.state))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                (".env".to_string(), DriftState::Missing),
+                ("a".to_string(), DriftState::InSync),
+                ("b".to_string(), DriftState::Outdated),
+                ("c".to_string(), DriftState::Modified),
+                ("app.env".to_string(), DriftState::InSync),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_symlinks() {
+        let hub = tempfile::tempdir().unwrap();
+        let hub = hub.path();
+        let worktree = hub.join("feat");
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::write(hub.join(".env"), "").unwrap();
+        std::fs::write(hub.join("other"), "").unwrap();
+        let vars = SyncVars {
+            path: worktree.to_string_lossy().to_string(),
+            ..SyncVars::default()
+        };
+        let ops = plan(
+            &parse_manifest("symlink .env\nsymlink other").unwrap(),
+            hub,
+            &worktree,
+            "",
+        );
+        std::os::unix::fs::symlink(hub.join(".env"), worktree.join(".env")).unwrap();
+        std::os::unix::fs::symlink(hub.join("gone"), worktree.join("other")).unwrap();
+
+        let states: Vec<_> = check(&ops, &vars, &SyncRecord::default(), hub)
+            .into_iter()
+            .map(|d| d.state)
+            .collect();
+        assert_eq!(states, vec![DriftState::InSync, DriftState::BrokenSymlink]);
+    }
+
+    #[test]
+    fn test_default_port_is_stable() {
+        assert_eq!(default_port("feat"), default_port("feat"));
+        assert!((20_000..30_000).contains(&default_port("anything")));
+        assert_eq!(default_port("feat") % 10, 0);
+    }
+}
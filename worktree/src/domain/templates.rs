@@ -0,0 +1,220 @@
+use crate::domain::repository::{ProjectRepository, is_valid_color};
+use crate::domain::stale::{expiry_after, parse_days};
+use crate::domain::sync::SyncEntry;
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Named templates from `templates:` in `.worktrees.yaml`.
+pub type Templates = BTreeMap<String, WorktreeTemplate>;
+
+/// How `worktree add --template` sets up a new worktree.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorktreeTemplate {
+    /// Shown by `worktree template list` and the TUI picker.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Ref new branches (or a detached worktree) start from; `HEAD` when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    /// Branch name with `{name}`, e.g. `hotfix/{name}`; the worktree name when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Extra `.worktrees.sync` lines, e.g. `copy .env.production .env`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sync: Vec<String>,
+    /// Shell commands run in the new worktree with its environment, in order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hooks: Vec<String>,
+    /// Check out `base` without creating a branch.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub detached: bool,
+    /// Age after which the worktree expires, e.g. `7d` or `2w`; `ttl` works too.
+    #[serde(alias = "ttl", skip_serializing_if = "Option::is_none")]
+    pub expire_after: Option<String>,
+}
+
+impl WorktreeTemplate {
+    /// The branch for worktree `name`, or `None` for a detached worktree.
+    pub fn branch_for(&self, name: &str) -> Option<String> {
+        if self.detached {
+            return None;
+        }
+        Some(self.branch.as_deref().map_or_else(
+            || name.to_string(),
+            |pattern| pattern.replace("{name}", name),
+        ))
+    }
+
+    /// Days until a worktree made from this template expires.
+    pub fn expire_days(&self) -> Option<u64> {
+        self.expire_after
+            .as_deref()
+            .and_then(|age| parse_days(age).ok())
+    }
+
+    /// Everything wrong with the template; empty when it can be used.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.detached && self.branch.is_some() {
+            problems.push("'detached' and 'branch' cannot be combined".to_string());
+        }
+        if let Some(branch) = &self.branch {
+            let sample = branch.replace("{name}", "x");
+            if sample.contains(['{', '}']) {
+                problems.push(format!(
+                    "branch '{branch}' has an unknown variable; only {{name}} is supported"
+                ));
+            } else if sample
+                .chars()
+                .any(|c| c.is_whitespace() || "~^:?*[\\".contains(c))
+                || sample.contains("..")
+                || sample.starts_with('/')
+                || sample.ends_with('/')
+            {
+                problems.push(format!("branch '{branch}' is not a valid branch name"));
+            }
+        }
+        if let Some(color) = &self.color
+            && !is_valid_color(color)
+        {
+            problems.push(format!("unknown color '{color}'"));
+        }
+        if let Some(age) = &self.expire_after
+            && let Err(e) = parse_days(age)
+        {
+            problems.push(format!("expire_after: {e}"));
+        }
+        for line in &self.sync {
+            if let Err(e) = SyncEntry::parse(line) {
+                problems.push(format!("sync '{line}': {e}"));
+            }
+        }
+        if self.hooks.iter().any(|hook| hook.trim().is_empty()) {
+            problems.push("hooks: empty command".to_string());
+        }
+        problems
+    }
+}
+
+/// The template called `name`, if it exists and is valid.
+pub fn find<'a>(templates: &'a Templates, name: &str) -> Result<&'a WorktreeTemplate> {
+    let template = templates.get(name).ok_or_else(|| {
+        let known = if templates.is_empty() {
+            "none are defined in .worktrees.yaml".to_string()
+        } else {
+            templates.keys().cloned().collect::<Vec<_>>().join(", ")
+        };
+        anyhow!("Unknown template '{name}'. HELP: Available templates: {known}.")
+    })?;
+    let problems = template.problems();
+    if !problems.is_empty() {
+        return Err(anyhow!(
+            "Template '{name}' is invalid: {}. HELP: Fix it in .worktrees.yaml and check with 'worktree template list'.",
+            problems.join("; ")
+        ));
+    }
+    Ok(template)
+}
+
+/// Creates worktree `name` on `branch` the way `template` says and records it in the metadata.
+/// Missing branches are created from the template's base. Hooks are left to the caller,
+/// which knows whether their output can reach the terminal.
+pub fn add_from_template<R: ProjectRepository + ?Sized>(
+    repo: &R,
+    name: &str,
+    branch: Option<&str>,
+    template_name: &str,
+    template: &WorktreeTemplate,
+    now: i64,
+) -> Result<()> {
+    let base = template.base.as_deref().unwrap_or("HEAD");
+    match branch {
+        None => repo.add_detached_worktree(name, base)?,
+        Some(branch) => {
+            let exists = repo
+                .list_branch_info()?
+                .iter()
+                .any(|b| !b.is_remote() && b.name == branch);
+            if exists {
+                repo.add_worktree(name, branch)?;
+            } else {
+                repo.add_new_worktree(name, branch, base)?;
+            }
+        }
+    }
+
+    let mut metadata = repo.worktree_metadata(name)?;
+    metadata.template = Some(template_name.to_string());
+    for (key, value) in [
+        ("purpose", &template.purpose),
+        ("color", &template.color),
+        ("icon", &template.icon),
+    ] {
+        if value.is_some() {
+            metadata.set(key, value.clone())?;
+        }
+    }
+    if let Some(days) = template.expire_days() {
+        metadata.expires_at = expiry_after(now, days);
+    }
+    repo.update_worktree_metadata(name, &metadata)?;
+
+    if !template.sync.is_empty() {
+        repo.sync_entries(name, &template.sync)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_validation() {
+        let templates: Templates = serde_yaml::from_str(
+            r"
+hotfix:
+  base: main
+  branch: hotfix/{name}
+  purpose: Production hotfix
+  color: red
+  sync: ['copy .env.production .env']
+  hooks: ['npm ci']
+spike:
+  detached: true
+  expire_after: 7d
+broken:
+  detached: true
+  branch: spike/{id}
+  color: mauve
+  expire_after: soon
+  sync: ['teleport a b']
+",
+        )
+        .unwrap();
+
+        let hotfix = find(&templates, "hotfix").unwrap();
+        assert_eq!(hotfix.branch_for("login").as_deref(), Some("hotfix/login"));
+        let spike = find(&templates, "spike").unwrap();
+        assert_eq!(spike.branch_for("try"), None);
+        assert_eq!(spike.expire_days(), Some(7));
+
+        assert_eq!(templates["broken"].problems().len(), 5);
+        assert!(
+            find(&templates, "broken").is_err_and(|e| e.to_string().contains("cannot be combined"))
+        );
+        assert!(
+            find(&templates, "nope")
+                .is_err_and(|e| e.to_string().contains("broken, hotfix, spike"))
+        );
+        assert!(serde_yaml::from_str::<Templates>("x:\n  bass: main\n").is_err());
+    }
+}
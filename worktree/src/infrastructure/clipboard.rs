@@ -0,0 +1,82 @@
+use anyhow::{Context, Result};
+use base64::Engine;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Clipboard programs to try, in order, for the current platform.
+fn clipboard_commands() -> Vec<(&'static str, &'static [&'static str])> {
+    if cfg!(target_os = "macos") {
+        vec![("pbcopy", &[])]
+    } else if cfg!(windows) {
+        vec![("clip", &[])]
+    } else {
+        let mut commands: Vec<(&'static str, &'static [&'static str])> = Vec::new();
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            commands.push(("wl-copy", &[]));
+        }
+        commands.push(("xclip", &["-selection", "clipboard"]));
+        commands.push(("xsel", &["--clipboard", "--input"]));
+        commands
+    }
+}
+
+fn pipe_to(program: &str, args: &[&str], text: &str) -> Result<()> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to spawn {program}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .with_context(|| format!("Failed to write to {program}"))?;
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("{program} exited with {status}"));
+    }
+    Ok(())
+}
+
+/// OSC 52 escape sequence asking the terminal itself to set the clipboard.
+pub fn osc52_sequence(text: &str) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    format!("\x1b]52;c;{encoded}\x07")
+}
+
+/// Copies `text` using the platform's clipboard program (pbcopy, clip, wl-copy, xclip, xsel).
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    let commands = clipboard_commands();
+    for (program, args) in &commands {
+        if pipe_to(program, args, text).is_ok() {
+            return Ok(());
+        }
+    }
+    let tried: Vec<&str> = commands.iter().map(|(p, _)| *p).collect();
+    Err(anyhow::anyhow!(
+        "No clipboard program available (tried {})",
+        tried.join(", ")
+    ))
+}
+
+/// Asks the terminal to set the clipboard via OSC 52, which also works over SSH.
+///
+/// Only call this while stdout is the terminal (e.g. from the TUI), never when it is piped.
+pub fn copy_via_terminal(text: &str) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    stdout.write_all(osc52_sequence(text).as_bytes())?;
+    stdout.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(osc52_sequence("abc123"), "\x1b]52;c;YWJjMTIz\x07");
+    }
+}
@@ -0,0 +1,326 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::process::Command;
+
+/// What a dynamic completion request should enumerate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompletionKind {
+    /// Worktrees of the hub (branch and directory names)
+    Worktrees,
+    /// Branch names, with remote prefixes stripped (what `add`/`checkout` expect)
+    Branches,
+    /// Stash indices of the current worktree
+    Stashes,
+    /// Fully qualified local and remote-tracking refs (e.g. `origin/main`)
+    Upstreams,
+    /// Configured remote names
+    Remotes,
+}
+
+/// Works out which kind of value is expected at `cword` in a command line.
+///
+/// `words[0]` is the program name and `words[1]` the subcommand, mirroring `COMP_WORDS`.
+pub fn kind_for_position(words: &[String], cword: usize) -> Option<CompletionKind> {
+    if cword < 2 {
+        return None;
+    }
+    let sub = words.get(1)?.as_str();
+
+    if let Some(prev) = words.get(cword - 1)
+        && sub == "run"
+        && matches!(prev.as_str(), "-b" | "--branch")
+    {
+        return Some(CompletionKind::Branches);
+    }
+    if let Some(prev) = words.get(cword - 1)
+        && matches!(sub, "add" | "push" | "pull")
+        && matches!(prev.as_str(), "-r" | "--remote")
+    {
+        return Some(CompletionKind::Remotes);
+    }
+    if let Some(prev) = words.get(cword - 1)
+        && sub == "port"
+        && matches!(prev.as_str(), "-t" | "--to" | "-f" | "--from")
+    {
+        return Some(CompletionKind::Worktrees);
+    }
+
+    let position = words
+        .get(2..cword)
+        .unwrap_or_default()
+        .iter()
+        .filter(|w| !w.starts_with('-'))
+        .count();
+
+    if sub == "branch" {
+        return match (words.get(2).map(String::as_str), position) {
+            (Some("rename" | "delete" | "set-upstream"), 1) => Some(CompletionKind::Branches),
+            (Some("set-upstream"), 2) | (Some("open"), 1) => Some(CompletionKind::Upstreams),
+            _ => None,
+        };
+    }
+    if sub == "stash" {
+        return match (words.get(2).map(String::as_str), position) {
+            (Some("apply" | "pop" | "drop"), 1) => Some(CompletionKind::Stashes),
+            _ => None,
+        };
+    }
+    if sub == "remote" {
+        return match (words.get(2).map(String::as_str), position) {
+            (Some("remove" | "fetch"), 1) => Some(CompletionKind::Remotes),
+            _ => None,
+        };
+    }
+
+    match (sub, position) {
+        (
+            "remove" | "switch" | "sync" | "push" | "pull" | "teleport" | "checkout" | "edit"
+            | "log",
+            0,
+        )
+        | ("compare", 0 | 1) => Some(CompletionKind::Worktrees),
+        ("add" | "checkout", 1) => Some(CompletionKind::Branches),
+        ("rebase", 0) => Some(CompletionKind::Upstreams),
+        _ => None,
+    }
+}
+
+fn git(args: &[&str]) -> Result<String> {
+    let git_cmd = std::env::var("WORKTREES_GIT_PATH").unwrap_or_else(|_| "git".to_string());
+    let output = Command::new(&git_cmd)
+        .args(args)
+        .env("GIT_OPTIONAL_LOCKS", "0")
+        .output()
+        .with_context(|| format!("Failed to execute git {args:?}"))?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "Git error: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Extracts branch and directory names from `git worktree list --porcelain`, skipping the bare entry.
+pub fn parse_worktree_names(porcelain: &str) -> Vec<String> {
+    let mut names = BTreeSet::new();
+    for entry in porcelain.split("\n\n") {
+        if entry.lines().any(|l| l == "bare") {
+            continue;
+        }
+        for line in entry.lines() {
+            if let Some(branch) = line.strip_prefix("branch refs/heads/") {
+                names.insert(branch.to_string());
+            } else if let Some(path) = line.strip_prefix("worktree ")
+                && let Some(dir) = Path::new(path).file_name()
+            {
+                names.insert(dir.to_string_lossy().to_string());
+            }
+        }
+    }
+    names.into_iter().collect()
+}
+
+/// Turns `for-each-ref` output into candidates; `strip_remote` drops the `<remote>/` prefix.
+pub fn parse_refs(output: &str, remotes: &[&str], strip_remote: bool) -> Vec<String> {
+    let mut refs = BTreeSet::new();
+    for line in output.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        // `refs/remotes/origin/HEAD` shortens to the bare remote name.
+        if remotes.contains(&line) || line.ends_with("/HEAD") {
+            continue;
+        }
+        let name = if strip_remote {
+            remotes
+                .iter()
+                .find_map(|r| line.strip_prefix(r).and_then(|s| s.strip_prefix('/')))
+                .unwrap_or(line)
+        } else {
+            line
+        };
+        refs.insert(name.to_string());
+    }
+    refs.into_iter().collect()
+}
+
+/// Lists candidates of `kind` using cheap git plumbing (no status or size scans).
+pub fn candidates(kind: CompletionKind) -> Result<Vec<String>> {
+    match kind {
+        CompletionKind::Worktrees => Ok(parse_worktree_names(&git(&[
+            "worktree",
+            "list",
+            "--porcelain",
+        ])?)),
+        CompletionKind::Branches | CompletionKind::Upstreams => {
+            let remotes_out = git(&["remote"]).unwrap_or_default();
+            let remotes: Vec<&str> = remotes_out.lines().map(str::trim).collect();
+            let refs = git(&[
+                "for-each-ref",
+                "--format=%(refname:short)",
+                "refs/heads",
+                "refs/remotes",
+            ])?;
+            Ok(parse_refs(
+                &refs,
+                &remotes,
+                kind == CompletionKind::Branches,
+            ))
+        }
+        CompletionKind::Remotes => Ok(git(&["remote"])?
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .collect()),
+        CompletionKind::Stashes => Ok(git(&["stash", "list", "--format=%gd"])?
+            .lines()
+            .filter_map(|l| {
+                l.trim()
+                    .strip_prefix("stash@{")
+                    .and_then(|s| s.strip_suffix('}'))
+                    .map(str::to_string)
+            })
+            .collect()),
+    }
+}
+
+/// Resolves a completion request: an explicit `kind`, or the kind implied by `words`/`cword`.
+///
+/// Results are filtered by the word under the cursor. Errors (e.g. outside a repository)
+/// yield no candidates so the shell can fall back to its defaults.
+pub fn complete(
+    kind: Option<CompletionKind>,
+    words: &[String],
+    cword: Option<usize>,
+) -> Vec<String> {
+    let cword = cword.unwrap_or_else(|| words.len().saturating_sub(1));
+    let Some(kind) = kind.or_else(|| kind_for_position(words, cword)) else {
+        return Vec::new();
+    };
+    let prefix = words.get(cword).map_or("", String::as_str);
+
+    candidates(kind)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|c| c.starts_with(prefix))
+        .collect()
+}
+
+/// Snippet appended to `worktree completions <shell>` so the static clap script also offers
+/// live worktree and branch names. Shells without a shim keep the static behaviour.
+pub fn dynamic_completion_shim(shell: clap_complete::Shell) -> Option<&'static str> {
+    match shell {
+        clap_complete::Shell::Bash => Some(
+            r#"
+_worktree_dynamic() {
+    local IFS=$'\n'
+    local dynamic=($(command worktree __complete --cword "$COMP_CWORD" -- "${COMP_WORDS[@]}" 2>/dev/null))
+    if [ ${#dynamic[@]} -gt 0 ]; then
+        COMPREPLY=("${dynamic[@]}")
+        return 0
+    fi
+    _worktree "$@"
+}
+complete -F _worktree_dynamic -o nosort -o bashdefault -o default worktree
+"#,
+        ),
+        clap_complete::Shell::Fish => Some(FISH_DYNAMIC),
+        _ => None,
+    }
+}
+
+/// Fish completions for dynamic positions. File completion is only switched off where
+/// `__complete` has candidates, so path arguments keep completing files like the bash shim.
+pub const FISH_DYNAMIC: &str = r#"function __worktree_dynamic
+    command worktree __complete --cword (count (commandline -opc)) -- (commandline -opc) (commandline -ct) 2>/dev/null
+end
+complete -c worktree -f -n 'not __fish_use_subcommand; and count (__worktree_dynamic) >/dev/null' -a '(__worktree_dynamic)'
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(line: &str) -> Vec<String> {
+        line.split(' ').map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_kind_for_position() {
+        assert_eq!(
+            kind_for_position(&words("worktree switch "), 2),
+            Some(CompletionKind::Worktrees)
+        );
+        assert_eq!(
+            kind_for_position(&words("worktree remove -f fe"), 3),
+            Some(CompletionKind::Worktrees)
+        );
+        assert_eq!(
+            kind_for_position(&words("worktree checkout feat ma"), 3),
+            Some(CompletionKind::Branches)
+        );
+        assert_eq!(
+            kind_for_position(&words("worktree add feat "), 3),
+            Some(CompletionKind::Branches)
+        );
+        assert_eq!(
+            kind_for_position(&words("worktree run tmp -b "), 4),
+            Some(CompletionKind::Branches)
+        );
+        assert_eq!(
+            kind_for_position(&words("worktree rebase "), 2),
+            Some(CompletionKind::Upstreams)
+        );
+        assert_eq!(
+            kind_for_position(&words("worktree branch delete -f fe"), 4),
+            Some(CompletionKind::Branches)
+        );
+        assert_eq!(
+            kind_for_position(&words("worktree branch set-upstream feat or"), 4),
+            Some(CompletionKind::Upstreams)
+        );
+        assert_eq!(kind_for_position(&words("worktree branch de"), 2), None);
+        assert_eq!(
+            kind_for_position(&words("worktree port main..HEAD --to "), 4),
+            Some(CompletionKind::Worktrees)
+        );
+        assert_eq!(
+            kind_for_position(&words("worktree add fix-docs --remote up"), 4),
+            Some(CompletionKind::Remotes)
+        );
+        assert_eq!(
+            kind_for_position(&words("worktree remote remove up"), 3),
+            Some(CompletionKind::Remotes)
+        );
+        assert_eq!(
+            kind_for_position(&words("worktree stash pop "), 3),
+            Some(CompletionKind::Stashes)
+        );
+        assert_eq!(kind_for_position(&words("worktree add "), 2), None);
+        assert_eq!(kind_for_position(&words("worktree sw"), 1), None);
+    }
+
+    #[test]
+    fn test_parse_worktree_names() {
+        let porcelain = "worktree /hub/.bare\nbare\n\nworktree /hub/main\nHEAD abc\nbranch refs/heads/main\n\nworktree /hub/feat-login\nHEAD def\nbranch refs/heads/feature/login\n\nworktree /hub/detached\nHEAD 123\ndetached\n";
+        assert_eq!(
+            parse_worktree_names(porcelain),
+            vec!["detached", "feat-login", "feature/login", "main"]
+        );
+    }
+
+    #[test]
+    fn test_parse_refs() {
+        let refs = "main\nfeature/x\norigin\norigin/HEAD\norigin/main\nupstream/release\n";
+        let remotes = ["origin", "upstream"];
+        assert_eq!(
+            parse_refs(refs, &remotes, true),
+            vec!["feature/x", "main", "release"]
+        );
+        assert_eq!(
+            parse_refs(refs, &remotes, false),
+            vec!["feature/x", "main", "origin/main", "upstream/release"]
+        );
+    }
+}
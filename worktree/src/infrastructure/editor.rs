@@ -0,0 +1,122 @@
+use crate::domain::repository::{EditorConfig, EditorTarget};
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Checks whether `program` can be executed, either as a path or via `$PATH`.
+pub fn is_installed(program: &str) -> bool {
+    let candidate = Path::new(program);
+    if candidate.components().count() > 1 {
+        return candidate.is_file();
+    }
+    std::env::var_os("PATH").is_some_and(|paths| {
+        std::env::split_paths(&paths).any(|dir| {
+            let full = dir.join(program);
+            full.is_file() || (cfg!(windows) && full.with_extension("exe").is_file())
+        })
+    })
+}
+
+/// Orders editors with installed ones first, keeping their relative order, and returns how
+/// many are installed.
+pub fn sort_by_availability(editors: &mut [EditorConfig]) -> usize {
+    editors.sort_by_cached_key(|e| !is_installed(&e.command));
+    editors.partition_point(|e| is_installed(&e.command))
+}
+
+/// Resolves which editor to use.
+///
+/// `preferred` (project or global preference) may be an editor name, its command, or a raw
+/// command line. Without a preference `$VISUAL` and then `$EDITOR` are used.
+pub fn resolve_editor(preferred: Option<&str>, editors: &[EditorConfig]) -> Option<EditorConfig> {
+    if let Some(pref) = preferred.map(str::trim).filter(|p| !p.is_empty()) {
+        if let Some(editor) = editors
+            .iter()
+            .find(|e| e.name.eq_ignore_ascii_case(pref) || e.command == pref)
+        {
+            return Some(editor.clone());
+        }
+        return EditorConfig::from_command_line(pref, editors);
+    }
+
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|v| !v.trim().is_empty())
+        .and_then(|cmd| EditorConfig::from_command_line(&cmd, editors))
+}
+
+pub fn build_command(editor: &EditorConfig, target: &EditorTarget) -> Command {
+    let mut cmd = Command::new(&editor.command);
+    cmd.args(target.render_args(editor))
+        .current_dir(&target.path);
+    cmd
+}
+
+/// Opens the target. GUI editors are detached; terminal editors inherit the TTY and are
+/// waited for, so callers owning the terminal must release it first.
+pub fn launch(editor: &EditorConfig, target: &EditorTarget) -> Result<()> {
+    let mut cmd = build_command(editor, target);
+    if editor.terminal {
+        let status = cmd
+            .status()
+            .with_context(|| format!("Failed to run editor '{}'", editor.command))?;
+        if !status.success() {
+            return Err(anyhow::anyhow!(
+                "Editor '{}' exited with {status}",
+                editor.command
+            ));
+        }
+    } else {
+        cmd.stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Failed to launch editor '{}'", editor.command))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_args_templates() {
+        let editors = EditorConfig::defaults();
+        let code = editors.iter().find(|e| e.command == "code").unwrap();
+        let nvim = editors.iter().find(|e| e.command == "nvim").unwrap();
+
+        let worktree = EditorTarget::worktree("/hub/feat");
+        assert_eq!(worktree.render_args(code), vec!["/hub/feat"]);
+
+        let file = EditorTarget {
+            path: "/hub/feat".into(),
+            file: Some("/hub/feat/src/main.rs".into()),
+            line: Some(42),
+        };
+        assert_eq!(
+            file.render_args(code),
+            vec!["/hub/feat", "-g", "/hub/feat/src/main.rs:42"]
+        );
+        assert_eq!(file.render_args(nvim), vec!["+42", "/hub/feat/src/main.rs"]);
+    }
+
+    #[test]
+    fn test_resolve_editor_by_name_and_command_line() {
+        let editors = EditorConfig::defaults();
+        assert_eq!(
+            resolve_editor(Some("neovim"), &editors).map(|e| e.command),
+            Some("nvim".to_string())
+        );
+
+        let custom = resolve_editor(Some("/usr/local/bin/code --wait"), &editors).unwrap();
+        assert_eq!(custom.command, "/usr/local/bin/code");
+        assert!(!custom.terminal);
+        assert_eq!(custom.args, vec!["--wait", "{path}"]);
+
+        let unknown = resolve_editor(Some("kak"), &editors).unwrap();
+        assert!(unknown.terminal);
+        assert_eq!(unknown.goto_args, vec!["+{line}", "{file}"]);
+    }
+}
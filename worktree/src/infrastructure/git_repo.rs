@@ -1,6 +1,22 @@
+use crate::domain::artifacts::{Artifact, ArtifactRules, Detector, detectors_for, tracked_paths};
+use crate::domain::cache::{CARGO_CONFIG_HEADER, CacheKind, CacheStatus, cache_env, cargo_config};
+use crate::domain::context::{context_env, detect_contexts, detect_handlers};
+use crate::domain::env::{EnvLayers, parse_dotenv};
+use crate::domain::issues::{IssueConfig, IssueLink};
+use crate::domain::list_layout::ListLayout;
 use crate::domain::repository::{
-    GitCommit, GitStatus, ProjectContext, ProjectRepository, RepoStatus, Worktree, WorktreeMetadata,
+    BranchInfo, ChangedFile, CommitDetail, EditorConfig, FileStat, GitCommit, GitStatus,
+    HistoryQuery, ProjectConfig, ProjectContext, ProjectRepository, RefComparison, Remote,
+    RepoStatus, Worktree, WorktreeMetadata,
 };
+use crate::domain::resources::{Resources, allocate};
+use crate::domain::stale::expires_in;
+use crate::domain::sync::{
+    SyncAction, SyncDrift, SyncEntry, SyncOp, SyncRecord, SyncVars, check, default_port,
+    expected_content, parse_manifest, plan,
+};
+use crate::infrastructure::issue_providers::provider_for;
+use crate::infrastructure::metadata_store::{MetadataMap, MetadataStore};
 
 use crate::domain::repository::RepositoryEvent;
 use anyhow::{Context, Result};
@@ -16,6 +32,33 @@ use tracing::{debug, error, instrument};
 #[derive(Clone)]
 pub struct GitProjectRepository;
 
+/// Metadata key of a non-bare repository's main worktree, which has no admin id.
+const MAIN_WORKTREE_ID: &str = ".git";
+
+/// A worktree as recorded in git's admin files.
+struct AdminEntry {
+    id: String,
+    path: PathBuf,
+    branch: Option<String>,
+}
+
+/// `.worktrees.sync` resolved for one worktree.
+struct SyncPlan {
+    hub: PathBuf,
+    ops: Vec<SyncOp>,
+    vars: SyncVars,
+}
+
+/// `git log` format parsed by `parse_git_history` (the graph, if any, precedes the first NUL).
+const HISTORY_FORMAT: &str = "%x00%h%x00%an%x00%ad%x00%s";
+
+/// `git show` format for [`CommitDetail`]: NUL-separated so subjects and bodies may contain anything.
+const COMMIT_DETAIL_FORMAT: &str =
+    "%H%x00%P%x00%an <%ae>%x00%ad%x00%cn <%ce>%x00%cd%x00%D%x00%s%x00%b";
+
+/// `git for-each-ref` format parsed by `parse_branch_refs`, one branch per line.
+const BRANCH_FORMAT: &str = "%(refname)%00%(objectname:short)%00%(committerdate:short)%00%(committerdate:unix)%00%(upstream:short)%00%(upstream:track,nobracket)%00%(worktreepath)%00%(contents:subject)";
+
 impl GitProjectRepository {
     #[allow(dead_code)]
     fn calculate_dir_size(path: &Path) -> u64 {
@@ -34,6 +77,81 @@ impl GitProjectRepository {
         total_size
     }
 
+    /// Walks `root` for build artifacts, matching each directory's entries against the detectors.
+    ///
+    /// `tracked` holds every directory (relative, `/`-separated) that contains a file tracked by git;
+    /// such directories and tracked files are never reported. Artifacts are not searched for nested
+    /// artifacts, and symlinks are not followed.
+    fn find_artifacts(
+        root: &Path,
+        rules: &ArtifactRules,
+        tracked: &std::collections::HashSet<String>,
+        size_of: &dyn Fn(&Path) -> u64,
+    ) -> Vec<Artifact> {
+        let mut found = Vec::new();
+        // (directory, relative path, detectors active above it)
+        let mut stack = vec![(root.to_path_buf(), String::new(), Vec::<&Detector>::new())];
+        while let Some((dir, relative, inherited)) = stack.pop() {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            let mut entries: Vec<_> = entries.flatten().collect();
+            entries.sort_by_key(std::fs::DirEntry::file_name);
+            let names: Vec<String> = entries
+                .iter()
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect();
+            let here = detectors_for(names.iter().map(String::as_str));
+
+            let mut subdirs = Vec::new();
+            for (entry, name) in entries.iter().zip(&names) {
+                if name == ".git" {
+                    continue;
+                }
+                let Ok(file_type) = entry.file_type() else {
+                    continue;
+                };
+                let child = if relative.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{relative}/{name}")
+                };
+                if rules.is_excluded(&child) {
+                    continue;
+                }
+
+                let ecosystem = rules.classify(&child, file_type.is_dir(), &here, &inherited);
+                if let Some(ecosystem) = ecosystem
+                    && !tracked.contains(&child)
+                {
+                    found.push(Artifact {
+                        path: entry.path().to_string_lossy().to_string(),
+                        size_bytes: if file_type.is_dir() {
+                            size_of(&entry.path())
+                        } else {
+                            entry.metadata().map_or(0, |m| m.len())
+                        },
+                        relative: child,
+                        ecosystem,
+                    });
+                } else if file_type.is_dir() {
+                    subdirs.push((entry.path(), child));
+                }
+            }
+
+            let active: Vec<&Detector> = inherited
+                .into_iter()
+                .chain(here)
+                .filter(|d| d.has_nested())
+                .collect();
+            for (path, child) in subdirs {
+                stack.push((path, child, active.clone()));
+            }
+        }
+        found.sort_by(|a, b| a.relative.cmp(&b.relative));
+        found
+    }
+
     fn is_safe_for_cleaning(path: &Path) -> bool {
         // 1. Check for root (no parent)
         if path.parent().is_none() {
@@ -114,58 +232,336 @@ impl GitProjectRepository {
         None
     }
 
+    /// Reads the hub's `.worktrees.sync` and resolves it for the worktree at `path`.
+    fn load_sync_plan(path: &str) -> Result<Option<SyncPlan>> {
+        // Sources are resolved from the hub root so `sync` behaves the same from any directory;
+        // outside a hub (e.g. a plain directory) the current directory stands in for it.
+        let hub = Self::get_project_root_path()
+            .or_else(|_| std::env::current_dir().map_err(anyhow::Error::from))?;
+        let manifest_path = hub.join(".worktrees.sync");
+        if !manifest_path.exists() {
+            debug!("No .worktrees.sync manifest found, skipping generic synchronization.");
+            return Ok(None);
+        }
+
+        let metadata = std::fs::metadata(&manifest_path)
+            .context("Failed to read metadata of .worktrees.sync manifest.")?;
+        let mtime = metadata
+            .modified()
+            .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+
+        static SYNC_CONFIG_CACHE: std::sync::OnceLock<
+            std::sync::Mutex<std::collections::HashMap<PathBuf, (std::time::SystemTime, String)>>,
+        > = std::sync::OnceLock::new();
+        let cache = SYNC_CONFIG_CACHE
+            .get_or_init(|| std::sync::Mutex::new(std::collections::HashMap::new()));
+
+        let content = {
+            let mut guard = cache.lock().unwrap();
+            match guard.get(&manifest_path) {
+                Some((cached_mtime, cached_content)) if *cached_mtime == mtime => {
+                    debug!("Using cached .worktrees.sync manifest.");
+                    cached_content.clone()
+                }
+                _ => {
+                    let content = std::fs::read_to_string(&manifest_path)
+                        .context("Failed to read .worktrees.sync manifest.")?;
+                    guard.insert(manifest_path.clone(), (mtime, content.clone()));
+                    content
+                }
+            }
+        };
+
+        let entries = parse_manifest(&content)?;
+        Self::sync_plan(path, hub, &entries).map(Some)
+    }
+
+    /// Resolves `entries` for the worktree at `path` against the hub.
+    fn sync_plan(path: &str, hub: PathBuf, entries: &[SyncEntry]) -> Result<SyncPlan> {
+        let worktree = std::path::absolute(path).unwrap_or_else(|_| PathBuf::from(path));
+        let branch = Self::run_git(&["-C", path, "rev-parse", "--abbrev-ref", "HEAD"])
+            .map(|b| b.trim().to_string())
+            .unwrap_or_default();
+        let name = worktree
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let resources = Self::admin_id(path)
+            .ok()
+            .and_then(|id| Self::load_metadata().remove(&id))
+            .and_then(|meta| meta.resources);
+        let vars = SyncVars {
+            port: resources
+                .as_ref()
+                .map_or_else(|| default_port(&name), |r| r.port_base),
+            db_name: resources
+                .as_ref()
+                .and_then(|r| r.db_name.clone())
+                .unwrap_or_default(),
+            compose_project: resources
+                .and_then(|r| r.compose_project)
+                .unwrap_or_default(),
+            name,
+            branch,
+            path: worktree.to_string_lossy().to_string(),
+            hub: hub.to_string_lossy().to_string(),
+        };
+
+        let ops = plan(entries, &hub, &worktree, &vars.branch);
+        Ok(SyncPlan { hub, ops, vars })
+    }
+
+    /// Applies the planned entries selected by `filter` and records what was written.
+    fn apply_sync_ops(path: &str, sync: &SyncPlan, filter: impl Fn(&SyncOp) -> bool) -> Result<()> {
+        let worktree = Path::new(&sync.vars.path);
+        let mut record = Self::load_sync_record(path);
+        for op in sync.ops.iter().filter(|op| filter(op)) {
+            Self::apply_sync_op(op, &sync.vars)?;
+            record.record(op, worktree);
+        }
+        if let Some(file) = Self::sync_record_path(path) {
+            let json = serde_json::to_string_pretty(&record)?;
+            if let Err(e) = std::fs::write(&file, json) {
+                debug!(error = %e, ?file, "Failed to save sync record.");
+            }
+        }
+        Ok(())
+    }
+
+    /// Kept in the worktree's git admin dir so it never shows up as an untracked file.
+    fn sync_record_path(path: &str) -> Option<PathBuf> {
+        let git_dir = Self::run_git(&[
+            "-C",
+            path,
+            "rev-parse",
+            "--path-format=absolute",
+            "--git-dir",
+        ])
+        .ok()?;
+        Some(Path::new(git_dir.trim()).join("worktree-sync.json"))
+    }
+
+    fn load_sync_record(path: &str) -> SyncRecord {
+        Self::sync_record_path(path)
+            .and_then(|file| std::fs::read_to_string(file).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    fn apply_sync_op(op: &SyncOp, vars: &SyncVars) -> Result<()> {
+        let (source, destination) = (&op.source, &op.destination);
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {parent:?}"))?;
+        }
+        match op.action {
+            SyncAction::Symlink => {
+                #[cfg(unix)]
+                {
+                    if destination.symlink_metadata().is_ok() {
+                        let _ = std::fs::remove_file(destination);
+                        let _ = std::fs::remove_dir_all(destination);
+                    }
+                    std::os::unix::fs::symlink(source, destination).with_context(|| {
+                        format!("Failed to symlink {source:?} to {destination:?}")
+                    })?;
+                }
+                #[cfg(not(unix))]
+                Self::copy_recursive(source, destination)?;
+            }
+            SyncAction::Copy => Self::copy_recursive(source, destination)?,
+            SyncAction::Template => {
+                if source.is_dir() {
+                    debug!(?source, "Templates must be files, skipping.");
+                    return Ok(());
+                }
+                let template = std::fs::read_to_string(source)
+                    .with_context(|| format!("Failed to read template {source:?}"))?;
+                std::fs::write(destination, vars.render(&template))
+                    .with_context(|| format!("Failed to write {destination:?}"))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Copies a file, or a directory tree without following symlinks inside it.
+    fn copy_recursive(source: &Path, destination: &Path) -> Result<()> {
+        if !source.is_dir() {
+            std::fs::copy(source, destination)
+                .with_context(|| format!("Failed to copy {source:?} to {destination:?}"))?;
+            return Ok(());
+        }
+        std::fs::create_dir_all(destination)
+            .with_context(|| format!("Failed to create {destination:?}"))?;
+        for entry in std::fs::read_dir(source)?.flatten() {
+            let target = destination.join(entry.file_name());
+            let file_type = entry.file_type()?;
+            if file_type.is_symlink() {
+                #[cfg(unix)]
+                {
+                    let link = std::fs::read_link(entry.path())?;
+                    let _ = std::fs::remove_file(&target);
+                    std::os::unix::fs::symlink(link, &target)?;
+                }
+            } else {
+                Self::copy_recursive(&entry.path(), &target)?;
+            }
+        }
+        Ok(())
+    }
+
     fn handle_context_files(&self, path: &str) {
         // 1. Generic synchronization from manifest
         if let Err(e) = self.sync_configs(path) {
             debug!(error = %e, "Generic configuration synchronization failed.");
         }
 
-        // 2. Specialized KMP/Android synchronization
-        if self.detect_context(Path::new(".")) == ProjectContext::KmpAndroid {
-            // 1. Sync local.properties (Android SDK paths, etc.)
-            let local_props = Path::new("local.properties");
-            if local_props.exists() {
-                let dest = Path::new(path).join("local.properties");
-                let _ =
-                    std::fs::copy(local_props, dest).context("Failed to copy local.properties.");
-            }
-
-            // 2. Sync and Optimize gradle.properties
-            let gradle_props = Path::new("gradle.properties");
-            let dest_gradle = Path::new(path).join("gradle.properties");
-
-            if gradle_props.exists() {
-                let _ = std::fs::copy(gradle_props, &dest_gradle)
-                    .context("Failed to copy gradle.properties.");
+        // 2. Stack-specific files (Android SDK paths, Firebase plists, .env, ...)
+        let handlers = detect_handlers(Path::new("."));
+        for handler in &handlers {
+            for file in handler.sync_files() {
+                let (source, dest) = (Path::new(file), Path::new(path).join(file));
+                if !source.is_file() || dest.exists() {
+                    continue;
+                }
+                if let Some(parent) = dest.parent() {
+                    let _ = std::fs::create_dir_all(parent);
+                }
+                if let Err(e) = std::fs::copy(source, &dest) {
+                    debug!(file, error = %e, "Failed to copy context file.");
+                }
             }
+        }
 
-            // 3. Ensure Build Caching is enabled for Worktree performance
-            #[cfg(unix)]
-            let open_result = {
-                use std::os::unix::fs::OpenOptionsExt;
-                OpenOptions::new()
-                    .create(true)
-                    .append(true)
-                    .mode(0o600)
-                    .open(&dest_gradle)
-            };
+        // 3. Shared build caches (Cargo target/sccache, pnpm store, Gradle)
+        match self.setup_shared_caches(path) {
+            Ok(kinds) if !kinds.is_empty() => debug!(?kinds, "Shared build caches configured."),
+            Ok(_) => {}
+            Err(e) => debug!(error = %e, "Shared build cache setup failed."),
+        }
+    }
 
-            #[cfg(not(unix))]
-            let open_result = OpenOptions::new()
+    /// Appends `line` to the worktree's `file` and hides the file through `info/exclude`.
+    ///
+    /// Tracked files and files that already mention `key` are left alone, so a new worktree
+    /// never starts out with local changes. Returns whether the setting was written.
+    fn append_local_setting(path: &str, file: &str, key: &str, line: &str) -> Result<bool> {
+        let dest = Path::new(path).join(file);
+        let tracked = Self::run_git(&["-C", path, "ls-files", "--error-unmatch", file]).is_ok();
+        let existing = std::fs::read_to_string(&dest).unwrap_or_default();
+        if tracked || existing.contains(key) {
+            return Ok(false);
+        }
+        let mut options = OpenOptions::new();
+        options.create(true).append(true);
+        // Both files may hold credentials, so a new one is private to the user.
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut out = options
+            .open(&dest)
+            .with_context(|| format!("Failed to write {file}"))?;
+        writeln!(out, "{line}")?;
+        // Keep the generated file from showing up as an untracked change.
+        let common_dir = Self::run_git(&[
+            "-C",
+            path,
+            "rev-parse",
+            "--path-format=absolute",
+            "--git-common-dir",
+        ])?;
+        let exclude = Path::new(common_dir.trim()).join("info").join("exclude");
+        let pattern = format!("/{file}");
+        let excluded = std::fs::read_to_string(&exclude).unwrap_or_default();
+        if !excluded.lines().any(|l| l.trim() == pattern) {
+            std::fs::create_dir_all(exclude.parent().unwrap_or(Path::new(".")))?;
+            let mut out = OpenOptions::new()
                 .create(true)
                 .append(true)
-                .open(&dest_gradle);
+                .open(&exclude)?;
+            writeln!(out, "{pattern}")?;
+        }
+        Ok(true)
+    }
 
-            if let Ok(mut file) = open_result {
-                // Check if already contains caching flag (simple check)
-                if std::fs::read_to_string(&dest_gradle)
-                    .map(|c| !c.contains("org.gradle.caching"))
-                    .unwrap_or(false)
-                {
-                    let _ = writeln!(file, "\n# Optimized for Worktrees\norg.gradle.caching=true");
+    fn gradle_build_cache_dir() -> PathBuf {
+        std::env::var_os("GRADLE_USER_HOME")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".gradle")))
+            .unwrap_or_default()
+            .join("caches")
+            .join("build-cache-1")
+    }
+
+    fn sccache_installed() -> bool {
+        Command::new("sccache")
+            .arg("--version")
+            .output()
+            .is_ok_and(|o| o.status.success())
+    }
+
+    /// Points the worktree at `path` to the hub's shared build caches.
+    ///
+    /// Rust uses a hub-level `.cargo/config.toml`, which cargo picks up from every worktree below
+    /// the hub; pnpm gets a `store-dir` in an untracked `.npmrc` and Gradle `org.gradle.caching`
+    /// in an untracked `gradle.properties`. Tracked files and files written by hand are left
+    /// alone. `.cache/cache.env` carries the same settings as shell exports.
+    fn setup_shared_caches(&self, path: &str) -> Result<Vec<CacheKind>> {
+        let config = self.get_project_config().unwrap_or_default().cache;
+        let kinds = config.kinds_for(Path::new(path));
+        if kinds.is_empty() {
+            return Ok(kinds);
+        }
+        let root = self.get_project_root()?;
+        let cache_root = root.join(".cache");
+        std::fs::create_dir_all(&cache_root).context("Failed to create the shared cache dir")?;
+
+        for kind in &kinds {
+            match kind {
+                CacheKind::CargoTarget | CacheKind::Sccache => {
+                    if *kind == CacheKind::Sccache && !Self::sccache_installed() {
+                        debug!("sccache is not installed, leaving Rust builds unshared");
+                        continue;
+                    }
+                    let cargo_config_path = root.join(".cargo").join("config.toml");
+                    let is_ours = std::fs::read_to_string(&cargo_config_path)
+                        .map_or(true, |content| content.starts_with(CARGO_CONFIG_HEADER));
+                    if !is_ours {
+                        debug!(path = ?cargo_config_path, "Hub cargo config is hand-written, leaving it");
+                        continue;
+                    }
+                    if let Some(content) = cargo_config(*kind, &cache_root) {
+                        std::fs::create_dir_all(root.join(".cargo"))?;
+                        std::fs::write(&cargo_config_path, content)
+                            .context("Failed to write the hub cargo config")?;
+                    }
+                }
+                CacheKind::PnpmStore => {
+                    let store = cache_root.join("pnpm-store");
+                    Self::append_local_setting(
+                        path,
+                        ".npmrc",
+                        "store-dir",
+                        &format!("store-dir={}", store.to_string_lossy()),
+                    )?;
+                }
+                CacheKind::GradleBuildCache => {
+                    Self::append_local_setting(
+                        path,
+                        "gradle.properties",
+                        "org.gradle.caching",
+                        "org.gradle.caching=true",
+                    )?;
                 }
             }
         }
+
+        let env: String = cache_env(&config.enabled_kinds(), &cache_root)
+            .into_iter()
+            .map(|(key, value)| format!("export {key}=\"{value}\"\n"))
+            .collect();
+        std::fs::write(cache_root.join("cache.env"), env)
+            .context("Failed to write .cache/cache.env")?;
+        Ok(kinds)
     }
 
     fn get_status_summary(path: &str) -> Result<String> {
@@ -194,33 +590,29 @@ impl GitProjectRepository {
         }
     }
 
+    /// Collapses `for-each-ref --format=%(refname)` output into checkout-able branch names.
+    ///
+    /// Local branches win; a remote branch adds its name without the `<remote>/` prefix.
     fn parse_branches(output: &str) -> Vec<String> {
         let mut branches: Vec<String> = Vec::new();
         let mut seen: std::collections::HashSet<String> = std::collections::HashSet::new();
 
         // 1. First pass: Collect local branches
         for line in output.lines() {
-            let line = line.trim();
-            if line.is_empty() || line.contains("origin/HEAD") {
-                continue;
-            }
-            if !line.starts_with("origin/") {
-                branches.push(line.to_string());
-                seen.insert(line.to_string());
+            if let Some(name) = line.trim().strip_prefix("refs/heads/") {
+                branches.push(name.to_string());
+                seen.insert(name.to_string());
             }
         }
 
-        // 2. Second pass: Add remote branches
+        // 2. Second pass: Add remote branches from every remote
         for line in output.lines() {
-            let line = line.trim();
-            if line.is_empty() || line.contains("origin/HEAD") {
-                continue;
-            }
-            if let Some(stripped) = line.strip_prefix("origin/")
-                && !seen.contains(stripped)
+            if let Some(remote_ref) = line.trim().strip_prefix("refs/remotes/")
+                && let Some((_, name)) = remote_ref.split_once('/')
+                && name != "HEAD"
+                && seen.insert(name.to_string())
             {
-                branches.push(stripped.to_string());
-                seen.insert(stripped.to_string());
+                branches.push(name.to_string());
             }
         }
 
@@ -228,6 +620,117 @@ impl GitProjectRepository {
         branches
     }
 
+    /// Parses `git remote -v`, which lists a fetch and a push line per remote.
+    fn parse_remotes(output: &str) -> Vec<Remote> {
+        let mut remotes: Vec<Remote> = Vec::new();
+        for line in output.lines() {
+            let mut parts = line.split_whitespace();
+            let (Some(name), Some(url), Some(kind)) = (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            let index = remotes
+                .iter()
+                .position(|r| r.name == name)
+                .unwrap_or_else(|| {
+                    remotes.push(Remote {
+                        name: name.to_string(),
+                        fetch_url: String::new(),
+                        push_url: String::new(),
+                    });
+                    remotes.len() - 1
+                });
+            if kind == "(push)" {
+                remotes[index].push_url = url.to_string();
+            } else {
+                remotes[index].fetch_url = url.to_string();
+            }
+        }
+        remotes
+    }
+
+    /// The branch checked out in `path`, failing on a detached HEAD.
+    fn current_branch(path: &str) -> Result<String> {
+        let branch = Self::run_git(&["-C", path, "rev-parse", "--abbrev-ref", "HEAD"])?
+            .trim()
+            .to_string();
+        if branch == "HEAD" {
+            return Err(anyhow::anyhow!(
+                "'{path}' has a detached HEAD. HELP: Check out a branch first."
+            ));
+        }
+        Ok(branch)
+    }
+
+    fn parse_branch_refs(output: &str) -> Vec<BranchInfo> {
+        let mut branches: Vec<BranchInfo> = output
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split('\0').collect();
+                let [
+                    refname,
+                    tip,
+                    date,
+                    timestamp,
+                    upstream,
+                    track,
+                    worktree,
+                    subject,
+                ] = fields[..]
+                else {
+                    return None;
+                };
+                let (name, remote) = if let Some(name) = refname.strip_prefix("refs/heads/") {
+                    (name, None)
+                } else {
+                    let name = refname.strip_prefix("refs/remotes/")?;
+                    // `origin/HEAD` only points at the remote's default branch.
+                    if name.ends_with("/HEAD") {
+                        return None;
+                    }
+                    (name, name.split_once('/').map(|(r, _)| r.to_string()))
+                };
+                let count = |key: &str| {
+                    track
+                        .split(", ")
+                        .find_map(|part| part.strip_prefix(key)?.trim().parse().ok())
+                        .unwrap_or(0)
+                };
+                let non_empty = |v: &str| (!v.is_empty()).then(|| v.to_string());
+                Some(BranchInfo {
+                    name: name.to_string(),
+                    remote,
+                    tip: tip.to_string(),
+                    subject: subject.to_string(),
+                    date: date.to_string(),
+                    timestamp: timestamp.parse().unwrap_or(0),
+                    upstream: non_empty(upstream),
+                    ahead: count("ahead "),
+                    behind: count("behind "),
+                    upstream_gone: track == "gone",
+                    worktree: non_empty(worktree),
+                })
+            })
+            .collect();
+        // Local branches first, each group by name.
+        branches.sort_by(|a, b| (a.is_remote(), &a.name).cmp(&(b.is_remote(), &b.name)));
+        branches
+    }
+
+    /// The branch the hub's HEAD points at, used as the default merge target.
+    fn hub_default_branch() -> Result<String> {
+        let common_dir =
+            Self::run_git(&["rev-parse", "--path-format=absolute", "--git-common-dir"])?;
+        let head = Self::run_git(&[
+            "--git-dir",
+            common_dir.trim(),
+            "symbolic-ref",
+            "--short",
+            "HEAD",
+        ])?;
+        Ok(head.trim().to_string())
+    }
+
     pub(crate) fn parse_stash_list(output: &str) -> Vec<crate::domain::repository::StashEntry> {
         use crate::domain::repository::StashEntry;
         output
@@ -280,30 +783,312 @@ impl GitProjectRepository {
             .collect()
     }
 
-    fn get_metadata_path() -> PathBuf {
-        Path::new(".worktree.json").to_path_buf()
+    /// `git log` arguments for a history query.
+    fn history_args(query: &HistoryQuery) -> Vec<String> {
+        let mut args = vec!["log".to_string()];
+        if query.graph {
+            args.push("--graph".into());
+        }
+        args.push(format!("-{}", query.limit));
+        if query.skip > 0 {
+            args.push(format!("--skip={}", query.skip));
+        }
+        args.push(format!("--pretty=format:{HISTORY_FORMAT}"));
+        args.push("--date=short".into());
+        if let Some(grep) = &query.grep {
+            args.push(format!("--grep={grep}"));
+            args.push("--regexp-ignore-case".into());
+        }
+        if let Some(author) = &query.author {
+            args.push(format!("--author={author}"));
+        }
+        if let Some(since) = &query.since {
+            args.push(format!("--since={since}"));
+        }
+        if let Some(until) = &query.until {
+            args.push(format!("--until={until}"));
+        }
+        if let Some(path) = &query.path {
+            args.push("--".into());
+            args.push(path.clone());
+        }
+        args
     }
 
-    fn load_metadata() -> std::collections::HashMap<String, WorktreeMetadata> {
-        let path = Self::get_metadata_path();
-        if !path.exists() {
-            return std::collections::HashMap::new();
+    /// Parses `-U0` diff output into the files it touches.
+    fn parse_changed_files(diff: &str) -> Vec<ChangedFile> {
+        let mut files: Vec<ChangedFile> = Vec::new();
+        for line in diff.lines() {
+            if let Some(header) = line.strip_prefix("diff --git ") {
+                let path = header
+                    .split_once(" b/")
+                    .map_or(header, |(_, b)| b)
+                    .to_string();
+                files.push(ChangedFile {
+                    path,
+                    status: 'M',
+                    line: None,
+                });
+                continue;
+            }
+            let Some(current) = files.last_mut() else {
+                continue;
+            };
+            if line.starts_with("new file mode") {
+                current.status = 'A';
+            } else if line.starts_with("deleted file mode") {
+                current.status = 'D';
+            } else if let Some(to) = line.strip_prefix("rename to ") {
+                current.status = 'R';
+                current.path = to.to_string();
+            } else if let Some(path) = line.strip_prefix("+++ b/") {
+                current.path = path.to_string();
+            } else if line.starts_with("@@ ")
+                && current.line.is_none()
+                && let Some(new_range) = line
+                    .split_whitespace()
+                    .find_map(|part| part.strip_prefix('+'))
+            {
+                // `+14,0` is a pure deletion after line 14; point at that line.
+                let start = new_range.split(',').next().unwrap_or("1");
+                current.line = start.parse::<usize>().ok().map(|n| n.max(1));
+            }
         }
+        files
+    }
 
-        std::fs::read_to_string(path)
-            .ok()
-            .and_then(|content| serde_json::from_str(&content).ok())
+    /// Parses `git show -s` output produced with [`COMMIT_DETAIL_FORMAT`].
+    fn parse_commit_header(output: &str) -> Option<CommitDetail> {
+        let parts: Vec<&str> = output.splitn(9, '\x00').collect();
+        let [
+            hash,
+            parents,
+            author,
+            author_date,
+            committer,
+            committer_date,
+            refs,
+            subject,
+            body,
+        ] = parts.as_slice()
+        else {
+            return None;
+        };
+        Some(CommitDetail {
+            hash: hash.trim().to_string(),
+            author: (*author).to_string(),
+            author_date: (*author_date).to_string(),
+            committer: (*committer).to_string(),
+            committer_date: (*committer_date).to_string(),
+            parents: parents.split_whitespace().map(str::to_string).collect(),
+            refs: refs
+                .split(", ")
+                .map(str::trim)
+                .filter(|r| !r.is_empty())
+                .map(str::to_string)
+                .collect(),
+            subject: (*subject).to_string(),
+            body: body.trim_end().to_string(),
+            files: Vec::new(),
+            diff: String::new(),
+        })
+    }
+
+    /// Parses `--numstat` output; binary files report `-` for both counts.
+    fn parse_numstat(output: &str) -> Vec<FileStat> {
+        output
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(3, '\t');
+                let additions = parts.next()?;
+                let deletions = parts.next()?;
+                let path = parts.next()?;
+                Some(FileStat {
+                    path: path.to_string(),
+                    additions: additions.parse().ok(),
+                    deletions: deletions.parse().ok(),
+                })
+            })
+            .collect()
+    }
+
+    /// The hub's metadata store, able to migrate the old branch-keyed format.
+    fn metadata_store() -> Result<MetadataStore> {
+        let hub = Self::get_project_root_path()?;
+        let ids_by_branch = Self::admin_entries()
             .unwrap_or_default()
+            .into_iter()
+            .filter_map(|entry| Some((entry.branch?, entry.id)))
+            .collect();
+        Ok(MetadataStore::new(&hub, ids_by_branch))
     }
 
-    #[allow(dead_code)]
-    fn save_metadata(metadata: &std::collections::HashMap<String, WorktreeMetadata>) -> Result<()> {
-        let path = Self::get_metadata_path();
-        let content = serde_json::to_string_pretty(metadata)?;
-        std::fs::write(path, content)?;
+    /// Metadata of every worktree, keyed by admin id.
+    fn load_metadata() -> MetadataMap {
+        Self::metadata_store()
+            .map(|store| store.load())
+            .unwrap_or_default()
+    }
+
+    /// Git's admin entries: linked worktrees from `<common-dir>/worktrees/<id>/` plus the main
+    /// worktree of a non-bare repository.
+    fn admin_entries() -> Result<Vec<AdminEntry>> {
+        let common = Self::run_git(&["rev-parse", "--path-format=absolute", "--git-common-dir"])?;
+        let common = Path::new(common.trim());
+        let branch_of = |admin: &Path| {
+            std::fs::read_to_string(admin.join("HEAD"))
+                .ok()
+                .and_then(|head| {
+                    head.trim()
+                        .strip_prefix("ref: refs/heads/")
+                        .map(str::to_string)
+                })
+        };
+
+        let mut entries = Vec::new();
+        if let Ok(dirs) = std::fs::read_dir(common.join("worktrees")) {
+            for dir in dirs.flatten() {
+                let Ok(gitdir) = std::fs::read_to_string(dir.path().join("gitdir")) else {
+                    continue;
+                };
+                entries.push(AdminEntry {
+                    id: dir.file_name().to_string_lossy().to_string(),
+                    path: Path::new(gitdir.trim())
+                        .parent()
+                        .map(Path::to_path_buf)
+                        .unwrap_or_default(),
+                    branch: branch_of(&dir.path()),
+                });
+            }
+        }
+        let bare = Self::run_git(&[
+            "--git-dir",
+            &common.to_string_lossy(),
+            "config",
+            "--bool",
+            "core.bare",
+        ])
+        .is_ok_and(|out| out.trim() == "true");
+        if !bare && let Some(main) = common.parent() {
+            entries.push(AdminEntry {
+                id: MAIN_WORKTREE_ID.to_string(),
+                path: main.to_path_buf(),
+                branch: branch_of(common),
+            });
+        }
+        Ok(entries)
+    }
+
+    /// The worktree's admin id; unlike its branch or directory, it survives checkouts.
+    fn admin_id(path: &str) -> Result<String> {
+        let git_dir = Self::run_git(&["-C", path, "rev-parse", "--absolute-git-dir"])
+            .context(format!("'{path}' is not a worktree of this hub"))?;
+        let git_dir = Path::new(git_dir.trim());
+        let linked = git_dir
+            .parent()
+            .and_then(Path::file_name)
+            .is_some_and(|parent| parent == "worktrees");
+        Ok(match git_dir.file_name() {
+            Some(id) if linked => id.to_string_lossy().to_string(),
+            _ => MAIN_WORKTREE_ID.to_string(),
+        })
+    }
+
+    /// Starts a new worktree's metadata: `created_at` and, when enabled, its resources.
+    /// Records a worktree's metadata right after `git worktree add` and sets up its context files.
+    ///
+    /// Without metadata the worktree has no creation date, ports or service names, so when that
+    /// fails (e.g. the port pool is exhausted) the worktree and a `new_branch` are removed again.
+    fn finish_new_worktree(&self, abs_path: &Path, new_branch: Option<&str>) -> Result<()> {
+        let path = abs_path.to_string_lossy();
+        if let Err(e) = self.init_metadata(abs_path) {
+            let _ = Self::run_git(&["worktree", "remove", "--force", "--", &path]);
+            if let Some(branch) = new_branch {
+                let _ = Self::run_git(&["branch", "-D", branch]);
+            }
+            return Err(anyhow::anyhow!(
+                "Failed to record metadata for '{path}', so the new worktree was removed again: {e:#}"
+            ));
+        }
+        self.handle_context_files(&path);
         Ok(())
     }
 
+    fn init_metadata(&self, path: &Path) -> Result<()> {
+        let id = Self::admin_id(&path.to_string_lossy())?;
+        let project_config = self.get_project_config().unwrap_or_default();
+        let issue = Self::current_branch(&path.to_string_lossy())
+            .ok()
+            .and_then(|branch| Self::issue_for_branch(&project_config.issues, &branch));
+        let config = project_config.resources;
+        let project = Self::get_project_root_path()
+            .ok()
+            .and_then(|root| root.file_name().map(|n| n.to_string_lossy().to_string()))
+            .unwrap_or_default();
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        Self::metadata_store()?.update(|all| {
+            // Git reuses the ids of removed worktrees, so never inherit an old entry.
+            let mut metadata = WorktreeMetadata {
+                created_at: Some(
+                    chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                ),
+                issue,
+                ..WorktreeMetadata::default()
+            };
+            if config.enabled {
+                let taken: Vec<Resources> = all
+                    .iter()
+                    .filter(|(key, _)| **key != id)
+                    .filter_map(|(_, meta)| meta.resources.clone())
+                    .collect();
+                let resources = allocate(&config, &project, &name, &taken)?;
+                debug!(%id, ports = %resources.ports_label(), "Allocated worktree resources.");
+                metadata.resources = Some(resources);
+            }
+            all.insert(id.clone(), metadata);
+            Ok(())
+        })?
+    }
+
+    /// The ticket named in `branch`, linked without asking the tracker.
+    fn issue_for_branch(config: &IssueConfig, branch: &str) -> Option<IssueLink> {
+        match config.extract_id(branch) {
+            Ok(id) => id.map(|id| provider_for(config).link(&id)),
+            Err(e) => {
+                debug!(error = %e, "Skipping issue detection.");
+                None
+            }
+        }
+    }
+
+    /// Drops metadata whose worktree git no longer knows, returning its ports to the pool.
+    fn prune_metadata() -> Result<()> {
+        let live: std::collections::HashSet<String> =
+            Self::admin_entries()?.into_iter().map(|e| e.id).collect();
+        Self::metadata_store()?.update(|all| {
+            all.retain(|id, _| live.contains(id));
+        })
+    }
+
+    /// `path` as given when it exists, otherwise relative to the hub root (like `add`).
+    fn resolve_worktree_path(path: &str) -> String {
+        if Path::new(path).exists() {
+            return path.to_string();
+        }
+        Self::get_project_root_path().map_or_else(
+            |_| path.to_string(),
+            |root| root.join(path).to_string_lossy().to_string(),
+        )
+    }
+
+    fn project_config_path() -> Result<PathBuf> {
+        Ok(Self::get_project_root_path()?.join(".worktrees.yaml"))
+    }
+
     fn get_project_root_path() -> Result<PathBuf> {
         // Use git rev-parse --git-common-dir to find the bare repo location
         let output = Self::run_git(&["rev-parse", "--path-format=absolute", "--git-common-dir"])?;
@@ -371,6 +1156,9 @@ impl GitProjectRepository {
                 status_summary: None,
                 size_bytes: 0,
                 metadata: None,
+                upstream: None,
+                contexts: Vec::new(),
+                sync_drift: Vec::new(),
             },
             |mut wt, line| {
                 if let Some(path) = line.strip_prefix("worktree ") {
@@ -413,13 +1201,18 @@ impl ProjectRepository for GitProjectRepository {
         std::fs::write(".git", "gitdir: ./.bare\n")
             .context("Failed to write .git redirection file.")?;
 
+        // A bare clone has no fetch refspec. The remote is not always `origin`, e.g. with
+        // `clone.defaultRemoteName` set, so ask git what it was called.
         if url.is_some() {
-            Self::run_git(&[
-                "config",
-                "remote.origin.fetch",
-                "+refs/heads/*:refs/remotes/origin/*",
-            ])?;
-            Self::run_git(&["fetch", "origin"])?;
+            let remotes = Self::run_git(&["remote"])?;
+            for remote in remotes.lines().map(str::trim).filter(|r| !r.is_empty()) {
+                Self::run_git(&[
+                    "config",
+                    &format!("remote.{remote}.fetch"),
+                    &format!("+refs/heads/*:refs/remotes/{remote}/*"),
+                ])?;
+                Self::run_git(&["fetch", remote])?;
+            }
         }
 
         Ok(())
@@ -432,10 +1225,9 @@ impl ProjectRepository for GitProjectRepository {
         let abs_path_str = abs_path.to_string_lossy();
 
         Self::run_git(&["worktree", "add", "--", &abs_path_str, branch]).context(format!(
-            "Failed to add worktree '{path}'. HELP: Ensure the branch '{branch}' exists on origin."
+            "Failed to add worktree '{path}'. HELP: Ensure the branch '{branch}' exists locally or on exactly one remote."
         ))?;
-        self.handle_context_files(&abs_path_str);
-        Ok(())
+        self.finish_new_worktree(&abs_path, None)
     }
 
     fn add_new_worktree(&self, path: &str, branch: &str, base: &str) -> Result<()> {
@@ -450,13 +1242,25 @@ impl ProjectRepository for GitProjectRepository {
             debug!("Normal worktree add failed on HEAD, trying --orphan for fresh repository...");
             Self::run_git(&["worktree", "add", "--orphan", "-b", branch, &abs_path_str])
                 .context(format!("Failed to create orphan worktree '{path}'. HELP: Ensure your Git version is 2.42+ or manually create the first commit."))?;
-            self.handle_context_files(&abs_path_str);
-            return Ok(());
+            return self.finish_new_worktree(&abs_path, Some(branch));
         }
 
         res.context(format!("Failed to create new worktree '{path}' from '{base}'. HELP: Ensure the base branch '{base}' is valid."))?;
-        self.handle_context_files(&abs_path_str);
-        Ok(())
+        self.finish_new_worktree(&abs_path, Some(branch))
+    }
+
+    fn add_detached_worktree(&self, path: &str, base: &str) -> Result<()> {
+        self.validate_worktree_path(path)?;
+        let root = self.get_project_root()?;
+        let abs_path = root.join(path);
+        let abs_path_str = abs_path.to_string_lossy();
+
+        Self::run_git(&["worktree", "add", "--detach", "--", &abs_path_str, base]).context(
+            format!(
+                "Failed to add worktree '{path}' at '{base}'. HELP: Ensure '{base}' is a valid ref."
+            ),
+        )?;
+        self.finish_new_worktree(&abs_path, None)
     }
 
     fn remove_worktree(&self, path: &str, force: bool) -> Result<()> {
@@ -467,127 +1271,183 @@ impl ProjectRepository for GitProjectRepository {
         args.push("--");
         args.push(path);
 
+        let id = Self::admin_id(&Self::resolve_worktree_path(path)).ok();
         Self::run_git(&args).context(format!("Failed to remove worktree '{path}'. HELP: Ensure the directory is not in use by another process."))?;
+        // Also returns the worktree's ports to the pool.
+        if let Some(id) = id
+            && let Err(e) = Self::metadata_store().and_then(|store| {
+                store.update(|all| {
+                    all.remove(&id);
+                })
+            })
+        {
+            debug!(error = %e, "Failed to remove worktree metadata.");
+        }
         Ok(())
     }
 
     fn sync_configs(&self, path: &str) -> Result<()> {
-        let manifest_path = Path::new(".worktrees.sync");
-        if !manifest_path.exists() {
-            debug!("No .worktrees.sync manifest found, skipping generic synchronization.");
+        let Some(sync) = Self::load_sync_plan(path)? else {
             return Ok(());
-        }
-
-        let abs_manifest_path = std::env::current_dir()
-            .unwrap_or_else(|_| PathBuf::from("."))
-            .join(manifest_path);
+        };
+        Self::apply_sync_ops(path, &sync, |_| true)
+    }
 
-        let metadata = std::fs::metadata(manifest_path)
-            .context("Failed to read metadata of .worktrees.sync manifest.")?;
-        let mtime = metadata
-            .modified()
-            .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+    fn sync_entries(&self, path: &str, entries: &[String]) -> Result<()> {
+        let entries = entries
+            .iter()
+            .map(|line| {
+                SyncEntry::parse(line).with_context(|| format!("Invalid sync entry '{line}'"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let hub = Self::get_project_root_path()?;
+        let sync = Self::sync_plan(path, hub, &entries)?;
+        Self::apply_sync_ops(path, &sync, |_| true)
+    }
 
-        static SYNC_CONFIG_CACHE: std::sync::OnceLock<
-            std::sync::Mutex<std::collections::HashMap<PathBuf, (std::time::SystemTime, String)>>,
-        > = std::sync::OnceLock::new();
-        let cache = SYNC_CONFIG_CACHE
-            .get_or_init(|| std::sync::Mutex::new(std::collections::HashMap::new()));
+    fn check_sync(&self, path: &str) -> Result<Vec<SyncDrift>> {
+        let Some(sync) = Self::load_sync_plan(path)? else {
+            return Ok(Vec::new());
+        };
+        let record = Self::load_sync_record(path);
+        Ok(check(&sync.ops, &sync.vars, &record, &sync.hub))
+    }
 
-        let content = {
-            let mut guard = cache.lock().unwrap();
-            if let Some((cached_mtime, cached_content)) = guard.get(&abs_manifest_path) {
-                if *cached_mtime == mtime {
-                    debug!("Using cached .worktrees.sync manifest.");
-                    cached_content.clone()
-                } else {
-                    let content = std::fs::read_to_string(manifest_path)
-                        .context("Failed to read .worktrees.sync manifest.")?;
-                    guard.insert(abs_manifest_path, (mtime, content.clone()));
-                    content
-                }
-            } else {
-                let content = std::fs::read_to_string(manifest_path)
-                    .context("Failed to read .worktrees.sync manifest.")?;
-                guard.insert(abs_manifest_path, (mtime, content.clone()));
-                content
-            }
+    fn fix_sync(&self, path: &str, destinations: &[String]) -> Result<()> {
+        let Some(sync) = Self::load_sync_plan(path)? else {
+            return Ok(());
         };
+        let worktree = PathBuf::from(&sync.vars.path);
+        Self::apply_sync_ops(path, &sync, |op| {
+            op.destination
+                .strip_prefix(&worktree)
+                .is_ok_and(|d| destinations.iter().any(|x| Path::new(x) == d))
+        })
+    }
 
-        for line in content.lines() {
-            let line = line.trim();
-            if line.is_empty() || line.starts_with('#') {
-                continue;
-            }
+    fn sync_diff(&self, path: &str, destination: &str) -> Result<String> {
+        let sync = Self::load_sync_plan(path)?
+            .ok_or_else(|| anyhow::anyhow!("No .worktrees.sync manifest found"))?;
+        let target = Path::new(&sync.vars.path).join(destination);
+        let op = sync
+            .ops
+            .iter()
+            .find(|op| op.destination == target)
+            .ok_or_else(|| anyhow::anyhow!("{destination} is not a synced entry"))?;
+
+        // Directories are compared with their source; files with what a sync would write.
+        let expected = if op.source.is_dir() || op.action == SyncAction::Symlink {
+            op.source.clone()
+        } else {
+            let file = std::env::temp_dir().join(format!(
+                "worktree-sync-{}-{}",
+                std::process::id(),
+                target.file_name().unwrap_or_default().to_string_lossy()
+            ));
+            std::fs::write(&file, expected_content(op, &sync.vars).unwrap_or_default())
+                .context("Failed to write expected sync content")?;
+            file
+        };
+        let output = Command::new("git")
+            .args(["diff", "--no-index", "--no-color", "--"])
+            .arg(&target)
+            .arg(&expected)
+            .output()
+            .context("Failed to execute git diff")?;
+        if expected != op.source {
+            let _ = std::fs::remove_file(&expected);
+        }
+        // `--no-index` exits with 1 when the files differ.
+        if output.status.code().is_some_and(|c| c > 1) {
+            return Err(anyhow::anyhow!(
+                "git diff failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
 
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() < 2 {
-                continue;
-            }
+    fn lookup_issue(&self, id: &str) -> Result<IssueLink> {
+        let config = self.get_project_config()?.issues;
+        provider_for(&config).lookup(id)
+    }
 
-            let action = parts[0];
-            let source_name = parts[1];
-            let source = Path::new(source_name);
+    fn worktree_metadata(&self, path: &str) -> Result<WorktreeMetadata> {
+        let id = Self::admin_id(&Self::resolve_worktree_path(path))?;
+        Ok(Self::load_metadata().remove(&id).unwrap_or_default())
+    }
 
-            if source.is_absolute()
-                || source
-                    .components()
-                    .any(|c| matches!(c, std::path::Component::ParentDir))
-            {
-                debug!(
-                    ?source,
-                    "Source path is unsafe (absolute or contains '..'), skipping."
-                );
-                continue;
-            }
+    fn update_worktree_metadata(&self, path: &str, metadata: &WorktreeMetadata) -> Result<()> {
+        let id = Self::admin_id(&Self::resolve_worktree_path(path))?;
+        Self::metadata_store()?.update(|all| {
+            all.insert(id, metadata.clone());
+        })
+    }
 
-            let destination = Path::new(path).join(source_name);
+    fn list_layout(&self) -> ListLayout {
+        Self::metadata_store()
+            .map(|store| store.layout())
+            .unwrap_or_default()
+    }
 
-            if !source.exists() {
-                debug!(
-                    ?source,
-                    "Source file defined in manifest does not exist, skipping."
-                );
-                continue;
-            }
+    fn save_list_layout(&self, layout: &ListLayout) -> Result<()> {
+        Self::metadata_store()?.save_layout(layout)
+    }
 
-            match action {
-                "symlink" => {
-                    #[cfg(unix)]
-                    {
-                        if destination.exists() {
-                            let _ = std::fs::remove_file(&destination);
-                            let _ = std::fs::remove_dir_all(&destination);
-                        }
-                        std::os::unix::fs::symlink(source, &destination).with_context(|| {
-                            format!("Failed to symlink {source:?} to {destination:?}")
-                        })?;
-                    }
-                    #[cfg(not(unix))]
-                    {
-                        // Fallback to copy on non-unix systems for simplicity in this implementation
-                        if source.is_dir() {
-                            // Minimal dir copy logic or just skip
-                        } else {
-                            std::fs::copy(source, &destination)?;
-                        }
-                    }
-                }
-                "copy" => {
-                    if source.is_dir() {
-                        // For simplicity, we only support file copies in this version
-                        debug!(?source, "Directory copy not yet supported, skipping.");
-                    } else {
-                        std::fs::copy(source, &destination).with_context(|| {
-                            format!("Failed to copy {source:?} to {destination:?}")
-                        })?;
-                    }
-                }
-                _ => debug!(?action, "Unknown action in manifest, skipping."),
-            }
+    fn expired_worktrees(&self, now: i64) -> Result<Vec<String>> {
+        let mut metadata = Self::load_metadata();
+        if metadata.values().all(|m| m.expires_at.is_none()) {
+            return Ok(Vec::new());
         }
+        Ok(Self::admin_entries()?
+            .into_iter()
+            .filter(|entry| {
+                metadata
+                    .remove(&entry.id)
+                    .and_then(|m| expires_in(&m, now))
+                    .is_some_and(|left| left <= 0)
+            })
+            .map(|entry| {
+                entry.branch.unwrap_or_else(|| {
+                    entry
+                        .path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_default()
+                })
+            })
+            .collect())
+    }
 
-        Ok(())
+    fn worktree_env(&self, path: &str) -> Result<Vec<(String, String)>> {
+        let root = std::path::absolute(Self::resolve_worktree_path(path))
+            .context("Failed to resolve worktree path")?;
+        let hub = Self::get_project_root_path()?;
+        let read_env = |file: PathBuf| {
+            std::fs::read_to_string(file)
+                .map(|content| parse_dotenv(&content))
+                .unwrap_or_default()
+        };
+        let branch = Self::current_branch(&root.to_string_lossy()).unwrap_or_default();
+        let metadata = Self::admin_id(&root.to_string_lossy())
+            .ok()
+            .and_then(|id| Self::load_metadata().remove(&id))
+            .unwrap_or_default();
+
+        Ok(EnvLayers {
+            hub: read_env(hub.join(".env")),
+            cache: read_env(hub.join(".cache").join("cache.env")),
+            context: context_env(&root),
+            resources: metadata.resources.map(|r| r.env()).unwrap_or_default(),
+            worktree: metadata.env,
+            name: root
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            branch,
+            root: root.to_string_lossy().to_string(),
+        }
+        .merge())
     }
 
     fn list_worktrees(&self) -> Result<Vec<Worktree>> {
@@ -610,10 +1470,38 @@ impl ProjectRepository for GitProjectRepository {
                 Ok(wt)
             })
             .collect::<Result<Vec<_>>>()?;
-        let metadata_map = Self::load_metadata();
+        let mut metadata_map = Self::load_metadata();
+        let issue_config = self.get_project_config().unwrap_or_default().issues;
+        let ids: std::collections::HashMap<PathBuf, String> = Self::admin_entries()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|entry| (entry.path.canonicalize().unwrap_or(entry.path), entry.id))
+            .collect();
+        let upstreams = Self::run_git(&[
+            "for-each-ref",
+            "--format=%(refname:short)%00%(upstream:short)",
+            "refs/heads",
+        ])
+        .unwrap_or_default();
+        let upstreams: std::collections::HashMap<&str, &str> = upstreams
+            .lines()
+            .filter_map(|line| line.split_once('\0'))
+            .filter(|(_, upstream)| !upstream.is_empty())
+            .collect();
         for wt in &mut worktrees {
-            if let Some(meta) = metadata_map.get(&wt.branch) {
-                wt.metadata = Some(meta.clone());
+            let path = Path::new(&wt.path);
+            wt.metadata = ids
+                .get(&path.canonicalize().unwrap_or_else(|_| path.to_path_buf()))
+                .and_then(|id| metadata_map.remove(id));
+            // Worktrees added before issues were configured still show their ticket.
+            if !wt.is_bare
+                && wt.metadata.as_ref().is_none_or(|m| m.issue.is_none())
+                && let Some(issue) = Self::issue_for_branch(&issue_config, &wt.branch)
+            {
+                wt.metadata.get_or_insert_default().issue = Some(issue);
+            }
+            if !wt.is_detached {
+                wt.upstream = upstreams.get(wt.branch.as_str()).map(|u| (*u).to_string());
             }
         }
 
@@ -621,27 +1509,18 @@ impl ProjectRepository for GitProjectRepository {
     }
 
     fn detect_context(&self, base_path: &Path) -> ProjectContext {
-        use std::ffi::OsStr;
-        const INDICATORS: &[&str] = &[
-            "build.gradle",
-            "build.gradle.kts",
-            "settings.gradle",
-            "settings.gradle.kts",
-            "local.properties",
-        ];
-
-        if let Ok(entries) = std::fs::read_dir(base_path) {
-            for entry in entries.flatten() {
-                let file_name = entry.file_name();
-                if INDICATORS.iter().any(|&i| OsStr::new(i) == file_name) {
-                    return ProjectContext::KmpAndroid;
-                }
-            }
+        ProjectContext {
+            kinds: detect_contexts(base_path),
         }
-        ProjectContext::Standard
     }
 
     fn get_preferred_editor(&self) -> Result<Option<String>> {
+        // Per-project preference wins over the global one.
+        if let Ok(config) = self.get_project_config()
+            && let Some(editor) = config.editor
+        {
+            return Ok(Some(editor));
+        }
         if let Some(path) = Self::resolve_config_path(".worktrees.editor", "editor")
             && path.exists()
         {
@@ -675,30 +1554,99 @@ impl ProjectRepository for GitProjectRepository {
             perms.set_mode(0o600);
             file.set_permissions(perms)?;
 
-            file.write_all(editor.as_bytes())
-                .context("Failed to write editor config to file")?;
+            file.write_all(editor.as_bytes())
+                .context("Failed to write editor config to file")?;
+        }
+
+        #[cfg(not(unix))]
+        {
+            std::fs::write(path, editor)?;
+        }
+
+        Ok(())
+    }
+
+    fn list_editors(&self) -> Result<Vec<EditorConfig>> {
+        let mut editors = EditorConfig::defaults();
+        if let Some(path) = Self::resolve_config_path(".worktrees.editors.yaml", "editors.yaml")
+            && path.exists()
+        {
+            let content = std::fs::read_to_string(&path)?;
+            let custom: Vec<EditorConfig> = serde_yaml::from_str(&content)
+                .with_context(|| format!("Invalid editor definitions in {path:?}"))?;
+            for editor in custom {
+                if let Some(existing) = editors.iter_mut().find(|e| e.name == editor.name) {
+                    *existing = editor;
+                } else {
+                    editors.push(editor);
+                }
+            }
+        }
+        Ok(editors)
+    }
+
+    fn get_project_config(&self) -> Result<ProjectConfig> {
+        let path = Self::project_config_path()?;
+        if !path.exists() {
+            return Ok(ProjectConfig::default());
+        }
+        let content = std::fs::read_to_string(&path)?;
+        serde_yaml::from_str(&content).with_context(|| format!("Invalid project config {path:?}"))
+    }
+
+    fn save_project_config(&self, config: &ProjectConfig) -> Result<()> {
+        let path = Self::project_config_path()?;
+        let content = serde_yaml::to_string(config)?;
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write project config {path:?}"))
+    }
+
+    fn fetch(&self, path: &str, remote: Option<&str>) -> Result<()> {
+        match remote {
+            Some(remote) => Self::run_git(&["-C", path, "fetch", "--prune", "--", remote])?,
+            None => Self::run_git(&["-C", path, "fetch", "--all", "--prune"])?,
+        };
+        Ok(())
+    }
+
+    fn pull(&self, path: &str, remote: Option<&str>) -> Result<()> {
+        if let Some(remote) = remote {
+            let branch = Self::current_branch(path)?;
+            Self::run_git(&["-C", path, "pull", "--", remote, &branch])?;
+        } else {
+            Self::run_git(&["-C", path, "pull"])?;
         }
+        Ok(())
+    }
 
-        #[cfg(not(unix))]
-        {
-            std::fs::write(path, editor)?;
+    fn push(&self, path: &str, remote: Option<&str>) -> Result<()> {
+        if let Some(remote) = remote {
+            let branch = Self::current_branch(path)?;
+            Self::run_git(&["-C", path, "push", "--", remote, &branch])?;
+        } else {
+            Self::run_git(&["-C", path, "push"])?;
         }
-
         Ok(())
     }
 
-    fn fetch(&self, path: &str) -> Result<()> {
-        Self::run_git(&["-C", path, "fetch", "--all", "--prune"])?;
-        Ok(())
+    fn list_remotes(&self) -> Result<Vec<Remote>> {
+        let output = Self::run_git(&["remote", "-v"])?;
+        Ok(Self::parse_remotes(&output))
     }
 
-    fn pull(&self, path: &str) -> Result<()> {
-        Self::run_git(&["-C", path, "pull"])?;
+    fn add_remote(&self, name: &str, url: &str) -> Result<()> {
+        Self::run_git(&["remote", "add", "--", name, url]).context(format!(
+            "Failed to add remote '{name}'. HELP: Check that no remote with this name exists."
+        ))?;
+        Self::run_git(&["fetch", "--prune", "--", name]).context(format!(
+            "Added remote '{name}' but could not fetch it. HELP: Verify the URL and your credentials."
+        ))?;
         Ok(())
     }
 
-    fn push(&self, path: &str) -> Result<()> {
-        Self::run_git(&["-C", path, "push"])?;
+    fn remove_remote(&self, name: &str) -> Result<()> {
+        Self::run_git(&["remote", "remove", "--", name])
+            .context(format!("Failed to remove remote '{name}'."))?;
         Ok(())
     }
 
@@ -761,26 +1709,518 @@ impl ProjectRepository for GitProjectRepository {
         })
     }
 
-    fn get_history(&self, path: &str, limit: usize) -> Result<Vec<GitCommit>> {
-        let limit_str = limit.to_string();
+    fn get_history(&self, path: &str, query: &HistoryQuery) -> Result<Vec<GitCommit>> {
+        let mut args = vec!["-C".to_string(), path.to_string()];
+        args.extend(Self::history_args(query));
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let output = Self::run_git(&args)?;
+
+        Ok(Self::parse_git_history(&output))
+    }
+
+    fn get_commit_files(&self, path: &str, hash: &str) -> Result<Vec<ChangedFile>> {
         let output = Self::run_git(&[
             "-C",
             path,
-            "log",
-            "--graph",
-            &format!("-{limit_str}"),
-            "--pretty=format:%x00%h%x00%an%x00%ad%x00%s",
-            "--date=short",
+            "-c",
+            "core.quotePath=false",
+            "show",
+            "-U0",
+            "-M",
+            "--no-color",
+            "--no-ext-diff",
+            "--format=",
+            hash,
         ])?;
+        Ok(Self::parse_changed_files(&output))
+    }
 
-        Ok(Self::parse_git_history(&output))
+    fn get_commit_detail(&self, path: &str, hash: &str) -> Result<CommitDetail> {
+        let header = Self::run_git(&[
+            "-C",
+            path,
+            "show",
+            "-s",
+            "--date=iso",
+            &format!("--format={COMMIT_DETAIL_FORMAT}"),
+            hash,
+        ])?;
+        let mut detail = Self::parse_commit_header(&header)
+            .ok_or_else(|| anyhow::anyhow!("Unexpected git show output for {hash}"))?;
+
+        let numstat = Self::run_git(&[
+            "-C",
+            path,
+            "-c",
+            "core.quotePath=false",
+            "show",
+            "-M",
+            "--numstat",
+            "--format=",
+            hash,
+        ])?;
+        detail.files = Self::parse_numstat(&numstat);
+        detail.diff = Self::run_git(&[
+            "-C",
+            path,
+            "show",
+            "-M",
+            "--no-color",
+            "--no-ext-diff",
+            "--format=",
+            hash,
+        ])?;
+        Ok(detail)
+    }
+
+    fn cherry_pick(&self, path: &str, hash: &str) -> Result<()> {
+        Self::run_git(&["-C", path, "cherry-pick", hash])?;
+        Ok(())
+    }
+
+    fn revert_commit(&self, path: &str, hash: &str) -> Result<()> {
+        Self::run_git(&["-C", path, "revert", "--no-edit", hash])?;
+        Ok(())
+    }
+
+    fn resolve_commit_range(&self, path: &str, range: &str) -> Result<Vec<String>> {
+        let output = if range.contains("..") || range.ends_with("^!") {
+            Self::run_git(&[
+                "-C",
+                path,
+                "rev-list",
+                "--reverse",
+                "--no-merges",
+                range,
+                "--",
+            ])?
+        } else {
+            Self::run_git(&[
+                "-C",
+                path,
+                "rev-parse",
+                "--verify",
+                &format!("{range}^{{commit}}"),
+            ])?
+        };
+        let commits: Vec<String> = output
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(String::from)
+            .collect();
+        if commits.is_empty() {
+            return Err(anyhow::anyhow!("'{range}' does not select any commits"));
+        }
+        Ok(commits)
+    }
+
+    fn check_droppable(&self, path: &str, hashes: &[String]) -> Result<()> {
+        let (Some(first), Some(last)) = (hashes.first(), hashes.last()) else {
+            return Ok(());
+        };
+        // `rebase` refuses to run on local changes, which would leave the commits ported but kept.
+        let changes =
+            Self::run_git(&["-C", path, "status", "--porcelain", "--untracked-files=no"])?;
+        if !changes.trim().is_empty() {
+            return Err(anyhow::anyhow!(
+                "{path} has uncommitted changes; commit or stash them before removing commits"
+            ));
+        }
+        if Self::run_git(&[
+            "-C",
+            path,
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{first}^"),
+        ])
+        .is_err()
+        {
+            return Err(anyhow::anyhow!(
+                "{first} is the branch's root commit and cannot be removed"
+            ));
+        }
+        let chain = Self::run_git(&[
+            "-C",
+            path,
+            "rev-list",
+            "--reverse",
+            "--first-parent",
+            &format!("{first}^..{last}"),
+        ])?;
+        let chain: Vec<&str> = chain.lines().collect();
+        let contiguous = chain.len() == hashes.len()
+            && chain
+                .iter()
+                .zip(hashes)
+                .all(|(full, hash)| full.starts_with(hash.as_str()));
+        if !contiguous {
+            return Err(anyhow::anyhow!(
+                "The commits are not a contiguous run; remove them with an interactive rebase instead"
+            ));
+        }
+        Self::run_git(&["-C", path, "merge-base", "--is-ancestor", last, "HEAD"])
+            .map_err(|_| anyhow::anyhow!("{last} is not on the branch checked out at {path}"))?;
+        Ok(())
+    }
+
+    fn drop_commits(&self, path: &str, hashes: &[String]) -> Result<()> {
+        let (Some(first), Some(last)) = (hashes.first(), hashes.last()) else {
+            return Ok(());
+        };
+        self.check_droppable(path, hashes)?;
+
+        // Replay everything after `last` onto the parent of `first`.
+        if let Err(e) = Self::run_git(&["-C", path, "rebase", "--onto", &format!("{first}^"), last])
+        {
+            let _ = Self::run_git(&["-C", path, "rebase", "--abort"]);
+            return Err(e.context(
+                "Removing the commits from the source branch failed; it was left unchanged",
+            ));
+        }
+        Ok(())
+    }
+
+    fn compare_refs(&self, path: &str, left: &str, right: &str) -> Result<RefComparison> {
+        for side in [left, right] {
+            Self::run_git(&[
+                "-C",
+                path,
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("{side}^{{commit}}"),
+            ])
+            .map_err(|_| anyhow::anyhow!("'{side}' is not a worktree, branch, tag or commit"))?;
+        }
+        let log = |range: &str| -> Result<Vec<GitCommit>> {
+            let output = Self::run_git(&[
+                "-C",
+                path,
+                "log",
+                &format!("--pretty=format:{HISTORY_FORMAT}"),
+                "--date=short",
+                range,
+                "--",
+            ])?;
+            Ok(Self::parse_git_history(&output))
+        };
+        let left_only = log(&format!("{right}..{left}"))?;
+        let right_only = log(&format!("{left}..{right}"))?;
+        // Unrelated histories have no merge base; `git merge-base` then exits with 1.
+        let merge_base = Self::run_git(&["-C", path, "merge-base", left, right])
+            .ok()
+            .map(|out| out.trim().to_string())
+            .filter(|out| !out.is_empty());
+
+        let (files, diff) = if merge_base.is_some() {
+            let range = format!("{right}...{left}");
+            let numstat = Self::run_git(&[
+                "-C",
+                path,
+                "-c",
+                "core.quotePath=false",
+                "diff",
+                "-M",
+                "--numstat",
+                &range,
+                "--",
+            ])?;
+            let diff = Self::run_git(&[
+                "-C",
+                path,
+                "diff",
+                "-M",
+                "--no-color",
+                "--no-ext-diff",
+                &range,
+                "--",
+            ])?;
+            (Self::parse_numstat(&numstat), diff)
+        } else {
+            (Vec::new(), String::new())
+        };
+
+        Ok(RefComparison {
+            left: left.to_string(),
+            right: right.to_string(),
+            merge_base,
+            left_only,
+            right_only,
+            files,
+            diff,
+        })
+    }
+
+    fn first_changed_line(&self, path: &str, file: &str, staged: bool) -> Result<Option<usize>> {
+        let mut args = vec![
+            "-C",
+            path,
+            "-c",
+            "core.quotePath=false",
+            "diff",
+            "-U0",
+            "--no-color",
+            "--no-ext-diff",
+        ];
+        if staged {
+            args.push("--cached");
+        }
+        args.extend(["--", file]);
+        let output = Self::run_git(&args)?;
+        Ok(Self::parse_changed_files(&output)
+            .into_iter()
+            .find_map(|f| f.line))
     }
 
     fn list_branches(&self) -> Result<Vec<String>> {
-        let output = Self::run_git(&["branch", "-a", "--format=%(refname:short)"])?;
+        let output = Self::run_git(&[
+            "for-each-ref",
+            "--format=%(refname)",
+            "refs/heads",
+            "refs/remotes",
+        ])?;
         Ok(Self::parse_branches(&output))
     }
 
+    fn list_branch_info(&self) -> Result<Vec<BranchInfo>> {
+        let output = Self::run_git(&[
+            "for-each-ref",
+            &format!("--format={BRANCH_FORMAT}"),
+            "refs/heads",
+            "refs/remotes",
+        ])?;
+        Ok(Self::parse_branch_refs(&output))
+    }
+
+    fn create_branch(&self, name: &str, base: &str) -> Result<()> {
+        Self::run_git(&["branch", "--", name, base])
+            .context(format!("Failed to create branch '{name}' from '{base}'"))?;
+        Ok(())
+    }
+
+    fn rename_branch(&self, from: &str, to: &str) -> Result<()> {
+        Self::run_git(&["branch", "-m", "--", from, to])
+            .context(format!("Failed to rename branch '{from}' to '{to}'"))?;
+        Ok(())
+    }
+
+    fn delete_branch(&self, name: &str) -> Result<()> {
+        Self::run_git(&["branch", "-D", "--", name])
+            .context(format!("Failed to delete branch '{name}'"))?;
+        Ok(())
+    }
+
+    fn branch_unmerged_into(&self, branch: &str) -> Result<Option<String>> {
+        let upstream = Self::run_git(&[
+            "rev-parse",
+            "--abbrev-ref",
+            "--verify",
+            "--quiet",
+            &format!("{branch}@{{upstream}}"),
+        ])
+        .ok()
+        .map(|u| u.trim().to_string())
+        .filter(|u| !u.is_empty());
+        let target = match upstream {
+            Some(upstream) => upstream,
+            None => Self::hub_default_branch()?,
+        };
+        if target == branch {
+            return Ok(None);
+        }
+        let merged = Self::run_git(&["merge-base", "--is-ancestor", branch, &target]).is_ok();
+        Ok((!merged).then_some(target))
+    }
+
+    fn set_upstream(&self, branch: &str, upstream: &str) -> Result<()> {
+        Self::run_git(&[
+            "branch",
+            &format!("--set-upstream-to={upstream}"),
+            "--",
+            branch,
+        ])
+        .context(format!(
+            "Failed to set the upstream of '{branch}' to '{upstream}'"
+        ))?;
+        Ok(())
+    }
+
+    fn default_branch(&self) -> Result<String> {
+        Self::hub_default_branch()
+    }
+
+    fn merged_branches(&self, target: &str) -> Result<Vec<String>> {
+        let output = Self::run_git(&[
+            "for-each-ref",
+            &format!("--merged={target}"),
+            "--format=%(refname:short)",
+            "refs/heads",
+        ])?;
+        Ok(output.lines().map(str::to_string).collect())
+    }
+
+    fn last_activity(&self, path: &str) -> Result<i64> {
+        let committed = Self::run_git(&["-C", path, "log", "-1", "--format=%ct"])
+            .ok()
+            .and_then(|t| t.trim().parse().ok())
+            .unwrap_or(0);
+        // Checkouts rewrite HEAD and its reflog, staging rewrites the index.
+        let git_dir = Self::run_git(&["-C", path, "rev-parse", "--absolute-git-dir"])?;
+        let touched = ["HEAD", "index", "logs/HEAD"]
+            .iter()
+            .filter_map(|file| {
+                std::fs::metadata(Path::new(git_dir.trim()).join(file))
+                    .and_then(|m| m.modified())
+                    .ok()
+            })
+            .filter_map(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|age| i64::try_from(age.as_secs()).unwrap_or(i64::MAX))
+            .max()
+            .unwrap_or(0);
+        Ok(committed.max(touched))
+    }
+
+    fn scan_artifacts(&self, path: &str) -> Result<Vec<Artifact>> {
+        let mut config = self.get_project_config().unwrap_or_default();
+        for handler in detect_handlers(Path::new(path)) {
+            config
+                .artifacts
+                .include
+                .extend(handler.artifact_globs().iter().map(|g| g.to_string()));
+        }
+        let files = Self::run_git(&["-C", path, "ls-files", "-z"])?;
+        let tracked = tracked_paths(files.split('\0').filter(|f| !f.is_empty()));
+        Ok(Self::find_artifacts(
+            Path::new(path),
+            &ArtifactRules::new(&config.artifacts),
+            &tracked,
+            &Self::calculate_dir_size,
+        ))
+    }
+
+    fn remove_artifacts(&self, path: &str, artifacts: &[Artifact]) -> Result<()> {
+        let root = Path::new(path)
+            .canonicalize()
+            .with_context(|| format!("Worktree {path} does not exist"))?;
+        if !Self::is_safe_for_cleaning(&root) {
+            return Err(anyhow::anyhow!(
+                "Refusing to clean artifacts from unsafe path {root:?}"
+            ));
+        }
+        for artifact in artifacts {
+            let target = Path::new(&artifact.path);
+            // Resolve the parent only, so a symlinked artifact removes the link, not its target.
+            let resolved = target
+                .parent()
+                .and_then(|parent| parent.canonicalize().ok())
+                .zip(target.file_name())
+                .map(|(parent, name)| parent.join(name));
+            let Some(resolved) = resolved.filter(|p| p.starts_with(&root) && *p != root) else {
+                return Err(anyhow::anyhow!(
+                    "Refusing to remove {} outside of worktree {path}",
+                    artifact.path
+                ));
+            };
+            let metadata = match std::fs::symlink_metadata(&resolved) {
+                Ok(metadata) => metadata,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            if metadata.is_dir() {
+                std::fs::remove_dir_all(&resolved)
+            } else {
+                std::fs::remove_file(&resolved)
+            }
+            .with_context(|| format!("Failed to remove {}", artifact.path))?;
+        }
+        Ok(())
+    }
+
+    fn cache_status(&self) -> Result<Vec<CacheStatus>> {
+        let config = self.get_project_config().unwrap_or_default().cache;
+        let root = self.get_project_root()?;
+        let cache_root = root.join(".cache");
+        let worktrees: Vec<Worktree> = self
+            .list_worktrees()?
+            .into_iter()
+            .filter(|wt| !wt.is_bare)
+            .collect();
+        let cargo_config_path = root.join(".cargo").join("config.toml");
+        let cargo_content = std::fs::read_to_string(&cargo_config_path).ok();
+
+        let mut statuses = Vec::new();
+        for kind in config.enabled_kinds() {
+            let location = kind
+                .dir_name()
+                .map_or_else(Self::gradle_build_cache_dir, |name| cache_root.join(name));
+            let location_str = location.to_string_lossy().to_string();
+            let users = |uses: &dyn Fn(&Path) -> bool| -> Vec<String> {
+                worktrees
+                    .iter()
+                    .filter(|wt| config.kinds_for(Path::new(&wt.path)).contains(&kind))
+                    .filter(|wt| uses(Path::new(&wt.path)))
+                    .map(|wt| wt.head_ref().to_string())
+                    .collect()
+            };
+            let (configured_by, worktrees, problem) = match kind {
+                CacheKind::CargoTarget | CacheKind::Sccache => {
+                    let applied = cargo_content.as_deref().is_some_and(|c| {
+                        c.starts_with(CARGO_CONFIG_HEADER)
+                            && c.contains(&location_str.replace('\\', "/"))
+                    });
+                    let problem = if kind == CacheKind::Sccache && !Self::sccache_installed() {
+                        Some("sccache is not installed".to_string())
+                    } else if cargo_content.is_some() && !applied {
+                        Some(format!(
+                            "{} is not managed by worktree",
+                            cargo_config_path.display()
+                        ))
+                    } else if !applied {
+                        Some("not set up yet; add or re-add a Rust worktree".to_string())
+                    } else {
+                        None
+                    };
+                    (
+                        cargo_config_path.to_string_lossy().to_string(),
+                        if applied {
+                            users(&|_| true)
+                        } else {
+                            Vec::new()
+                        },
+                        problem,
+                    )
+                }
+                CacheKind::PnpmStore => (
+                    ".npmrc (store-dir)".to_string(),
+                    users(&|wt| {
+                        std::fs::read_to_string(wt.join(".npmrc"))
+                            .is_ok_and(|c| c.contains(&location_str))
+                    }),
+                    None,
+                ),
+                CacheKind::GradleBuildCache => (
+                    "gradle.properties (org.gradle.caching)".to_string(),
+                    users(&|wt| {
+                        std::fs::read_to_string(wt.join("gradle.properties"))
+                            .is_ok_and(|c| c.contains("org.gradle.caching=true"))
+                    }),
+                    None,
+                ),
+            };
+            statuses.push(CacheStatus {
+                kind,
+                size_bytes: Self::calculate_dir_size(&location),
+                location: location_str,
+                configured_by,
+                worktrees,
+                problem,
+            });
+        }
+        Ok(statuses)
+    }
+
     fn switch_branch(&self, path: &str, branch: &str) -> Result<()> {
         Self::run_git(&["-C", path, "checkout", branch])?;
         Ok(())
@@ -983,16 +2423,6 @@ impl ProjectRepository for GitProjectRepository {
             let current_dir = std::env::current_dir().context("Failed to get current directory")?;
             let worktrees = self.list_worktrees()?;
 
-            let artifact_dirs = [
-                "node_modules",
-                "target",
-                "build",
-                "dist",
-                ".gradle",
-                "bin",
-                "obj",
-            ];
-
             for wt in worktrees {
                 if wt.is_bare {
                     continue;
@@ -1010,28 +2440,26 @@ impl ProjectRepository for GitProjectRepository {
                     continue;
                 }
 
-                if !Self::is_safe_for_cleaning(wt_path) {
-                    error!(
-                        path = ?wt_path,
-                        "Refusing to clean artifacts from unsafe path (root or home dir)"
+                let artifacts = match self.scan_artifacts(&wt.path) {
+                    Ok(artifacts) => artifacts,
+                    Err(e) => {
+                        error!(error = %e, path = ?wt_path, "Failed to scan for artifacts");
+                        continue;
+                    }
+                };
+                if dry_run {
+                    cleaned_paths.extend(
+                        artifacts
+                            .iter()
+                            .map(|a| format!("[dry-run] build artifact: {}", a.path)),
                     );
                     continue;
                 }
-
-                for artifact in &artifact_dirs {
-                    let target = wt_path.join(artifact);
-                    if target.exists() {
-                        let path_str = target.to_string_lossy().to_string();
-                        if dry_run {
-                            cleaned_paths.push(format!("[dry-run] build artifact: {path_str}"));
-                        } else {
-                            match fs::remove_dir_all(&target) {
-                                Ok(()) => cleaned_paths.push(format!("cleaned: {path_str}")),
-                                Err(e) => {
-                                    error!(error = %e, path = %path_str, "Failed to remove artifact directory");
-                                }
-                            }
-                        }
+                match self.remove_artifacts(&wt.path, &artifacts) {
+                    Ok(()) => cleaned_paths
+                        .extend(artifacts.iter().map(|a| format!("cleaned: {}", a.path))),
+                    Err(e) => {
+                        error!(error = %e, path = ?wt_path, "Failed to remove artifacts");
                     }
                 }
             }
@@ -1115,6 +2543,9 @@ impl ProjectRepository for GitProjectRepository {
             debug!("Running git worktree prune to clean up stale entries");
             Self::run_git(&["worktree", "prune", "-v"])
                 .context("Failed to prune stale worktrees")?;
+            if let Err(e) = Self::prune_metadata() {
+                debug!(error = %e, "Failed to prune worktree metadata.");
+            }
         }
 
         Ok(to_remove)
@@ -1424,10 +2855,83 @@ impl ProjectRepository for GitProjectRepository {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::artifacts::{ArtifactConfig, Ecosystem};
+    use crate::domain::context::ContextKind;
     use std::sync::Mutex;
 
     static CWD_MUTEX: Mutex<()> = Mutex::new(());
 
+    fn touch(root: &Path, relative: &str) {
+        let path = root.join(relative);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, "x").unwrap();
+    }
+
+    fn relatives(found: &[Artifact]) -> Vec<(&str, Ecosystem)> {
+        found
+            .iter()
+            .map(|a| (a.relative.as_str(), a.ecosystem))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_artifacts_by_ecosystem() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        touch(root, "package.json");
+        touch(root, "node_modules/left-pad/index.js");
+        touch(root, "packages/ui/package.json");
+        touch(root, "packages/ui/node_modules/react/index.js");
+        touch(root, "crates/core/Cargo.toml");
+        touch(root, "crates/core/target/debug/core");
+        touch(root, "api/App.csproj");
+        touch(root, "api/bin/App.dll");
+        touch(root, "api/obj/project.assets.json");
+        touch(root, "tools/pyproject.toml");
+        touch(root, "tools/src/pkg/__pycache__/mod.pyc");
+        // Checked-in scripts and stray directories without a marker stay.
+        touch(root, "bin/deploy.sh");
+        touch(root, "docs/build/index.html");
+        touch(root, "coverage/lcov.info");
+
+        let tracked = tracked_paths(["bin/deploy.sh", "package.json"]);
+        let rules = ArtifactRules::new(&ArtifactConfig {
+            include: vec!["coverage".into()],
+            exclude: vec!["api/obj".into()],
+        });
+        let found = GitProjectRepository::find_artifacts(root, &rules, &tracked, &|_| 1);
+
+        assert_eq!(
+            relatives(&found),
+            vec![
+                ("api/bin", Ecosystem::DotNet),
+                ("coverage", Ecosystem::Custom),
+                ("crates/core/target", Ecosystem::Cargo),
+                ("node_modules", Ecosystem::Node),
+                ("packages/ui/node_modules", Ecosystem::Node),
+                ("tools/src/pkg/__pycache__", Ecosystem::Python),
+            ]
+        );
+        assert!(found.iter().all(|a| a.size_bytes == 1));
+    }
+
+    #[test]
+    fn test_find_artifacts_skips_tracked_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        touch(root, "go.mod");
+        touch(root, "vendor/modules.txt");
+        touch(root, "bin/server");
+
+        let found = GitProjectRepository::find_artifacts(
+            root,
+            &ArtifactRules::new(&ArtifactConfig::default()),
+            &tracked_paths(["go.mod", "vendor/modules.txt"]),
+            &|_| 0,
+        );
+        assert_eq!(relatives(&found), vec![("bin", Ecosystem::Go)]);
+    }
+
     #[test]
     fn test_parse_git_history_normal() {
         let output = "* \x00abc1234\x00John Doe\x002023-01-01\x00Fix bug\n* \x00def4567\x00Jane Smith\x002023-01-02\x00Add feature";
@@ -1443,6 +2947,66 @@ mod tests {
         assert_eq!(commits[1].message, "Add feature");
     }
 
+    #[test]
+    fn test_history_args() {
+        let args = GitProjectRepository::history_args(&HistoryQuery::latest(10));
+        assert_eq!(args[..3], ["log", "--graph", "-10"]);
+        assert!(!args.iter().any(|a| a.starts_with("--skip") || a == "--"));
+
+        let mut query = HistoryQuery {
+            skip: 50,
+            graph: false,
+            ..HistoryQuery::default()
+        };
+        query.apply_search("login author:jane path:src/main.rs since:2026-01-01");
+        let args = GitProjectRepository::history_args(&query);
+        assert_eq!(args[..3], ["log", "-50", "--skip=50"]);
+        assert!(args.contains(&"--grep=login".to_string()));
+        assert!(args.contains(&"--regexp-ignore-case".to_string()));
+        assert!(args.contains(&"--author=jane".to_string()));
+        assert!(args.contains(&"--since=2026-01-01".to_string()));
+        assert_eq!(args[args.len() - 2..], ["--", "src/main.rs"]);
+    }
+
+    #[test]
+    fn test_parse_changed_files() {
+        let diff = "diff --git a/src/lib.rs b/src/lib.rs\nindex 1..2 100644\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -10,0 +11,2 @@ fn main() {\n+a\n+b\n@@ -40 +42 @@\n-x\n+y\ndiff --git a/new.txt b/new.txt\nnew file mode 100644\n--- /dev/null\n+++ b/new.txt\n@@ -0,0 +1 @@\n+hi\ndiff --git a/gone.txt b/gone.txt\ndeleted file mode 100644\n--- a/gone.txt\n+++ /dev/null\n@@ -1 +0,0 @@\n-bye\ndiff --git a/old name.rs b/new name.rs\nsimilarity index 90%\nrename from old name.rs\nrename to new name.rs\n";
+        let files = GitProjectRepository::parse_changed_files(diff);
+        let summary: Vec<(&str, char, Option<usize>)> = files
+            .iter()
+            .map(|f| (f.path.as_str(), f.status, f.line))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("src/lib.rs", 'M', Some(11)),
+                ("new.txt", 'A', Some(1)),
+                ("gone.txt", 'D', Some(1)),
+                ("new name.rs", 'R', None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_commit_header_and_numstat() {
+        let header = "abc123\x00p1 p2\x00Jane <jane@x.io>\x002026-01-01 10:00:00 +0000\x00Bot <bot@x.io>\x002026-01-02 10:00:00 +0000\x00HEAD -> main, tag: v1.0\x00Fix bug\x00Longer body\n\nwith paragraphs\n\n";
+        let detail = GitProjectRepository::parse_commit_header(header).unwrap();
+        assert_eq!(detail.hash, "abc123");
+        assert_eq!(detail.parents, vec!["p1", "p2"]);
+        assert_eq!(detail.author, "Jane <jane@x.io>");
+        assert_eq!(detail.committer, "Bot <bot@x.io>");
+        assert_eq!(detail.refs, vec!["HEAD -> main", "tag: v1.0"]);
+        assert_eq!(detail.subject, "Fix bug");
+        assert_eq!(detail.body, "Longer body\n\nwith paragraphs");
+        assert!(GitProjectRepository::parse_commit_header("garbage").is_none());
+
+        let stats = GitProjectRepository::parse_numstat("3\t1\tsrc/lib.rs\n-\t-\tlogo.png\n");
+        assert_eq!(stats[0].additions, Some(3));
+        assert_eq!(stats[0].deletions, Some(1));
+        assert_eq!(stats[1].path, "logo.png");
+        assert_eq!(stats[1].additions, None);
+    }
+
     #[test]
     fn test_parse_git_history_single() {
         let output = "* \x00abc1234\x00John Doe\x002023-01-01\x00Fix bug";
@@ -1516,16 +3080,14 @@ mod tests {
         let repo = GitProjectRepository;
 
         // Test Standard
-        assert_eq!(
-            repo.detect_context(&temp_dir),
-            crate::domain::repository::ProjectContext::Standard
-        );
+        assert!(repo.detect_context(&temp_dir).kinds.is_empty());
 
-        // Test KMP/Android
+        // Test KMP/Android stacked with Node
         std::fs::write(temp_dir.join("build.gradle.kts"), "").unwrap();
+        std::fs::write(temp_dir.join("package.json"), "{}").unwrap();
         assert_eq!(
-            repo.detect_context(&temp_dir),
-            crate::domain::repository::ProjectContext::KmpAndroid
+            repo.detect_context(&temp_dir).kinds,
+            vec![ContextKind::Node, ContextKind::Kmp]
         );
 
         // Cleanup
@@ -1598,14 +3160,94 @@ mod tests {
 
     #[test]
     fn test_parse_branches() {
-        let output = "main\norigin/HEAD\norigin/main\norigin/feature-branch\norigin/other-branch";
+        let output = "refs/heads/main\nrefs/heads/feat/x\nrefs/remotes/origin/HEAD\nrefs/remotes/origin/main\nrefs/remotes/origin/feature-branch\nrefs/remotes/upstream/main\nrefs/remotes/upstream/release";
         let branches = GitProjectRepository::parse_branches(output);
 
-        assert_eq!(branches.len(), 3);
-        assert_eq!(branches[0], "feature-branch");
-        assert_eq!(branches[1], "main"); // deduplicated origin/main
-        assert_eq!(branches[2], "other-branch");
-        // ensure origin/HEAD is ignored
+        // Remote prefixes are stripped for every remote, duplicates collapse, HEAD is skipped.
+        assert_eq!(
+            branches,
+            vec!["feat/x", "feature-branch", "main", "release"]
+        );
+    }
+
+    #[test]
+    fn test_parse_remotes() {
+        let output = "origin\tgit@example.com:me/app.git (fetch)\norigin\tgit@example.com:me/app.git (push)\nupstream\thttps://example.com/org/app.git (fetch)\nupstream\tno_push (push)\n";
+        let remotes = GitProjectRepository::parse_remotes(output);
+
+        assert_eq!(remotes.len(), 2);
+        assert_eq!(remotes[0].name, "origin");
+        assert_eq!(remotes[0].push_url, "git@example.com:me/app.git");
+        assert_eq!(remotes[1].name, "upstream");
+        assert_eq!(remotes[1].fetch_url, "https://example.com/org/app.git");
+        assert_eq!(remotes[1].push_url, "no_push");
+    }
+
+    #[test]
+    fn test_parse_branch_refs() {
+        let line = |fields: [&str; 8]| fields.join("\0");
+        let output = [
+            line([
+                "refs/remotes/origin/main",
+                "abc1234",
+                "2026-01-02",
+                "1767312000",
+                "",
+                "",
+                "",
+                "Merge pull request",
+            ]),
+            line([
+                "refs/heads/main",
+                "abc1234",
+                "2026-01-02",
+                "1767312000",
+                "origin/main",
+                "ahead 2, behind 3",
+                "/hub/main",
+                "Fix: a, b",
+            ]),
+            line([
+                "refs/heads/feat/old",
+                "def5678",
+                "2025-10-01",
+                "1759276800",
+                "origin/feat/old",
+                "gone",
+                "",
+                "WIP",
+            ]),
+            line([
+                "refs/remotes/origin/HEAD",
+                "abc1234",
+                "2026-01-02",
+                "1767312000",
+                "",
+                "",
+                "",
+                "",
+            ]),
+        ]
+        .join("\n");
+
+        let branches = GitProjectRepository::parse_branch_refs(&output);
+        let names: Vec<&str> = branches.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["feat/old", "main", "origin/main"]);
+
+        let main = &branches[1];
+        assert_eq!(main.upstream.as_deref(), Some("origin/main"));
+        assert_eq!((main.ahead, main.behind), (2, 3));
+        assert_eq!(main.worktree.as_deref(), Some("/hub/main"));
+        assert_eq!(main.subject, "Fix: a, b");
+        assert_eq!(main.timestamp, 1_767_312_000);
+
+        assert!(branches[0].upstream_gone);
+        assert_eq!(branches[0].worktree, None);
+
+        let remote = &branches[2];
+        assert_eq!(remote.remote.as_deref(), Some("origin"));
+        assert_eq!(remote.local_name(), "main");
+        assert!(!remote.upstream_gone);
     }
 
     #[test]
@@ -1630,6 +3272,7 @@ mod tests {
                 description: Some("Development branch".to_string()),
                 color: Some("#FF0000".to_string()),
                 icon: Some("🚀".to_string()),
+                ..WorktreeMetadata::default()
             },
         );
 
@@ -1946,9 +3589,12 @@ mod tests {
             .output()
             .unwrap();
 
-        // 4. Create artifacts
-        std::fs::create_dir_all(hub_dir.join("dev").join("target")).unwrap();
-        std::fs::create_dir_all(hub_dir.join("main").join("target")).unwrap();
+        // 4. Create artifacts next to a Cargo manifest, plus a checked-in `bin/`
+        for wt in ["dev", "main"] {
+            std::fs::write(hub_dir.join(wt).join("Cargo.toml"), "").unwrap();
+            std::fs::create_dir_all(hub_dir.join(wt).join("target")).unwrap();
+        }
+        std::fs::create_dir_all(hub_dir.join("dev").join("bin")).unwrap();
 
         std::env::set_current_dir(hub_dir.join("main")).unwrap();
 
@@ -1961,6 +3607,7 @@ mod tests {
         repo.clean_worktrees(false, true).unwrap();
         assert!(!hub_dir.join("dev").join("target").exists());
         assert!(hub_dir.join("main").join("target").exists()); // Should stay
+        assert!(hub_dir.join("dev").join("bin").exists()); // No .NET/Go project
 
         // Cleanup
         std::env::set_current_dir(original_cwd).unwrap();
@@ -2205,6 +3852,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sync_configs_copies_directories_and_templates() {
+        let _lock = CWD_MUTEX.lock().unwrap();
+        let hub = tempfile::tempdir().unwrap();
+        let hub = hub.path();
+        std::fs::create_dir_all(hub.join("certs/dev")).unwrap();
+        std::fs::write(hub.join("certs/dev/local.pem"), "pem").unwrap();
+        std::fs::write(hub.join("app.env.tmpl"), "NAME={{name}}\nPORT={{port}}\n").unwrap();
+        std::fs::write(
+            hub.join(".worktrees.sync"),
+            "copy certs\ntemplate app.env.tmpl .env\n",
+        )
+        .unwrap();
+        std::fs::create_dir(hub.join("wt1")).unwrap();
+
+        let original_cwd = std::env::current_dir().unwrap();
+        std::env::set_current_dir(hub).unwrap();
+        let res = GitProjectRepository.sync_configs("wt1");
+        std::env::set_current_dir(original_cwd).unwrap();
+
+        res.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(hub.join("wt1/certs/dev/local.pem")).unwrap(),
+            "pem"
+        );
+        assert_eq!(
+            std::fs::read_to_string(hub.join("wt1/.env")).unwrap(),
+            format!(
+                "NAME=wt1\nPORT={}\n",
+                crate::domain::sync::default_port("wt1")
+            )
+        );
+    }
+
     #[test]
     fn test_parse_status_output_clean() {
         let output = "";
@@ -2369,57 +4050,49 @@ mod tests {
 
     #[test]
     #[cfg(unix)]
-    #[serial_test::serial]
     fn test_gradle_properties_secure_creation() {
         use std::os::unix::fs::PermissionsExt;
 
-        let _lock = CWD_MUTEX.lock().unwrap();
-        // Setup temp dir as project root
-        let temp_dir = std::env::temp_dir().join(format!(
-            "worktrees_gradle_perms_test_{}",
-            std::process::id()
-        ));
-        if temp_dir.exists() {
-            std::fs::remove_dir_all(&temp_dir).unwrap();
-        }
-        std::fs::create_dir_all(&temp_dir).unwrap();
-
-        // Create build.gradle.kts to trigger KMP/Android detection
-        std::fs::write(temp_dir.join("build.gradle.kts"), "").unwrap();
-
-        // Create destination worktree directory
-        let dest_dir = temp_dir.join("wt_dest");
-        std::fs::create_dir(&dest_dir).unwrap();
-
-        // Set CWD to temp_dir (project root)
-        let original_cwd = std::env::current_dir().unwrap();
-        std::env::set_current_dir(&temp_dir).unwrap();
-
-        let repo = GitProjectRepository;
-        // This should create gradle.properties in dest_dir and append caching config
-        repo.handle_context_files(dest_dir.to_str().unwrap());
-
-        std::env::set_current_dir(original_cwd).unwrap();
-
-        let gradle_props = dest_dir.join("gradle.properties");
-        assert!(gradle_props.exists(), "gradle.properties should be created");
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        let path = root.to_str().unwrap();
+        GitProjectRepository::run_git(&["init", "-q", path]).unwrap();
+        let write = || {
+            GitProjectRepository::append_local_setting(
+                path,
+                "gradle.properties",
+                "org.gradle.caching",
+                "org.gradle.caching=true",
+            )
+            .unwrap()
+        };
 
-        // Verify content
+        assert!(write());
+        assert!(!write(), "an existing setting is kept");
+        let gradle_props = root.join("gradle.properties");
         let content = std::fs::read_to_string(&gradle_props).unwrap();
-        assert!(content.contains("org.gradle.caching=true"));
-
-        // Verify permissions
-        let metadata = std::fs::metadata(&gradle_props).unwrap();
-        let mode = metadata.permissions().mode() & 0o777;
-
-        // Cleanup
-        std::fs::remove_dir_all(&temp_dir).unwrap();
-
-        // This assertion should FAIL before the fix (likely 644 or 664)
+        assert_eq!(content.matches("org.gradle.caching=true").count(), 1);
+        let mode = std::fs::metadata(&gradle_props)
+            .unwrap()
+            .permissions()
+            .mode()
+            & 0o777;
         assert_eq!(
             mode, 0o600,
             "gradle.properties permissions should be 600, but were {mode:o}"
         );
+        // The generated file is excluded, so the worktree stays clean.
+        let status = GitProjectRepository::run_git(&["-C", path, "status", "--porcelain"]).unwrap();
+        assert!(status.trim().is_empty(), "unexpected status: {status}");
+
+        // A tracked file is never touched.
+        std::fs::write(&gradle_props, "kotlin.code.style=official\n").unwrap();
+        GitProjectRepository::run_git(&["-C", path, "add", "-f", "gradle.properties"]).unwrap();
+        assert!(!write());
+        assert_eq!(
+            std::fs::read_to_string(&gradle_props).unwrap(),
+            "kotlin.code.style=official\n"
+        );
     }
 
     #[test]
@@ -0,0 +1,79 @@
+use crate::domain::issues::{IssueConfig, IssueLink, IssueProvider, UrlProvider};
+use anyhow::{Context, Result, anyhow};
+use std::process::Command;
+
+/// Asks the tracker for the title by running `issues.command`, e.g. the `gh` or `jira` CLI.
+pub struct CommandProvider {
+    pub command: String,
+    pub urls: UrlProvider,
+}
+
+impl IssueProvider for CommandProvider {
+    fn link(&self, id: &str) -> IssueLink {
+        self.urls.link(id)
+    }
+
+    fn lookup(&self, id: &str) -> Result<IssueLink> {
+        let command = self.command.replace("{id}", id);
+        let output = Command::new("sh")
+            .args(["-c", &command])
+            .output()
+            .with_context(|| format!("Failed to run issues.command '{command}'"))?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "issues.command '{command}' failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        let title = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(str::to_string);
+        Ok(IssueLink {
+            title,
+            ..self.link(id)
+        })
+    }
+}
+
+/// The provider `.worktrees.yaml` asks for: a command when one is set, otherwise URLs only.
+pub fn provider_for(config: &IssueConfig) -> Box<dyn IssueProvider> {
+    let urls = UrlProvider {
+        template: config.url.clone(),
+    };
+    match &config.command {
+        Some(command) => Box::new(CommandProvider {
+            command: command.clone(),
+            urls,
+        }),
+        None => Box::new(urls),
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_provider_reads_title() {
+        let provider = provider_for(&IssueConfig {
+            url: Some("https://tracker.local/{id}".into()),
+            command: Some("printf '\\nFix login for {id}\\nignored\\n'".into()),
+            ..IssueConfig::default()
+        });
+        let issue = provider.lookup("PROJ-7").unwrap();
+        assert_eq!(issue.title.as_deref(), Some("Fix login for PROJ-7"));
+        assert_eq!(issue.url.as_deref(), Some("https://tracker.local/PROJ-7"));
+
+        let failing = provider_for(&IssueConfig {
+            command: Some("echo nope >&2; exit 1".into()),
+            ..IssueConfig::default()
+        });
+        assert!(
+            failing
+                .lookup("PROJ-7")
+                .is_err_and(|e| e.to_string().contains("nope"))
+        );
+    }
+}
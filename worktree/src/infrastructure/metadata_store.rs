@@ -0,0 +1,286 @@
+use crate::domain::list_layout::ListLayout;
+use crate::domain::repository::WorktreeMetadata;
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Format written by this version; files without `version` are the old branch-keyed map.
+const VERSION: u32 = 2;
+/// Give up waiting for another command's lock after this long.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+/// A lock this old was left behind by a crashed command.
+const STALE_LOCK: Duration = Duration::from_secs(30);
+
+pub type MetadataMap = BTreeMap<String, WorktreeMetadata>;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct MetadataFile {
+    version: u32,
+    /// Keyed by the worktree's admin id, the directory name under `.bare/worktrees/`.
+    worktrees: MetadataMap,
+    /// Sort order, grouping and collapsed groups last chosen in the TUI.
+    #[serde(default, skip_serializing_if = "ListLayout::is_default")]
+    list: ListLayout,
+}
+
+/// `.worktree.json` in the hub root: metadata for every worktree of the hub and the list layout.
+pub struct MetadataStore {
+    path: PathBuf,
+    /// Branch checked out in each worktree mapped to its admin id, to migrate old files.
+    ids_by_branch: HashMap<String, String>,
+}
+
+impl MetadataStore {
+    pub fn new(hub: &Path, ids_by_branch: HashMap<String, String>) -> Self {
+        Self {
+            path: hub.join(".worktree.json"),
+            ids_by_branch,
+        }
+    }
+
+    /// All entries; an old branch-keyed file is migrated on first read.
+    pub fn load(&self) -> MetadataMap {
+        match self.read() {
+            Ok((file, false)) => file.worktrees,
+            Ok((_, true)) => self.update(|entries| entries.clone()).unwrap_or_default(),
+            Err(e) => {
+                debug!(error = %e, path = ?self.path, "Failed to read worktree metadata.");
+                MetadataMap::new()
+            }
+        }
+    }
+
+    /// Runs `f` on the entries while holding the hub's lock, then writes them atomically.
+    pub fn update<T>(&self, f: impl FnOnce(&mut MetadataMap) -> T) -> Result<T> {
+        self.modify(|file| f(&mut file.worktrees))
+    }
+
+    /// The saved list layout; the default when none was saved.
+    pub fn layout(&self) -> ListLayout {
+        self.read().map(|(file, _)| file.list).unwrap_or_default()
+    }
+
+    pub fn save_layout(&self, layout: &ListLayout) -> Result<()> {
+        self.modify(|file| file.list = layout.clone())
+    }
+
+    fn modify<T>(&self, f: impl FnOnce(&mut MetadataFile) -> T) -> Result<T> {
+        let _lock = Lock::acquire(&self.path.with_extension("json.lock"))?;
+        let (mut file, legacy) = self.read()?;
+        if legacy {
+            let backup = self.path.with_extension("json.bak");
+            std::fs::copy(&self.path, &backup)
+                .with_context(|| format!("Failed to back up {}", self.path.display()))?;
+            info!(backup = ?backup, "Migrated branch-keyed worktree metadata.");
+        }
+        let result = f(&mut file);
+        file.version = VERSION;
+        file.worktrees
+            .retain(|_, meta| *meta != WorktreeMetadata::default());
+        write_atomic(&self.path, &serde_json::to_string_pretty(&file)?)?;
+        Ok(result)
+    }
+
+    /// Reads the hub's file. The flag is set when it is still the old branch-keyed map,
+    /// which [`Self::modify`] backs up as `.worktree.json.bak` before rewriting it.
+    fn read(&self) -> Result<(MetadataFile, bool)> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok((MetadataFile::default(), false));
+            }
+            Err(e) => return Err(e).context(format!("Failed to read {}", self.path.display())),
+        };
+
+        let value: serde_json::Value = serde_json::from_str(&content)
+            .with_context(|| format!("{} is not valid JSON", self.path.display()))?;
+        if value.get("version").is_some() {
+            return Ok((serde_json::from_value(value)?, false));
+        }
+
+        let legacy: HashMap<String, WorktreeMetadata> = serde_json::from_value(value)?;
+        let mut entries = MetadataMap::new();
+        for (branch, meta) in legacy {
+            match self.ids_by_branch.get(&branch) {
+                Some(id) => {
+                    entries.insert(id.clone(), meta);
+                }
+                None => {
+                    warn!(%branch, "Not migrating metadata of a branch without a worktree; see .worktree.json.bak.")
+                }
+            }
+        }
+        Ok((
+            MetadataFile {
+                version: VERSION,
+                worktrees: entries,
+                list: ListLayout::default(),
+            },
+            true,
+        ))
+    }
+}
+
+/// An exclusive `<file>.lock`, removed on drop, in the style of git's own lock files.
+struct Lock(PathBuf);
+
+impl Lock {
+    fn acquire(path: &Path) -> Result<Self> {
+        let start = Instant::now();
+        loop {
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(path)
+            {
+                Ok(_) => return Ok(Self(path.to_path_buf())),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let stale = std::fs::metadata(path)
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .and_then(|t| t.elapsed().ok())
+                        .is_some_and(|age| age > STALE_LOCK);
+                    if stale {
+                        debug!(?path, "Removing stale metadata lock.");
+                        let _ = std::fs::remove_file(path);
+                    } else if start.elapsed() > LOCK_TIMEOUT {
+                        return Err(anyhow!(
+                            "Worktree metadata is locked by another command. HELP: Delete {} if no other worktree command is running.",
+                            path.display()
+                        ));
+                    } else {
+                        std::thread::sleep(Duration::from_millis(25));
+                    }
+                }
+                Err(e) => {
+                    return Err(e).context(format!("Failed to create {}", path.display()));
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Writes to a temporary file next to `path` and renames it over, so readers never see half a file.
+fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+    let write = || -> std::io::Result<()> {
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, path)
+    };
+    write().map_err(|e| {
+        let _ = std::fs::remove_file(&tmp);
+        anyhow!(e).context(format!("Failed to write {}", path.display()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(purpose: &str) -> WorktreeMetadata {
+        WorktreeMetadata {
+            purpose: Some(purpose.into()),
+            ..WorktreeMetadata::default()
+        }
+    }
+
+    #[test]
+    fn test_metadata_store_migrates_branch_keyed_file() {
+        let hub = tempfile::tempdir().unwrap();
+        std::fs::write(
+            hub.path().join(".worktree.json"),
+            r#"{"feature/login": {"created_at": null, "purpose": "Login", "description": null, "color": null, "icon": null},
+                "gone": {"created_at": null, "purpose": "Old", "description": null, "color": null, "icon": null}}"#,
+        )
+        .unwrap();
+        let store = MetadataStore::new(
+            hub.path(),
+            HashMap::from([("feature/login".to_string(), "login".to_string())]),
+        );
+
+        let entries = store.load();
+        assert_eq!(
+            entries,
+            MetadataMap::from([("login".into(), meta("Login"))])
+        );
+
+        let written: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(hub.path().join(".worktree.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(written["version"], VERSION);
+        assert_eq!(written["worktrees"]["login"]["purpose"], "Login");
+
+        // The original, including the branch without a worktree, is kept once.
+        let backup = std::fs::read_to_string(hub.path().join(".worktree.json.bak")).unwrap();
+        assert!(backup.contains("\"gone\""));
+        store.update(|entries| entries.clear()).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(hub.path().join(".worktree.json.bak")).unwrap(),
+            backup
+        );
+    }
+
+    #[test]
+    fn test_metadata_store_update_is_locked_and_drops_empty_entries() {
+        let hub = tempfile::tempdir().unwrap();
+        let store = MetadataStore::new(hub.path(), HashMap::new());
+
+        store
+            .update(|entries| {
+                entries.insert("a".into(), meta("A"));
+                entries.insert("b".into(), WorktreeMetadata::default());
+            })
+            .unwrap();
+        assert_eq!(store.load().keys().collect::<Vec<_>>(), ["a"]);
+        assert!(!hub.path().join(".worktree.json.lock").exists());
+
+        // A live lock held elsewhere blocks writers until it is released.
+        let lock = Lock::acquire(&hub.path().join(".worktree.json.lock")).unwrap();
+        let writer = std::thread::spawn({
+            let path = hub.path().to_path_buf();
+            move || {
+                MetadataStore::new(&path, HashMap::new())
+                    .update(|entries| entries.remove("a"))
+                    .unwrap()
+            }
+        });
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(store.load().contains_key("a"));
+        drop(lock);
+        assert_eq!(writer.join().unwrap(), Some(meta("A")));
+        assert!(store.load().is_empty());
+    }
+
+    #[test]
+    fn test_metadata_store_keeps_the_layout_across_updates() {
+        use crate::domain::list_layout::{GroupMode, SortMode};
+
+        let hub = tempfile::tempdir().unwrap();
+        let store = MetadataStore::new(hub.path(), HashMap::new());
+        let layout = ListLayout {
+            sort: SortMode::Size,
+            group: GroupMode::Prefix,
+            collapsed: vec!["fix".into()],
+        };
+
+        store.save_layout(&layout).unwrap();
+        store
+            .update(|entries| entries.insert("a".into(), meta("A")))
+            .unwrap();
+        assert_eq!(store.layout(), layout);
+        assert_eq!(store.load().keys().collect::<Vec<_>>(), ["a"]);
+    }
+}
@@ -1,3 +1,10 @@
+pub mod clipboard;
+pub mod completion;
+pub mod editor;
 pub mod gemini_client;
 pub mod git_repo;
+pub mod issue_providers;
+pub mod metadata_store;
+pub mod process;
+pub mod shell_integration;
 pub mod warp_integration;
@@ -0,0 +1,94 @@
+use std::path::Path;
+use std::process::ExitStatus;
+use tokio::process::Command;
+
+/// Exit code the way shells report it: the child's code, or `128 + signal` when it was killed.
+pub fn exit_code(status: ExitStatus) -> i32 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return 128 + signal;
+        }
+    }
+    status.code().unwrap_or(1)
+}
+
+/// Runs a shell command in `dir` without the terminal, failing with the end of its stderr.
+pub fn run_captured(command: &str, dir: &Path, env: &[(String, String)]) -> anyhow::Result<()> {
+    let output = std::process::Command::new("sh")
+        .args(["-c", command])
+        .envs(env.iter().cloned())
+        .current_dir(dir)
+        .stdin(std::process::Stdio::null())
+        .output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let mut tail: Vec<&str> = stderr.lines().rev().take(3).collect();
+    tail.reverse();
+    Err(anyhow::anyhow!(
+        "exited with {}: {}",
+        exit_code(output.status),
+        tail.join(" | ")
+    ))
+}
+
+/// Runs `command` to completion and returns its exit code.
+///
+/// `SIGINT` and `SIGQUIT` are ignored here: a terminal already delivers them to the child,
+/// which shares our process group. `SIGTERM` and `SIGHUP` sent to us are passed on so a
+/// supervisor stopping `worktree exec` also stops the command.
+pub async fn run_forwarding_signals(mut command: Command) -> std::io::Result<i32> {
+    let mut child = command.spawn()?;
+
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        let mut interrupt = signal(SignalKind::interrupt())?;
+        let mut quit = signal(SignalKind::quit())?;
+        let mut terminate = signal(SignalKind::terminate())?;
+        let mut hangup = signal(SignalKind::hangup())?;
+
+        let forward = |child: &tokio::process::Child, sig: libc::c_int| {
+            if let Some(pid) = child.id().and_then(|id| libc::pid_t::try_from(id).ok()) {
+                // SAFETY: `kill` has no memory-safety preconditions; `pid` is our live child.
+                unsafe {
+                    libc::kill(pid, sig);
+                }
+            }
+        };
+
+        loop {
+            tokio::select! {
+                status = child.wait() => return status.map(exit_code),
+                _ = interrupt.recv() => {}
+                _ = quit.recv() => {}
+                _ = terminate.recv() => forward(&child, libc::SIGTERM),
+                _ = hangup.recv() => forward(&child, libc::SIGHUP),
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        child.wait().await.map(exit_code)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_forwarding_signals_reports_exit_codes() {
+        let mut command = Command::new("sh");
+        command.args(["-c", "exit 7"]);
+        assert_eq!(run_forwarding_signals(command).await.unwrap(), 7);
+
+        let mut command = Command::new("sh");
+        command.args(["-c", "kill -TERM $$"]);
+        assert_eq!(run_forwarding_signals(command).await.unwrap(), 128 + 15);
+    }
+}
@@ -0,0 +1,314 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+
+use super::completion::FISH_DYNAMIC;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// Environment variable the shell wrapper sets to a temp file. When present, commands that
+/// "switch" write the target directory there instead of printing it, and the wrapper `cd`s.
+pub const CD_FILE_ENV: &str = "WORKTREE_CD_FILE";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ShellKind {
+    Bash,
+    Zsh,
+    Fish,
+    Nushell,
+}
+
+fn subcommand_names(cli: &clap::Command) -> Vec<String> {
+    cli.get_subcommands()
+        .filter(|c| !c.is_hide_set())
+        .map(|c| c.get_name().to_string())
+        .collect()
+}
+
+/// Returns the shell snippet to `eval` from the user's rc file; `cli` supplies the subcommands.
+pub fn init_script(shell: ShellKind, cli: &clap::Command) -> String {
+    let subcommands = subcommand_names(cli).join(" ");
+
+    match shell {
+        ShellKind::Bash | ShellKind::Zsh => {
+            let name = if shell == ShellKind::Bash {
+                "bash"
+            } else {
+                "zsh"
+            };
+            let completion = if shell == ShellKind::Bash {
+                format!(
+                    r#"_worktree_complete() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    if [ "$COMP_CWORD" -eq 1 ]; then
+        COMPREPLY=($(compgen -W "{subcommands}" -- "$cur"))
+        return
+    fi
+    local IFS=$'\n'
+    COMPREPLY=($(command worktree __complete --cword "$COMP_CWORD" -- "${{COMP_WORDS[@]}}" 2>/dev/null))
+}}
+complete -F _worktree_complete worktree wt
+"#
+                )
+            } else {
+                format!(
+                    r#"_worktree_complete() {{
+    if (( CURRENT == 2 )); then
+        compadd -- {subcommands}
+        return
+    fi
+    compadd -- ${{(f)"$(command worktree __complete --cword $((CURRENT - 1)) -- "${{words[@]}}" 2>/dev/null)"}}
+}}
+(( $+functions[compdef] )) && compdef _worktree_complete worktree wt
+"#
+                )
+            };
+
+            format!(
+                r#"# worktree shell integration ({name})
+worktree() {{
+    local __wt_cd_file __wt_status __wt_target
+    __wt_cd_file="$(mktemp "${{TMPDIR:-/tmp}}/worktree-cd.XXXXXX")" || {{ command worktree "$@"; return $?; }}
+    {CD_FILE_ENV}="$__wt_cd_file" command worktree "$@"
+    __wt_status=$?
+    if [ -s "$__wt_cd_file" ]; then
+        __wt_target="$(cat "$__wt_cd_file")"
+        [ -d "$__wt_target" ] && cd -- "$__wt_target"
+    fi
+    rm -f -- "$__wt_cd_file"
+    return $__wt_status
+}}
+alias wt=worktree
+{completion}"#
+            )
+        }
+        ShellKind::Fish => format!(
+            r#"# worktree shell integration (fish)
+function worktree --description 'worktree with directory switching'
+    set -l cd_file (mktemp "$TMPDIR/worktree-cd.XXXXXX" 2>/dev/null; or mktemp /tmp/worktree-cd.XXXXXX)
+    {CD_FILE_ENV}=$cd_file command worktree $argv
+    set -l code $status
+    if test -s $cd_file
+        set -l target (cat $cd_file)
+        test -d "$target"; and cd $target
+    end
+    rm -f $cd_file
+    return $code
+end
+alias wt worktree
+complete -c worktree -f -n '__fish_use_subcommand' -a '{subcommands}'
+{FISH_DYNAMIC}"#
+        ),
+        ShellKind::Nushell => format!(
+            r#"# worktree shell integration (nushell)
+# Nushell has no per-position completion for wrapped externals; use `worktree completions`.
+def --env --wrapped worktree [...args] {{
+    let cd_file = (mktemp -t worktree-cd.XXXXXX)
+    do --ignore-errors {{ with-env {{ {CD_FILE_ENV}: $cd_file }} {{ ^worktree ...$args }} }}
+    let code = $env.LAST_EXIT_CODE
+    let target = (open --raw $cd_file | str trim)
+    rm -f $cd_file
+    if ($target | is-not-empty) and ($target | path exists) {{ cd $target }}
+    $env.LAST_EXIT_CODE = $code
+    if $code != 0 {{ error make --unspanned {{ msg: $"worktree exited with status ($code)" }} }}
+}}
+
+alias wt = worktree
+"#
+        ),
+    }
+}
+
+/// Hands a directory to the shell wrapper when it is active.
+///
+/// Returns `Ok(false)` when no wrapper is installed, so callers can fall back to printing.
+pub fn write_cd_target(path: &str) -> Result<bool> {
+    let Some(cd_file) = std::env::var_os(CD_FILE_ENV).filter(|v| !v.is_empty()) else {
+        return Ok(false);
+    };
+    std::fs::write(&cd_file, path)
+        .with_context(|| format!("Failed to write switch target to {cd_file:?}"))?;
+    Ok(true)
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PromptInfo {
+    pub name: String,
+    pub branch: Option<String>,
+    pub dirty: bool,
+    pub ahead: u32,
+    pub behind: u32,
+}
+
+impl PromptInfo {
+    /// Compact segment, e.g. `feat-login* ⇡2⇣1`.
+    pub fn render(&self) -> String {
+        let mut out = self.name.clone();
+        if self.dirty {
+            out.push('*');
+        }
+        if self.ahead > 0 || self.behind > 0 {
+            out.push(' ');
+            if self.ahead > 0 {
+                out.push_str(&format!("⇡{}", self.ahead));
+            }
+            if self.behind > 0 {
+                out.push_str(&format!("⇣{}", self.behind));
+            }
+        }
+        out
+    }
+}
+
+/// Parses `git status --porcelain=v2 --branch` output.
+pub fn parse_porcelain_v2(output: &str) -> (Option<String>, bool, u32, u32) {
+    let mut branch = None;
+    let mut dirty = false;
+    let (mut ahead, mut behind) = (0, 0);
+
+    for line in output.lines() {
+        if let Some(head) = line.strip_prefix("# branch.head ") {
+            if head != "(detached)" {
+                branch = Some(head.to_string());
+            }
+        } else if let Some(ab) = line.strip_prefix("# branch.ab ") {
+            for part in ab.split_whitespace() {
+                if let Some(n) = part.strip_prefix('+') {
+                    ahead = n.parse().unwrap_or(0);
+                } else if let Some(n) = part.strip_prefix('-') {
+                    behind = n.parse().unwrap_or(0);
+                }
+            }
+        } else if !line.starts_with('#') && !line.is_empty() {
+            dirty = true;
+        }
+    }
+
+    (branch, dirty, ahead, behind)
+}
+
+/// Gathers prompt data for the worktree containing `dir` with a single `git` call.
+///
+/// If git does not answer within `budget` the process is killed and only the worktree
+/// name is reported, so a slow filesystem never stalls the shell prompt.
+pub fn prompt_info(dir: &Path, budget: Duration) -> Option<PromptInfo> {
+    let toplevel = find_worktree_root(dir)?;
+    let name = toplevel.file_name()?.to_string_lossy().to_string();
+
+    let git = std::env::var("WORKTREES_GIT_PATH").unwrap_or_else(|_| "git".to_string());
+    let mut child = Command::new(git)
+        .args([
+            "status",
+            "--porcelain=v2",
+            "--branch",
+            "--untracked-files=no",
+            "--no-renames",
+        ])
+        .env("GIT_OPTIONAL_LOCKS", "0")
+        .current_dir(&toplevel)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    // Read on a helper thread so a large status output cannot fill the pipe and stall us.
+    let mut out = child.stdout.take()?;
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let mut buf = String::new();
+        let _ = out.read_to_string(&mut buf);
+        let _ = tx.send(buf);
+    });
+
+    let Ok(stdout) = rx.recv_timeout(budget) else {
+        let _ = child.kill();
+        let _ = child.wait();
+        return Some(PromptInfo {
+            name,
+            ..Default::default()
+        });
+    };
+    if !child.wait().is_ok_and(|s| s.success()) {
+        return Some(PromptInfo {
+            name,
+            ..Default::default()
+        });
+    }
+    let (branch, dirty, ahead, behind) = parse_porcelain_v2(&stdout);
+
+    Some(PromptInfo {
+        name,
+        branch,
+        dirty,
+        ahead,
+        behind,
+    })
+}
+
+/// Walks up from `dir` to the first directory holding a `.git` entry, without spawning git.
+fn find_worktree_root(dir: &Path) -> Option<std::path::PathBuf> {
+    dir.ancestors()
+        .find(|p| p.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_porcelain_v2() {
+        let output = "# branch.oid abc123\n# branch.head feat-x\n# branch.upstream origin/feat-x\n# branch.ab +2 -1\n1 .M N... 100644 100644 100644 a b src/lib.rs\n";
+        let (branch, dirty, ahead, behind) = parse_porcelain_v2(output);
+        assert_eq!(branch.as_deref(), Some("feat-x"));
+        assert!(dirty);
+        assert_eq!((ahead, behind), (2, 1));
+
+        let (branch, dirty, ..) = parse_porcelain_v2("# branch.head (detached)\n");
+        assert_eq!(branch, None);
+        assert!(!dirty);
+    }
+
+    #[test]
+    fn test_prompt_render() {
+        let info = PromptInfo {
+            name: "feat-x".into(),
+            branch: Some("feat-x".into()),
+            dirty: true,
+            ahead: 2,
+            behind: 1,
+        };
+        assert_eq!(info.render(), "feat-x* ⇡2⇣1");
+
+        let clean = PromptInfo {
+            name: "main".into(),
+            ..Default::default()
+        };
+        assert_eq!(clean.render(), "main");
+    }
+
+    #[test]
+    fn test_init_script_defines_wrapper() {
+        use clap::CommandFactory;
+        let cli = crate::cli::Cli::command();
+        for shell in [
+            ShellKind::Bash,
+            ShellKind::Zsh,
+            ShellKind::Fish,
+            ShellKind::Nushell,
+        ] {
+            let script = init_script(shell, &cli);
+            assert!(
+                script.contains(CD_FILE_ENV),
+                "{shell:?} must pass the cd file"
+            );
+            assert!(script.contains("wt"), "{shell:?} must define the wt alias");
+        }
+        assert!(
+            init_script(ShellKind::Bash, &cli)
+                .contains("complete -F _worktree_complete worktree wt")
+        );
+        assert!(init_script(ShellKind::Nushell, &cli).contains("$env.LAST_EXIT_CODE = $code"));
+    }
+}
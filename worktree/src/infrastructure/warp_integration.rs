@@ -1,53 +1,221 @@
 use anyhow::{Context, Result};
+use clap::ArgAction;
+use serde::Serialize;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-pub fn generate_warp_workflows(project_path: &Path) -> Result<()> {
+const AUTHOR: &str = "Worktree Hub";
+
+/// Lists the directory names of the hub's worktrees, skipping the bare entry.
+const WORKTREE_NAMES_COMMAND: &str = r#"git worktree list --porcelain | awk '/^worktree /{p=substr($0,10)} /^bare$/{p=""} /^$/{if(p!=""){sub(".*/","",p);print p}p=""}'"#;
+const BRANCH_NAMES_COMMAND: &str = "worktree __complete --kind branches";
+const UPSTREAM_REFS_COMMAND: &str = "worktree __complete --kind upstreams";
+const REMOTE_NAMES_COMMAND: &str = "worktree __complete --kind remotes";
+const STASH_INDICES_COMMAND: &str = "worktree __complete --kind stashes";
+
+#[derive(Debug, Serialize)]
+pub struct WarpWorkflow {
+    pub name: String,
+    pub command: String,
+    pub description: String,
+    pub author: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<WarpArgument>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WarpArgument {
+    pub name: String,
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arg_type: Option<String>,
+    #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
+    pub enum_values: Option<WarpEnum>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WarpEnum {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dynamic_enum: Option<DynamicEnum>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DynamicEnum {
+    pub command: String,
+}
+
+/// Maps a (subcommand path, argument id) pair to a shell command producing live suggestions.
+fn dynamic_suggestions(command_path: &str, arg: &str) -> Option<&'static str> {
+    match (command_path, arg) {
+        ("remove" | "sync" | "push" | "pull" | "checkout", "intent")
+        | ("switch" | "edit" | "log", "name")
+        | ("teleport", "target")
+        | ("compare", "left" | "right")
+        | ("port", "to" | "from") => Some(WORKTREE_NAMES_COMMAND),
+        ("add" | "checkout", "branch")
+        | ("branch rename", "old")
+        | ("branch delete", "name")
+        | ("branch set-upstream", "branch") => Some(BRANCH_NAMES_COMMAND),
+        ("rebase" | "branch set-upstream", "upstream")
+        | ("branch open", "branch")
+        | ("branch create", "base") => Some(UPSTREAM_REFS_COMMAND),
+        ("stash apply" | "stash pop" | "stash drop", "index") => Some(STASH_INDICES_COMMAND),
+        ("add" | "push" | "pull", "remote") | ("remote remove" | "remote fetch", "name") => {
+            Some(REMOTE_NAMES_COMMAND)
+        }
+        _ => None,
+    }
+}
+
+fn title_case(words: &str) -> String {
+    words
+        .split(['-', ' ', '_'])
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect::<String>()
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn first_line(text: Option<&clap::builder::StyledStr>) -> String {
+    text.map(ToString::to_string)
+        .and_then(|s| s.lines().next().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn build_workflow(path: &[&str], cmd: &clap::Command) -> WarpWorkflow {
+    let command_path = path.join(" ");
+    let mut command = format!("worktree {command_path}");
+    let mut arguments = Vec::new();
+
+    for arg in cmd.get_arguments() {
+        let id = arg.get_id().as_str();
+        if arg.is_hide_set()
+            || arg.is_global_set()
+            || matches!(
+                arg.get_action(),
+                ArgAction::SetTrue
+                    | ArgAction::SetFalse
+                    | ArgAction::Count
+                    | ArgAction::Help
+                    | ArgAction::HelpShort
+                    | ArgAction::HelpLong
+                    | ArgAction::Version
+            )
+        {
+            continue;
+        }
+
+        // Optional flags are left out of the command line to keep workflows short;
+        // positionals (optional or not) and required options become placeholders.
+        if arg.is_positional() {
+            command.push_str(&format!(" {{{id}}}"));
+        } else if arg.is_required_set() {
+            let flag = arg
+                .get_long()
+                .map(|l| format!("--{l}"))
+                .or_else(|| arg.get_short().map(|s| format!("-{s}")))
+                .unwrap_or_default();
+            command.push_str(&format!(" {flag} {{{id}}}"));
+        } else {
+            continue;
+        }
+
+        let variants: Vec<String> = arg
+            .get_possible_values()
+            .iter()
+            .filter(|v| !v.is_hide_set())
+            .map(|v| v.get_name().to_string())
+            .collect();
+        let dynamic = dynamic_suggestions(&command_path, id).map(|c| DynamicEnum {
+            command: c.to_string(),
+        });
+
+        let enum_values = if variants.is_empty() && dynamic.is_none() {
+            None
+        } else {
+            Some(WarpEnum {
+                variants,
+                dynamic_enum: dynamic,
+            })
+        };
+
+        arguments.push(WarpArgument {
+            name: id.to_string(),
+            description: first_line(arg.get_help()),
+            default_value: arg
+                .get_default_values()
+                .first()
+                .map(|v| v.to_string_lossy().to_string()),
+            arg_type: enum_values.as_ref().map(|_| "Enum".to_string()),
+            enum_values,
+        });
+    }
+
+    WarpWorkflow {
+        name: format!("Worktree {}", title_case(&command_path)),
+        command,
+        description: first_line(cmd.get_about()),
+        author: AUTHOR.to_string(),
+        arguments,
+    }
+}
+
+fn collect_workflows(path: &mut Vec<String>, cmd: &clap::Command, out: &mut Vec<WarpWorkflow>) {
+    for sub in cmd.get_subcommands() {
+        if sub.is_hide_set() || sub.get_name() == "help" {
+            continue;
+        }
+        path.push(sub.get_name().to_string());
+        if sub.has_subcommands() {
+            collect_workflows(path, sub, out);
+        } else {
+            let parts: Vec<&str> = path.iter().map(String::as_str).collect();
+            out.push(build_workflow(&parts, sub));
+        }
+        path.pop();
+    }
+}
+
+/// Builds one Warp workflow per leaf subcommand of the CLI definition `cli`.
+pub fn build_workflows(cli: &clap::Command) -> Vec<WarpWorkflow> {
+    let mut workflows = Vec::new();
+    collect_workflows(&mut Vec::new(), cli, &mut workflows);
+    workflows
+}
+
+pub fn render_workflows(workflows: &[WarpWorkflow]) -> Result<String> {
+    let mut content = String::new();
+    for workflow in workflows {
+        content.push_str("---\n");
+        content.push_str(
+            &serde_yaml::to_string(workflow).context("Failed to serialize Warp workflow")?,
+        );
+    }
+    Ok(content)
+}
+
+/// Writes `.warp/workflows/worktrees.yaml` under `project_path` and returns its location.
+pub fn generate_warp_workflows(project_path: &Path, cli: &clap::Command) -> Result<PathBuf> {
     let warp_dir = project_path.join(".warp").join("workflows");
     fs::create_dir_all(&warp_dir)
         .with_context(|| format!("Failed to create Warp directory: {warp_dir:?}"))?;
 
-    let workflow_content = r"---
-name: Worktree Setup
-command: worktree setup
-description: Setup canonical environment (main and dev worktrees)
-author: Worktree Hub
----
-name: Worktree Add
-command: worktree add {intent}
-description: Add a new worktree for a specific feature/intent
-author: Worktree Hub
-arguments:
-  - name: intent
-    description: The name/intent of the worktree (e.g., feature-xyz)
----
-name: Worktree List
-command: worktree list
-description: List all active worktrees and their status
-author: Worktree Hub
----
-name: Worktree Push
-command: worktree push {intent}
-description: Push changes to the remote repository
-author: Worktree Hub
-arguments:
-  - name: intent
-    description: The name of the worktree to push
----
-name: Worktree Switch
-command: worktree switch {name}
-description: Switch to a worktree by name (optimized for Warp)
-author: Worktree Hub
-arguments:
-  - name: name
-    description: The name of the worktree to switch to
-";
+    let workflow_content = render_workflows(&build_workflows(cli))?;
 
     let workflow_path = warp_dir.join("worktrees.yaml");
     fs::write(&workflow_path, workflow_content)
         .with_context(|| format!("Failed to write Warp workflow file: {workflow_path:?}"))?;
 
-    Ok(())
+    Ok(workflow_path)
 }
 
 pub fn is_warp_terminal() -> bool {
@@ -1,15 +1,21 @@
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 use miette::Result;
 use ratatui::widgets::TableState;
 use std::io::{self, Write};
 use tracing::{error, info};
+use worktree::app::event_handlers::stale::open_expired_review;
 use worktree::app::intent::Intent;
-use worktree::app::model::{AppState, RefreshType};
-use worktree::app::reducer::Reducer;
+use worktree::app::model::{AppState, RefreshType, listed_worktrees};
+use worktree::app::reducer::{CommandExit, Reducer};
 use worktree::app::view::View;
 use worktree::cli::{self, Cli, Commands};
-use worktree::domain::repository::{ProjectRepository, RepoStatus};
+use worktree::domain::list_layout::arrange_worktrees;
+use worktree::domain::repository::{
+    HistoryQuery, ProjectRepository, RepoStatus, annotate_worktrees,
+};
+use worktree::domain::stale::{StaleCriteria, is_expired};
 use worktree::infrastructure::git_repo::GitProjectRepository;
+use worktree::infrastructure::shell_integration;
 
 fn setup_logging(json_mode: bool) {
     if json_mode {
@@ -105,6 +111,14 @@ fn check_and_handle_repo_state(repo: &GitProjectRepository) -> Result<bool> {
     }
 }
 
+/// Hands the chosen worktree to the shell wrapper, or prints it when none is installed.
+fn emit_switch_target(path: &str) -> Result<()> {
+    if !shell_integration::write_cd_target(path).map_err(|e| miette::miette!("{e:?}"))? {
+        println!("{path}");
+    }
+    Ok(())
+}
+
 fn render_tui_mode(
     repo: &GitProjectRepository,
     selection_mode: bool,
@@ -116,15 +130,20 @@ fn render_tui_mode(
     if !quiet {
         View::render_banner();
     }
-    let worktrees = repo
+    let mut worktrees = repo
         .list_worktrees()
         .map_err(|e| miette::miette!("{e:?}"))?;
+    annotate_worktrees(repo, &mut worktrees);
+    let layout = repo.list_layout();
+    arrange_worktrees(repo, &mut worktrees, &layout);
+    let now = chrono::Utc::now().timestamp();
+    let any_expired = worktrees.iter().any(|wt| is_expired(wt, now));
     let mut table_state = TableState::default();
     if !worktrees.is_empty() {
         table_state.select(Some(0));
     }
     let initial_state = AppState::ListingWorktrees {
-        filtered_indices: (0..worktrees.len()).collect(),
+        filtered_indices: listed_worktrees(&worktrees, "", &layout),
         worktrees,
         table_state,
         refresh_needed: RefreshType::None,
@@ -139,23 +158,62 @@ fn render_tui_mode(
         is_filtering: false,
         mode: worktree::app::model::AppMode::Normal,
         last_selection_change: std::time::Instant::now(),
+        layout,
+    };
+    // Expired worktrees are offered for removal before anything else.
+    let initial_state = if any_expired && !selection_mode {
+        open_expired_review(repo, initial_state)
+    } else {
+        initial_state
     };
     View::render_tui(repo, initial_state).map_err(|e| miette::miette!("{e:?}"))
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    // Completion requests skip the main parser and logging setup to answer as fast as possible.
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).is_some_and(|a| a == cli::COMPLETE_COMMAND) {
+        let cli::CompleteArgs { kind, cword, words } = cli::CompleteArgs::parse_from(&args[1..]);
+        return Reducer::new(GitProjectRepository, false, true)
+            .handle(Intent::Complete { kind, cword, words })
+            .await;
+    }
+
     let cli = Cli::parse();
     setup_logging(cli.json);
 
     let repo = GitProjectRepository;
-    let reducer = Reducer::new(repo, cli.json, cli.quiet);
+    let reducer = Reducer::new(repo, cli.json, cli.quiet).with_cli(Cli::command());
 
     let intent = match cli.command {
         Some(Commands::Init { url, name, warp }) => Intent::Initialize { url, name, warp },
-        Some(Commands::Add { intent, branch }) => Intent::AddWorktree { intent, branch },
+        Some(Commands::Add {
+            intent,
+            branch,
+            remote,
+            purpose,
+            color,
+            icon,
+            issue,
+            template,
+            ttl,
+        }) => Intent::AddWorktree {
+            intent,
+            branch,
+            remote,
+            purpose,
+            color,
+            icon,
+            issue,
+            template,
+            ttl,
+        },
         Some(Commands::Remove { intent, force }) => Intent::RemoveWorktree { intent, force },
-        Some(Commands::List) => Intent::ListWorktrees,
+        Some(Commands::List { sort, group }) => Intent::ListWorktrees {
+            sort: sort.map(Into::into),
+            group: group.map(Into::into),
+        },
         Some(Commands::Setup) => Intent::SetupDefaults,
         Some(Commands::Run {
             intent,
@@ -166,8 +224,19 @@ async fn main() -> Result<()> {
             branch,
             command,
         },
-        Some(Commands::Sync { intent }) => Intent::SyncConfigurations { intent },
-        Some(Commands::Push { intent }) => Intent::Push { intent },
+        Some(Commands::Exec { name, all, command }) => Intent::Exec {
+            name: if all { None } else { name },
+            command,
+        },
+        Some(Commands::Sync { intent, check, fix }) => {
+            if check || fix {
+                Intent::CheckSync { intent, fix }
+            } else {
+                Intent::SyncConfigurations { intent }
+            }
+        }
+        Some(Commands::Push { intent, remote }) => Intent::Push { intent, remote },
+        Some(Commands::Pull { intent, remote }) => Intent::Pull { intent, remote },
         Some(Commands::Config { action }) => match action {
             cli::ConfigAction::SetKey { key } => Intent::Config {
                 key: Some(key),
@@ -178,8 +247,35 @@ async fn main() -> Result<()> {
                 show: true,
             },
         },
-        Some(Commands::Clean { dry_run, artifacts }) => {
-            Intent::CleanWorktrees { dry_run, artifacts }
+        Some(Commands::Clean {
+            dry_run,
+            artifacts,
+            merged,
+            gone,
+            expired,
+            older_than,
+            force,
+        }) => {
+            let criteria = StaleCriteria {
+                merged,
+                upstream_gone: gone,
+                inactive_days: older_than,
+                expired,
+            };
+            if artifacts {
+                Intent::CleanArtifacts {
+                    dry_run,
+                    inactive_days: older_than,
+                }
+            } else if criteria.is_empty() {
+                Intent::CleanWorktrees { dry_run }
+            } else {
+                Intent::CleanStale {
+                    criteria,
+                    dry_run,
+                    force,
+                }
+            }
         }
         Some(Commands::Switch { name, copy }) => {
             if let Some(n) = name {
@@ -187,39 +283,179 @@ async fn main() -> Result<()> {
             } else {
                 let result = render_tui_mode(&GitProjectRepository, true, cli.quiet)?;
                 if let Some(path) = result {
-                    println!("{path}");
+                    emit_switch_target(&path)?;
                 }
                 return Ok(());
             }
         }
+        Some(Commands::Edit {
+            name,
+            editor,
+            file,
+            line,
+            save,
+        }) => Intent::EditWorktree {
+            name,
+            editor,
+            file,
+            line,
+            save,
+        },
         Some(Commands::Convert { name, branch }) => Intent::Convert { name, branch },
         Some(Commands::Migrate { force, dry_run }) => Intent::Migrate { force, dry_run },
         Some(Commands::Checkout { intent, branch }) => Intent::CheckoutWorktree { intent, branch },
         Some(Commands::Completions { shell }) => Intent::Completions { shell },
         Some(Commands::Open) => Intent::Open,
         Some(Commands::Rebase { upstream }) => Intent::Rebase { upstream },
+        Some(Commands::Log {
+            name,
+            grep,
+            author,
+            path,
+            since,
+            until,
+            limit,
+            skip,
+        }) => Intent::ShowLog {
+            name,
+            query: HistoryQuery {
+                limit,
+                skip,
+                grep,
+                author,
+                path,
+                since,
+                until,
+                graph: false,
+            },
+        },
+        Some(Commands::Compare { left, right, patch }) => Intent::Compare { left, right, patch },
         Some(Commands::Teleport { target }) => Intent::Teleport { target },
+        Some(Commands::Port {
+            range,
+            to,
+            from,
+            remove,
+        }) => Intent::Port {
+            range,
+            to,
+            from,
+            remove,
+        },
+        Some(Commands::Branch { action }) => match action {
+            None => Intent::ListBranches { all: false },
+            Some(cli::BranchAction::List { all }) => Intent::ListBranches { all },
+            Some(cli::BranchAction::Create { name, base }) => Intent::CreateBranch { name, base },
+            Some(cli::BranchAction::Rename { old, new }) => Intent::RenameBranch { old, new },
+            Some(cli::BranchAction::Delete { name, force }) => Intent::DeleteBranch { name, force },
+            Some(cli::BranchAction::SetUpstream { branch, upstream }) => {
+                Intent::SetUpstream { branch, upstream }
+            }
+            Some(cli::BranchAction::Open { branch, name }) => Intent::OpenBranch { branch, name },
+        },
+        Some(Commands::Meta { name, action }) => match action {
+            None => Intent::ShowMetadata { name, key: None },
+            Some(cli::MetaAction::Get { key }) => Intent::ShowMetadata { name, key },
+            Some(cli::MetaAction::Set { key, value }) => Intent::SetMetadata {
+                name,
+                key,
+                value: Some(value),
+            },
+            Some(cli::MetaAction::Unset { key }) => Intent::SetMetadata {
+                name,
+                key,
+                value: None,
+            },
+        },
+        Some(Commands::Remote { action }) => match action {
+            None | Some(cli::RemoteAction::List) => Intent::ListRemotes,
+            Some(cli::RemoteAction::Add { name, url }) => Intent::AddRemote { name, url },
+            Some(cli::RemoteAction::Remove { name }) => Intent::RemoveRemote { name },
+            Some(cli::RemoteAction::Fetch { name }) => Intent::FetchRemote { remote: name },
+        },
+        Some(Commands::Cache { action }) => match action {
+            None | Some(cli::CacheAction::Status) => Intent::CacheStatus,
+        },
+        Some(Commands::Template { action }) => match action {
+            None | Some(cli::TemplateAction::List) => Intent::ListTemplates,
+        },
+        Some(Commands::ShellInit { shell }) => Intent::ShellInit { shell },
+        Some(Commands::Prompt { timeout_ms }) => Intent::Prompt { timeout_ms },
+        Some(Commands::Stash { action }) => {
+            let path = ".".to_string();
+            match action {
+                cli::StashAction::Apply { index } => Intent::ApplyStash { path, index },
+                cli::StashAction::Pop { index } => Intent::PopStash { path, index },
+                cli::StashAction::Drop { index } => Intent::DropStash { path, index },
+            }
+        }
+        Some(Commands::Warp { action }) => match action {
+            cli::WarpAction::Sync => Intent::SyncWarpWorkflows,
+        },
         None => {
             if cli.json {
-                let worktrees = GitProjectRepository
+                let mut worktrees = GitProjectRepository
                     .list_worktrees()
                     .map_err(|e| miette::miette!("{e:?}"))?;
+                annotate_worktrees(&GitProjectRepository, &mut worktrees);
                 return View::render_json(&worktrees).map_err(|e| miette::miette!("{e:?}"));
             }
             // TUI Mode
-            render_tui_mode(&GitProjectRepository, false, cli.quiet)?;
+            if let Some(path) = render_tui_mode(&GitProjectRepository, false, cli.quiet)? {
+                emit_switch_target(&path)?;
+            }
             return Ok(());
         }
     };
 
-    tokio::select! {
-        res = reducer.handle(intent) => {
-            res?;
+    // Commands whose stdout is consumed by shells or scripts must not get the feedback footer,
+    // nor should `edit`, which hands the terminal back after an editor exits, or `exec`/`run`,
+    // whose output belongs to the command they ran and is often piped.
+    let machine_output = matches!(
+        intent,
+        Intent::Completions { .. }
+            | Intent::Exec { .. }
+            | Intent::RunCommand { .. }
+            | Intent::ShellInit { .. }
+            | Intent::Prompt { .. }
+            | Intent::SwitchWorktree { .. }
+            | Intent::ShowLog { .. }
+            | Intent::Compare { .. }
+            | Intent::EditWorktree { .. }
+    );
+
+    // The TUI reviews expired worktrees itself; `clean` and `init` have nothing to warn about yet.
+    let warns_expired = !matches!(
+        intent,
+        Intent::CleanStale { .. } | Intent::Initialize { .. }
+    );
+    if !cli.json && !cli.quiet && !machine_output && warns_expired {
+        let now = chrono::Utc::now().timestamp();
+        if let Ok(expired) = GitProjectRepository.expired_worktrees(now)
+            && !expired.is_empty()
+        {
+            View::render_expired_warning(&expired);
+        }
+    }
+
+    // `exec` handles signals itself so the command it runs is never orphaned.
+    let forwards_signals = matches!(intent, Intent::Exec { .. });
+    let res = if forwards_signals {
+        reducer.handle(intent).await
+    } else {
+        tokio::select! {
+            res = reducer.handle(intent) => res,
+            () = wait_for_shutdown() => Ok(()),
+        }
+    };
+    if let Err(report) = res {
+        if let Some(exit) = report.downcast_ref::<CommandExit>() {
+            std::process::exit(exit.code);
         }
-        () = wait_for_shutdown() => {}
+        return Err(report);
     }
 
-    if !cli.json && !cli.quiet {
+    if !cli.json && !cli.quiet && !machine_output {
         View::render_feedback_prompt();
     }
 
@@ -1,4 +1,5 @@
 use crate::app::model::{AppMode, AppState};
+use crate::domain::repository::Worktree;
 use ratatui::style::Color;
 
 pub struct CyberTheme {
@@ -46,6 +47,14 @@ impl CyberTheme {
             _ => self.accent,
         }
     }
+
+    /// The `color` a worktree's metadata asks for, when the terminal can show it.
+    pub fn worktree_color(wt: &Worktree) -> Option<Color> {
+        wt.metadata
+            .as_ref()
+            .and_then(|m| m.color.as_deref())
+            .and_then(|c| c.parse().ok())
+    }
 }
 
 pub struct Icons;
@@ -56,4 +65,7 @@ impl Icons {
     pub const WORKTREE: &'static str = "󰘬 "; // nf-md-source_branch
     pub const CLEAN: &'static str = " "; // nf-fa-check_circle
     pub const DIRTY: &'static str = " "; // nf-fa-edit
+    pub const SYNC_DRIFT: &'static str = "󰓦 "; // nf-md-sync
+    pub const EXPIRY: &'static str = "󰔛 "; // nf-md-timer_sand
+    pub const PIN: &'static str = "󰐃 "; // nf-md-pin
 }
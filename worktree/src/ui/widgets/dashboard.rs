@@ -230,7 +230,7 @@ impl Widget for LogTabWidget<'_> {
 pub struct DashboardWidget<'a> {
     worktree: Option<&'a Worktree>,
     all_worktrees: &'a [Worktree],
-    context: ProjectContext,
+    context: &'a ProjectContext,
     active_tab: DashboardTab,
     status: Option<&'a GitStatus>,
     history: Option<&'a [GitCommit]>,
@@ -240,7 +240,7 @@ impl<'a> DashboardWidget<'a> {
     pub const fn new(
         worktree: Option<&'a Worktree>,
         all_worktrees: &'a [Worktree],
-        context: ProjectContext,
+        context: &'a ProjectContext,
         active_tab: DashboardTab,
         status: Option<&'a GitStatus>,
         history: Option<&'a [GitCommit]>,
@@ -1,3 +1,4 @@
+use crate::domain::context::ContextKind;
 use crate::domain::repository::{ProjectContext, Worktree};
 use crate::ui::theme::CyberTheme;
 use ratatui::{
@@ -11,14 +12,14 @@ use std::borrow::Cow;
 pub struct DetailsWidget<'a> {
     worktree: Option<&'a Worktree>,
     all_worktrees: &'a [Worktree],
-    context: ProjectContext,
+    context: &'a ProjectContext,
 }
 
 impl<'a> DetailsWidget<'a> {
     pub const fn new(
         worktree: Option<&'a Worktree>,
         all_worktrees: &'a [Worktree],
-        context: ProjectContext,
+        context: &'a ProjectContext,
     ) -> Self {
         Self {
             worktree,
@@ -121,13 +122,13 @@ impl Widget for DetailsWidget<'_> {
                                 .add_modifier(Modifier::ITALIC),
                         ),
                     ]));
-                    if self.context == ProjectContext::KmpAndroid {
+                    if !self.context.kinds.is_empty() {
                         lines.push(Line::from(vec![
                             Span::styled(
                                 " CONTEXT        : ",
                                 Style::default().fg(theme.secondary),
                             ),
-                            Span::styled("KMP / ANDROID", Style::default().fg(theme.accent)),
+                            Span::styled(self.context.label(), Style::default().fg(theme.accent)),
                         ]));
                     }
 
@@ -169,23 +170,98 @@ impl Widget for DetailsWidget<'_> {
                         "IDENTITY ───────────────",
                         Style::default().fg(theme.subtle),
                     )));
-                    lines.push(Line::from(vec![
-                        Span::styled(" INTENT : ", Style::default().fg(theme.secondary)),
-                        Span::styled(
-                            std::path::Path::new(&wt.path)
-                                .file_name()
-                                .map_or_else(|| Cow::Borrowed("UNKNOWN"), |n| n.to_string_lossy()),
-                            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
-                        ),
-                    ]));
-                    if let Some(meta) = &wt.metadata
-                        && let Some(purpose) = &meta.purpose
-                    {
+                    let mut intent = vec![Span::styled(
+                        " INTENT : ",
+                        Style::default().fg(theme.secondary),
+                    )];
+                    let meta = wt.metadata.as_ref();
+                    if let Some(icon) = meta.and_then(|m| m.icon.as_deref()) {
+                        intent.push(Span::raw(format!("{icon} ")));
+                    }
+                    intent.push(Span::styled(
+                        std::path::Path::new(&wt.path)
+                            .file_name()
+                            .map_or_else(|| Cow::Borrowed("UNKNOWN"), |n| n.to_string_lossy()),
+                        Style::default()
+                            .fg(CyberTheme::worktree_color(wt).unwrap_or(theme.text))
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                    lines.push(Line::from(intent));
+                    if let Some(purpose) = meta.and_then(|m| m.purpose.as_deref()) {
                         lines.push(Line::from(vec![
                             Span::styled(" PURPOSE: ", Style::default().fg(theme.secondary)),
                             Span::styled(purpose, Style::default().fg(theme.primary)),
                         ]));
                     }
+                    if let Some(description) = meta.and_then(|m| m.description.as_deref()) {
+                        lines.push(Line::from(vec![
+                            Span::styled(" DESCR  : ", Style::default().fg(theme.secondary)),
+                            Span::styled(description, Style::default().fg(theme.text)),
+                        ]));
+                    }
+                    if let Some(created) = meta.and_then(|m| m.created_at.as_deref()) {
+                        let created = chrono::DateTime::parse_from_rfc3339(created).map_or_else(
+                            |_| created.to_string(),
+                            |t| {
+                                t.with_timezone(&chrono::Local)
+                                    .format("%Y-%m-%d %H:%M")
+                                    .to_string()
+                            },
+                        );
+                        lines.push(Line::from(vec![
+                            Span::styled(" CREATED: ", Style::default().fg(theme.secondary)),
+                            Span::styled(created, Style::default().fg(theme.subtle)),
+                        ]));
+                    }
+                    if let Some(template) = meta.and_then(|m| m.template.as_deref()) {
+                        lines.push(Line::from(vec![
+                            Span::styled(" TEMPL  : ", Style::default().fg(theme.secondary)),
+                            Span::styled(template, Style::default().fg(theme.text)),
+                        ]));
+                    }
+                    if let Some(expires) = meta.and_then(|m| m.expires_at.as_deref()) {
+                        let expires = chrono::DateTime::parse_from_rfc3339(expires).map_or_else(
+                            |_| expires.to_string(),
+                            |t| {
+                                t.with_timezone(&chrono::Local)
+                                    .format("%Y-%m-%d %H:%M")
+                                    .to_string()
+                            },
+                        );
+                        lines.push(Line::from(vec![
+                            Span::styled(" EXPIRES: ", Style::default().fg(theme.secondary)),
+                            Span::styled(expires, Style::default().fg(theme.warning)),
+                        ]));
+                    }
+                    if let Some(issue) = meta.and_then(|m| m.issue.as_ref()) {
+                        let mut spans = vec![
+                            Span::styled(" ISSUE  : ", Style::default().fg(theme.secondary)),
+                            Span::styled(
+                                issue.id.as_str(),
+                                Style::default()
+                                    .fg(theme.accent)
+                                    .add_modifier(Modifier::BOLD),
+                            ),
+                        ];
+                        if let Some(title) = issue.title.as_deref() {
+                            spans.push(Span::styled(
+                                format!(" {title}"),
+                                Style::default().fg(theme.text),
+                            ));
+                        }
+                        lines.push(Line::from(spans));
+                        if let Some(url) = issue.url.as_deref() {
+                            lines.push(Line::from(vec![
+                                Span::raw("          "),
+                                Span::styled(
+                                    url,
+                                    Style::default()
+                                        .fg(theme.subtle)
+                                        .add_modifier(Modifier::UNDERLINED),
+                                ),
+                            ]));
+                        }
+                    }
                     lines.push(Line::from(vec![
                         Span::styled(" BRANCH : ", Style::default().fg(theme.secondary)),
                         Span::styled(&wt.branch, Style::default().fg(theme.text)),
@@ -195,17 +271,20 @@ impl Widget for DetailsWidget<'_> {
                         Span::styled(&wt.commit, Style::default().fg(theme.subtle)),
                     ]));
 
-                    // Added for Vibe Engineering: Implicit Remote/Upstream
+                    let (remote, track) = wt.upstream.as_deref().map_or_else(
+                        || (Cow::Borrowed("NONE"), Cow::Borrowed("not tracking")),
+                        |upstream| {
+                            let remote = upstream.split_once('/').map_or(upstream, |(r, _)| r);
+                            (Cow::Owned(remote.to_uppercase()), Cow::Borrowed(upstream))
+                        },
+                    );
                     lines.push(Line::from(vec![
                         Span::styled(" REMOTE : ", Style::default().fg(theme.secondary)),
-                        Span::styled("ORIGIN", Style::default().fg(theme.subtle)),
+                        Span::styled(remote, Style::default().fg(theme.subtle)),
                     ]));
                     lines.push(Line::from(vec![
                         Span::styled(" TRACK  : ", Style::default().fg(theme.secondary)),
-                        Span::styled(
-                            format!("origin/{}", wt.branch),
-                            Style::default().fg(theme.subtle),
-                        ),
+                        Span::styled(track, Style::default().fg(theme.subtle)),
                     ]));
 
                     lines.push(Line::from(""));
@@ -270,13 +349,66 @@ impl Widget for DetailsWidget<'_> {
                         ),
                     ]));
 
-                    if self.context == ProjectContext::KmpAndroid {
+                    if !wt.contexts.is_empty() {
+                        let stacks = ProjectContext {
+                            kinds: wt.contexts.clone(),
+                        };
+                        lines.push(Line::from(vec![
+                            Span::styled(" STACK  : ", Style::default().fg(theme.secondary)),
+                            Span::styled(stacks.label(), Style::default().fg(theme.accent)),
+                        ]));
+                    }
+
+                    if wt.contexts.contains(&ContextKind::Kmp) {
                         lines.push(Line::from(vec![
                             Span::styled(" OPTIM  : ", Style::default().fg(theme.secondary)),
                             Span::styled("GRADLE_CACHE_ACTIVE", Style::default().fg(theme.success)),
                         ]));
                     }
 
+                    if let Some(resources) = wt.metadata.as_ref().and_then(|m| m.resources.as_ref())
+                    {
+                        lines.push(Line::from(vec![
+                            Span::styled(" PORTS  : ", Style::default().fg(theme.secondary)),
+                            Span::styled(
+                                resources.ports_label(),
+                                Style::default().fg(theme.accent),
+                            ),
+                        ]));
+                        if let Some(db) = &resources.db_name {
+                            lines.push(Line::from(vec![
+                                Span::styled(" DB     : ", Style::default().fg(theme.secondary)),
+                                Span::styled(db.as_str(), Style::default().fg(theme.subtle)),
+                            ]));
+                        }
+                        if let Some(project) = &resources.compose_project {
+                            lines.push(Line::from(vec![
+                                Span::styled(" COMPOSE: ", Style::default().fg(theme.secondary)),
+                                Span::styled(project.as_str(), Style::default().fg(theme.subtle)),
+                            ]));
+                        }
+                    }
+
+                    if !wt.sync_drift.is_empty() {
+                        lines.push(Line::from(""));
+                        lines.push(Line::from(Span::styled(
+                            "SYNC DRIFT ─────────────",
+                            Style::default().fg(theme.subtle),
+                        )));
+                        for drift in &wt.sync_drift {
+                            lines.push(Line::from(vec![
+                                Span::styled(
+                                    format!(" {:<15}: ", drift.state.label().to_uppercase()),
+                                    Style::default().fg(theme.warning),
+                                ),
+                                Span::styled(
+                                    drift.destination.as_str(),
+                                    Style::default().fg(theme.subtle),
+                                ),
+                            ]));
+                        }
+                    }
+
                     lines.push(Line::from(""));
                     lines.push(Line::from(Span::styled(
                         "ACTIONS ────────────────",
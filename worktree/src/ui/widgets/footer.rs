@@ -21,6 +21,7 @@ impl Widget for FooterWidget<'_> {
                     vec![
                         ("[j/k]", "NAV", theme.primary),
                         ("[ENT]", "OPEN", theme.primary),
+                        ("[e]", "EDITOR", theme.primary),
                     ],
                     vec![
                         ("[m]", "MANAGE", theme.secondary),
@@ -31,17 +32,24 @@ impl Widget for FooterWidget<'_> {
                     vec![
                         ("[v]", "STATUS", theme.text),
                         ("[l]", "LOG", theme.text),
+                        ("[t]", "CD", theme.text),
                         ("[q]", "EXIT", theme.error),
                     ],
+                    vec![
+                        ("[*]", "PIN", theme.accent),
+                        ("[S/G]", "SORT", theme.accent),
+                    ],
                 ],
                 crate::app::model::AppMode::Manage => vec![
                     vec![
                         ("[a]", "ADD", theme.success),
+                        ("[e]", "EDIT", theme.accent),
                         ("[ESC]", "BACK", theme.accent),
                     ],
                     vec![
                         ("[r]", "REMOVE", theme.error),
                         ("[c]", "CLEAN", theme.error), // Changed to error for safety
+                        ("[s]", "STALE", theme.warning),
                     ],
                 ],
                 crate::app::model::AppMode::Git => vec![
@@ -50,6 +58,8 @@ impl Widget for FooterWidget<'_> {
                         ("[P]", "PUSH", theme.success),
                         ("[s]", "SYNC", theme.success),
                         ("[R]", "REBASE", theme.success),
+                        ("[c]", "COMPARE", theme.accent),
+                        ("[b]", "BRANCHES", theme.accent),
                     ],
                     vec![("[ESC]", "BACK", theme.accent)],
                 ],
@@ -66,15 +76,71 @@ impl Widget for FooterWidget<'_> {
                     ("[j/k]", "NAV", theme.primary),
                     ("[TAB]", "STAGE", theme.success),
                     ("[C]", "COMMIT", theme.success),
+                    ("[e]", "EDIT", theme.accent),
+                ],
+                vec![("[ESC]", "BACK", theme.accent)],
+            ],
+            AppState::ViewingHistory { .. } => vec![
+                vec![
+                    ("[j/k]", "NAV", theme.primary),
+                    ("[ENT]", "DETAIL", theme.success),
+                    ("[F]", "FILES", theme.success),
+                    ("[SPC]", "MARK", theme.accent),
+                    ("[P]", "PORT", theme.success),
+                ],
+                vec![("[ESC]", "BACK", theme.accent)],
+            ],
+            AppState::ViewingComparison { .. } => vec![
+                vec![
+                    ("[j/k]", "SCROLL", theme.primary),
+                    ("[S]", "SWAP", theme.accent),
+                ],
+                vec![("[ESC]", "BACK", theme.accent)],
+            ],
+            AppState::ViewingCommitDetail { .. } => vec![
+                vec![
+                    ("[j/k]", "SCROLL", theme.primary),
+                    ("[Y]", "COPY", theme.accent),
+                    ("[P]", "PICK", theme.success),
+                    ("[R]", "REVERT", theme.warning),
+                    ("[W]", "WORKTREE", theme.success),
                 ],
                 vec![("[ESC]", "BACK", theme.accent)],
             ],
-            AppState::ViewingHistory { .. } => vec![vec![
+            AppState::ManagingBranches { .. } => vec![
+                vec![
+                    ("[j/k]", "NAV", theme.primary),
+                    ("[n]", "NEW", theme.success),
+                    ("[r]", "RENAME", theme.accent),
+                    ("[u]", "UPSTREAM", theme.accent),
+                    ("[d]", "DELETE", theme.error),
+                    ("[ENT]", "WORKTREE", theme.success),
+                    ("[a]", "REMOTES", theme.primary),
+                ],
+                vec![("[ESC]", "BACK", theme.accent)],
+            ],
+            AppState::ReviewingStale { .. } => vec![
+                vec![
+                    ("[j/k]", "NAV", theme.primary),
+                    ("[SPC]", "TOGGLE", theme.accent),
+                    ("[a]", "ALL", theme.accent),
+                    ("[ENT]", "REMOVE", theme.error),
+                ],
+                vec![("[ESC]", "BACK", theme.accent)],
+            ],
+            AppState::EditingMetadata { .. } => vec![vec![
+                ("[TAB]", "NEXT", theme.primary),
+                ("[ENT]", "SAVE", theme.success),
+                ("[ESC]", "CANCEL", theme.accent),
+            ]],
+            AppState::ViewingCommitFiles { .. } => vec![vec![
                 ("[j/k]", "NAV", theme.primary),
+                ("[ENT]", "OPEN", theme.success),
                 ("[ESC]", "BACK", theme.accent),
             ]],
             AppState::SwitchingBranch { .. }
             | AppState::PickingBaseRef { .. }
+            | AppState::PickingCompareTarget { .. }
             | AppState::Committing { .. } => vec![
                 vec![
                     ("[j/k]", "NAV", theme.primary),
@@ -82,6 +148,14 @@ impl Widget for FooterWidget<'_> {
                 ],
                 vec![("[ESC]", "BACK", theme.accent)],
             ],
+            AppState::PickingCherryPickTarget { .. } => vec![
+                vec![
+                    ("[j/k]", "NAV", theme.primary),
+                    ("[ENT]", "COPY", theme.success),
+                    ("[M]", "MOVE", theme.warning),
+                ],
+                vec![("[ESC]", "BACK", theme.accent)],
+            ],
             AppState::SelectingEditor { .. } => vec![
                 vec![
                     ("[j/k]", "NAV", theme.primary),
@@ -172,6 +246,7 @@ mod tests {
             is_filtering: false,
             mode: AppMode::Normal,
             last_selection_change: std::time::Instant::now(),
+            layout: crate::domain::list_layout::ListLayout::default(),
         };
 
         terminal
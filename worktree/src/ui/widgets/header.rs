@@ -9,7 +9,7 @@ use ratatui::{
 };
 
 pub struct HeaderWidget<'a> {
-    pub context: ProjectContext,
+    pub context: &'a ProjectContext,
     pub project_name: &'a str,
     pub state: &'a AppState,
     pub spinner_tick: usize,
@@ -81,8 +81,17 @@ impl Widget for HeaderWidget<'_> {
                 crate::app::model::AppMode::Filter => "FILTERING",
             },
             AppState::ViewingStatus { .. } => "INSPECT",
-            AppState::ViewingHistory { .. } => "LOG_VIEW",
+            AppState::ViewingHistory { .. }
+            | AppState::ViewingCommitFiles { .. }
+            | AppState::ViewingCommitDetail { .. } => "LOG_VIEW",
+            AppState::PickingCherryPickTarget { .. } | AppState::ApplyingCommit { .. } => "PICK",
+            AppState::PickingCompareTarget { .. }
+            | AppState::LoadingComparison { .. }
+            | AppState::ViewingComparison { .. } => "COMPARE",
             AppState::SwitchingBranch { .. } => "SWITCH",
+            AppState::ManagingBranches { .. } => "BRANCHES",
+            AppState::ReviewingStale { .. } => "CLEANUP",
+            AppState::EditingMetadata { .. } => "EDIT",
             AppState::Committing { .. } => "COMMIT",
             AppState::Prompting { .. } => "INPUT",
             AppState::Syncing { .. } => "NET_SYNC",
@@ -93,10 +102,7 @@ impl Widget for HeaderWidget<'_> {
             _ => "UNKNOWN",
         };
 
-        let context_str = match self.context {
-            ProjectContext::Standard => "STD_GIT",
-            ProjectContext::KmpAndroid => "KMP_DROID",
-        };
+        let context_str = self.context.label();
 
         let mut spans = vec![
             Span::styled(format!(" {} ", time), Style::default().fg(theme.subtle)),
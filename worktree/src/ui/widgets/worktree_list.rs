@@ -1,5 +1,9 @@
 use crate::app::model::AppMode;
+use crate::domain::list_layout::{
+    GroupMode, ListLayout, PINNED_GROUP, SortMode, group_of, is_pinned,
+};
 use crate::domain::repository::Worktree;
+use crate::domain::stale::{format_countdown, seconds_until_expiry};
 use crate::ui::theme::{CyberTheme, Icons};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -19,6 +23,50 @@ pub struct WorktreeListWidget<'a> {
     spinner_tick: usize,
     filter_query: Option<&'a str>,
     mode: Option<AppMode>,
+    layout: Option<&'a ListLayout>,
+}
+
+/// A line of the grouped list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListRow<'a> {
+    Group {
+        name: &'a str,
+        count: usize,
+        collapsed: bool,
+    },
+    /// Position in the visible indices.
+    Worktree(usize),
+}
+
+/// Lines of the list grouped by `layout.group`: a header before each group, followed by the
+/// group's worktrees among `indices` (none when it is collapsed).
+pub fn list_rows<'a>(
+    worktrees: &'a [Worktree],
+    indices: &[usize],
+    layout: &ListLayout,
+) -> Vec<ListRow<'a>> {
+    let groups: Vec<Option<&str>> = worktrees
+        .iter()
+        .map(|wt| group_of(wt, layout.group))
+        .collect();
+    let mut rows = Vec::with_capacity(indices.len() + 8);
+    let mut positions = indices.iter().enumerate().peekable();
+    for (i, group) in groups.iter().enumerate() {
+        if let Some(name) = *group
+            && (i == 0 || groups[i - 1] != *group)
+        {
+            rows.push(ListRow::Group {
+                name,
+                count: groups[i..].iter().take_while(|g| *g == group).count(),
+                collapsed: layout.is_collapsed(name),
+            });
+        }
+        if let Some(&(pos, _)) = positions.peek().filter(|(_, idx)| **idx == i) {
+            rows.push(ListRow::Worktree(pos));
+            positions.next();
+        }
+    }
+    rows
 }
 
 impl<'a> WorktreeListWidget<'a> {
@@ -30,6 +78,7 @@ impl<'a> WorktreeListWidget<'a> {
             spinner_tick: 0,
             filter_query: None,
             mode: None,
+            layout: None,
         }
     }
 
@@ -52,6 +101,200 @@ impl<'a> WorktreeListWidget<'a> {
         self.mode = Some(mode);
         self
     }
+
+    /// Shows group headers when the layout groups worktrees; `indices` must then be in list order.
+    pub const fn grouped(mut self, layout: Option<&'a ListLayout>) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// One worktree: cursor, icon, intent, branch, status badges, size and commit.
+    fn worktree_row(
+        &self,
+        wt: &'a Worktree,
+        is_selected: bool,
+        now: i64,
+        theme: &CyberTheme,
+    ) -> Row<'a> {
+        let (icon, branch_style) = if wt.is_bare {
+            (
+                Icons::HUB,
+                if self.is_dimmed {
+                    Style::default().fg(theme.subtle)
+                } else {
+                    Style::default()
+                        .fg(theme.primary)
+                        .add_modifier(Modifier::BOLD)
+                },
+            )
+        } else if wt.is_detached {
+            (Icons::DETACHED, Style::default().fg(theme.error))
+        } else {
+            (
+                Icons::WORKTREE,
+                if self.is_dimmed {
+                    Style::default().fg(theme.subtle)
+                } else {
+                    Style::default().fg(theme.primary)
+                },
+            )
+        };
+        let custom_color = CyberTheme::worktree_color(wt).filter(|_| !self.is_dimmed);
+        let icon = match wt.metadata.as_ref().and_then(|m| m.icon.as_deref()) {
+            Some(custom) if !wt.is_bare && !wt.is_detached => Cow::Owned(format!("{custom} ")),
+            _ => Cow::Borrowed(icon),
+        };
+
+        let intent_str = if wt.is_bare {
+            Cow::Borrowed("MAIN")
+        } else if let Some(meta) = &wt.metadata
+            && let Some(purpose) = &meta.purpose
+        {
+            Cow::Borrowed(purpose.as_str())
+        } else {
+            std::path::Path::new(&wt.path).file_name().map_or_else(
+                || Cow::Borrowed(wt.branch.as_str()),
+                |n| n.to_string_lossy(),
+            )
+        };
+
+        let mut row_style = Style::default().fg(theme.text);
+
+        if self.is_dimmed {
+            row_style = row_style.add_modifier(Modifier::DIM);
+        }
+
+        if is_selected {
+            row_style = row_style
+                .bg(theme.selection_bg)
+                .fg(theme.primary)
+                .add_modifier(Modifier::BOLD);
+
+            // Remove dim modifier if selected, to make it pop even when filtering
+            if self.is_dimmed {
+                row_style = row_style.remove_modifier(Modifier::DIM);
+            }
+        }
+
+        // Cyber-style cursor animation
+        // ▊, ▋, ▌, ▍, ▎, ▏
+        let spinner_prefixes = [" ▊ ", " ▋ ", " ▌ ", " ▍ ", " ▌ ", " ▋ "];
+        let spinner_idx = (self.spinner_tick / 2) % spinner_prefixes.len();
+
+        let prefix = if is_selected {
+            spinner_prefixes[spinner_idx]
+        } else {
+            "   "
+        };
+
+        let status_cell = wt.status_summary.as_ref().map_or_else(
+            || Cell::from("-"),
+            |summary| {
+                let (color, icon) = if summary == "clean" {
+                    (theme.success, Icons::CLEAN)
+                } else {
+                    (theme.warning, Icons::DIRTY)
+                };
+
+                let style = if self.is_dimmed && !is_selected {
+                    Style::default().fg(theme.subtle)
+                } else {
+                    Style::default().fg(color)
+                };
+
+                let summary_text = if summary == "clean" {
+                    "CLEAN"
+                } else {
+                    summary.as_str()
+                };
+                let mut spans = vec![Span::raw(icon), Span::raw(" "), Span::raw(summary_text)];
+                // Synced files that drifted from the hub: `worktree sync --check` explains.
+                if !wt.sync_drift.is_empty() {
+                    spans.push(Span::styled(
+                        format!(" {}{}", Icons::SYNC_DRIFT, wt.sync_drift.len()),
+                        Style::default().fg(theme.warning),
+                    ));
+                }
+                // Time left before `clean --expired` removes it.
+                if let Some(left) = seconds_until_expiry(wt, now) {
+                    spans.push(Span::styled(
+                        format!(" {}{}", Icons::EXPIRY, format_countdown(left)),
+                        Style::default().fg(if left <= 0 {
+                            theme.error
+                        } else {
+                            theme.warning
+                        }),
+                    ));
+                }
+                Cell::from(Line::from(spans)).style(style)
+            },
+        );
+
+        let mut cell_style = Style::default();
+        if self.is_dimmed && !is_selected {
+            cell_style = cell_style.fg(theme.subtle);
+        }
+        let icon_style = custom_color.map_or_else(Style::default, |c| Style::default().fg(c));
+
+        Row::new([
+            Cell::from(Line::from(vec![
+                Span::raw(prefix),
+                Span::styled(icon, icon_style),
+            ])),
+            Cell::from(if is_pinned(wt) {
+                Line::from(vec![Span::raw(Icons::PIN), Span::raw(intent_str)])
+            } else {
+                Line::from(intent_str)
+            })
+            .style(if is_selected {
+                branch_style
+            } else {
+                custom_color.map_or(cell_style, |c| cell_style.fg(c))
+            }),
+            Cell::from(wt.branch.as_str()).style(if is_selected {
+                Style::default().fg(theme.primary)
+            } else {
+                cell_style
+            }),
+            status_cell,
+            Cell::from(format_size(wt.size_bytes)).style(Style::default().fg(theme.subtle)),
+            Cell::from(&wt.commit[..wt.commit.len().min(7)])
+                .style(Style::default().fg(theme.subtle)),
+        ])
+        .style(row_style)
+        .height(1)
+    }
+
+    /// A `▾ feat (3)` line above a group's worktrees, `▸` when collapsed.
+    fn group_row(
+        &self,
+        name: &'a str,
+        count: usize,
+        collapsed: bool,
+        theme: &CyberTheme,
+    ) -> Row<'a> {
+        let mut style = Style::default()
+            .fg(theme.secondary)
+            .add_modifier(Modifier::BOLD);
+        if self.is_dimmed {
+            style = style.add_modifier(Modifier::DIM);
+        }
+        let marker = if collapsed { "  ▸" } else { "  ▾" };
+        let label = if name == PINNED_GROUP {
+            format!("{}{name}", Icons::PIN)
+        } else {
+            name.to_string()
+        };
+        Row::new([
+            Cell::from(marker),
+            Cell::from(Line::from(vec![
+                Span::raw(label),
+                Span::styled(format!(" ({count})"), Style::default().fg(theme.subtle)),
+            ])),
+        ])
+        .style(style)
+        .height(1)
+    }
 }
 
 impl StatefulWidget for WorktreeListWidget<'_> {
@@ -88,13 +331,27 @@ impl StatefulWidget for WorktreeListWidget<'_> {
         };
 
         let total_count = self.indices.map_or(self.worktrees.len(), |i| i.len());
+        // e.g. `· size · by prefix` when not in `git worktree list` order.
+        let arranged: String = self
+            .layout
+            .map(|layout| {
+                let sort =
+                    (layout.sort != SortMode::Default).then(|| layout.sort.label().to_string());
+                let group = (layout.group != GroupMode::None)
+                    .then(|| format!("by {}", layout.group.label()));
+                sort.into_iter()
+                    .chain(group)
+                    .map(|part| format!("· {part} "))
+                    .collect()
+            })
+            .unwrap_or_default();
 
         let block = Block::default()
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .border_style(border_style)
             .title(Span::styled(
-                format!(" {} ({}) ", mode_title, total_count),
+                format!(" {} ({}) {arranged}", mode_title, total_count),
                 title_style,
             ));
 
@@ -155,152 +412,71 @@ impl StatefulWidget for WorktreeListWidget<'_> {
         // This significantly reduces allocations for large lists.
         let header_height = 2; // 1 for text + 1 bottom margin
         let available_height = inner_area.height.saturating_sub(header_height) as usize;
+        let now = chrono::Utc::now().timestamp();
 
-        let start_index = state.offset().min(total_count.saturating_sub(1));
-        let end_index = (start_index + available_height).min(total_count);
-
-        // Iterate over the visible range and retrieve worktrees either directly or via indices
-        let rows = (start_index..end_index).map(|i| {
-            let actual_index = start_index + (i - start_index); // Redundant but explicit
-            let wt_index = self.indices.map_or(actual_index, |idxs| idxs[actual_index]);
-
-            // Safety: indices are derived from worktrees, so this should be valid.
-            // Fallback to safe get to prevent panic if indices are stale (though they shouldn't be).
-            let wt = self
-                .worktrees
-                .get(wt_index)
-                .expect("Invalid worktree index");
-
-            let is_selected = Some(actual_index) == state.selected();
-
-            let (icon, branch_style) = if wt.is_bare {
-                (
-                    Icons::HUB,
-                    if self.is_dimmed {
-                        Style::default().fg(theme.subtle)
-                    } else {
-                        Style::default()
-                            .fg(theme.primary)
-                            .add_modifier(Modifier::BOLD)
-                    },
-                )
-            } else if wt.is_detached {
-                (Icons::DETACHED, Style::default().fg(theme.error))
-            } else {
-                (
-                    Icons::WORKTREE,
-                    if self.is_dimmed {
-                        Style::default().fg(theme.subtle)
-                    } else {
-                        Style::default().fg(theme.primary)
-                    },
-                )
-            };
-
-            let intent_str = if wt.is_bare {
-                Cow::Borrowed("MAIN")
-            } else if let Some(meta) = &wt.metadata
-                && let Some(purpose) = &meta.purpose
+        // Grouped lists interleave group headers, so rows no longer line up with `indices`.
+        let (rows, selected_row, row_count): (Vec<Row>, Option<usize>, usize) =
+            if let (Some(layout), Some(indices)) = (self.layout, self.indices)
+                && layout.group != GroupMode::None
             {
-                Cow::Borrowed(purpose.as_str())
-            } else {
-                std::path::Path::new(&wt.path).file_name().map_or_else(
-                    || Cow::Borrowed(wt.branch.as_str()),
-                    |n| n.to_string_lossy(),
-                )
-            };
-
-            let mut row_style = Style::default().fg(theme.text);
-
-            if self.is_dimmed {
-                row_style = row_style.add_modifier(Modifier::DIM);
-            }
-
-            if is_selected {
-                row_style = row_style
-                    .bg(theme.selection_bg)
-                    .fg(theme.primary)
-                    .add_modifier(Modifier::BOLD);
-
-                // Remove dim modifier if selected, to make it pop even when filtering
-                if self.is_dimmed {
-                    row_style = row_style.remove_modifier(Modifier::DIM);
-                }
-            }
-
-            // Cyber-style cursor animation
-            // ▊, ▋, ▌, ▍, ▎, ▏
-            let spinner_prefixes = [" ▊ ", " ▋ ", " ▌ ", " ▍ ", " ▌ ", " ▋ "];
-            let spinner_idx = (self.spinner_tick / 2) % spinner_prefixes.len();
-
-            let prefix = if is_selected {
-                spinner_prefixes[spinner_idx]
+                let lines = list_rows(self.worktrees, indices, layout);
+                let selected_row = state.selected().and_then(|selected| {
+                    lines
+                        .iter()
+                        .position(|line| *line == ListRow::Worktree(selected))
+                });
+                // Scroll just far enough to keep the selection in view.
+                let start =
+                    selected_row.map_or(0, |row| (row + 1).saturating_sub(available_height));
+                let end = (start + available_height).min(lines.len());
+                let rows = lines[start..end]
+                    .iter()
+                    .map(|line| match *line {
+                        ListRow::Group {
+                            name,
+                            count,
+                            collapsed,
+                        } => self.group_row(name, count, collapsed, &theme),
+                        ListRow::Worktree(pos) => self.worktree_row(
+                            &self.worktrees[indices[pos]],
+                            Some(pos) == state.selected(),
+                            now,
+                            &theme,
+                        ),
+                    })
+                    .collect();
+                (rows, selected_row.map(|row| row - start), lines.len())
             } else {
-                "   "
+                let start_index = state.offset().min(total_count.saturating_sub(1));
+                let end_index = (start_index + available_height).min(total_count);
+
+                // Iterate over the visible range and retrieve worktrees either directly or via indices
+                let rows = (start_index..end_index)
+                    .map(|i| {
+                        let wt_index = self.indices.map_or(i, |idxs| idxs[i]);
+
+                        // Safety: indices are derived from worktrees, so this should be valid.
+                        let wt = self
+                            .worktrees
+                            .get(wt_index)
+                            .expect("Invalid worktree index");
+                        self.worktree_row(wt, Some(i) == state.selected(), now, &theme)
+                    })
+                    .collect();
+                let selected_row = state
+                    .selected()
+                    .filter(|&selected| selected >= start_index && selected < end_index)
+                    .map(|selected| selected - start_index);
+                (rows, selected_row, total_count)
             };
 
-            let status_cell = wt.status_summary.as_ref().map_or_else(
-                || Cell::from("-"),
-                |summary| {
-                    let (color, icon) = if summary == "clean" {
-                        (theme.success, Icons::CLEAN)
-                    } else {
-                        (theme.warning, Icons::DIRTY)
-                    };
-
-                    let style = if self.is_dimmed && !is_selected {
-                        Style::default().fg(theme.subtle)
-                    } else {
-                        Style::default().fg(color)
-                    };
-
-                    let summary_text = if summary == "clean" {
-                        "CLEAN"
-                    } else {
-                        summary.as_str()
-                    };
-                    Cell::from(Line::from(vec![
-                        Span::raw(icon),
-                        Span::raw(" "),
-                        Span::raw(summary_text),
-                    ]))
-                    .style(style)
-                },
-            );
-
-            let mut cell_style = Style::default();
-            if self.is_dimmed && !is_selected {
-                cell_style = cell_style.fg(theme.subtle);
-            }
-
-            Row::new([
-                Cell::from(Line::from(vec![Span::raw(prefix), Span::raw(icon)])),
-                Cell::from(intent_str).style(if is_selected {
-                    branch_style
-                } else {
-                    cell_style
-                }),
-                Cell::from(wt.branch.as_str()).style(if is_selected {
-                    Style::default().fg(theme.primary)
-                } else {
-                    cell_style
-                }),
-                status_cell,
-                Cell::from(format_size(wt.size_bytes)).style(Style::default().fg(theme.subtle)),
-                Cell::from(&wt.commit[..wt.commit.len().min(7)])
-                    .style(Style::default().fg(theme.subtle)),
-            ])
-            .style(row_style)
-            .height(1)
-        });
-
         let table = Table::new(
             rows,
             [
                 Constraint::Length(6), // Reduced width for icon/cursor
                 Constraint::Percentage(20),
                 Constraint::Percentage(20),
-                Constraint::Length(12),
+                Constraint::Length(16),
                 Constraint::Length(10), // Size column
                 Constraint::Length(10), // Commit column
             ],
@@ -323,18 +499,13 @@ impl StatefulWidget for WorktreeListWidget<'_> {
 
         // Create a temporary state for rendering the slice
         let mut temp_state = TableState::default();
-        if let Some(selected) = state.selected()
-            && selected >= start_index
-            && selected < end_index
-        {
-            temp_state.select(Some(selected - start_index));
-        }
+        temp_state.select(selected_row);
         // Offset is always 0 because we are feeding exactly what needs to be rendered from top
         *temp_state.offset_mut() = 0;
 
         StatefulWidget::render(table, area, buf, &mut temp_state);
 
-        if total_count > inner_area.height as usize {
+        if row_count > inner_area.height as usize {
             let scrollbar = Scrollbar::default()
                 .orientation(ScrollbarOrientation::VerticalRight)
                 .begin_symbol(None)
@@ -349,7 +520,7 @@ impl StatefulWidget for WorktreeListWidget<'_> {
                     Style::default().fg(theme.secondary)
                 });
 
-            let mut scrollbar_state = ScrollbarState::new(total_count)
+            let mut scrollbar_state = ScrollbarState::new(row_count)
                 .position(state.offset())
                 .viewport_content_length(inner_area.height as usize);
 
@@ -358,7 +529,7 @@ impl StatefulWidget for WorktreeListWidget<'_> {
     }
 }
 
-fn format_size(bytes: u64) -> Cow<'static, str> {
+pub fn format_size(bytes: u64) -> Cow<'static, str> {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
     const GB: u64 = MB * 1024;
@@ -462,4 +633,67 @@ mod tests {
 
         assert!(content.contains("MANAGE WORKTREES (0)"));
     }
+
+    #[test]
+    fn test_render_grouped_worktree_list() {
+        let worktree = |branch: &str| Worktree {
+            path: format!("/hub/{}", branch.replace('/', "-")),
+            commit: "abc1234".into(),
+            branch: branch.into(),
+            is_bare: false,
+            is_detached: false,
+            status_summary: Some("clean".into()),
+            size_bytes: 0,
+            metadata: None,
+            upstream: None,
+            contexts: Vec::new(),
+            sync_drift: Vec::new(),
+        };
+        let worktrees = vec![worktree("feat/a"), worktree("feat/b"), worktree("fix/c")];
+        let layout = ListLayout {
+            group: GroupMode::Prefix,
+            collapsed: vec!["fix".into()],
+            ..ListLayout::default()
+        };
+        let indices = crate::domain::list_layout::expanded_indices(&worktrees, &layout);
+        assert_eq!(
+            list_rows(&worktrees, &indices, &layout),
+            [
+                ListRow::Group {
+                    name: "feat",
+                    count: 2,
+                    collapsed: false
+                },
+                ListRow::Worktree(0),
+                ListRow::Worktree(1),
+                ListRow::Group {
+                    name: "fix",
+                    count: 1,
+                    collapsed: true
+                },
+            ]
+        );
+
+        let backend = TestBackend::new(80, 10);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut state = TableState::default();
+        state.select(Some(1));
+        let widget = WorktreeListWidget::new(&worktrees, Some(&indices)).grouped(Some(&layout));
+        terminal
+            .draw(|f| f.render_stateful_widget(widget, f.area(), &mut state))
+            .unwrap();
+
+        let content = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect::<String>();
+        assert!(content.contains("ACTIVE WORKTREES (2) · by prefix"));
+        assert!(content.contains("▾"));
+        assert!(content.contains("feat (2)"));
+        assert!(content.contains("fix (1)"));
+        assert!(content.contains("feat-b"));
+    }
 }
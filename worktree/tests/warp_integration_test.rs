@@ -1,14 +1,19 @@
+use clap::CommandFactory;
 use serial_test::serial;
 use std::fs;
 use tempfile::tempdir;
-use worktree::infrastructure::warp_integration::{generate_warp_workflows, is_warp_terminal};
+use worktree::cli::Cli;
+use worktree::infrastructure::warp_integration::{
+    build_workflows, generate_warp_workflows, is_warp_terminal,
+};
 
 #[test]
 fn test_generate_warp_workflows() {
     let temp_dir = tempdir().expect("Failed to create temp dir");
     let project_path = temp_dir.path();
 
-    generate_warp_workflows(project_path).expect("Failed to generate warp workflows");
+    generate_warp_workflows(project_path, &Cli::command())
+        .expect("Failed to generate warp workflows");
 
     let workflow_dir = project_path.join(".warp").join("workflows");
     assert!(
@@ -25,6 +30,43 @@ fn test_generate_warp_workflows() {
     assert!(content.contains("name: Worktree Switch"));
 }
 
+#[test]
+fn test_workflows_follow_cli_definition() {
+    let workflows = build_workflows(&Cli::command());
+
+    let switch = workflows
+        .iter()
+        .find(|w| w.command == "worktree switch {name}")
+        .expect("switch workflow missing");
+    let name_arg = &switch.arguments[0];
+    assert_eq!(name_arg.name, "name");
+    assert_eq!(name_arg.arg_type.as_deref(), Some("Enum"));
+    assert!(
+        name_arg
+            .enum_values
+            .as_ref()
+            .and_then(|e| e.dynamic_enum.as_ref())
+            .is_some_and(|d| d.command.starts_with("git worktree list --porcelain"))
+    );
+
+    let completions = workflows
+        .iter()
+        .find(|w| w.name == "Worktree Completions")
+        .expect("completions workflow missing");
+    let variants = &completions.arguments[0]
+        .enum_values
+        .as_ref()
+        .expect("shell choices missing")
+        .variants;
+    assert!(variants.iter().any(|v| v == "zsh"));
+
+    // Nested subcommands are flattened into their own workflows.
+    assert!(workflows.iter().any(
+        |w| w.name == "Worktree Config Set Key" && w.command == "worktree config set-key {key}"
+    ));
+    assert!(workflows.iter().any(|w| w.command == "worktree warp sync"));
+}
+
 #[test]
 #[serial]
 fn test_is_warp_terminal_positive() {